@@ -0,0 +1,190 @@
+//! Measures the throughput of `SyncCell` against the primitives it is built on.
+//!
+//! Run with `cargo bench`, adding `--features parking_lot` to also measure `SyncCell` with the
+//! `parking_lot` backend, which `HeldSyncCell` then uses as well. Each workload is run on several threads and every iteration is one
+//! operation on each thread, so the reported time is the average time of one operation.
+
+use std::{
+    hint::black_box,
+    sync::{atomic::{AtomicU64, Ordering}, Barrier, Mutex, RwLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion};
+use sync_cell::{HeldSyncCell, RawSyncLock, RwLockRawLock, SyncCell};
+
+/// The number of threads each workload is run on.
+const THREADS: usize = 4;
+
+/// The mix of reads and writes performed by a workload.
+#[derive(Debug, Clone, Copy)]
+enum Workload {
+    /// One write for every 100 operations.
+    ReadHeavy,
+    /// Only writes.
+    WriteHeavy,
+    /// One write for every 4 operations.
+    Mixed,
+}
+
+impl Workload {
+    /// Whether the operation with the given index is a write.
+    fn is_write(self, operation: u64) -> bool {
+        match self {
+            Self::ReadHeavy => operation.is_multiple_of(100),
+            Self::WriteHeavy => true,
+            Self::Mixed => operation.is_multiple_of(4),
+        }
+    }
+}
+
+/// A shared value that can be read and incremented.
+trait Target: Sync {
+    /// Reads the value.
+    fn read(&self) -> u64;
+
+    /// Increments the value.
+    fn write(&self);
+}
+
+impl <L: RawSyncLock + Sync> Target for SyncCell<u64, L> {
+    fn read(&self) -> u64 {
+        *self.borrow()
+    }
+
+    fn write(&self) {
+        *self.borrow_mut() += 1;
+    }
+}
+
+impl Target for RwLock<u64> {
+    fn read(&self) -> u64 {
+        *self.read().unwrap()
+    }
+
+    fn write(&self) {
+        *self.write().unwrap() += 1;
+    }
+}
+
+impl Target for parking_lot::RwLock<u64> {
+    fn read(&self) -> u64 {
+        *self.read()
+    }
+
+    fn write(&self) {
+        *self.write() += 1;
+    }
+}
+
+impl Target for Mutex<u64> {
+    fn read(&self) -> u64 {
+        *self.lock().unwrap()
+    }
+
+    fn write(&self) {
+        *self.lock().unwrap() += 1;
+    }
+}
+
+impl Target for AtomicU64 {
+    fn read(&self) -> u64 {
+        self.load(Ordering::Acquire)
+    }
+
+    fn write(&self) {
+        self.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Runs a function on every thread at once and returns the time the slowest thread took.
+///
+/// - `f` - The function run by each thread, given the index of the thread.
+fn run(f: impl Fn(usize) + Sync) -> Duration {
+    let barrier = Barrier::new(THREADS);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS).map(|index| {
+            let barrier = &barrier;
+            let f = &f;
+            scope.spawn(move || {
+                barrier.wait();
+                let start = Instant::now();
+                f(index);
+                start.elapsed()
+            })
+        }).collect();
+
+        handles.into_iter()
+            .map(|handle| handle.join().unwrap())
+            .max()
+            .unwrap_or_default()
+    })
+}
+
+/// Measures a workload against a target.
+///
+/// - `group` - The group of the workload.
+/// - `name` - The name of the target.
+/// - `target` - The shared value.
+/// - `workload` - The mix of reads and writes.
+fn bench_target(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, target: &impl Target, workload: Workload) {
+    group.bench_function(name, |b| b.iter_custom(|iterations| run(|_| {
+        for operation in 0..iterations {
+            if workload.is_write(operation) {
+                target.write();
+            } else {
+                black_box(target.read());
+            }
+        }
+    })));
+}
+
+/// Measures a workload against every target.
+///
+/// - `c` - The benchmark manager.
+/// - `workload` - The mix of reads and writes.
+fn bench_workload(c: &mut Criterion, workload: Workload) {
+    let mut group = c.benchmark_group(format!("{:?}", workload));
+
+    bench_target(&mut group, "SyncCell<RwLockRawLock>", &SyncCell::with_lock(0, RwLockRawLock::INIT), workload);
+    #[cfg(feature = "parking_lot")]
+    bench_target(&mut group, "SyncCell<parking_lot::RawRwLock>", &SyncCell::with_lock(0, <parking_lot::RawRwLock as RawSyncLock>::INIT), workload);
+    bench_target(&mut group, "RwLock", &RwLock::new(0), workload);
+    bench_target(&mut group, "parking_lot::RwLock", &parking_lot::RwLock::new(0), workload);
+    bench_target(&mut group, "Mutex", &Mutex::new(0), workload);
+    bench_target(&mut group, "AtomicU64", &AtomicU64::new(0), workload);
+
+    group.finish();
+}
+
+fn bench_read_heavy(c: &mut Criterion) {
+    bench_workload(c, Workload::ReadHeavy);
+}
+
+fn bench_write_heavy(c: &mut Criterion) {
+    bench_workload(c, Workload::WriteHeavy);
+}
+
+fn bench_mixed(c: &mut Criterion) {
+    bench_workload(c, Workload::Mixed);
+}
+
+/// Measures how quickly a `HeldSyncCell` can be set by several threads and updated by one.
+fn bench_held_update(c: &mut Criterion) {
+    let cell = HeldSyncCell::new(0u64);
+
+    c.bench_function("HeldSyncCell/update", |b| b.iter_custom(|iterations| run(|index| {
+        for operation in 0..iterations {
+            if index == 0 {
+                cell.update();
+            } else {
+                cell.set(operation);
+            }
+        }
+    })));
+}
+
+criterion_group!(benches, bench_read_heavy, bench_write_heavy, bench_mixed, bench_held_update);
+criterion_main!(benches);
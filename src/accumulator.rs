@@ -0,0 +1,170 @@
+use std::mem::replace;
+
+use crate::{SyncCell, SyncCellRef};
+
+/// A function folding an item into an accumulator.
+type FoldFn<T, A> = Box<dyn Fn(&mut A, T) + Send + Sync>;
+
+/// A cell that folds submitted items into an accumulated value.
+/// Any number of threads can submit items which are combined into the accumulator using a
+/// function provided when the cell is created. The accumulated value can be read at any time or
+/// taken out of the cell, resetting it to its initial value.
+///
+/// This is useful for aggregating metrics such as counts, sums or histograms from many threads.
+///
+/// # Usage
+/// ```
+/// use sync_cell::AccumulatorCell;
+///
+/// let cell = AccumulatorCell::new(|| 0, |sum, item: i32| *sum += item);
+///
+/// cell.submit(1);
+/// cell.submit(2);
+/// cell.submit_all([3, 4]);
+///
+/// assert_eq!(10, cell.take());
+/// assert_eq!(0, cell.get());
+/// ```
+pub struct AccumulatorCell<T, A> {
+    /// The accumulated value.
+    accumulator: SyncCell<A>,
+    /// The function creating the initial value of the accumulator.
+    init: Box<dyn Fn() -> A + Send + Sync>,
+    /// The function folding an item into the accumulator.
+    fold: FoldFn<T, A>,
+}
+
+impl <T, A> AccumulatorCell<T, A> {
+    /// Creates a new `AccumulatorCell`.
+    ///
+    /// - `init` - A function creating the initial value of the accumulator. This is called when
+    ///   the cell is created and every time it is reset.
+    /// - `fold` - A function folding an item into the accumulator.
+    pub fn new(init: impl Fn() -> A + Send + Sync + 'static,
+            fold: impl Fn(&mut A, T) + Send + Sync + 'static) -> Self {
+        Self {
+            accumulator: SyncCell::new(init()),
+            init: Box::new(init),
+            fold: Box::new(fold),
+        }
+    }
+
+    /// Folds an item into the accumulator.
+    ///
+    /// - `item` - The item to submit.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn submit(&self, item: T) {
+        (self.fold)(&mut self.accumulator.borrow_mut(), item)
+    }
+
+    /// Folds many items into the accumulator while only acquiring the lock once.
+    ///
+    /// - `items` - The items to submit.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn submit_all(&self, items: impl IntoIterator<Item = T>) {
+        let mut accumulator = self.accumulator.borrow_mut();
+
+        for item in items {
+            (self.fold)(&mut accumulator, item);
+        }
+    }
+
+    /// Takes the accumulated value out of the cell and resets it to its initial value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take(&self) -> A {
+        let initial = (self.init)();
+
+        replace(&mut *self.accumulator.borrow_mut(), initial)
+    }
+
+    /// Resets the accumulated value to its initial value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn reset(&self) {
+        self.accumulator.set((self.init)())
+    }
+
+    /// Borrows a immutable reference to the accumulated value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, A> {
+        self.accumulator.borrow()
+    }
+
+    /// Retrieves the accumulated value stored in this `AccumulatorCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> A {
+        self.accumulator.into_inner()
+    }
+}
+
+impl <T, A: Clone> AccumulatorCell<T, A> {
+    /// Gets the accumulated value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> A {
+        self.accumulator.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::AccumulatorCell;
+
+    #[test]
+    pub fn test_accumulator_cell_submit() {
+        let cell = AccumulatorCell::new(Vec::new, |items, item| items.push(item));
+
+        cell.submit(1);
+        cell.submit(2);
+
+        assert_eq!(vec![1, 2], cell.get());
+    }
+
+    #[test]
+    pub fn test_accumulator_cell_take() {
+        let cell = AccumulatorCell::new(|| 1, |product, item: i32| *product *= item);
+
+        cell.submit_all([2, 3]);
+
+        assert_eq!(6, cell.take());
+        assert_eq!(1, cell.get());
+
+        cell.submit(4);
+        cell.reset();
+        assert_eq!(1, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_accumulator_cell_threads() {
+        let cell = Arc::new(AccumulatorCell::new(|| 0u64, |sum, item: u64| *sum += item));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for i in 1..=100 {
+                    cell.submit(i);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(20200, *cell.borrow());
+    }
+}
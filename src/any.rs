@@ -0,0 +1,218 @@
+use std::{any::Any, marker::PhantomData, ops::{Deref, DerefMut}};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A type-erased value stored in an `AnyCell`.
+type AnyValue = Box<dyn Any + Send + Sync>;
+
+/// A cell holding a value of any type that is checked when the value is accessed.
+/// The type of the value can change every time it is set. This is useful for plugin systems that
+/// share slots holding values of types unknown to the host.
+///
+/// # Usage
+/// ```
+/// use sync_cell::AnyCell;
+///
+/// let cell = AnyCell::new(1u32);
+///
+/// assert_eq!(Some(1), cell.get::<u32>());
+/// assert_eq!(None, cell.get::<String>());
+///
+/// cell.set(String::from("plugin"));
+///
+/// assert!(cell.is::<String>());
+/// *cell.borrow_mut::<String>().unwrap() += " data";
+/// assert_eq!("plugin data", &*cell.borrow::<String>().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct AnyCell {
+    /// The type-erased value of this cell.
+    data: SyncCell<AnyValue>,
+}
+
+impl AnyCell {
+    /// Creates a new `AnyCell`.
+    ///
+    /// - `data` - The initial value of the `AnyCell`.
+    pub fn new<T: Any + Send + Sync>(data: T) -> Self {
+        Self {
+            data: SyncCell::new(Box::new(data)),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// The new value does not need to have the same type as the previous value.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set<T: Any + Send + Sync>(&self, value: T) {
+        self.data.set(Box::new(value))
+    }
+
+    /// Replaces the value contained in this cell.
+    /// The previous value is returned as a type-erased box.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace<T: Any + Send + Sync>(&self, value: T) -> Box<dyn Any + Send + Sync> {
+        self.data.replace(Box::new(value))
+    }
+
+    /// Checks if the value contained in this cell has a given type.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is<T: Any>(&self) -> bool {
+        self.data.borrow().is::<T>()
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// Returns `None` if the value does not have the requested type.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow<T: Any>(&self) -> Option<AnyCellRef<'_, T>> {
+        let data = self.data.borrow();
+
+        data.is::<T>().then(|| AnyCellRef {
+            data,
+            _type: PhantomData,
+        })
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// Returns `None` if the value does not have the requested type.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut<T: Any>(&self) -> Option<AnyCellGuard<'_, T>> {
+        let data = self.data.borrow_mut();
+
+        data.is::<T>().then(|| AnyCellGuard {
+            data,
+            _type: PhantomData,
+        })
+    }
+
+    /// Gets the value contained in this cell.
+    /// Returns `None` if the value does not have the requested type.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get<T: Any + Clone>(&self) -> Option<T> {
+        self.data.borrow().downcast_ref::<T>().cloned()
+    }
+
+    /// Retrieves the inner value stored in this `AnyCell`.
+    /// If the value does not have the requested type the cell is returned as an error. The cell
+    /// is boxed to keep the result small.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner<T: Any>(self) -> Result<T, Box<Self>> {
+        match self.data.into_inner().downcast::<T>() {
+            Ok(value) => Ok(*value),
+            Err(data) => Err(Box::new(Self {
+                data: SyncCell::new(data),
+            })),
+        }
+    }
+
+    /// Retrieves the inner type-erased value stored in this `AnyCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_any(self) -> Box<dyn Any + Send + Sync> {
+        self.data.into_inner()
+    }
+}
+
+/// A typed borrow of the value in an `AnyCell`.
+pub struct AnyCellRef<'a, T> {
+    /// The lock guard over the type-erased value.
+    data: SyncCellRef<'a, AnyValue>,
+    /// The type of the value which has already been checked.
+    _type: PhantomData<&'a T>,
+}
+
+impl <T: Any> Deref for AnyCellRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.data.downcast_ref() {
+            Some(value) => value,
+            None => unreachable!("Cell value changed type while borrowed."),
+        }
+    }
+}
+
+/// A typed mutable borrow of the value in an `AnyCell`.
+pub struct AnyCellGuard<'a, T> {
+    /// The lock guard over the type-erased value.
+    data: SyncCellGuard<'a, AnyValue>,
+    /// The type of the value which has already been checked.
+    _type: PhantomData<&'a mut T>,
+}
+
+impl <T: Any> Deref for AnyCellGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.data.downcast_ref() {
+            Some(value) => value,
+            None => unreachable!("Cell value changed type while borrowed."),
+        }
+    }
+}
+
+impl <T: Any> DerefMut for AnyCellGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self.data.downcast_mut() {
+            Some(value) => value,
+            None => unreachable!("Cell value changed type while borrowed."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AnyCell;
+
+    #[test]
+    pub fn test_any_cell_get() {
+        let cell = AnyCell::new(1i32);
+
+        assert!(cell.is::<i32>());
+        assert_eq!(Some(1), cell.get::<i32>());
+        assert_eq!(None, cell.get::<i64>());
+
+        cell.set("text");
+
+        assert_eq!(None, cell.get::<i32>());
+        assert_eq!(Some("text"), cell.get::<&str>());
+    }
+
+    #[test]
+    pub fn test_any_cell_borrow() {
+        let cell = AnyCell::new(vec![1]);
+
+        assert!(cell.borrow::<String>().is_none());
+        assert!(cell.borrow_mut::<String>().is_none());
+
+        cell.borrow_mut::<Vec<i32>>().unwrap().push(2);
+
+        assert_eq!(2, cell.borrow::<Vec<i32>>().unwrap().len());
+    }
+
+    #[test]
+    pub fn test_any_cell_into_inner() {
+        let cell = AnyCell::new(5u8);
+        let cell = cell.into_inner::<u16>().unwrap_err();
+
+        assert_eq!(Ok(5u8), cell.into_inner::<u8>().map_err(|_| ()));
+    }
+}
@@ -0,0 +1,90 @@
+use proptest::{arbitrary::{Arbitrary, any_with}, option::{self, OptionStrategy}, strategy::{Map, Strategy}};
+
+use crate::{HeldSyncCell, RawSyncLock, SyncCell};
+
+/// Generates cells holding an arbitrary value.
+/// This is only available with the `proptest` feature.
+///
+/// # Usage
+/// ```
+/// use proptest::{arbitrary::any, strategy::{Strategy, ValueTree}, test_runner::TestRunner};
+/// use sync_cell::SyncCell;
+///
+/// let mut runner = TestRunner::deterministic();
+/// let cell = any::<SyncCell<u8>>().new_tree(&mut runner).unwrap().current();
+///
+/// cell.set(1);
+/// assert_eq!(1, cell.get());
+/// ```
+impl <T: Arbitrary + 'static, L: RawSyncLock + 'static> Arbitrary for SyncCell<T, L> {
+    type Parameters = T::Parameters;
+    type Strategy = Map<T::Strategy, fn(T) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        any_with::<T>(args).prop_map(|value| SyncCell::with_lock(value, L::INIT))
+    }
+}
+
+/// Generates cells holding an arbitrary current value and, about half of the time, an arbitrary
+/// incoming value that has not been applied by `update`.
+/// The parameters are used to generate both values.
+/// This is only available with the `proptest` feature.
+impl <T: Arbitrary + 'static> Arbitrary for HeldSyncCell<T> where T::Parameters: Clone {
+    type Parameters = T::Parameters;
+    type Strategy = Map<(T::Strategy, OptionStrategy<T::Strategy>), fn((T, Option<T>)) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        (any_with::<T>(args.clone()), option::of(any_with::<T>(args))).prop_map(|(current, incoming)| {
+            let cell = HeldSyncCell::new(current);
+
+            if let Some(incoming) = incoming {
+                cell.set(incoming);
+            }
+
+            cell
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{arbitrary::any, strategy::{Strategy, ValueTree}, test_runner::TestRunner};
+
+    use crate::{HeldSyncCell, SyncCell};
+
+    #[test]
+    pub fn test_sync_cell_arbitrary() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any::<SyncCell<(u8, bool)>>();
+
+        let values: Vec<_> = (0..32).map(|_| strategy.new_tree(&mut runner).unwrap().current().get()).collect();
+
+        assert!(values.iter().any(|value| *value != values[0]));
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_arbitrary() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any::<HeldSyncCell<u8>>();
+
+        let mut with_update = 0;
+        let mut without_update = 0;
+
+        for _ in 0..64 {
+            let cell = strategy.new_tree(&mut runner).unwrap().current();
+
+            let has_update = cell.has_update();
+
+            match has_update {
+                true => with_update += 1,
+                false => without_update += 1,
+            }
+
+            assert_eq!(has_update, cell.update());
+            assert!(!cell.has_update());
+        }
+
+        assert!(with_update > 0);
+        assert!(without_update > 0);
+    }
+}
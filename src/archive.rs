@@ -0,0 +1,189 @@
+use core::{fmt::{self, Debug, Formatter}, ops::Deref};
+
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize, bytecheck::CheckBytes, rancor::Fallible};
+
+use crate::{HeldSyncCell, RawSyncLock, SyncCell};
+
+/// The archived form of a `SyncCell`, which is the archived value of the cell.
+/// The value is read through `Deref` without deserializing it.
+/// This type is only available with the `rkyv` feature.
+///
+/// # Usage
+/// ```
+/// use rkyv::rancor::Error;
+/// use sync_cell::SyncCell;
+///
+/// let cell = SyncCell::new(vec![1, 2, 3]);
+/// let bytes = rkyv::to_bytes::<Error>(&cell).unwrap();
+///
+/// let archived = rkyv::access::<rkyv::Archived<SyncCell<Vec<i32>>>, Error>(&bytes).unwrap();
+/// assert_eq!(3, archived.len());
+///
+/// let cell: SyncCell<Vec<i32>> = rkyv::deserialize::<_, Error>(archived).unwrap();
+/// assert_eq!(vec![1, 2, 3], cell.get());
+/// ```
+#[derive(Portable, CheckBytes)]
+#[rkyv(crate = rkyv)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(transparent)]
+pub struct ArchivedSyncCell<A> {
+    /// The archived value of the cell.
+    value: A,
+}
+
+/// The archived form of a `HeldSyncCell`, which is the archived current value of the cell.
+/// The value is read through `Deref` without deserializing it.
+/// This type is only available with the `rkyv` feature.
+#[derive(Portable, CheckBytes)]
+#[rkyv(crate = rkyv)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(transparent)]
+pub struct ArchivedHeldSyncCell<A> {
+    /// The archived current value of the cell.
+    value: A,
+}
+
+impl <A> Deref for ArchivedSyncCell<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        &self.value
+    }
+}
+
+impl <A: Debug> Debug for ArchivedSyncCell<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArchivedSyncCell").field(&self.value).finish()
+    }
+}
+
+impl <A> Deref for ArchivedHeldSyncCell<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        &self.value
+    }
+}
+
+impl <A: Debug> Debug for ArchivedHeldSyncCell<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArchivedHeldSyncCell").field(&self.value).finish()
+    }
+}
+
+/// Archives the value of the cell.
+/// The value is borrowed once when it is serialized and again when it is resolved, so it must not
+/// be changed by another thread while the cell is being archived.
+///
+/// # Panicking
+/// Archiving the cell will panic if the lock becomes poisoned.
+impl <T: Archive, L: RawSyncLock> Archive for SyncCell<T, L> {
+    type Archived = ArchivedSyncCell<T::Archived>;
+    type Resolver = T::Resolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        rkyv::munge::munge!(let ArchivedSyncCell { value } = out);
+        self.borrow().resolve(resolver, value);
+    }
+}
+
+impl <T: Serialize<S>, L: RawSyncLock, S: Fallible + ?Sized> Serialize<S> for SyncCell<T, L> {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl <A: Deserialize<T, D>, T, L: RawSyncLock, D: Fallible + ?Sized> Deserialize<SyncCell<T, L>, D> for ArchivedSyncCell<A> {
+    fn deserialize(&self, deserializer: &mut D) -> Result<SyncCell<T, L>, D::Error> {
+        Ok(SyncCell::with_lock(self.value.deserialize(deserializer)?, L::INIT))
+    }
+}
+
+/// Archives the current value of the cell.
+/// Values that have been set but not applied by `update` are not archived, so the deserialized
+/// cell has no update available and uses the default update policy.
+/// The value is borrowed once when it is serialized and again when it is resolved, so it must not
+/// be updated by another thread while the cell is being archived.
+///
+/// # Panicking
+/// Archiving the cell will panic if the lock becomes poisoned.
+impl <T: Archive> Archive for HeldSyncCell<T> {
+    type Archived = ArchivedHeldSyncCell<T::Archived>;
+    type Resolver = T::Resolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        rkyv::munge::munge!(let ArchivedHeldSyncCell { value } = out);
+        self.borrow().resolve(resolver, value);
+    }
+}
+
+impl <T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for HeldSyncCell<T> {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl <A: Deserialize<T, D>, T, D: Fallible + ?Sized> Deserialize<HeldSyncCell<T>, D> for ArchivedHeldSyncCell<A> {
+    fn deserialize(&self, deserializer: &mut D) -> Result<HeldSyncCell<T>, D::Error> {
+        Ok(HeldSyncCell::new(self.value.deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::{Archived, rancor::Error};
+
+    use crate::{HeldSyncCell, SyncCell};
+
+    #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+    #[rkyv(crate = rkyv)]
+    struct State {
+        score: SyncCell<u32>,
+        names: HeldSyncCell<Vec<String>>,
+    }
+
+    #[test]
+    pub fn test_sync_cell_rkyv() {
+        let cell = SyncCell::new(vec![1, 2, 3]);
+        let bytes = rkyv::to_bytes::<Error>(&cell).unwrap();
+
+        let archived = rkyv::access::<Archived<SyncCell<Vec<i32>>>, Error>(&bytes).unwrap();
+        assert_eq!([1, 2, 3], archived.as_slice());
+
+        let cell: SyncCell<Vec<i32>> = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+        assert_eq!(vec![1, 2, 3], cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_rkyv() {
+        let cell = HeldSyncCell::new(1);
+        cell.set(2);
+
+        let bytes = rkyv::to_bytes::<Error>(&cell).unwrap();
+
+        let archived = rkyv::access::<Archived<HeldSyncCell<i32>>, Error>(&bytes).unwrap();
+        assert_eq!(1, archived.to_native());
+
+        let cell: HeldSyncCell<i32> = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+        assert_eq!(1, cell.get());
+        assert!(!cell.has_update());
+    }
+
+    #[test]
+    pub fn test_sync_cell_rkyv_derive() {
+        let state = State {
+            score: SyncCell::new(10),
+            names: HeldSyncCell::new(vec![String::from("a"), String::from("b")]),
+        };
+
+        let bytes = rkyv::to_bytes::<Error>(&state).unwrap();
+
+        let archived = rkyv::access::<ArchivedState, Error>(&bytes).unwrap();
+        assert_eq!(10, archived.score.to_native());
+        assert_eq!("b", archived.names[1].as_str());
+
+        let state: State = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(10, state.score.get());
+        assert_eq!(vec![String::from("a"), String::from("b")], state.names.get());
+    }
+}
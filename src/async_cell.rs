@@ -0,0 +1,1144 @@
+use std::{cell::UnsafeCell, future::{Future, poll_fn}, mem::replace, ops::{AsyncFnOnce, Deref, DerefMut}, pin::Pin, sync::{Mutex, MutexGuard}, task::{Context, Poll, Waker}, time::{Duration, Instant}};
+
+#[cfg(feature = "tokio")]
+use std::task::ready;
+
+#[cfg(feature = "tokio")]
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{DefaultRawLock, MAX_BACKOFF, MIN_BACKOFF, RawSyncLock, SyncCell, SyncCellError, SyncCellGuard, SyncCellRef, block_on::block_on, timer::Sleep};
+
+/// The state of the lock of an `AsyncSyncCell`.
+/// Without the `tokio` feature the lock is implemented here so that the `async` feature does not
+/// pull in an async runtime. It wakes every waiting task whenever it is released, which is simple
+/// but wasteful when many tasks wait for the same cell. With the `tokio` feature borrows are
+/// handed out by a `tokio::sync::RwLock` instead, and only the version of the value and the tasks
+/// waiting for it to change are tracked here.
+struct LockState {
+    /// The number of immutable borrows held.
+    #[cfg(not(feature = "tokio"))]
+    readers: usize,
+    /// Whether a mutable borrow is held.
+    #[cfg(not(feature = "tokio"))]
+    writer: bool,
+    /// The number of mutable borrows waiting for the lock.
+    /// New immutable borrows wait while this is non-zero so that writers are not starved.
+    #[cfg(not(feature = "tokio"))]
+    writers_waiting: usize,
+    /// The number of mutable borrows that have been released.
+    version: u64,
+    /// The wakers of tasks waiting for the lock or, with the `tokio` feature, for the value to
+    /// change.
+    waiters: Vec<Waker>,
+}
+
+/// A boxed future waiting for the `tokio::sync::RwLock` of an `AsyncSyncCell`.
+#[cfg(feature = "tokio")]
+type LockFuture<'a, G> = Pin<Box<dyn Future<Output = G> + Send + 'a>>;
+
+/// Polls a request for the `tokio::sync::RwLock` of an `AsyncSyncCell`.
+/// The lock is first taken without waiting, so the request is only boxed and queued if the lock
+/// is contended.
+///
+/// - `waiting` - The queued request or `None` if the lock has not been contended yet.
+/// - `try_lock` - A function taking the lock without waiting.
+/// - `lock` - A function creating a queued request for the lock.
+/// - `cx` - The context of the current task.
+#[cfg(feature = "tokio")]
+fn poll_lock<'a, G>(waiting: &mut Option<LockFuture<'a, G>>, try_lock: impl FnOnce() -> Option<G>, lock: impl FnOnce() -> LockFuture<'a, G>, cx: &mut Context<'_>) -> Poll<G> {
+    let request = match waiting {
+        Some(request) => request,
+        None => match try_lock() {
+            Some(guard) => return Poll::Ready(guard),
+            None => waiting.insert(lock()),
+        },
+    };
+
+    let guard = ready!(request.as_mut().poll(cx));
+    *waiting = None;
+
+    Poll::Ready(guard)
+}
+
+impl LockState {
+    /// Registers a waker to be woken when the lock is released.
+    fn register(&mut self, waker: &Waker) {
+        if !self.waiters.iter().any(|waiter| waiter.will_wake(waker)) {
+            self.waiters.push(waker.clone());
+        }
+    }
+
+    /// Wakes every task waiting for the lock.
+    fn wake_all(&mut self) {
+        for waiter in self.waiters.drain(..) {
+            waiter.wake();
+        }
+    }
+}
+
+/// A cell like `SyncCell` for use in async code.
+/// Borrowing the value returns a future that yields to the executor while the value is
+/// borrowed elsewhere instead of blocking the thread.
+///
+/// Unlike `SyncCell`, the lock is never poisoned, so none of the methods of this cell panic.
+///
+/// Unlike the guards of `std::sync::RwLock`, the borrows of this cell are `Send` whenever the
+/// cell is `Sync`. They can be held across an `.await` in tasks that are spawned on
+/// multi-threaded executors.
+///
+/// With the `tokio` feature the value is protected by a `tokio::sync::RwLock`, which queues
+/// waiting tasks fairly and wakes only the tasks that can take the lock. Without it a simpler lock
+/// built into this crate is used that wakes every waiting task whenever the lock is released.
+///
+/// This type is only available with the `async` feature.
+///
+/// # Usage
+/// ```
+/// use sync_cell::AsyncSyncCell;
+///
+/// # async fn example() {
+/// let cell = AsyncSyncCell::new(0);
+///
+/// cell.set(1).await;
+/// *cell.borrow_mut().await += 1;
+///
+/// assert_eq!(2, cell.get().await);
+/// # }
+/// ```
+pub struct AsyncSyncCell<T: ?Sized> {
+    /// The state of the lock protecting the value.
+    state: Mutex<LockState>,
+    /// The lock protecting the value.
+    #[cfg(feature = "tokio")]
+    lock: RwLock<()>,
+    /// The value of this cell.
+    data: UnsafeCell<T>,
+}
+
+// SAFETY: The value is only accessed through borrows that are handed out according to the lock,
+// in the same way as `std::sync::RwLock`.
+unsafe impl <T: ?Sized + Send + Sync> Sync for AsyncSyncCell<T> {}
+
+impl <T> AsyncSyncCell<T> {
+    /// Creates a new `AsyncSyncCell`.
+    ///
+    /// - `data` - The initial value of the `AsyncSyncCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            state: Mutex::new(LockState {
+                #[cfg(not(feature = "tokio"))]
+                readers: 0,
+                #[cfg(not(feature = "tokio"))]
+                writer: false,
+                #[cfg(not(feature = "tokio"))]
+                writers_waiting: 0,
+                version: 0,
+                waiters: Vec::new(),
+            }),
+            #[cfg(feature = "tokio")]
+            lock: RwLock::const_new(()),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// This waits until no other borrows of the value are held.
+    ///
+    /// - `value` - The new value of the cell.
+    pub async fn set(&self, value: T) {
+        *self.borrow_mut().await = value;
+    }
+
+    /// Replaces the value contained in this cell.
+    /// This waits until no other borrows of the value are held. The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    pub async fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut().await, value)
+    }
+
+    /// Sets the value contained in this cell from synchronous code.
+    /// This blocks the current thread until no other borrows of the value are held, so threads
+    /// and async tasks can share the same cell.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if it is called from inside another blocking call of this crate.
+    /// Calls from inside other async runtimes cannot be detected and will block the executor
+    /// thread instead.
+    pub fn blocking_set(&self, value: T) {
+        block_on(self.set(value))
+    }
+
+    /// Retrieves the inner value stored in this `AsyncSyncCell`.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl <T: ?Sized> AsyncSyncCell<T> {
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// The returned future resolves once no mutable borrow is held or waiting.
+    pub fn borrow(&self) -> AsyncBorrow<'_, T> {
+        AsyncBorrow {
+            cell: self,
+            #[cfg(feature = "tokio")]
+            waiting: None,
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// The returned future resolves once no other borrows are held.
+    pub fn borrow_mut(&self) -> AsyncBorrowMut<'_, T> {
+        AsyncBorrowMut {
+            cell: self,
+            #[cfg(not(feature = "tokio"))]
+            waiting: false,
+            #[cfg(feature = "tokio")]
+            waiting: None,
+        }
+    }
+
+    /// Modifies the value of this cell with an async function.
+    /// The mutable borrow is held until the future returned by the function completes, so the
+    /// function can await other work such as I/O while computing the new value. The result of
+    /// the function is returned.
+    ///
+    /// - `f` - The function to apply to the value.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::AsyncSyncCell;
+    ///
+    /// # async fn load() -> i32 { 1 }
+    /// # async fn example() {
+    /// let cell = AsyncSyncCell::new(1);
+    ///
+    /// cell.update_async(async |value| *value += load().await).await;
+    /// assert_eq!(2, cell.get().await);
+    /// # }
+    /// ```
+    pub async fn update_async<R>(&self, f: impl AsyncFnOnce(&mut T) -> R) -> R {
+        let mut borrowed = self.borrow_mut().await;
+
+        f(&mut borrowed).await
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell waiting at most `timeout`.
+    /// Returns `None` if the borrow could not be acquired in time. The timeout is measured by the
+    /// timer thread shared by this crate, so no timer from an async runtime is required.
+    ///
+    /// - `timeout` - The maximum duration to wait for.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::time::Duration;
+    /// use sync_cell::AsyncSyncCell;
+    ///
+    /// # async fn example() {
+    /// let cell = AsyncSyncCell::new(1);
+    ///
+    /// let borrowed = cell.borrow_timeout(Duration::from_millis(10)).await;
+    /// assert_eq!(Some(1), borrowed.map(|value| *value));
+    /// # }
+    /// ```
+    pub async fn borrow_timeout(&self, timeout: Duration) -> Option<AsyncSyncCellRef<'_, T>> {
+        Timeout::new(self.borrow(), timeout).await
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell waiting at most `timeout`.
+    /// Returns `None` if the borrow could not be acquired in time. The timeout is measured by the
+    /// timer thread shared by this crate, so no timer from an async runtime is required.
+    ///
+    /// - `timeout` - The maximum duration to wait for.
+    pub async fn borrow_mut_timeout(&self, timeout: Duration) -> Option<AsyncSyncCellGuard<'_, T>> {
+        Timeout::new(self.borrow_mut(), timeout).await
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell without waiting.
+    /// Returns `None` if the value is mutably borrowed or a mutable borrow is waiting.
+    pub fn try_borrow(&self) -> Option<AsyncSyncCellRef<'_, T>> {
+        #[cfg(feature = "tokio")]
+        let guard = self.lock.try_read().ok()?;
+
+        #[cfg(not(feature = "tokio"))]
+        {
+            let mut state = self.lock_state();
+
+            if state.writer || state.writers_waiting > 0 {
+                return None;
+            }
+
+            state.readers += 1;
+        }
+
+        Some(AsyncSyncCellRef {
+            cell: self,
+            #[cfg(feature = "tokio")]
+            _guard: guard,
+        })
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell without waiting.
+    /// Returns `None` if the value is borrowed.
+    pub fn try_borrow_mut(&self) -> Option<AsyncSyncCellGuard<'_, T>> {
+        #[cfg(feature = "tokio")]
+        let guard = self.lock.try_write().ok()?;
+
+        #[cfg(not(feature = "tokio"))]
+        {
+            let mut state = self.lock_state();
+
+            if state.writer || state.readers > 0 {
+                return None;
+            }
+
+            state.writer = true;
+        }
+
+        Some(AsyncSyncCellGuard {
+            cell: self,
+            #[cfg(feature = "tokio")]
+            _guard: guard,
+        })
+    }
+
+    /// Gets the version of the value of this cell.
+    /// The version is incremented every time a mutable borrow of the value is released.
+    pub fn version(&self) -> u64 {
+        self.lock_state().version
+    }
+
+    /// Waits until the value of this cell has been modified since it was last observed.
+    /// The returned future resolves to the new version once the version differs from `seen`,
+    /// which allows a task to await changes instead of polling the value.
+    ///
+    /// - `seen` - The last version observed by the caller.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::AsyncSyncCell;
+    ///
+    /// # async fn example(cell: &AsyncSyncCell<i32>) {
+    /// let mut seen = cell.version();
+    ///
+    /// loop {
+    ///     seen = cell.changed(seen).await;
+    ///     println!("{}", cell.get().await);
+    /// }
+    /// # }
+    /// ```
+    pub fn changed(&self, seen: u64) -> AsyncChanged<'_, T> {
+        AsyncChanged {
+            cell: self,
+            seen,
+        }
+    }
+
+    /// Gets a mutable reference to the value of this cell.
+    /// No waiting is required as the cell is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    /// Gets a raw pointer to the value of this cell.
+    /// Creating the pointer does not lock the cell. Dereferencing it is only sound while the
+    /// caller holds a borrow of this cell that permits the access.
+    pub fn data_ptr(&self) -> *mut T {
+        self.data.get()
+    }
+
+    /// Locks the state of the lock protecting the value.
+    fn lock_state(&self) -> MutexGuard<'_, LockState> {
+        // The state is never left inconsistent while locked, so a poisoned lock can be used.
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+impl <T: Clone> AsyncSyncCell<T> {
+    /// Gets the value contained in this cell.
+    /// This waits until no mutable borrow of the value is held or waiting.
+    pub async fn get(&self) -> T {
+        self.borrow().await.clone()
+    }
+
+    /// Gets the value contained in this cell from synchronous code.
+    /// This blocks the current thread until no mutable borrow of the value is held or waiting.
+    ///
+    /// # Panicking
+    /// This method will panic if it is called from inside another blocking call of this crate.
+    /// Calls from inside other async runtimes cannot be detected and will block the executor
+    /// thread instead.
+    pub fn blocking_get(&self) -> T {
+        block_on(self.get())
+    }
+
+    /// Creates a stream of the values of this cell.
+    /// The stream yields a clone of the value every time it is modified after the stream was
+    /// created. If the value is modified several times before the stream is polled only the
+    /// latest value is yielded.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::AsyncSyncCell;
+    ///
+    /// # async fn example(cell: &AsyncSyncCell<i32>) {
+    /// let mut values = cell.value_stream();
+    ///
+    /// loop {
+    ///     println!("{}", values.next().await);
+    /// }
+    /// # }
+    /// ```
+    pub fn value_stream(&self) -> AsyncValueStream<'_, T> {
+        AsyncValueStream {
+            cell: self,
+            seen: self.version(),
+            #[cfg(feature = "tokio")]
+            reading: None,
+        }
+    }
+}
+
+impl <T: Default> Default for AsyncSyncCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for AsyncSyncCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A future that resolves to an immutable borrow of the value of an `AsyncSyncCell`.
+pub struct AsyncBorrow<'a, T: ?Sized> {
+    /// The cell being borrowed.
+    cell: &'a AsyncSyncCell<T>,
+    /// The queued request for the lock or `None` if the lock has not been contended yet.
+    #[cfg(feature = "tokio")]
+    waiting: Option<LockFuture<'a, RwLockReadGuard<'a, ()>>>,
+}
+
+impl <'a, T: ?Sized> Future for AsyncBorrow<'a, T> {
+    type Output = AsyncSyncCellRef<'a, T>;
+
+    #[cfg(feature = "tokio")]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cell = self.cell;
+
+        poll_lock(&mut self.waiting, || cell.lock.try_read().ok(), || Box::pin(cell.lock.read()), cx)
+            .map(|guard| AsyncSyncCellRef {
+                cell,
+                _guard: guard,
+            })
+    }
+
+    #[cfg(not(feature = "tokio"))]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.cell.lock_state();
+
+        if state.writer || state.writers_waiting > 0 {
+            state.register(cx.waker());
+            return Poll::Pending;
+        }
+
+        state.readers += 1;
+
+        Poll::Ready(AsyncSyncCellRef {
+            cell: self.cell,
+        })
+    }
+}
+
+/// A future that resolves to a mutable borrow of the value of an `AsyncSyncCell`.
+pub struct AsyncBorrowMut<'a, T: ?Sized> {
+    /// The cell being borrowed.
+    cell: &'a AsyncSyncCell<T>,
+    /// Whether this borrow is counted as a waiting writer.
+    #[cfg(not(feature = "tokio"))]
+    waiting: bool,
+    /// The queued request for the lock or `None` if the lock has not been contended yet.
+    #[cfg(feature = "tokio")]
+    waiting: Option<LockFuture<'a, RwLockWriteGuard<'a, ()>>>,
+}
+
+impl <'a, T: ?Sized> Future for AsyncBorrowMut<'a, T> {
+    type Output = AsyncSyncCellGuard<'a, T>;
+
+    #[cfg(feature = "tokio")]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cell = self.cell;
+
+        poll_lock(&mut self.waiting, || cell.lock.try_write().ok(), || Box::pin(cell.lock.write()), cx)
+            .map(|guard| AsyncSyncCellGuard {
+                cell,
+                _guard: guard,
+            })
+    }
+
+    #[cfg(not(feature = "tokio"))]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cell = self.cell;
+        let mut state = cell.lock_state();
+
+        if state.writer || state.readers > 0 {
+            if !self.waiting {
+                state.writers_waiting += 1;
+                self.waiting = true;
+            }
+
+            state.register(cx.waker());
+            return Poll::Pending;
+        }
+
+        if self.waiting {
+            state.writers_waiting -= 1;
+            self.waiting = false;
+        }
+
+        state.writer = true;
+
+        Poll::Ready(AsyncSyncCellGuard {
+            cell,
+        })
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+impl <T: ?Sized> Drop for AsyncBorrowMut<'_, T> {
+    fn drop(&mut self) {
+        if self.waiting {
+            let mut state = self.cell.lock_state();
+
+            // Readers held back by this writer can now continue.
+            state.writers_waiting -= 1;
+            state.wake_all();
+        }
+    }
+}
+
+/// A future that gives up on another future once a deadline has passed.
+struct Timeout<F> {
+    /// The future being waited for.
+    future: F,
+    /// The time after which the future is given up on or `None` if it never is.
+    deadline: Option<Instant>,
+    /// The registration with the shared timer thread or `None` if no timer was started.
+    /// Dropping it cancels the timer.
+    sleep: Option<Sleep>,
+}
+
+impl <F> Timeout<F> {
+    /// Creates a new `Timeout`.
+    ///
+    /// - `future` - The future to wait for.
+    /// - `timeout` - The maximum duration to wait for.
+    fn new(future: F, timeout: Duration) -> Self {
+        Self {
+            future,
+            deadline: Instant::now().checked_add(timeout),
+            sleep: None,
+        }
+    }
+}
+
+impl <F: Future + Unpin> Future for Timeout<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = Pin::new(&mut self.future).poll(cx) {
+            self.sleep = None;
+            return Poll::Ready(Some(output));
+        }
+
+        let Some(deadline) = self.deadline else {
+            return Poll::Pending;
+        };
+
+        if Instant::now() >= deadline {
+            self.sleep = None;
+            return Poll::Ready(None);
+        }
+
+        match &self.sleep {
+            Some(sleep) => sleep.set_waker(cx.waker()),
+            None => self.sleep = Some(Sleep::new(deadline, cx.waker())),
+        }
+
+        Poll::Pending
+    }
+}
+
+/// A future that resolves once the value of an `AsyncSyncCell` has been modified.
+pub struct AsyncChanged<'a, T: ?Sized> {
+    /// The cell being watched.
+    cell: &'a AsyncSyncCell<T>,
+    /// The last version observed.
+    seen: u64,
+}
+
+impl <T: ?Sized> Future for AsyncChanged<'_, T> {
+    type Output = u64;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.cell.lock_state();
+
+        if state.version != self.seen {
+            return Poll::Ready(state.version);
+        }
+
+        state.register(cx.waker());
+        Poll::Pending
+    }
+}
+
+/// A stream of the values of an `AsyncSyncCell`.
+/// Created by `AsyncSyncCell::value_stream`.
+pub struct AsyncValueStream<'a, T: ?Sized> {
+    /// The cell being watched.
+    cell: &'a AsyncSyncCell<T>,
+    /// The version of the last value yielded.
+    seen: u64,
+    /// The borrow of the next value or `None` if the value has not changed yet.
+    #[cfg(feature = "tokio")]
+    reading: Option<AsyncBorrow<'a, T>>,
+}
+
+impl <T: Clone> AsyncValueStream<'_, T> {
+    /// Waits for the next value of the cell.
+    pub async fn next(&mut self) -> T {
+        poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    /// Attempts to get the next value of the cell registering the current task to be woken if it
+    /// is not available yet.
+    /// This has the same contract as `Stream::poll_next` except that the stream never ends.
+    ///
+    /// - `cx` - The context of the current task.
+    #[cfg(feature = "tokio")]
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<T> {
+        let reading = match &mut self.reading {
+            Some(reading) => reading,
+            None => {
+                let mut state = self.cell.lock_state();
+
+                if state.version == self.seen {
+                    state.register(cx.waker());
+                    return Poll::Pending;
+                }
+
+                drop(state);
+                self.reading.insert(self.cell.borrow())
+            },
+        };
+
+        let borrowed = ready!(Pin::new(reading).poll(cx));
+        self.reading = None;
+
+        // The version is incremented before a mutable borrow releases the lock, so it cannot
+        // change while the value is borrowed.
+        self.seen = self.cell.version();
+
+        Poll::Ready(borrowed.clone())
+    }
+
+    /// Attempts to get the next value of the cell registering the current task to be woken if it
+    /// is not available yet.
+    /// This has the same contract as `Stream::poll_next` except that the stream never ends.
+    ///
+    /// - `cx` - The context of the current task.
+    #[cfg(not(feature = "tokio"))]
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.cell.lock_state();
+
+        if state.version == self.seen || state.writer || state.writers_waiting > 0 {
+            state.register(cx.waker());
+            return Poll::Pending;
+        }
+
+        self.seen = state.version;
+        state.readers += 1;
+        drop(state);
+
+        let borrowed = AsyncSyncCellRef {
+            cell: self.cell,
+        };
+
+        Poll::Ready(borrowed.clone())
+    }
+}
+
+/// An immutable borrow of the value of an `AsyncSyncCell`.
+/// This is `Send` when `T` is `Send + Sync` and can be held across an `.await`.
+pub struct AsyncSyncCellRef<'a, T: ?Sized> {
+    /// The borrowed cell.
+    cell: &'a AsyncSyncCell<T>,
+    /// The read lock held by this borrow.
+    #[cfg(feature = "tokio")]
+    _guard: RwLockReadGuard<'a, ()>,
+}
+
+impl <T: ?Sized> Deref for AsyncSyncCellRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: A read lock is held for as long as this borrow exists, so no mutable borrow can
+        // exist.
+        unsafe { &*self.cell.data.get() }
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+impl <T: ?Sized> Drop for AsyncSyncCellRef<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.cell.lock_state();
+
+        state.readers -= 1;
+
+        if state.readers == 0 {
+            state.wake_all();
+        }
+    }
+}
+
+/// A mutable borrow of the value of an `AsyncSyncCell`.
+/// This is `Send` when `T` is `Send + Sync` and can be held across an `.await`.
+pub struct AsyncSyncCellGuard<'a, T: ?Sized> {
+    /// The borrowed cell.
+    cell: &'a AsyncSyncCell<T>,
+    /// The write lock held by this borrow, which is released after the version is incremented.
+    #[cfg(feature = "tokio")]
+    _guard: RwLockWriteGuard<'a, ()>,
+}
+
+impl <T: ?Sized> Deref for AsyncSyncCellGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: A write lock is held for as long as this borrow exists, so no other borrow can
+        // exist.
+        unsafe { &*self.cell.data.get() }
+    }
+}
+
+impl <T: ?Sized> DerefMut for AsyncSyncCellGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: A write lock is held for as long as this borrow exists, so no other borrow can
+        // exist.
+        unsafe { &mut *self.cell.data.get() }
+    }
+}
+
+impl <T: ?Sized> Drop for AsyncSyncCellGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.cell.lock_state();
+
+        #[cfg(not(feature = "tokio"))]
+        {
+            state.writer = false;
+        }
+
+        state.version += 1;
+        state.wake_all();
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> SyncCell<T, L> {
+    /// Borrows a immutable reference to the data stored in this cell from async code.
+    /// While the value is mutably borrowed, the returned future yields to the executor and retries
+    /// after a short delay that grows the longer it waits, instead of blocking the thread.
+    ///
+    /// The returned guard is the same as for `borrow` and should not be held across an `.await`.
+    /// This method is only available with the `async` feature.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// # async fn example() {
+    /// let cell = SyncCell::new(1);
+    ///
+    /// *cell.borrow_mut_async().await += 1;
+    /// assert_eq!(2, *cell.borrow_async().await);
+    /// # }
+    /// ```
+    ///
+    /// # Panicking
+    /// The returned future will panic if the lock becomes poisoned and the poison policy of the
+    /// cell does not recover from it.
+    pub fn borrow_async(&self) -> SyncCellBorrow<'_, T, L> {
+        SyncCellBorrow {
+            cell: self,
+            backoff: Backoff::new(),
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell from async code.
+    /// While the value is borrowed, the returned future yields to the executor and retries after a
+    /// short delay that grows the longer it waits, instead of blocking the thread.
+    ///
+    /// The returned guard is the same as for `borrow_mut` and should not be held across an
+    /// `.await`. This method is only available with the `async` feature.
+    ///
+    /// # Panicking
+    /// The returned future will panic if the lock becomes poisoned and the poison policy of the
+    /// cell does not recover from it.
+    pub fn borrow_mut_async(&self) -> SyncCellBorrowMut<'_, T, L> {
+        SyncCellBorrowMut {
+            cell: self,
+            backoff: Backoff::new(),
+        }
+    }
+}
+
+/// The retry state of a future waiting to borrow a `SyncCell`.
+/// Releasing the lock of a `SyncCell` does not notify anyone, so a contended borrow is retried
+/// first right away and then after a delay that doubles with every attempt. The delays are
+/// measured by the shared timer thread, so a waiting task does not keep its executor busy.
+struct Backoff {
+    /// The delay before the next retry, which is zero before the first retry.
+    delay: Duration,
+    /// The timer that wakes the task for the next retry.
+    sleep: Option<Sleep>,
+}
+
+impl Backoff {
+    /// Creates a new `Backoff` for a borrow that has not been tried yet.
+    const fn new() -> Self {
+        Self {
+            delay: Duration::ZERO,
+            sleep: None,
+        }
+    }
+
+    /// Converts the result of trying to borrow a `SyncCell` into a poll result, scheduling the
+    /// next retry if the lock is contended.
+    ///
+    /// - `result` - The result of trying to borrow the cell.
+    /// - `cx` - The context of the current task.
+    fn poll<G>(&mut self, result: Result<G, SyncCellError>, cx: &mut Context<'_>) -> Poll<G> {
+        match result {
+            Ok(guard) => {
+                self.sleep = None;
+                Poll::Ready(guard)
+            },
+            Err(SyncCellError::WouldBlock(_)) => {
+                if self.delay.is_zero() {
+                    cx.waker().wake_by_ref();
+                    self.delay = MIN_BACKOFF;
+                } else {
+                    self.sleep = Some(Sleep::new(Instant::now() + self.delay, cx.waker()));
+                    self.delay = (self.delay * 2).min(MAX_BACKOFF);
+                }
+
+                Poll::Pending
+            },
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+/// A future that resolves to an immutable borrow of the value of a `SyncCell`.
+pub struct SyncCellBorrow<'a, T: ?Sized, L = DefaultRawLock> {
+    /// The cell being borrowed.
+    cell: &'a SyncCell<T, L>,
+    /// When to retry the borrow if the lock is contended.
+    backoff: Backoff,
+}
+
+impl <'a, T: ?Sized, L: RawSyncLock> Future for SyncCellBorrow<'a, T, L> {
+    type Output = SyncCellRef<'a, T, L>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        this.backoff.poll(this.cell.try_borrow(), cx)
+    }
+}
+
+/// A future that resolves to a mutable borrow of the value of a `SyncCell`.
+pub struct SyncCellBorrowMut<'a, T: ?Sized, L = DefaultRawLock> {
+    /// The cell being borrowed.
+    cell: &'a SyncCell<T, L>,
+    /// When to retry the borrow if the lock is contended.
+    backoff: Backoff,
+}
+
+impl <'a, T: ?Sized, L: RawSyncLock> Future for SyncCellBorrowMut<'a, T, L> {
+    type Output = SyncCellGuard<'a, T, L>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        this.backoff.poll(this.cell.try_borrow_mut(), cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::{Future, poll_fn}, pin::Pin, sync::Arc, thread, time::Duration};
+
+    use crate::{AsyncSyncCell, AsyncSyncCellGuard, AsyncSyncCellRef, SyncCell, block_on::block_on};
+
+    /// Fails to compile if the value is not `Send`.
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    pub fn test_async_sync_cell_set_get() {
+        let cell = AsyncSyncCell::new(1);
+
+        block_on(async {
+            cell.set(2).await;
+            assert_eq!(2, cell.replace(3).await);
+            assert_eq!(3, cell.get().await);
+        });
+
+        assert_eq!(3, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_try_borrow() {
+        let cell = AsyncSyncCell::new(1);
+        let borrowed = cell.try_borrow().unwrap();
+
+        assert!(cell.try_borrow().is_some());
+        assert!(cell.try_borrow_mut().is_none());
+
+        drop(borrowed);
+
+        let borrowed = cell.try_borrow_mut().unwrap();
+
+        assert!(cell.try_borrow().is_none());
+
+        drop(borrowed);
+
+        assert!(cell.try_borrow_mut().is_some());
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_wait_for_borrow() {
+        let cell = Arc::new(AsyncSyncCell::new(0));
+        let borrowed = cell.try_borrow().unwrap();
+        let writer = cell.clone();
+
+        let handle = thread::spawn(move || block_on(async {
+            *writer.borrow_mut().await = 1;
+        }));
+
+        thread::sleep(Duration::from_millis(20));
+
+        // The waiting writer holds back new readers.
+        assert!(cell.try_borrow().is_none());
+        assert_eq!(0, *borrowed);
+
+        drop(borrowed);
+        handle.join().unwrap();
+
+        assert_eq!(1, block_on(cell.get()));
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_contended() {
+        let cell = Arc::new(AsyncSyncCell::new(0));
+
+        let handles: Vec<_> = (0..8).map(|_| {
+            let cell = cell.clone();
+
+            thread::spawn(move || block_on(async {
+                for _ in 0..100 {
+                    let mut borrowed = cell.borrow_mut().await;
+                    let value = *borrowed;
+                    thread::yield_now();
+                    *borrowed = value + 1;
+                    drop(borrowed);
+
+                    assert!(*cell.borrow().await > 0);
+                }
+            }))
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(800, block_on(cell.get()));
+    }
+
+    #[test]
+    pub fn test_sync_cell_borrow_async() {
+        let cell = Arc::new(SyncCell::new(0));
+        let borrowed = cell.borrow();
+        let writer = cell.clone();
+
+        let handle = thread::spawn(move || block_on(async {
+            *writer.borrow_mut_async().await = 1;
+        }));
+
+        thread::sleep(Duration::from_millis(20));
+        drop(borrowed);
+        handle.join().unwrap();
+
+        assert_eq!(1, *block_on(cell.borrow_async()));
+    }
+
+    #[test]
+    pub fn test_sync_cell_borrow_async_backoff() {
+        let cell = Arc::new(SyncCell::new(0));
+        let borrowed = cell.borrow_mut();
+        let reader = cell.clone();
+
+        let handle = thread::spawn(move || block_on(async {
+            let mut borrow = reader.borrow_async();
+            let mut polls = 0;
+
+            let value = poll_fn(|cx| {
+                polls += 1;
+                Pin::new(&mut borrow).poll(cx).map(|guard| *guard)
+            }).await;
+
+            (value, polls)
+        }));
+
+        thread::sleep(Duration::from_millis(50));
+        drop(borrowed);
+
+        let (value, polls) = handle.join().unwrap();
+
+        assert_eq!(0, value);
+        assert!(polls < 100, "polled {} times", polls);
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_changed() {
+        let cell = Arc::new(AsyncSyncCell::new(0));
+        let seen = cell.version();
+        let writer = cell.clone();
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            block_on(writer.set(1));
+        });
+
+        let version = block_on(cell.changed(seen));
+        handle.join().unwrap();
+
+        assert_ne!(seen, version);
+        assert_eq!(1, block_on(cell.get()));
+        assert_eq!(version, block_on(cell.changed(seen)));
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_value_stream() {
+        let cell = Arc::new(AsyncSyncCell::new(0));
+        let mut values = cell.value_stream();
+        let writer = cell.clone();
+
+        let handle = thread::spawn(move || {
+            for i in 1..=3 {
+                block_on(writer.set(i));
+                thread::sleep(Duration::from_millis(20));
+            }
+        });
+
+        let mut last = 0;
+
+        while last != 3 {
+            let value = block_on(values.next());
+            assert!(value > last);
+            last = value;
+        }
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_borrow_timeout() {
+        let cell = AsyncSyncCell::new(1);
+        let borrowed = cell.try_borrow_mut().unwrap();
+
+        assert!(block_on(cell.borrow_timeout(Duration::from_millis(20))).is_none());
+        assert!(block_on(cell.borrow_mut_timeout(Duration::from_millis(20))).is_none());
+
+        drop(borrowed);
+
+        // The timed out writer no longer holds back readers.
+        assert!(cell.try_borrow().is_some());
+        assert_eq!(Some(1), block_on(cell.borrow_mut_timeout(Duration::from_millis(20))).map(|value| *value));
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_borrow_timeout_max() {
+        let cell = Arc::new(AsyncSyncCell::new(0));
+        let borrowed = block_on(cell.borrow_mut());
+        let reader = cell.clone();
+
+        // A timeout too large to be represented as an instant never elapses.
+        let handle = thread::spawn(move || block_on(reader.borrow_timeout(Duration::MAX)).map(|value| *value));
+
+        thread::sleep(Duration::from_millis(20));
+        drop(borrowed);
+
+        assert_eq!(Some(0), handle.join().unwrap());
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_update_async() {
+        let cell = AsyncSyncCell::new(1);
+
+        let blocked = block_on(cell.update_async(async |value| {
+            let blocked = cell.borrow_timeout(Duration::from_millis(10)).await.is_none();
+            *value += 1;
+            blocked
+        }));
+
+        // The value stays borrowed while the function awaits.
+        assert!(blocked);
+        assert_eq!(2, block_on(cell.get()));
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_send_guards() {
+        let cell = AsyncSyncCell::new(String::from("value"));
+
+        let task = async {
+            let borrowed = cell.borrow().await;
+            cell.changed(0).await;
+            drop(borrowed);
+
+            let mut borrowed = cell.borrow_mut().await;
+            cell.borrow_timeout(Duration::from_millis(1)).await;
+            borrowed.push('s');
+        };
+
+        assert_send(&task);
+        assert_send(&cell.try_borrow());
+        assert_send(&cell.try_borrow_mut());
+        assert_send(&None::<AsyncSyncCellRef<'_, [u8]>>);
+        assert_send(&None::<AsyncSyncCellGuard<'_, [u8]>>);
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_blocking() {
+        let cell = Arc::new(AsyncSyncCell::new(0));
+        let setter = cell.clone();
+
+        thread::spawn(move || setter.blocking_set(1)).join().unwrap();
+        assert_eq!(1, cell.blocking_get());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_async_sync_cell_blocking_in_async() {
+        let cell = AsyncSyncCell::new(0);
+
+        block_on(async {
+            cell.blocking_set(1);
+        });
+    }
+}
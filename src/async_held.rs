@@ -0,0 +1,231 @@
+use std::{future::Future, pin::Pin, sync::{Mutex, MutexGuard}, task::{Context, Poll, Waker}};
+
+use crate::{HeldSyncCell, SyncCellGuard, SyncCellRef};
+
+/// A variant of `HeldSyncCell` whose waiting methods are futures.
+/// A task driving updates can await staged changes with `wait_for_pending` instead of blocking a
+/// worker thread. All other methods behave the same as for `HeldSyncCell`.
+///
+/// The borrows returned by `borrow` and `borrow_mut` are borrows of a `SyncCell`, so they are not
+/// `Send` and should not be held across an `.await`.
+///
+/// This type is only available with the `async` feature.
+///
+/// # Usage
+/// ```
+/// use sync_cell::AsyncHeldSyncCell;
+///
+/// # async fn example(cell: &AsyncHeldSyncCell<i32>) {
+/// loop {
+///     cell.wait_and_update().await;
+///     println!("{}", cell.get());
+/// }
+/// # }
+/// ```
+pub struct AsyncHeldSyncCell<T> {
+    /// The cell holding the values.
+    cell: HeldSyncCell<T>,
+    /// The wakers of tasks waiting for an update.
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl <T> AsyncHeldSyncCell<T> {
+    /// Creates a new `AsyncHeldSyncCell`.
+    ///
+    /// - `data` - The initial value of the `AsyncHeldSyncCell`.
+    pub fn new(data: T) -> Self {
+        Self {
+            cell: HeldSyncCell::new(data),
+            waiters: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// This value will only become available once the `update` method is called. Any tasks
+    /// waiting for an update are woken.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, value: T) {
+        self.cell.set(value);
+        self.wake_waiters();
+    }
+
+    /// Queues a function to modify the value of this cell when it is next updated.
+    /// Any tasks waiting for an update are woken.
+    ///
+    /// - `f` - The function to apply to the value.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn defer(&self, f: impl FnOnce(&mut T) + Send + 'static) {
+        self.cell.defer(f);
+        self.wake_waiters();
+    }
+
+    /// Checks if a new value is available that can be applied by calling `update`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn has_update(&self) -> bool {
+        self.cell.has_update()
+    }
+
+    /// Updates the internal value of this cell.
+    /// Returns `true` if an update was applied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update(&self) -> bool {
+        self.cell.update()
+    }
+
+    /// Waits until an update is available.
+    /// The returned future resolves immediately if an update is already available.
+    ///
+    /// # Panicking
+    /// The returned future will panic if any of the locks become poisoned.
+    pub fn wait_for_pending(&self) -> WaitForPending<'_, T> {
+        WaitForPending {
+            cell: self,
+        }
+    }
+
+    /// Waits until an update is available and then applies it.
+    /// Returns `true` if an update was applied, which may not be the case if another task
+    /// applied it first.
+    ///
+    /// # Panicking
+    /// The returned future will panic if any of the locks become poisoned.
+    pub async fn wait_and_update(&self) -> bool {
+        self.wait_for_pending().await;
+        self.update()
+    }
+
+    /// Borrows a immutable reference to the current value of this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.cell.borrow()
+    }
+
+    /// Borrows a mutable reference to the current value of this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        self.cell.borrow_mut()
+    }
+
+    /// Retrieves the inner value stored in this `AsyncHeldSyncCell`.
+    /// This will return the most up-to-date value even if `update` has not been called.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> T {
+        self.cell.into_inner()
+    }
+
+    /// Wakes every task waiting for an update.
+    fn wake_waiters(&self) {
+        for waiter in self.lock_waiters().drain(..) {
+            waiter.wake();
+        }
+    }
+
+    /// Locks the wakers of tasks waiting for an update.
+    fn lock_waiters(&self) -> MutexGuard<'_, Vec<Waker>> {
+        // The list of wakers is never left inconsistent while locked, so a poisoned lock can be
+        // used.
+        match self.waiters.lock() {
+            Ok(waiters) => waiters,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+impl <T: Clone> AsyncHeldSyncCell<T> {
+    /// Gets the current value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.cell.get()
+    }
+}
+
+impl <T: Default> Default for AsyncHeldSyncCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for AsyncHeldSyncCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A future that resolves once an update of an `AsyncHeldSyncCell` is available.
+pub struct WaitForPending<'a, T> {
+    /// The cell being waited on.
+    cell: &'a AsyncHeldSyncCell<T>,
+}
+
+impl <T> Future for WaitForPending<'_, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The update is checked while holding the wakers so that a value set after the check
+        // always finds the waker registered.
+        let mut waiters = self.cell.lock_waiters();
+
+        if self.cell.has_update() {
+            return Poll::Ready(());
+        }
+
+        if !waiters.iter().any(|waiter| waiter.will_wake(cx.waker())) {
+            waiters.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::{AsyncHeldSyncCell, block_on::block_on};
+
+    #[test]
+    pub fn test_async_held_sync_cell_update() {
+        let cell = AsyncHeldSyncCell::new(1);
+
+        cell.set(2);
+        assert_eq!(1, cell.get());
+
+        block_on(cell.wait_for_pending());
+        assert!(cell.update());
+        assert_eq!(2, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_async_held_sync_cell_wait_and_update() {
+        let cell = Arc::new(AsyncHeldSyncCell::new(0));
+        let setter = cell.clone();
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            setter.defer(|value| *value += 1);
+        });
+
+        assert!(block_on(cell.wait_and_update()));
+        handle.join().unwrap();
+
+        assert_eq!(1, cell.get());
+    }
+}
@@ -0,0 +1,252 @@
+use std::{marker::PhantomData, mem::replace, ptr, sync::{Arc, atomic::{AtomicPtr, Ordering}}};
+
+/// The incoming value of an `AtomicHeldCell` shared with its setters.
+struct Slot<T> {
+    /// The boxed incoming value or null if no value has been set since the last update.
+    next: AtomicPtr<T>,
+    /// Marks this slot as owning the boxed incoming value.
+    _owned: PhantomData<Box<T>>,
+}
+
+impl <T> Slot<T> {
+    /// Stores a boxed value in this slot dropping any value that was already stored.
+    fn store(&self, value: T) {
+        let previous = self.next.swap(Box::into_raw(Box::new(value)), Ordering::AcqRel);
+
+        // SAFETY: Non-null pointers in the slot always come from `Box::into_raw` and the swap
+        // gives this thread sole ownership of the previous pointer.
+        if !previous.is_null() {
+            drop(unsafe { Box::from_raw(previous) });
+        }
+    }
+
+    /// Takes the value stored in this slot.
+    fn take(&self) -> Option<T> {
+        let next = self.next.swap(ptr::null_mut(), Ordering::AcqRel);
+
+        // SAFETY: Non-null pointers in the slot always come from `Box::into_raw` and the swap
+        // gives this thread sole ownership of the pointer.
+        (!next.is_null()).then(|| *unsafe { Box::from_raw(next) })
+    }
+
+    /// Checks if a value is stored in this slot.
+    fn is_set(&self) -> bool {
+        !self.next.load(Ordering::Acquire).is_null()
+    }
+}
+
+impl <T> Drop for Slot<T> {
+    fn drop(&mut self) {
+        self.take();
+    }
+}
+
+/// A lock-free variant of `HeldSyncCell` for a single consumer and any number of producers.
+/// Setting a value stores it with a single atomic pointer swap and `update` swaps it in without
+/// taking any locks.
+///
+/// The cell itself is owned by the consumer, so the current value is accessed through plain
+/// references and `update` requires mutable access. Producers on other threads set values through
+/// an `AtomicHeldSetter` obtained from `setter`. Only the most recently set value is kept.
+///
+/// # Usage
+/// ```
+/// use std::thread;
+/// use sync_cell::AtomicHeldCell;
+///
+/// let mut cell = AtomicHeldCell::new(0);
+/// let setter = cell.setter();
+///
+/// thread::spawn(move || setter.set(1)).join().unwrap();
+///
+/// // The cell continues to hold a value of 0 until the `update` method is called.
+/// assert_eq!(0, *cell.current());
+///
+/// assert!(cell.update());
+/// assert_eq!(1, *cell.current());
+/// ```
+pub struct AtomicHeldCell<T> {
+    /// The current value that is made available.
+    current: T,
+    /// The incoming value shared with setters.
+    slot: Arc<Slot<T>>,
+}
+
+impl <T> AtomicHeldCell<T> {
+    /// Creates a new `AtomicHeldCell`.
+    ///
+    /// - `data` - The initial value of the `AtomicHeldCell`.
+    pub fn new(data: T) -> Self {
+        Self {
+            current: data,
+            slot: Arc::new(Slot {
+                next: AtomicPtr::new(ptr::null_mut()),
+                _owned: PhantomData,
+            }),
+        }
+    }
+
+    /// Creates a handle that can set the incoming value of this cell from other threads.
+    pub fn setter(&self) -> AtomicHeldSetter<T> {
+        AtomicHeldSetter {
+            slot: self.slot.clone(),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// This value will only become available once the `update` method is called. Any value set
+    /// since the last update is dropped.
+    ///
+    /// - `value` - The new value of the cell.
+    pub fn set(&self, value: T) {
+        self.slot.store(value)
+    }
+
+    /// Checks if a new value is available that can be applied by calling `update`.
+    pub fn has_update(&self) -> bool {
+        self.slot.is_set()
+    }
+
+    /// Updates the internal value of this cell.
+    /// This involves replacing the current value with the incoming value if it is available.
+    /// Returns `true` if a value was applied.
+    pub fn update(&mut self) -> bool {
+        self.update_take().is_some()
+    }
+
+    /// Updates the internal value of this cell.
+    /// This involves replacing the current value with the incoming value if it is available.
+    /// The replaced value is returned or `None` if no new value was available.
+    pub fn update_take(&mut self) -> Option<T> {
+        self.slot.take()
+            .map(|next| replace(&mut self.current, next))
+    }
+
+    /// Discards the incoming value of this cell without applying it.
+    /// The discarded value is returned or `None` if no new value had been set.
+    pub fn cancel_update(&self) -> Option<T> {
+        self.slot.take()
+    }
+
+    /// Gets a reference to the current value of this cell.
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Gets a mutable reference to the current value of this cell.
+    pub fn current_mut(&mut self) -> &mut T {
+        &mut self.current
+    }
+
+    /// Retrieves the inner value stored in this `AtomicHeldCell`.
+    /// This will return the most up-to-date value even if `update` has not been called.
+    pub fn into_inner(self) -> T {
+        self.slot.take()
+            .unwrap_or(self.current)
+    }
+}
+
+impl <T: Clone> AtomicHeldCell<T> {
+    /// Gets the current value contained in this cell.
+    pub fn get(&self) -> T {
+        self.current.clone()
+    }
+}
+
+impl <T: Default> Default for AtomicHeldCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for AtomicHeldCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A handle that sets the incoming value of an `AtomicHeldCell`.
+/// Setters can be cloned and sent to other threads and keep working after the cell is dropped.
+pub struct AtomicHeldSetter<T> {
+    /// The incoming value of the cell.
+    slot: Arc<Slot<T>>,
+}
+
+impl <T> AtomicHeldSetter<T> {
+    /// Sets the incoming value of the cell.
+    /// Any value set since the last update is dropped.
+    ///
+    /// - `value` - The new value of the cell.
+    pub fn set(&self, value: T) {
+        self.slot.store(value)
+    }
+
+    /// Checks if a value has been set that the cell has not applied yet.
+    pub fn has_update(&self) -> bool {
+        self.slot.is_set()
+    }
+}
+
+impl <T> Clone for AtomicHeldSetter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::AtomicHeldCell;
+
+    #[test]
+    pub fn test_atomic_held_cell_update() {
+        let mut cell = AtomicHeldCell::new(1);
+
+        assert!(!cell.update());
+
+        cell.set(2);
+        cell.set(3);
+
+        assert!(cell.has_update());
+        assert_eq!(Some(1), cell.update_take());
+        assert_eq!(3, cell.get());
+        assert!(!cell.has_update());
+    }
+
+    #[test]
+    pub fn test_atomic_held_cell_setters() {
+        let mut cell = AtomicHeldCell::new(0);
+
+        let handles: Vec<_> = (1..=4).map(|i| {
+            let setter = cell.setter();
+            thread::spawn(move || setter.set(i))
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(cell.update());
+        assert!((1..=4).contains(cell.current()));
+    }
+
+    #[test]
+    pub fn test_atomic_held_cell_drops_values() {
+        let value = Arc::new(());
+        let cell = AtomicHeldCell::new(value.clone());
+        let setter = cell.setter();
+
+        setter.set(value.clone());
+        setter.set(value.clone());
+        assert_eq!(3, Arc::strong_count(&value));
+
+        drop(cell);
+        assert_eq!(2, Arc::strong_count(&value));
+
+        drop(setter);
+        assert_eq!(1, Arc::strong_count(&value));
+    }
+}
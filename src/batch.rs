@@ -0,0 +1,81 @@
+use crate::{RawSyncLock, SyncCell};
+
+impl <T: ?Sized, L: RawSyncLock> SyncCell<T, L> {
+    /// Applies many changes to the value of this cell under a single mutable borrow.
+    /// Taking the lock once for a group of small changes is much cheaper than borrowing the cell
+    /// for each one, and other threads never see the value part way through the group.
+    ///
+    /// - `f` - The function making the changes.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(Vec::new());
+    ///
+    /// cell.batch(|values| {
+    ///     for i in 0..1000 {
+    ///         values.push(i);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(1000, cell.borrow().len());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn batch<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.borrow_mut())
+    }
+
+    /// Applies every change produced by an iterator under a single mutable borrow.
+    /// The iterator is drained while the cell is borrowed, so it should not block or access this
+    /// cell. The number of changes applied is returned.
+    ///
+    /// - `changes` - The changes to apply in order.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(0);
+    ///
+    /// let applied = cell.apply_all((1..=4).map(|i| move |value: &mut i32| *value += i));
+    ///
+    /// assert_eq!(4, applied);
+    /// assert_eq!(10, cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn apply_all<F: FnOnce(&mut T)>(&self, changes: impl IntoIterator<Item = F>) -> usize {
+        let mut data = self.borrow_mut();
+
+        changes.into_iter()
+            .map(|change| change(&mut data))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_apply_all() {
+        let cell = SyncCell::new(String::new());
+        let (sender, receiver) = mpsc::channel::<Box<dyn FnOnce(&mut String) + Send>>();
+
+        thread::spawn(move || {
+            for c in ['a', 'b', 'c'] {
+                sender.send(Box::new(move |value| value.push(c))).unwrap();
+            }
+        }).join().unwrap();
+
+        assert_eq!(3, cell.apply_all(receiver.try_iter()));
+        assert_eq!(1, cell.batch(|value| value.find('b').unwrap()));
+        assert_eq!("abc", cell.get());
+    }
+}
@@ -0,0 +1,233 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of bits stored in each word of a `SyncBitSet`.
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// A fixed size set of flags that can be changed from multiple threads without a lock.
+/// Each flag is stored as a bit of an atomic word, which makes this much faster and smaller than
+/// a `SyncCell<Vec<bool>>` for flags such as per-entity dirty markers.
+///
+/// Operations on a single flag are atomic. Bulk operations update one word at a time, so other
+/// threads may see some of the flags change before others.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncBitSet;
+///
+/// let dirty = SyncBitSet::new(100);
+///
+/// dirty.set(3);
+/// dirty.set(70);
+///
+/// assert!(dirty.test(70));
+/// assert_eq!(vec![3, 70], dirty.iter_ones().collect::<Vec<_>>());
+///
+/// dirty.clear_all();
+/// assert_eq!(0, dirty.count_ones());
+/// ```
+///
+/// # Panicking
+/// Every method taking an index will panic if the index is not less than the length of the set.
+#[derive(Debug)]
+pub struct SyncBitSet {
+    /// The words holding the flags with flag `i` at bit `i % WORD_BITS` of word `i / WORD_BITS`.
+    words: Box<[AtomicUsize]>,
+    /// The number of flags in the set.
+    len: usize,
+}
+
+impl SyncBitSet {
+    /// Creates a new `SyncBitSet` with every flag cleared.
+    ///
+    /// - `len` - The number of flags in the set.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: (0..len.div_ceil(WORD_BITS)).map(|_| AtomicUsize::new(0)).collect(),
+            len,
+        }
+    }
+
+    /// Gets the number of flags in this set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if this set has no flags.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the word and bit mask of a flag.
+    fn locate(&self, index: usize) -> (&AtomicUsize, usize) {
+        assert!(index < self.len, "Bit index {} is out of bounds for a set of length {}", index, self.len);
+
+        (&self.words[index / WORD_BITS], 1 << (index % WORD_BITS))
+    }
+
+    /// Gets the mask of the bits of a word that are part of the set.
+    fn word_mask(&self, word: usize) -> usize {
+        let bits = self.len - word * WORD_BITS;
+
+        if bits >= WORD_BITS {
+            usize::MAX
+        } else {
+            (1 << bits) - 1
+        }
+    }
+
+    /// Sets a flag.
+    /// Returns whether the flag was already set.
+    ///
+    /// - `index` - The index of the flag.
+    pub fn set(&self, index: usize) -> bool {
+        let (word, mask) = self.locate(index);
+        word.fetch_or(mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Clears a flag.
+    /// Returns whether the flag was set.
+    ///
+    /// - `index` - The index of the flag.
+    pub fn clear(&self, index: usize) -> bool {
+        let (word, mask) = self.locate(index);
+        word.fetch_and(!mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Inverts a flag.
+    /// Returns whether the flag was set before it was inverted.
+    ///
+    /// - `index` - The index of the flag.
+    pub fn toggle(&self, index: usize) -> bool {
+        let (word, mask) = self.locate(index);
+        word.fetch_xor(mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Checks if a flag is set.
+    ///
+    /// - `index` - The index of the flag.
+    pub fn test(&self, index: usize) -> bool {
+        let (word, mask) = self.locate(index);
+        word.load(Ordering::Acquire) & mask != 0
+    }
+
+    /// Sets every flag.
+    pub fn set_all(&self) {
+        for (index, word) in self.words.iter().enumerate() {
+            word.store(self.word_mask(index), Ordering::Release);
+        }
+    }
+
+    /// Clears every flag.
+    pub fn clear_all(&self) {
+        for word in self.words.iter() {
+            word.store(0, Ordering::Release);
+        }
+    }
+
+    /// Counts the flags that are set.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter()
+            .map(|word| word.load(Ordering::Acquire).count_ones() as usize)
+            .sum()
+    }
+
+    /// Checks if any flag is set.
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|word| word.load(Ordering::Acquire) != 0)
+    }
+
+    /// Clears every flag and returns the indices of the flags that were set in ascending order.
+    /// Each word is swapped atomically, so a flag set concurrently is either returned or left set
+    /// for the next call.
+    pub fn take_ones(&self) -> Vec<usize> {
+        self.words.iter()
+            .enumerate()
+            .flat_map(|(index, word)| ones(index, word.swap(0, Ordering::AcqRel)))
+            .collect()
+    }
+
+    /// Iterates over the indices of the flags that are set in ascending order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter()
+            .enumerate()
+            .flat_map(|(index, word)| ones(index, word.load(Ordering::Acquire)))
+    }
+}
+
+/// Iterates over the indices of the set bits of a word.
+///
+/// - `index` - The index of the word in the set.
+/// - `bits` - The bits of the word.
+fn ones(index: usize, mut bits: usize) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if bits == 0 {
+            return None;
+        }
+
+        let bit = bits.trailing_zeros() as usize;
+        bits &= bits - 1;
+
+        Some(index * WORD_BITS + bit)
+    })
+}
+
+impl Clone for SyncBitSet {
+    fn clone(&self) -> Self {
+        Self {
+            words: self.words.iter().map(|word| AtomicUsize::new(word.load(Ordering::Acquire))).collect(),
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::SyncBitSet;
+
+    #[test]
+    pub fn test_sync_bit_set() {
+        let set = SyncBitSet::new(70);
+
+        assert!(!set.set(65));
+        assert!(set.set(65));
+        assert!(!set.toggle(1));
+        assert!(set.test(1));
+        assert!(set.clear(1));
+        assert!(!set.test(1));
+
+        set.set_all();
+        assert_eq!(70, set.count_ones());
+
+        set.clear_all();
+        assert!(!set.any());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_sync_bit_set_out_of_bounds() {
+        SyncBitSet::new(8).set(8);
+    }
+
+    #[test]
+    pub fn test_sync_bit_set_threads() {
+        let set = Arc::new(SyncBitSet::new(256));
+
+        let handles: Vec<_> = (0..4).map(|thread| {
+            let set = set.clone();
+            thread::spawn(move || {
+                for i in (thread..256).step_by(4) {
+                    set.set(i);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!((0..256).collect::<Vec<_>>(), set.take_ones());
+        assert_eq!(0, set.count_ones());
+    }
+}
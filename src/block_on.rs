@@ -0,0 +1,58 @@
+use std::{cell::Cell, future::Future, pin::pin, sync::Arc, task::{Context, Poll, Wake, Waker}, thread::{self, Thread}};
+
+thread_local! {
+    /// Whether the current thread is polling a future inside `block_on`.
+    static POLLING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A waker that unparks the thread blocked on a future.
+struct ThreadWaker {
+    /// The thread to unpark.
+    thread: Thread,
+}
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.thread.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.thread.unpark();
+    }
+}
+
+/// Clears the polling flag of the current thread when dropped, even if polling panics.
+struct PollingGuard;
+
+impl Drop for PollingGuard {
+    fn drop(&mut self) {
+        POLLING.with(|polling| polling.set(false));
+    }
+}
+
+/// Blocks the current thread until a future has completed.
+///
+/// - `future` - The future to run.
+///
+/// # Panicking
+/// This function will panic if it is called from inside a future that is itself being run by
+/// `block_on`, as blocking there would stall the outer future.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    if POLLING.with(|polling| polling.replace(true)) {
+        panic!("Cannot block the current thread from inside an async context.");
+    }
+
+    let _guard = PollingGuard;
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker {
+        thread: thread::current(),
+    }));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use crate::CondCell;
+
+/// The state shared between a `BroadcastCell` and its receivers.
+#[derive(Debug)]
+struct Broadcast<T> {
+    /// The most recently published values, oldest first.
+    history: VecDeque<T>,
+    /// The maximum number of values kept for receivers that have not caught up.
+    capacity: usize,
+    /// The total number of values published.
+    published: u64,
+    /// Whether the cell has been dropped.
+    closed: bool,
+}
+
+impl <T> Broadcast<T> {
+    /// Gets the sequence number of the oldest value still in the history.
+    fn oldest(&self) -> u64 {
+        self.published - self.history.len() as u64
+    }
+}
+
+/// A cell that delivers every value published to it to all of its receivers.
+/// Each receiver sees the values published after it subscribed in order. Only the most recent
+/// `capacity` values are kept, so receivers that fall further behind skip the oldest values.
+///
+/// Receivers block until a new value is available, so many threads can react to changes without
+/// polling.
+///
+/// # Usage
+/// ```
+/// use std::thread;
+/// use sync_cell::BroadcastCell;
+///
+/// let cell = BroadcastCell::new(16);
+/// let mut receiver = cell.subscribe();
+///
+/// let consumer = thread::spawn(move || {
+///     let mut values = Vec::new();
+///     while let Some(value) = receiver.recv() {
+///         values.push(value);
+///     }
+///     values
+/// });
+///
+/// cell.publish(1);
+/// cell.publish(2);
+/// drop(cell);
+///
+/// assert_eq!(vec![1, 2], consumer.join().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct BroadcastCell<T: Clone> {
+    /// The state shared with the receivers.
+    shared: Arc<CondCell<Broadcast<T>>>,
+}
+
+impl <T: Clone> BroadcastCell<T> {
+    /// Creates a new `BroadcastCell`.
+    ///
+    /// - `capacity` - The maximum number of values kept for receivers that have not caught up.
+    ///   A capacity of 1 means receivers only ever see the latest value.
+    ///
+    /// # Panicking
+    /// This method will panic if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        if capacity == 0 {
+            panic!("Broadcast capacity must be at least 1.");
+        }
+
+        Self {
+            shared: Arc::new(CondCell::new(Broadcast {
+                history: VecDeque::with_capacity(capacity),
+                capacity,
+                published: 0,
+                closed: false,
+            })),
+        }
+    }
+
+    /// Publishes a value to all receivers.
+    /// If the history is full the oldest value is discarded.
+    ///
+    /// - `value` - The value to publish.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn publish(&self, value: T) {
+        let mut shared = self.shared.borrow_mut();
+
+        if shared.history.len() == shared.capacity {
+            shared.history.pop_front();
+        }
+
+        shared.history.push_back(value);
+        shared.published += 1;
+    }
+
+    /// Creates a new receiver that will see all values published from now on.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn subscribe(&self) -> BroadcastReceiver<T> {
+        BroadcastReceiver {
+            next: self.shared.borrow().published,
+            missed: 0,
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Gets the most recently published value.
+    /// Returns `None` if no value has been published.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn latest(&self) -> Option<T> {
+        self.shared.borrow().history.back().cloned()
+    }
+
+    /// Gets the total number of values that have been published.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn published(&self) -> u64 {
+        self.shared.borrow().published
+    }
+}
+
+impl <T: Clone> Drop for BroadcastCell<T> {
+    fn drop(&mut self) {
+        self.shared.borrow_mut().closed = true;
+    }
+}
+
+/// A receiver of the values published to a `BroadcastCell`.
+/// Cloning a receiver creates a new receiver at the same position.
+#[derive(Debug, Clone)]
+pub struct BroadcastReceiver<T> {
+    /// The sequence number of the next value to receive.
+    next: u64,
+    /// The number of values skipped because this receiver fell behind.
+    missed: u64,
+    /// The state shared with the cell.
+    shared: Arc<CondCell<Broadcast<T>>>,
+}
+
+impl <T: Clone> BroadcastReceiver<T> {
+    /// Receives the next value, blocking until one is published.
+    /// Returns `None` once the cell has been dropped and all remaining values have been received.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn recv(&mut self) -> Option<T> {
+        let next = self.next;
+        let shared = self.shared.wait_until(|shared| shared.published > next || shared.closed);
+
+        Self::take(&mut self.next, &mut self.missed, &shared)
+    }
+
+    /// Receives the next value, blocking for at most `timeout` until one is published.
+    /// Returns `None` if the timeout elapsed or the cell has been dropped and all remaining values
+    /// have been received.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Option<T> {
+        let next = self.next;
+        let shared = self.shared.wait_until_timeout(timeout, |shared| shared.published > next || shared.closed)?;
+
+        Self::take(&mut self.next, &mut self.missed, &shared)
+    }
+
+    /// Receives the next value if one has already been published.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn try_recv(&mut self) -> Option<T> {
+        let shared = self.shared.borrow();
+
+        Self::take(&mut self.next, &mut self.missed, &shared)
+    }
+
+    /// Receives the most recent value, blocking until a value newer than the last one received
+    /// is published. Any older values that have not been received are skipped.
+    /// Returns `None` once the cell has been dropped and no newer value exists.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn recv_latest(&mut self) -> Option<T> {
+        let next = self.next;
+        let shared = self.shared.wait_until(|shared| shared.published > next || shared.closed);
+
+        if shared.published > self.next {
+            self.missed += shared.published - self.next - 1;
+            self.next = shared.published - 1;
+        }
+
+        Self::take(&mut self.next, &mut self.missed, &shared)
+    }
+
+    /// Gets the number of values this receiver has skipped.
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+
+    /// Gets the number of published values this receiver has not yet received.
+    /// This includes values that will be skipped because they are no longer in the history.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn pending(&self) -> u64 {
+        self.shared.borrow().published - self.next
+    }
+
+    /// Takes the next value from the history if it is available.
+    fn take(next: &mut u64, missed: &mut u64, shared: &Broadcast<T>) -> Option<T> {
+        if *next >= shared.published {
+            return None;
+        }
+
+        let oldest = shared.oldest();
+
+        if *next < oldest {
+            *missed += oldest - *next;
+            *next = oldest;
+        }
+
+        let value = shared.history[(*next - oldest) as usize].clone();
+        *next += 1;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crate::BroadcastCell;
+
+    #[test]
+    pub fn test_broadcast_cell_every_value() {
+        let cell = BroadcastCell::new(4);
+        let mut first = cell.subscribe();
+
+        cell.publish(1);
+
+        let mut second = cell.subscribe();
+
+        cell.publish(2);
+
+        assert_eq!(Some(1), first.try_recv());
+        assert_eq!(Some(2), first.try_recv());
+        assert_eq!(None, first.try_recv());
+        assert_eq!(Some(2), second.try_recv());
+        assert_eq!(Some(2), cell.latest());
+    }
+
+    #[test]
+    pub fn test_broadcast_cell_lagged() {
+        let cell = BroadcastCell::new(2);
+        let mut receiver = cell.subscribe();
+
+        for i in 0..5 {
+            cell.publish(i);
+        }
+
+        assert_eq!(5, receiver.pending());
+        assert_eq!(Some(3), receiver.try_recv());
+        assert_eq!(3, receiver.missed());
+        assert_eq!(Some(4), receiver.try_recv());
+    }
+
+    #[test]
+    pub fn test_broadcast_cell_recv_latest() {
+        let cell = BroadcastCell::new(8);
+        let mut receiver = cell.subscribe();
+
+        cell.publish(1);
+        cell.publish(2);
+        cell.publish(3);
+
+        assert_eq!(Some(3), receiver.recv_latest());
+        assert_eq!(2, receiver.missed());
+        assert_eq!(None, receiver.recv_timeout(Duration::from_millis(10)));
+    }
+
+    #[test]
+    pub fn test_broadcast_cell_threads() {
+        let cell = BroadcastCell::new(128);
+
+        let consumers: Vec<_> = (0..4).map(|_| {
+            let mut receiver = cell.subscribe();
+            thread::spawn(move || {
+                let mut sum = 0;
+                while let Some(value) = receiver.recv() {
+                    sum += value;
+                }
+                sum
+            })
+        }).collect();
+
+        for i in 1..=100 {
+            cell.publish(i);
+        }
+        drop(cell);
+
+        for consumer in consumers {
+            assert_eq!(5050, consumer.join().unwrap());
+        }
+    }
+}
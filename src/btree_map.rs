@@ -0,0 +1,254 @@
+use std::{borrow::Borrow, collections::{BTreeMap, btree_map::Range}, ops::RangeBounds};
+
+use crate::SyncCell;
+
+/// An ordered map that can be shared between threads.
+/// Range queries run under a single immutable borrow, so they see a consistent view of the map
+/// that a sharded map cannot provide.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncBTreeMap;
+///
+/// let map = SyncBTreeMap::new();
+///
+/// for i in 0..10 {
+///     map.insert(i, i * i);
+/// }
+///
+/// let total: i32 = map.range_with(2..5, |range| range.map(|(_, value)| value).sum());
+///
+/// assert_eq!(4 + 9 + 16, total);
+/// assert_eq!(Some((0, 0)), map.first_cloned());
+/// assert_eq!(Some((9, 81)), map.last_cloned());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this map will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct SyncBTreeMap<K, V> {
+    /// The entries of the map.
+    data: SyncCell<BTreeMap<K, V>>,
+}
+
+impl <K, V> SyncBTreeMap<K, V> {
+    /// Creates a new empty `SyncBTreeMap`.
+    pub const fn new() -> Self {
+        Self {
+            data: SyncCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Gets the number of entries in this map.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.data.borrow().len()
+    }
+
+    /// Checks if this map has no entries.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.data.borrow().is_empty()
+    }
+
+    /// Calls a function with an immutable reference to the whole map.
+    /// The map cannot change while the function runs.
+    ///
+    /// - `f` - The function to call with the map.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with<R>(&self, f: impl FnOnce(&BTreeMap<K, V>) -> R) -> R {
+        f(&self.data.borrow())
+    }
+
+    /// Calls a function with a mutable reference to the whole map.
+    ///
+    /// - `f` - The function to call with the map.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with_mut<R>(&self, f: impl FnOnce(&mut BTreeMap<K, V>) -> R) -> R {
+        f(&mut self.data.borrow_mut())
+    }
+
+    /// Removes every entry from this map.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn clear(&self) {
+        self.data.borrow_mut().clear();
+    }
+
+    /// Retrieves the entries stored in this `SyncBTreeMap`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> BTreeMap<K, V> {
+        self.data.into_inner()
+    }
+}
+
+impl <K: Ord, V> SyncBTreeMap<K, V> {
+    /// Inserts a value into this map.
+    /// The previous value for the key is returned or `None` if the key was not present.
+    ///
+    /// - `key` - The key to insert the value at.
+    /// - `value` - The value to insert.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.data.borrow_mut().insert(key, value)
+    }
+
+    /// Removes a key from this map.
+    /// The removed value is returned or `None` if the key was not present.
+    ///
+    /// - `key` - The key to remove.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn remove<Q: Ord + ?Sized>(&self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.data.borrow_mut().remove(key)
+    }
+
+    /// Checks if this map contains a key.
+    ///
+    /// - `key` - The key to look for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn contains_key<Q: Ord + ?Sized>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.data.borrow().contains_key(key)
+    }
+
+    /// Calls a function with an iterator over a range of entries of this map in key order.
+    /// The whole range is read under a single immutable borrow.
+    ///
+    /// - `range` - The range of keys to iterate over.
+    /// - `f` - The function to call with the iterator.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or the range starts after it ends.
+    pub fn range_with<Q: Ord + ?Sized, B: RangeBounds<Q>, R>(&self, range: B, f: impl FnOnce(Range<'_, K, V>) -> R) -> R where K: Borrow<Q> {
+        f(self.data.borrow().range(range))
+    }
+}
+
+impl <K: Ord, V: Clone> SyncBTreeMap<K, V> {
+    /// Gets a copy of the value of a key.
+    /// Returns `None` if the key is not present.
+    ///
+    /// - `key` - The key to look up.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_cloned<Q: Ord + ?Sized>(&self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.data.borrow().get(key).cloned()
+    }
+}
+
+impl <K: Ord + Clone, V: Clone> SyncBTreeMap<K, V> {
+    /// Gets a copy of the entry with the smallest key.
+    /// Returns `None` if the map is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn first_cloned(&self) -> Option<(K, V)> {
+        self.data.borrow()
+            .first_key_value()
+            .map(|(key, value)| (key.clone(), value.clone()))
+    }
+
+    /// Gets a copy of the entry with the largest key.
+    /// Returns `None` if the map is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn last_cloned(&self) -> Option<(K, V)> {
+        self.data.borrow()
+            .last_key_value()
+            .map(|(key, value)| (key.clone(), value.clone()))
+    }
+}
+
+impl <K: Clone, V: Clone> Clone for SyncBTreeMap<K, V> {
+    fn clone(&self) -> Self {
+        Self::from(self.data.get())
+    }
+}
+
+impl <K, V> Default for SyncBTreeMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <K, V> From<BTreeMap<K, V>> for SyncBTreeMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Self {
+            data: SyncCell::new(map),
+        }
+    }
+}
+
+impl <K: Ord, V> FromIterator<(K, V)> for SyncBTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<BTreeMap<K, V>>())
+    }
+}
+
+/// Inserts the entries into the map taking the lock once per call.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <K: Ord, V> Extend<(K, V)> for &SyncBTreeMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.data.borrow_mut().extend(iter);
+    }
+}
+
+/// Inserts the entries into the map.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <K: Ord, V> Extend<(K, V)> for SyncBTreeMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        (&*self).extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SyncBTreeMap;
+
+    #[test]
+    pub fn test_sync_btree_map() {
+        let map = SyncBTreeMap::new();
+
+        assert_eq!(None, map.insert("b", 2));
+        assert_eq!(None, map.insert("a", 1));
+        assert!(map.contains_key("a"));
+        assert_eq!(Some(2), map.get_cloned("b"));
+        assert_eq!(Some(("a", 1)), map.first_cloned());
+        assert_eq!(Some(1), map.remove("a"));
+        assert_eq!(1, map.len());
+    }
+
+    #[test]
+    pub fn test_sync_btree_map_range_with() {
+        let map: SyncBTreeMap<_, _> = (0..10).map(|i| (i, i.to_string())).collect();
+
+        let keys: Vec<_> = map.range_with(7.., |range| range.map(|(key, _)| *key).collect());
+
+        assert_eq!(vec![7, 8, 9], keys);
+        assert_eq!(Some((9, String::from("9"))), map.last_cloned());
+        assert!(map.range_with(10.., |mut range| range.next().is_none()));
+    }
+}
@@ -0,0 +1,158 @@
+use core::{marker::PhantomData, sync::atomic::AtomicBool};
+
+#[cfg(feature = "contention-stats")]
+use crate::contention::ContentionCounters;
+#[cfg(feature = "local-cache")]
+use crate::version::VersionCounter;
+use crate::{owner::LockOwner, poison::PoisonHandler, PaddedSyncCell, PoisonPolicy, DefaultRawLock, RawSyncCell, RawSyncLock, SyncCell};
+
+/// A builder that configures how a `SyncCell` behaves before creating it.
+/// Every option has the same default as `SyncCell::new`, so only the options that differ need to
+/// be set. A builder can be used in constants to declare configured `static` cells.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{PoisonPolicy, SyncCell};
+///
+/// static SETTINGS: SyncCell<Vec<String>> = SyncCell::builder()
+///     .label("SETTINGS")
+///     .poison_policy(PoisonPolicy::ClearAndDefault)
+///     .build(Vec::new());
+///
+/// SETTINGS.borrow_mut().push(String::from("verbose"));
+///
+/// assert_eq!(PoisonPolicy::ClearAndDefault, SETTINGS.poison_policy());
+/// ```
+#[derive(Debug)]
+pub struct SyncCellBuilder<T> {
+    /// The label that identifies the cell in panic messages.
+    label: Option<&'static str>,
+    /// How a poisoned lock is handled.
+    poison: PoisonHandler,
+    /// The type of value of the cell.
+    _value: PhantomData<fn() -> T>,
+}
+
+impl <T> SyncCellBuilder<T> {
+    /// Creates a new `SyncCellBuilder` with the default options.
+    pub const fn new() -> Self {
+        Self {
+            label: None,
+            poison: PoisonHandler::Panic,
+            _value: PhantomData,
+        }
+    }
+
+    /// Sets the label that identifies the cell in panic messages and, with the `tracing` feature,
+    /// names the spans of its borrows.
+    /// Labels are only kept in debug builds with the `owner-tracking` feature or with the
+    /// `tracing` feature.
+    ///
+    /// - `label` - The label of the cell, such as where it was created.
+    pub const fn label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Sets what the cell does when its lock has been poisoned.
+    /// The value must implement `Default` so that it can be reset by
+    /// `PoisonPolicy::ClearAndDefault`. Other values can ignore poisoning with `ignore_poison`.
+    ///
+    /// - `policy` - What to do when the lock is poisoned.
+    pub const fn poison_policy(mut self, policy: PoisonPolicy) -> Self where T: Default {
+        self.poison = PoisonHandler::new::<T>(policy);
+        self
+    }
+
+    /// Makes the cell ignore its lock being poisoned and use the value as the panicking thread
+    /// left it.
+    /// This is the `PoisonPolicy::Ignore` policy, which can be chosen for any value.
+    pub const fn ignore_poison(mut self) -> Self {
+        self.poison = PoisonHandler::Ignore;
+        self
+    }
+
+    /// Creates the configured `SyncCell`.
+    ///
+    /// - `data` - The initial value of the `SyncCell`.
+    pub const fn build(self, data: T) -> SyncCell<T> {
+        self.build_with_lock(data, DefaultRawLock::INIT)
+    }
+
+    /// Creates the configured `SyncCell` protected by an existing lock.
+    ///
+    /// - `data` - The initial value of the `SyncCell`.
+    /// - `lock` - The unlocked lock protecting the value.
+    pub const fn build_with_lock<L: RawSyncLock>(self, data: T, lock: L) -> SyncCell<T, L> {
+        SyncCell {
+            #[cfg(feature = "contention-stats")]
+            contention: ContentionCounters::new(),
+            owner: LockOwner::new(self.label),
+            poison: self.poison,
+            poisoned: AtomicBool::new(false),
+            #[cfg(feature = "local-cache")]
+            version: VersionCounter::new(),
+            data: RawSyncCell::with_lock(data, lock),
+        }
+    }
+
+    /// Creates the configured `SyncCell` aligned to a cache line.
+    ///
+    /// - `data` - The initial value of the `SyncCell`.
+    pub const fn build_padded(self, data: T) -> PaddedSyncCell<T> {
+        PaddedSyncCell::from_cell(self.build(data))
+    }
+}
+
+impl <T> Default for SyncCellBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> SyncCell<T> {
+    /// Creates a builder that configures a `SyncCell` before creating it.
+    pub const fn builder() -> SyncCellBuilder<T> {
+        SyncCellBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PoisonPolicy, RawSyncLock, SpinRawLock, SyncCell};
+
+    #[test]
+    pub fn test_sync_cell_builder() {
+        let cell = SyncCell::builder()
+            .label("test")
+            .poison_policy(PoisonPolicy::Ignore)
+            .build_padded(1);
+
+        assert_eq!(PoisonPolicy::Ignore, cell.poison_policy());
+        assert_eq!(1, cell.get());
+        assert_eq!(PoisonPolicy::Panic, SyncCell::builder().build(1).poison_policy());
+    }
+
+    #[test]
+    pub fn test_sync_cell_builder_poison_policy() {
+        struct NoDefault;
+
+        let cell = SyncCell::builder().poison_policy(PoisonPolicy::ClearAndDefault).build(1);
+        let ignored = SyncCell::builder().ignore_poison().build(NoDefault);
+
+        assert_eq!(PoisonPolicy::ClearAndDefault, cell.poison_policy());
+        assert_eq!(PoisonPolicy::Ignore, ignored.poison_policy());
+    }
+
+    #[test]
+    pub fn test_sync_cell_builder_with_lock() {
+        let cell = SyncCell::builder()
+            .poison_policy(PoisonPolicy::Ignore)
+            .build_with_lock(1, SpinRawLock::INIT);
+
+        *cell.borrow_mut() += 1;
+
+        assert_eq!(PoisonPolicy::Ignore, cell.poison_policy());
+        assert_eq!(2, cell.get());
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::atomic::Ordering;
+
+use crate::{RawSyncLock, SyncCell};
+
+/// A copy of the value of a `SyncCell` owned by one thread, used by `SyncCell::get_cached`.
+/// The copy remembers the version of the cell it was taken from so it is only refreshed once the
+/// cell has been mutably borrowed again.
+///
+/// A cache should only be used with a single cell.
+///
+/// This type is only available with the `local-cache` feature.
+#[derive(Debug, Clone)]
+pub struct LocalCache<T> {
+    /// The cached value and the version of the cell it was copied at.
+    value: Option<(T, u64)>,
+}
+
+impl <T> LocalCache<T> {
+    /// Creates a new empty `LocalCache`.
+    pub const fn new() -> Self {
+        Self {
+            value: None,
+        }
+    }
+
+    /// Gets the cached value, if the cache has been filled.
+    pub fn cached(&self) -> Option<&T> {
+        self.value.as_ref().map(|(value, _)| value)
+    }
+
+    /// Empties the cache so the next read takes the lock.
+    pub fn clear(&mut self) {
+        self.value = None;
+    }
+}
+
+impl <T> Default for LocalCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T: Clone, L: RawSyncLock> SyncCell<T, L> {
+    /// Gets the value contained in this cell using a locally cached copy where possible.
+    /// If the cell has not been mutably borrowed since the cache was filled the cached copy is
+    /// returned without taking the lock. Otherwise the value is copied into the cache again.
+    ///
+    /// This makes reads of values that rarely change almost free, at the cost of one copy of the
+    /// value per cache.
+    ///
+    /// This method is only available with the `local-cache` feature.
+    ///
+    /// - `cache` - The cache of the value of this cell.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::{LocalCache, SyncCell};
+    ///
+    /// let cell = SyncCell::new(String::from("a"));
+    /// let mut cache = LocalCache::new();
+    ///
+    /// assert_eq!("a", cell.get_cached(&mut cache));
+    ///
+    /// cell.set(String::from("b"));
+    ///
+    /// assert_eq!("b", cell.get_cached(&mut cache));
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_cached<'a>(&self, cache: &'a mut LocalCache<T>) -> &'a T {
+        let version = self.version.load(Ordering::Acquire);
+
+        if cache.value.as_ref().map(|(_, cached)| *cached) != Some(version) {
+            let data = self.borrow();
+            // Writers change the version while holding the lock, so it cannot change while the
+            // value is read.
+            let version = self.version.load(Ordering::Acquire);
+
+            cache.value = Some((data.clone(), version));
+        }
+
+        match &cache.value {
+            Some((value, _)) => value,
+            None => unreachable!("The cache was filled above."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LocalCache, SyncCell};
+
+    #[test]
+    pub fn test_sync_cell_get_cached() {
+        let cell = SyncCell::new(1);
+        let mut cache = LocalCache::new();
+
+        assert_eq!(None, cache.cached());
+        assert_eq!(1, *cell.get_cached(&mut cache));
+
+        *cell.borrow_mut() = 2;
+        assert_eq!(Some(&1), cache.cached());
+        assert_eq!(2, *cell.get_cached(&mut cache));
+
+        // Reads do not invalidate the cache.
+        let _ = cell.get();
+        assert_eq!(2, *cell.get_cached(&mut cache));
+    }
+}
@@ -0,0 +1,203 @@
+use std::{ops::Index, slice::Iter};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A fixed size array of independent `SyncCell`s.
+/// Each index has its own lock, which suits striped state such as one slot per worker thread.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::SyncCellArray;
+///
+/// let slots = Arc::new(SyncCellArray::<u32, 4>::default());
+///
+/// let handles: Vec<_> = (0..4).map(|worker| {
+///     let slots = slots.clone();
+///     thread::spawn(move || {
+///         for _ in 0..10 {
+///             *slots.borrow_mut(worker) += 1;
+///         }
+///     })
+/// }).collect();
+/// # for handle in handles { handle.join().unwrap(); }
+///
+/// assert_eq!([10, 10, 10, 10], slots.get_all());
+/// ```
+///
+/// # Panicking
+/// Every method taking an index will panic if the index is out of bounds. Like `SyncCell`, the
+/// cells will panic rather than return an error when a lock becomes poisoned.
+#[derive(Debug)]
+pub struct SyncCellArray<T, const N: usize> {
+    /// The cells of this array.
+    cells: [SyncCell<T>; N],
+}
+
+impl <T, const N: usize> SyncCellArray<T, N> {
+    /// Creates a new `SyncCellArray`.
+    ///
+    /// - `data` - The initial values of the cells.
+    pub fn new(data: [T; N]) -> Self {
+        Self {
+            cells: data.map(SyncCell::new),
+        }
+    }
+
+    /// Creates a new `SyncCellArray` with values computed from their index.
+    ///
+    /// - `f` - The function computing the initial value of each cell.
+    pub fn from_fn(mut f: impl FnMut(usize) -> T) -> Self {
+        Self {
+            cells: std::array::from_fn(|index| SyncCell::new(f(index))),
+        }
+    }
+
+    /// Gets the number of cells in this array.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Checks if this array has no cells.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Sets the value of a cell.
+    ///
+    /// - `index` - The index of the cell.
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the index is out of bounds or the lock becomes poisoned.
+    pub fn set(&self, index: usize, value: T) {
+        self.cells[index].set(value)
+    }
+
+    /// Replaces the value of a cell.
+    /// The previous value is returned.
+    ///
+    /// - `index` - The index of the cell.
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the index is out of bounds or the lock becomes poisoned.
+    pub fn replace(&self, index: usize, value: T) -> T {
+        self.cells[index].replace(value)
+    }
+
+    /// Borrows a immutable reference to the value of a cell.
+    ///
+    /// - `index` - The index of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the index is out of bounds or the lock becomes poisoned.
+    pub fn borrow(&self, index: usize) -> SyncCellRef<'_, T> {
+        self.cells[index].borrow()
+    }
+
+    /// Borrows a mutable reference to the value of a cell.
+    ///
+    /// - `index` - The index of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the index is out of bounds or the lock becomes poisoned.
+    pub fn borrow_mut(&self, index: usize) -> SyncCellGuard<'_, T> {
+        self.cells[index].borrow_mut()
+    }
+
+    /// Iterates over the cells of this array.
+    pub fn iter(&self) -> Iter<'_, SyncCell<T>> {
+        self.cells.iter()
+    }
+
+    /// Retrieves the values stored in this `SyncCellArray`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> [T; N] {
+        self.cells.map(SyncCell::into_inner)
+    }
+}
+
+impl <T: Clone, const N: usize> SyncCellArray<T, N> {
+    /// Gets the value of a cell.
+    ///
+    /// - `index` - The index of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the index is out of bounds or the lock becomes poisoned.
+    pub fn get(&self, index: usize) -> T {
+        self.cells[index].get()
+    }
+
+    /// Gets the values of every cell.
+    /// Cells are read one at a time so the result may include some concurrent changes but not
+    /// others.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get_all(&self) -> [T; N] {
+        std::array::from_fn(|index| self.get(index))
+    }
+}
+
+impl <T, const N: usize> Index<usize> for SyncCellArray<T, N> {
+    type Output = SyncCell<T>;
+
+    fn index(&self, index: usize) -> &SyncCell<T> {
+        &self.cells[index]
+    }
+}
+
+impl <'a, T, const N: usize> IntoIterator for &'a SyncCellArray<T, N> {
+    type Item = &'a SyncCell<T>;
+    type IntoIter = Iter<'a, SyncCell<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl <T: Clone, const N: usize> Clone for SyncCellArray<T, N> {
+    fn clone(&self) -> Self {
+        Self::new(self.get_all())
+    }
+}
+
+impl <T: Default, const N: usize> Default for SyncCellArray<T, N> {
+    fn default() -> Self {
+        Self::from_fn(|_| T::default())
+    }
+}
+
+impl <T, const N: usize> From<[T; N]> for SyncCellArray<T, N> {
+    fn from(data: [T; N]) -> Self {
+        Self::new(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SyncCellArray;
+
+    #[test]
+    pub fn test_sync_cell_array() {
+        let array = SyncCellArray::from([1, 2, 3]);
+
+        array.set(0, 4);
+        *array.borrow_mut(1) += 3;
+
+        assert_eq!(3, array.replace(2, 6));
+        assert_eq!(5, array.get(1));
+        assert_eq!(4, *array[0].borrow());
+        assert_eq!(15, array.iter().map(|cell| cell.get()).sum::<i32>());
+        assert_eq!([4, 5, 6], array.into_inner());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_sync_cell_array_out_of_bounds() {
+        SyncCellArray::<i32, 2>::default().get(2);
+    }
+}
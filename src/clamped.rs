@@ -0,0 +1,238 @@
+use std::{cmp::Ordering, mem::replace};
+
+use crate::{SyncCell, SyncCellRef};
+
+/// What a `ClampedCell` does when a value outside of its bounds is written.
+/// Values that cannot be compared with the bounds, such as `NaN`, are treated as outside of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ClampMode {
+    /// The value is clamped to the nearest bound.
+    /// Values that cannot be compared with the bounds are replaced by the lower bound.
+    #[default]
+    Clamp,
+    /// The write is rejected and the cell keeps its previous value.
+    Reject,
+}
+
+/// A cell whose value is kept within an inclusive range.
+/// Values written outside of the range are either clamped to the range or rejected depending on
+/// the `ClampMode` of the cell. This suits settings such as volumes or progress values.
+///
+/// # Usage
+/// ```
+/// use sync_cell::ClampedCell;
+///
+/// let volume = ClampedCell::new(50, 0, 100);
+///
+/// volume.set(150).unwrap();
+///
+/// assert_eq!(100, volume.get());
+/// ```
+#[derive(Debug)]
+pub struct ClampedCell<T: PartialOrd> {
+    /// The value of this cell.
+    data: SyncCell<T>,
+    /// The lower bound of the value.
+    min: T,
+    /// The upper bound of the value.
+    max: T,
+    /// What happens when a value outside of the bounds is written.
+    mode: ClampMode,
+}
+
+impl <T: PartialOrd + Clone> ClampedCell<T> {
+    /// Creates a new `ClampedCell` that clamps values to its bounds.
+    /// The initial value is clamped to the bounds.
+    ///
+    /// - `data` - The initial value of the `ClampedCell`.
+    /// - `min` - The inclusive lower bound of the value.
+    /// - `max` - The inclusive upper bound of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if `min` is greater than `max` or the bounds cannot be compared.
+    pub fn new(data: T, min: T, max: T) -> Self {
+        Self::with_mode(data, min, max, ClampMode::Clamp)
+    }
+
+    /// Creates a new `ClampedCell` with a given mode for values outside of its bounds.
+    /// The initial value is always clamped to the bounds.
+    ///
+    /// - `data` - The initial value of the `ClampedCell`.
+    /// - `min` - The inclusive lower bound of the value.
+    /// - `max` - The inclusive upper bound of the value.
+    /// - `mode` - What happens when a value outside of the bounds is written.
+    ///
+    /// # Panicking
+    /// This method will panic if `min` is greater than `max` or the bounds cannot be compared.
+    pub fn with_mode(data: T, min: T, max: T, mode: ClampMode) -> Self {
+        match min.partial_cmp(&max) {
+            Some(Ordering::Greater) => panic!("Minimum bound is greater than the maximum bound."),
+            None => panic!("Minimum and maximum bounds cannot be compared."),
+            _ => {},
+        }
+
+        Self {
+            data: SyncCell::new(clamp(data, &min, &max)),
+            min,
+            max,
+            mode,
+        }
+    }
+
+    /// Gets the inclusive lower bound of the value of this cell.
+    pub fn min(&self) -> &T {
+        &self.min
+    }
+
+    /// Gets the inclusive upper bound of the value of this cell.
+    pub fn max(&self) -> &T {
+        &self.max
+    }
+
+    /// Gets the mode used by this cell for values outside of its bounds.
+    pub fn mode(&self) -> ClampMode {
+        self.mode
+    }
+
+    /// Sets the value contained in this cell.
+    /// If the value is outside of the bounds it is either clamped or returned as an error
+    /// depending on the mode of the cell.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.replace(value).map(drop)
+    }
+
+    /// Replaces the value contained in this cell.
+    /// The previous value is returned. If the value is outside of the bounds it is either clamped
+    /// or returned as an error depending on the mode of the cell.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> Result<T, T> {
+        self.bound(value)
+            .map(|value| replace(&mut *self.data.borrow_mut(), value))
+    }
+
+    /// Replaces the value of this cell with the result of a function applied to the current
+    /// value. The result is bounded in the same way as `set`.
+    ///
+    /// - `f` - The function used to compute the new value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn update(&self, f: impl FnOnce(&T) -> T) -> Result<(), T> {
+        let mut data = self.data.borrow_mut();
+
+        *data = self.bound(f(&data))?;
+        Ok(())
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.data.borrow()
+    }
+
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+
+    /// Retrieves the inner value stored in this `ClampedCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Applies the mode of this cell to a value that may be outside of the bounds.
+    fn bound(&self, value: T) -> Result<T, T> {
+        match self.mode {
+            ClampMode::Clamp => Ok(clamp(value, &self.min, &self.max)),
+            ClampMode::Reject if in_range(&value, &self.min, &self.max) => Ok(value),
+            ClampMode::Reject => Err(value),
+        }
+    }
+}
+
+/// Checks if a value is within an inclusive range.
+/// Values that cannot be compared with the bounds are outside of the range.
+fn in_range<T: PartialOrd>(value: &T, min: &T, max: &T) -> bool {
+    matches!(value.partial_cmp(min), Some(Ordering::Greater | Ordering::Equal))
+        && matches!(value.partial_cmp(max), Some(Ordering::Less | Ordering::Equal))
+}
+
+/// Clamps a value to an inclusive range.
+/// Values that cannot be compared with the bounds are clamped to the lower bound.
+fn clamp<T: PartialOrd + Clone>(value: T, min: &T, max: &T) -> T {
+    match (value.partial_cmp(min), value.partial_cmp(max)) {
+        (Some(Ordering::Less) | None, _) | (_, None) => min.clone(),
+        (_, Some(Ordering::Greater)) => max.clone(),
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClampMode, ClampedCell};
+
+    #[test]
+    pub fn test_clamped_cell_clamp() {
+        let cell = ClampedCell::new(-5, 0, 10);
+        assert_eq!(0, cell.get());
+
+        assert_eq!(Ok(()), cell.set(20));
+        assert_eq!(10, cell.get());
+
+        assert_eq!(Ok(()), cell.update(|value| value - 3));
+        assert_eq!(7, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_clamped_cell_reject() {
+        let cell = ClampedCell::with_mode(5, 0, 10, ClampMode::Reject);
+
+        assert_eq!(Err(11), cell.set(11));
+        assert_eq!(Err(-1), cell.update(|_| -1));
+        assert_eq!(Ok(5), cell.replace(10));
+        assert_eq!(10, cell.get());
+    }
+
+    #[test]
+    pub fn test_clamped_cell_nan() {
+        let clamped = ClampedCell::new(f64::NAN, 0.0, 1.0);
+        let rejected = ClampedCell::with_mode(0.5, 0.0, 1.0, ClampMode::Reject);
+
+        assert_eq!(0.0, clamped.get());
+        assert_eq!(Ok(()), clamped.set(f64::NAN));
+        assert_eq!(0.0, clamped.get());
+
+        assert!(rejected.set(f64::NAN).unwrap_err().is_nan());
+        assert!(rejected.update(|_| f64::NAN).unwrap_err().is_nan());
+        assert_eq!(0.5, rejected.get());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_clamped_cell_nan_bounds() {
+        ClampedCell::new(0.5, f64::NAN, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_clamped_cell_invalid_bounds() {
+        ClampedCell::new(0.5, 1.0, 0.0);
+    }
+}
@@ -0,0 +1,294 @@
+use std::{ops::{Deref, DerefMut}, sync::{Condvar, Mutex, MutexGuard}, time::Duration};
+
+/// A cell that allows threads to block until its value satisfies a condition.
+/// Every change made to the value wakes any waiting threads so that they can check their
+/// condition again. This combines a `Mutex` and a `Condvar` behind the same easy to use API as
+/// `SyncCell`.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::CondCell;
+///
+/// let cell = Arc::new(CondCell::new(0));
+///
+/// let producer = {
+///     let cell = cell.clone();
+///     thread::spawn(move || {
+///         for i in 1..=5 {
+///             cell.set(i);
+///         }
+///     })
+/// };
+///
+/// let value = cell.wait_until(|value| *value == 5);
+/// assert_eq!(5, *value);
+/// # drop(value);
+/// # producer.join().unwrap();
+/// ```
+///
+/// # Panicking
+/// The same as `SyncCell`, `CondCell` will panic rather than return an error when the lock
+/// becomes poisoned.
+#[derive(Debug, Default)]
+pub struct CondCell<T> {
+    /// The lock holding the data of this cell.
+    data: Mutex<T>,
+    /// The condition variable used to wake waiting threads.
+    changed: Condvar,
+}
+
+impl <T> CondCell<T> {
+    /// Creates a new `CondCell`.
+    ///
+    /// - `data` - The initial value of the `CondCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            data: Mutex::new(data),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Sets the value contained in this cell and wakes any waiting threads.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the value contained in this cell and wakes any waiting threads.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        std::mem::replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Retrieves the inner value stored in this `CondCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        match self.data.into_inner() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// Waiting threads are not woken since the value cannot be changed through this reference.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> CondCellRef<'_, T> {
+        CondCellRef {
+            data: self.lock(),
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// Waiting threads are woken when the guard is dropped if the value was mutably accessed.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> CondCellGuard<'_, T> {
+        self.guard(self.lock())
+    }
+
+    /// Blocks the current thread until the value of this cell satisfies a condition.
+    /// The condition is checked immediately and then again every time the value changes.
+    /// The returned guard holds the lock so the value cannot change until it is dropped.
+    ///
+    /// - `condition` - The condition to wait for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait_until(&self, mut condition: impl FnMut(&T) -> bool) -> CondCellGuard<'_, T> {
+        let data = self.lock();
+
+        match self.changed.wait_while(data, |value| !condition(value)) {
+            Ok(data) => self.guard(data),
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Blocks the current thread until the value of this cell satisfies a condition or a timeout
+    /// elapses.
+    /// Returns `None` if the timeout elapsed before the condition held.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    /// - `condition` - The condition to wait for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait_until_timeout(&self, timeout: Duration, mut condition: impl FnMut(&T) -> bool)
+            -> Option<CondCellGuard<'_, T>> {
+        let data = self.lock();
+
+        match self.changed.wait_timeout_while(data, timeout, |value| !condition(value)) {
+            Ok((_, result)) if result.timed_out() => None,
+            Ok((data, _)) => Some(self.guard(data)),
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Wakes all threads waiting on this cell so that they check their conditions again.
+    pub fn notify_all(&self) {
+        self.changed.notify_all()
+    }
+
+    /// Acquires the lock over the data of this cell.
+    fn lock(&self) -> MutexGuard<'_, T> {
+        match self.data.lock() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Wraps a lock guard so that waiting threads are woken after modifications.
+    fn guard<'a>(&'a self, data: MutexGuard<'a, T>) -> CondCellGuard<'a, T> {
+        CondCellGuard {
+            data: Some(data),
+            changed: &self.changed,
+            modified: false,
+        }
+    }
+}
+
+impl <T: Clone> CondCell<T> {
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl <T> From<T> for CondCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// An immutable borrow of the value in a `CondCell`.
+pub struct CondCellRef<'a, T> {
+    /// The lock guard over the data.
+    data: MutexGuard<'a, T>,
+}
+
+impl <T> Deref for CondCellRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+/// A mutable borrow of the value in a `CondCell`.
+/// If the value is mutably accessed through this guard, waiting threads are woken when it is
+/// dropped.
+pub struct CondCellGuard<'a, T> {
+    /// The lock guard over the data. This is only `None` while the guard is being dropped so that
+    /// the lock is released before waiting threads are woken.
+    data: Option<MutexGuard<'a, T>>,
+    /// The condition variable used to wake waiting threads.
+    changed: &'a Condvar,
+    /// Whether the value has been mutably accessed.
+    modified: bool,
+}
+
+impl <T> Deref for CondCellGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &self.data {
+            Some(data) => data,
+            None => unreachable!("Guard was already dropped."),
+        }
+    }
+}
+
+impl <T> DerefMut for CondCellGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.modified = true;
+
+        match &mut self.data {
+            Some(data) => data,
+            None => unreachable!("Guard was already dropped."),
+        }
+    }
+}
+
+impl <T> Drop for CondCellGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.data.take().is_some() && self.modified {
+            self.changed.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::CondCell;
+
+    #[test]
+    pub fn test_cond_cell_set_get() {
+        let cell = CondCell::new(1);
+
+        cell.set(2);
+
+        assert_eq!(2, cell.get());
+        assert_eq!(2, cell.replace(3));
+        assert_eq!(3, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_cond_cell_borrow_mut_unmodified() {
+        let cell = CondCell::new(1);
+
+        assert_eq!(1, *cell.borrow());
+        assert_eq!(1, *cell.borrow_mut());
+
+        *cell.borrow_mut() += 1;
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    pub fn test_cond_cell_wait_until_already_true() {
+        let cell = CondCell::new(1);
+
+        assert_eq!(1, *cell.wait_until(|value| *value == 1));
+    }
+
+    #[test]
+    pub fn test_cond_cell_wait_until() {
+        let cell = Arc::new(CondCell::new(0));
+        let cell2 = cell.clone();
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            *cell2.borrow_mut() += 1;
+        });
+
+        assert_eq!(1, *cell.wait_until(|value| *value == 1));
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    pub fn test_cond_cell_wait_until_timeout() {
+        let cell = CondCell::new(0);
+
+        assert!(cell.wait_until_timeout(Duration::from_millis(10), |value| *value == 1).is_none());
+        assert!(cell.wait_until_timeout(Duration::from_millis(10), |value| *value == 0).is_some());
+    }
+}
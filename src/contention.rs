@@ -0,0 +1,126 @@
+use std::{sync::{LockResult, TryLockError, TryLockResult, atomic::{AtomicU64, Ordering}}, time::{Duration, Instant}};
+
+/// Statistics about how often borrows of a `SyncCell` had to wait for the lock.
+/// Only borrows that could not take the lock immediately are counted.
+///
+/// This type is only available with the `contention-stats` feature.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncCell;
+///
+/// let cell = SyncCell::new(0);
+///
+/// cell.set(1);
+///
+/// let stats = cell.contention_stats();
+/// assert_eq!(0, stats.blocked);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ContentionStats {
+    /// The number of borrows that had to wait for the lock.
+    pub blocked: u64,
+    /// The total time spent waiting for the lock.
+    pub total_wait: Duration,
+    /// The longest time a single borrow spent waiting for the lock.
+    pub max_wait: Duration,
+}
+
+/// The counters from which `ContentionStats` are read.
+#[derive(Debug)]
+pub(crate) struct ContentionCounters {
+    /// The number of borrows that had to wait for the lock.
+    blocked: AtomicU64,
+    /// The total time spent waiting for the lock in nanoseconds.
+    total_wait: AtomicU64,
+    /// The longest time a single borrow spent waiting for the lock in nanoseconds.
+    max_wait: AtomicU64,
+}
+
+/// The counters of every `SyncCell` combined.
+static GLOBAL: ContentionCounters = ContentionCounters::new();
+
+impl ContentionCounters {
+    /// Creates a new set of counters with nothing recorded.
+    pub(crate) const fn new() -> Self {
+        Self {
+            blocked: AtomicU64::new(0),
+            total_wait: AtomicU64::new(0),
+            max_wait: AtomicU64::new(0),
+        }
+    }
+
+    /// Records a borrow that waited for the lock.
+    fn record(&self, wait: Duration) {
+        let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+
+        self.blocked.fetch_add(1, Ordering::Relaxed);
+        self.total_wait.fetch_add(nanos, Ordering::Relaxed);
+        self.max_wait.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Reads the statistics recorded so far.
+    pub(crate) fn stats(&self) -> ContentionStats {
+        ContentionStats {
+            blocked: self.blocked.load(Ordering::Relaxed),
+            total_wait: Duration::from_nanos(self.total_wait.load(Ordering::Relaxed)),
+            max_wait: Duration::from_nanos(self.max_wait.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Takes a lock recording how long it was waited for if it could not be taken immediately.
+    ///
+    /// - `try_lock` - Attempts to take the lock without blocking.
+    /// - `lock` - Takes the lock blocking until it is available.
+    pub(crate) fn acquire<G>(&self, try_lock: impl FnOnce() -> TryLockResult<G>, lock: impl FnOnce() -> LockResult<G>) -> LockResult<G> {
+        match try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::Poisoned(err)) => Err(err),
+            Err(TryLockError::WouldBlock) => {
+                let start = Instant::now();
+                let result = lock();
+                let wait = start.elapsed();
+
+                self.record(wait);
+                GLOBAL.record(wait);
+
+                result
+            },
+        }
+    }
+}
+
+/// Gets the contention statistics of every `SyncCell` combined.
+/// This function is only available with the `contention-stats` feature.
+pub fn global_contention_stats() -> ContentionStats {
+    GLOBAL.stats()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::{SyncCell, global_contention_stats};
+
+    #[test]
+    pub fn test_sync_cell_contention_stats() {
+        let cell = Arc::new(SyncCell::new(0));
+        let borrowed = cell.borrow();
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(1))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        drop(borrowed);
+        writer.join().unwrap();
+
+        let stats = cell.contention_stats();
+
+        assert_eq!(1, stats.blocked);
+        assert!(stats.max_wait >= Duration::from_millis(10));
+        assert_eq!(stats.max_wait, stats.total_wait);
+        assert!(global_contention_stats().blocked >= 1);
+    }
+}
@@ -0,0 +1,275 @@
+use std::sync::atomic::{AtomicI32, AtomicI64, AtomicIsize, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+/// What a `SyncCounter` does when a change would move it past the range of its type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CounterMode {
+    /// The value wraps around to the other end of the range.
+    #[default]
+    Wrapping,
+    /// The value stops at the end of the range.
+    Saturating,
+}
+
+/// An integer type that can be counted by a `SyncCounter`.
+/// This is implemented for the integer types that have a matching atomic type.
+pub trait CounterValue: Copy + Default {
+    /// The atomic type storing the value.
+    #[doc(hidden)]
+    type Atomic: Send + Sync;
+
+    /// An atomic storing zero.
+    #[doc(hidden)]
+    const ZERO: Self::Atomic;
+
+    /// Creates the atomic storing a value.
+    #[doc(hidden)]
+    fn new_atomic(value: Self) -> Self::Atomic;
+
+    /// Gets the current value.
+    #[doc(hidden)]
+    fn load(atomic: &Self::Atomic) -> Self;
+
+    /// Replaces the current value, returning the previous value.
+    #[doc(hidden)]
+    fn swap(atomic: &Self::Atomic, value: Self) -> Self;
+
+    /// Adds to the current value, returning the new value.
+    #[doc(hidden)]
+    fn add(atomic: &Self::Atomic, amount: Self, mode: CounterMode) -> Self;
+
+    /// Subtracts from the current value, returning the new value.
+    #[doc(hidden)]
+    fn sub(atomic: &Self::Atomic, amount: Self, mode: CounterMode) -> Self;
+
+    /// Gets the value one.
+    #[doc(hidden)]
+    fn one() -> Self;
+}
+
+/// Implements `CounterValue` for integers and their atomic types.
+macro_rules! impl_counter_value {
+    ($($t:ty => $atomic:ty),*) => {
+        $(
+            impl CounterValue for $t {
+                type Atomic = $atomic;
+
+                // Each use of the constant creates a new atomic, which is what the counter needs.
+                #[allow(clippy::declare_interior_mutable_const)]
+                const ZERO: $atomic = <$atomic>::new(0);
+
+                fn new_atomic(value: Self) -> $atomic {
+                    <$atomic>::new(value)
+                }
+
+                fn load(atomic: &$atomic) -> Self {
+                    atomic.load(Ordering::Acquire)
+                }
+
+                fn swap(atomic: &$atomic, value: Self) -> Self {
+                    atomic.swap(value, Ordering::AcqRel)
+                }
+
+                fn add(atomic: &$atomic, amount: Self, mode: CounterMode) -> Self {
+                    match mode {
+                        CounterMode::Wrapping => atomic.fetch_add(amount, Ordering::AcqRel).wrapping_add(amount),
+                        CounterMode::Saturating => {
+                            let previous = atomic.fetch_update(Ordering::AcqRel, Ordering::Acquire, |value| Some(value.saturating_add(amount)));
+                            previous.unwrap_or_else(|value| value).saturating_add(amount)
+                        },
+                    }
+                }
+
+                fn sub(atomic: &$atomic, amount: Self, mode: CounterMode) -> Self {
+                    match mode {
+                        CounterMode::Wrapping => atomic.fetch_sub(amount, Ordering::AcqRel).wrapping_sub(amount),
+                        CounterMode::Saturating => {
+                            let previous = atomic.fetch_update(Ordering::AcqRel, Ordering::Acquire, |value| Some(value.saturating_sub(amount)));
+                            previous.unwrap_or_else(|value| value).saturating_sub(amount)
+                        },
+                    }
+                }
+
+                fn one() -> Self {
+                    1
+                }
+            }
+        )*
+    };
+}
+
+/// A counter that can be changed from multiple threads without a lock.
+/// The value is stored in an atomic integer, which makes this much faster than a `SyncCell`
+/// holding a number for shared counters such as request totals. The counter either wraps or
+/// saturates when it overflows depending on its `CounterMode`.
+///
+/// Counters of `u64`, `i64`, `u32`, `i32`, `usize` and `isize` are supported, with `u64` being the
+/// default.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{CounterMode, SyncCounter};
+///
+/// static REQUESTS: SyncCounter = SyncCounter::zero(CounterMode::Wrapping);
+///
+/// REQUESTS.increment();
+/// REQUESTS.add(2);
+/// assert_eq!(3, REQUESTS.get());
+///
+/// let remaining = SyncCounter::with_mode(1u32, CounterMode::Saturating);
+///
+/// assert_eq!(0, remaining.sub(5));
+/// ```
+#[derive(Debug)]
+pub struct SyncCounter<T: CounterValue = u64> {
+    /// The value of this counter.
+    value: T::Atomic,
+    /// What happens when a change would overflow the counter.
+    mode: CounterMode,
+}
+
+impl_counter_value!(u64 => AtomicU64, i64 => AtomicI64, u32 => AtomicU32, i32 => AtomicI32, usize => AtomicUsize, isize => AtomicIsize);
+
+impl <T: CounterValue> SyncCounter<T> {
+    /// Creates a new `SyncCounter` that wraps around on overflow.
+    ///
+    /// - `value` - The initial value of the counter.
+    pub fn new(value: T) -> Self {
+        Self::with_mode(value, CounterMode::Wrapping)
+    }
+
+    /// Creates a new `SyncCounter` with a mode for handling overflow.
+    ///
+    /// - `value` - The initial value of the counter.
+    /// - `mode` - What happens when a change would overflow the counter.
+    pub fn with_mode(value: T, mode: CounterMode) -> Self {
+        Self {
+            value: T::new_atomic(value),
+            mode,
+        }
+    }
+
+    /// Creates a new `SyncCounter` starting at zero.
+    /// Unlike `new` this can be used to initialize a `static`.
+    ///
+    /// - `mode` - What happens when a change would overflow the counter.
+    pub const fn zero(mode: CounterMode) -> Self {
+        Self {
+            value: T::ZERO,
+            mode,
+        }
+    }
+
+    /// Adds one to this counter.
+    /// The new value of the counter is returned.
+    pub fn increment(&self) -> T {
+        self.add(T::one())
+    }
+
+    /// Subtracts one from this counter.
+    /// The new value of the counter is returned.
+    pub fn decrement(&self) -> T {
+        self.sub(T::one())
+    }
+
+    /// Adds an amount to this counter.
+    /// The new value of the counter is returned.
+    ///
+    /// - `amount` - The amount to add.
+    pub fn add(&self, amount: T) -> T {
+        T::add(&self.value, amount, self.mode)
+    }
+
+    /// Subtracts an amount from this counter.
+    /// The new value of the counter is returned.
+    ///
+    /// - `amount` - The amount to subtract.
+    pub fn sub(&self, amount: T) -> T {
+        T::sub(&self.value, amount, self.mode)
+    }
+
+    /// Gets the value of this counter.
+    pub fn get(&self) -> T {
+        T::load(&self.value)
+    }
+
+    /// Sets the value of this counter.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the counter.
+    pub fn set(&self, value: T) -> T {
+        T::swap(&self.value, value)
+    }
+
+    /// Sets this counter back to zero.
+    /// The previous value is returned, which allows counts to be collected periodically.
+    pub fn reset(&self) -> T {
+        self.set(T::default())
+    }
+
+    /// Gets what this counter does when a change would overflow it.
+    pub fn mode(&self) -> CounterMode {
+        self.mode
+    }
+
+    /// Retrieves the value of this counter.
+    pub fn into_inner(self) -> T {
+        T::load(&self.value)
+    }
+}
+
+impl <T: CounterValue> Default for SyncCounter<T> {
+    fn default() -> Self {
+        Self::zero(CounterMode::default())
+    }
+}
+
+impl <T: CounterValue> From<T> for SyncCounter<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl <T: CounterValue> Clone for SyncCounter<T> {
+    fn clone(&self) -> Self {
+        Self::with_mode(self.get(), self.mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::{CounterMode, SyncCounter};
+
+    #[test]
+    pub fn test_sync_counter() {
+        let counter = Arc::new(SyncCounter::new(0));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let counter = counter.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    counter.increment();
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(4000, counter.reset());
+        assert_eq!(0, counter.get());
+    }
+
+    #[test]
+    pub fn test_sync_counter_modes() {
+        let wrapping = SyncCounter::new(u64::MAX);
+        let saturating = SyncCounter::with_mode(i64::MIN + 1, CounterMode::Saturating);
+
+        assert_eq!(0, wrapping.increment());
+        assert_eq!(u64::MAX, wrapping.decrement());
+        assert_eq!(i64::MIN, saturating.sub(5));
+        assert_eq!(i64::MIN + 3, saturating.add(3));
+    }
+}
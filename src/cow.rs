@@ -0,0 +1,169 @@
+use std::{ops::{Deref, DerefMut}, sync::Arc};
+
+use crate::{SyncCell, SyncCellGuard};
+
+/// A cell whose readers hold cheap snapshots and whose writers clone the value only when needed.
+/// Borrowing the value returns a snapshot that shares the value with the cell. Borrowing the value
+/// mutably modifies it in place if no snapshots exist. Otherwise the value is cloned first so that
+/// existing snapshots are unaffected and their holders are never blocked by the writer.
+///
+/// While a mutable borrow is held, new snapshots wait for it to be released.
+///
+/// # Usage
+/// ```
+/// use sync_cell::CowCell;
+///
+/// let cell = CowCell::new(vec![1, 2]);
+///
+/// let snapshot = cell.borrow();
+///
+/// // The value is cloned because a snapshot still refers to it.
+/// cell.borrow_mut().push(3);
+///
+/// assert_eq!(vec![1, 2], *snapshot);
+/// assert_eq!(vec![1, 2, 3], *cell.borrow());
+/// ```
+#[derive(Debug, Default)]
+pub struct CowCell<T> {
+    /// The current value shared with any snapshots.
+    data: SyncCell<Arc<T>>,
+}
+
+impl <T> CowCell<T> {
+    /// Creates a new `CowCell`.
+    ///
+    /// - `data` - The initial value of the `CowCell`.
+    pub fn new(data: T) -> Self {
+        Self {
+            data: SyncCell::new(Arc::new(data)),
+        }
+    }
+
+    /// Borrows a snapshot of the current value of this cell.
+    /// The snapshot is unaffected by later changes to the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> Arc<T> {
+        self.data.get()
+    }
+
+    /// Sets the value contained in this cell.
+    /// Existing snapshots continue to refer to the previous value.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        self.data.set(Arc::new(value))
+    }
+
+    /// Checks if any snapshots of the current value exist.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.data.borrow()) > 1
+    }
+}
+
+impl <T: Clone> CowCell<T> {
+    /// Borrows a mutable reference to the value of this cell.
+    /// If any snapshots of the value exist, the value is cloned before it is modified so the
+    /// snapshots are unaffected.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> CowCellGuard<'_, T> {
+        CowCellGuard {
+            data: self.data.borrow_mut(),
+        }
+    }
+
+    /// Gets a copy of the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        T::clone(&self.borrow())
+    }
+
+    /// Retrieves the inner value stored in this `CowCell`.
+    /// The value is cloned if any snapshots of it still exist.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        Arc::unwrap_or_clone(self.data.into_inner())
+    }
+}
+
+impl <T> From<T> for CowCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A mutable borrow of the value in a `CowCell`.
+/// The value is cloned the first time it is mutably accessed if any snapshots of it exist.
+pub struct CowCellGuard<'a, T> {
+    /// The lock guard over the shared value.
+    data: SyncCellGuard<'a, Arc<T>>,
+}
+
+impl <T> Deref for CowCellGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl <T: Clone> DerefMut for CowCellGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::CowCell;
+
+    #[test]
+    pub fn test_cow_cell_borrow_mut_in_place() {
+        let cell = CowCell::new(vec![1]);
+        let before = Arc::as_ptr(&cell.borrow());
+
+        cell.borrow_mut().push(2);
+
+        assert_eq!(before, Arc::as_ptr(&cell.borrow()));
+        assert_eq!(vec![1, 2], cell.get());
+    }
+
+    #[test]
+    pub fn test_cow_cell_borrow_mut_clones() {
+        let cell = CowCell::new(vec![1]);
+        let snapshot = cell.borrow();
+
+        assert!(cell.is_shared());
+        cell.borrow_mut().push(2);
+
+        assert!(!cell.is_shared());
+        assert_eq!(vec![1], *snapshot);
+        assert_eq!(vec![1, 2], cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_cow_cell_set() {
+        let cell = CowCell::new(1);
+        let snapshot = cell.borrow();
+
+        cell.set(2);
+
+        assert_eq!(1, *snapshot);
+        assert_eq!(2, cell.get());
+    }
+}
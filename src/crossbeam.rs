@@ -0,0 +1,91 @@
+use crossbeam_utils::atomic::AtomicCell;
+
+use crate::{HeldSyncCell, RawSyncLock, SyncCell};
+
+impl <T, L: RawSyncLock> SyncCell<T, L> {
+    /// Converts this cell into a `crossbeam_utils::atomic::AtomicCell` holding the value of this
+    /// cell.
+    /// `AtomicCell::load` and `AtomicCell::store` behave like `get` and `set` on a `SyncCell`, and
+    /// every change is seen by the next read on any thread. Unlike a `SyncCell`, the value of an
+    /// `AtomicCell` cannot be borrowed, and it is only lock-free for small `Copy` values.
+    /// An `AtomicCell` is converted back with `From`, which needs the type of the cell to be
+    /// written out since a `SyncCell` can also hold the `AtomicCell` itself.
+    /// This method is only available with the `crossbeam` feature.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(1);
+    /// let atomic = cell.into_atomic_cell();
+    ///
+    /// atomic.store(2);
+    /// assert_eq!(2, atomic.load());
+    ///
+    /// let cell: SyncCell<i32> = atomic.into();
+    /// assert_eq!(2, cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_atomic_cell(self) -> AtomicCell<T> {
+        AtomicCell::new(self.into_inner())
+    }
+}
+
+impl <T> HeldSyncCell<T> {
+    /// Converts this cell into a `crossbeam_utils::atomic::AtomicCell` holding the value returned
+    /// by `into_inner`, which is the incoming value if one has been set since the last call to
+    /// `update`.
+    /// This method is only available with the `crossbeam` feature.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_atomic_cell(self) -> AtomicCell<T> {
+        AtomicCell::new(self.into_inner())
+    }
+}
+
+impl <T, L: RawSyncLock> From<AtomicCell<T>> for SyncCell<T, L> {
+    /// Creates a new `SyncCell` holding the value of an `AtomicCell`.
+    fn from(cell: AtomicCell<T>) -> Self {
+        Self::with_lock(cell.into_inner(), L::INIT)
+    }
+}
+
+impl <T> From<AtomicCell<T>> for HeldSyncCell<T> {
+    /// Creates a new `HeldSyncCell` whose current value is the value of an `AtomicCell`.
+    fn from(cell: AtomicCell<T>) -> Self {
+        Self::new(cell.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossbeam_utils::atomic::AtomicCell;
+
+    use crate::{HeldSyncCell, SyncCell};
+
+    #[test]
+    pub fn test_sync_cell_atomic_cell() {
+        let atomic = SyncCell::new(String::from("a")).into_atomic_cell();
+
+        assert_eq!("a", atomic.swap(String::from("b")));
+
+        let cell: SyncCell<String> = atomic.into();
+        assert_eq!("b", cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_atomic_cell() {
+        let cell = HeldSyncCell::new(1);
+        cell.set(2);
+
+        let atomic = cell.into_atomic_cell();
+        assert_eq!(2, atomic.load());
+
+        let cell: HeldSyncCell<i32> = AtomicCell::new(3).into();
+        assert_eq!(3, cell.get());
+        assert!(!cell.has_update());
+    }
+}
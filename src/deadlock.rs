@@ -0,0 +1,309 @@
+use std::{collections::HashMap, fmt::Write, sync::Mutex, thread::{self, ThreadId}};
+
+use crate::{DefaultRawLock, RawSyncLock};
+
+/// The locks held and waited on by every thread using a `DetectingRawLock`.
+struct LockGraph {
+    /// The threads holding each lock, keyed by the address of the lock.
+    /// A thread appears once for every borrow it holds.
+    holders: HashMap<usize, Vec<ThreadId>>,
+    /// The lock each blocked thread is waiting for.
+    waiting: HashMap<ThreadId, usize>,
+}
+
+/// The lock graph shared by all `DetectingRawLock`s.
+static GRAPH: Mutex<Option<LockGraph>> = Mutex::new(None);
+
+/// Runs a function with the shared lock graph locked.
+fn with_graph<R>(f: impl FnOnce(&mut LockGraph) -> R) -> R {
+    // The graph is never left inconsistent while locked, so a poisoned lock can be used.
+    let mut graph = match GRAPH.lock() {
+        Ok(graph) => graph,
+        Err(err) => err.into_inner(),
+    };
+
+    f(graph.get_or_insert_with(|| LockGraph {
+        holders: HashMap::new(),
+        waiting: HashMap::new(),
+    }))
+}
+
+impl LockGraph {
+    /// Finds a cycle of threads waiting for each other that starts with `thread` waiting for
+    /// `lock`.
+    /// The path of the cycle is returned as pairs of a lock and the thread holding it.
+    fn find_cycle(&self, thread: ThreadId, lock: usize) -> Option<Vec<(usize, ThreadId)>> {
+        let mut path = Vec::new();
+
+        self.search(thread, lock, &mut path).then_some(path)
+    }
+
+    /// Searches the holders of `lock` for a chain of waiting threads leading back to `target`.
+    fn search(&self, target: ThreadId, lock: usize, path: &mut Vec<(usize, ThreadId)>) -> bool {
+        for &holder in self.holders.get(&lock).into_iter().flatten() {
+            // Each thread waits for at most one lock, so a thread already on the path is a cycle
+            // that does not involve the target.
+            if path.iter().any(|&(_, visited)| visited == holder) {
+                continue;
+            }
+
+            path.push((lock, holder));
+
+            if holder == target {
+                return true;
+            }
+
+            if let Some(&next) = self.waiting.get(&holder) {
+                if self.search(target, next, path) {
+                    return true;
+                }
+            }
+
+            path.pop();
+        }
+
+        false
+    }
+}
+
+/// A `RawSyncLock` backend that panics instead of deadlocking when threads wait for each other
+/// in a cycle.
+/// Every thread that blocks on the lock is recorded in a graph shared by all detecting locks.
+/// When the locks held by the waiting threads form a cycle, the thread that closes it panics
+/// with a report of the cycle.
+///
+/// Tracking lock acquisitions adds a global lock to every blocked borrow, so this backend is
+/// meant for debugging. It wraps another backend that does the actual locking.
+///
+/// This type is only available with the `deadlock-detection` feature.
+///
+/// A `SyncCell` detects deadlocks when it is created with this backend using
+/// `SyncCell::with_lock`.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{DetectingRawLock, RawSyncCell, RawSyncLock, SyncCell};
+///
+/// let accounts: RawSyncCell<i32, DetectingRawLock> = RawSyncCell::new(0);
+///
+/// *accounts.borrow_mut() += 1;
+/// assert_eq!(1, accounts.get());
+///
+/// let balance: SyncCell<i32, DetectingRawLock> = SyncCell::with_lock(0, DetectingRawLock::INIT);
+///
+/// balance.set(2);
+/// assert_eq!(2, balance.get());
+/// ```
+pub struct DetectingRawLock<L = DefaultRawLock> {
+    /// The lock doing the actual locking.
+    inner: L,
+}
+
+impl <L: RawSyncLock> DetectingRawLock<L> {
+    /// Creates a new `DetectingRawLock` wrapping an existing lock.
+    ///
+    /// - `inner` - The unlocked lock doing the actual locking.
+    pub const fn new(inner: L) -> Self {
+        Self {
+            inner,
+        }
+    }
+
+    /// Gets the key of this lock in the lock graph.
+    fn key(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// Acquires the lock with `lock` after `try_lock` fails recording the wait in the lock graph.
+    ///
+    /// # Panicking
+    /// This method will panic if waiting would complete a cycle of waiting threads.
+    fn acquire(&self, try_lock: impl FnOnce(&L) -> bool, lock: impl FnOnce(&L)) {
+        let thread = thread::current().id();
+
+        if !try_lock(&self.inner) {
+            self.wait(thread);
+            lock(&self.inner);
+        }
+
+        self.hold(thread);
+    }
+
+    /// Records that `thread` holds a borrow of this lock and is no longer waiting.
+    fn hold(&self, thread: ThreadId) {
+        with_graph(|graph| {
+            graph.waiting.remove(&thread);
+            graph.holders.entry(self.key()).or_default().push(thread);
+        });
+    }
+
+    /// Records that `thread` is about to wait for this lock.
+    ///
+    /// # Panicking
+    /// This method will panic if waiting would complete a cycle of waiting threads.
+    fn wait(&self, thread: ThreadId) {
+        let cycle = with_graph(|graph| {
+            let cycle = graph.find_cycle(thread, self.key());
+
+            if cycle.is_none() {
+                graph.waiting.insert(thread, self.key());
+            }
+
+            cycle
+        });
+
+        if let Some(cycle) = cycle {
+            let mut report = format!("Deadlock detected: {:?} waits for lock {:#x}", thread, self.key());
+
+            for (index, (lock, holder)) in cycle.into_iter().enumerate() {
+                if index > 0 {
+                    let _ = write!(report, ", which waits for lock {:#x}", lock);
+                }
+
+                let _ = write!(report, " held by {:?}", holder);
+            }
+
+            panic!("{}", report);
+        }
+    }
+
+    /// Removes a borrow of this lock held by the current thread from the lock graph.
+    fn release(&self) {
+        let thread = thread::current().id();
+
+        with_graph(|graph| {
+            if let Some(holders) = graph.holders.get_mut(&self.key()) {
+                if let Some(index) = holders.iter().position(|&holder| holder == thread) {
+                    holders.swap_remove(index);
+                }
+
+                if holders.is_empty() {
+                    graph.holders.remove(&self.key());
+                }
+            }
+        });
+    }
+}
+
+unsafe impl <L: RawSyncLock> RawSyncLock for DetectingRawLock<L> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self::new(L::INIT);
+
+    fn lock_shared(&self) {
+        self.acquire(L::try_lock_shared, L::lock_shared);
+    }
+
+    fn lock_shared_recursive(&self) {
+        self.acquire(L::try_lock_shared, L::lock_shared_recursive);
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        let locked = self.inner.try_lock_shared();
+
+        if locked {
+            self.hold(thread::current().id());
+        }
+
+        locked
+    }
+
+    unsafe fn unlock_shared(&self) {
+        self.release();
+
+        // SAFETY: The caller holds a shared lock, which was acquired from the inner lock.
+        unsafe { self.inner.unlock_shared() }
+    }
+
+    fn lock_exclusive(&self) {
+        self.acquire(L::try_lock_exclusive, L::lock_exclusive);
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        let locked = self.inner.try_lock_exclusive();
+
+        if locked {
+            self.hold(thread::current().id());
+        }
+
+        locked
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        self.release();
+
+        // SAFETY: The caller holds an exclusive lock, which was acquired from the inner lock.
+        unsafe { self.inner.unlock_exclusive() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, Barrier}, thread};
+
+    use crate::{DetectingRawLock, PoisonPolicy, RawSyncCell, RawSyncLock, SyncCell};
+
+    #[test]
+    pub fn test_detecting_raw_lock_borrow() {
+        let cell: RawSyncCell<i32, DetectingRawLock> = RawSyncCell::new(1);
+        let borrowed = cell.borrow();
+
+        assert!(cell.try_borrow_mut().is_none());
+        assert_eq!(1, *cell.borrow());
+
+        drop(borrowed);
+        cell.set(2);
+
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_detecting_raw_lock_cycle() {
+        let first: Arc<RawSyncCell<i32, DetectingRawLock>> = Arc::new(RawSyncCell::new(0));
+        let second: Arc<RawSyncCell<i32, DetectingRawLock>> = Arc::new(RawSyncCell::new(0));
+        let barrier = Arc::new(Barrier::new(2));
+
+        let handles: Vec<_> = [(first.clone(), second.clone()), (second, first)].into_iter().map(|(held, wanted)| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let _held = held.borrow_mut();
+                barrier.wait();
+                *wanted.borrow_mut() += 1;
+            })
+        }).collect();
+
+        let panicked = handles.into_iter()
+            .map(|handle| handle.join().is_err())
+            .filter(|&panicked| panicked)
+            .count();
+
+        // The thread closing the cycle panics, which releases its lock for the other thread.
+        assert_eq!(1, panicked);
+    }
+
+    #[test]
+    pub fn test_detecting_raw_lock_sync_cell_cycle() {
+        // The thread closing the cycle poisons the cell it holds, so the other thread must be
+        // able to use it afterwards.
+        let cell = || -> Arc<SyncCell<i32, DetectingRawLock>> {
+            Arc::new(SyncCell::builder().poison_policy(PoisonPolicy::Ignore).build_with_lock(0, DetectingRawLock::INIT))
+        };
+        let (first, second) = (cell(), cell());
+        let barrier = Arc::new(Barrier::new(2));
+
+        let handles: Vec<_> = [(first.clone(), second.clone()), (second, first)].into_iter().map(|(held, wanted)| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let _held = held.borrow_mut();
+                barrier.wait();
+                *wanted.borrow_mut() += 1;
+            })
+        }).collect();
+
+        let panicked = handles.into_iter()
+            .map(|handle| handle.join().is_err())
+            .filter(|&panicked| panicked)
+            .count();
+
+        assert_eq!(1, panicked);
+    }
+}
@@ -0,0 +1,271 @@
+use std::time::{Duration, Instant};
+
+use crate::{SyncCell, SyncCellError, SyncCellGuard, SyncCellRef};
+
+/// A cell that only publishes a new value once no other value has been set for a period of time.
+/// Each call to `set` replaces the pending value and restarts the quiet period. The pending value
+/// becomes visible when the cell is polled after the quiet period has elapsed. Polling happens
+/// automatically when getting or borrowing the value and can be triggered explicitly by calling
+/// `poll`. Polling never blocks on the published value, so a value that is borrowed at the time
+/// of a poll stays pending until the next poll.
+///
+/// This is useful for values that change in rapid bursts such as the position of a settings slider
+/// or the state of a watched file.
+///
+/// # Usage
+/// ```
+/// use std::time::Duration;
+/// use sync_cell::DebouncedCell;
+///
+/// let cell = DebouncedCell::new(0, Duration::from_secs(60));
+///
+/// cell.set(1);
+/// cell.set(2);
+///
+/// // The quiet period has not elapsed so the original value is still visible.
+/// assert_eq!(0, cell.get());
+///
+/// // The pending value can be published early.
+/// cell.flush();
+/// assert_eq!(2, cell.get());
+/// ```
+pub struct DebouncedCell<T> {
+    /// The amount of time without a new value before the pending value is published.
+    quiet_period: Duration,
+    /// The published value of this cell.
+    current_value: SyncCell<T>,
+    /// The most recently set value and the instant at which it was set.
+    /// This lock is always acquired before `current_value` when both are needed.
+    pending_value: SyncCell<Option<(T, Instant)>>,
+}
+
+impl <T> DebouncedCell<T> {
+    /// Creates a new `DebouncedCell`.
+    ///
+    /// - `data` - The initial value of the `DebouncedCell`.
+    /// - `quiet_period` - The amount of time without a new value before the pending value is
+    ///   published.
+    pub const fn new(data: T, quiet_period: Duration) -> Self {
+        Self {
+            quiet_period,
+            current_value: SyncCell::new(data),
+            pending_value: SyncCell::new(None),
+        }
+    }
+
+    /// Gets the amount of time without a new value before the pending value is published.
+    pub fn quiet_period(&self) -> Duration {
+        self.quiet_period
+    }
+
+    /// Sets the pending value of this cell and restarts the quiet period.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, value: T) {
+        self.pending_value.set(Some((value, Instant::now())))
+    }
+
+    /// Publishes the pending value if the quiet period has elapsed since it was set.
+    /// If the published value is currently borrowed the pending value is kept for a later poll.
+    ///
+    /// Returns `true` if a new value was published.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn poll(&self) -> bool {
+        let mut pending = self.pending_value.borrow_mut();
+
+        match pending.take() {
+            Some((value, set_at)) if set_at.elapsed() >= self.quiet_period => {
+                match self.try_publish(value) {
+                    Ok(()) => true,
+                    Err(value) => {
+                        *pending = Some((value, set_at));
+                        false
+                    },
+                }
+            },
+            other => {
+                *pending = other;
+                false
+            },
+        }
+    }
+
+    /// Publishes the pending value immediately regardless of the quiet period.
+    /// If the published value is currently borrowed the pending value is kept for a later poll.
+    ///
+    /// Returns `true` if a new value was published.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn flush(&self) -> bool {
+        let mut pending = self.pending_value.borrow_mut();
+
+        match pending.take() {
+            Some((value, set_at)) => match self.try_publish(value) {
+                Ok(()) => true,
+                Err(value) => {
+                    *pending = Some((value, set_at));
+                    false
+                },
+            },
+            None => false,
+        }
+    }
+
+    /// Replaces the published value without blocking.
+    /// The value is handed back if the published value is currently borrowed, which also covers
+    /// a borrow held by the calling thread.
+    fn try_publish(&self, value: T) -> Result<(), T> {
+        match self.current_value.try_borrow_mut() {
+            Ok(mut current) => {
+                *current = value;
+                Ok(())
+            },
+            Err(SyncCellError::WouldBlock(_) | SyncCellError::Timeout(_)) => Err(value),
+            Err(SyncCellError::Poisoned) => panic!("Failed to get cell value. Lock was poisoned"),
+            Err(err @ SyncCellError::Invalid) => panic!("Failed to publish the pending value: {}", err),
+        }
+    }
+
+    /// Checks if a pending value is waiting to be published.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn has_pending(&self) -> bool {
+        self.pending_value.borrow().is_some()
+    }
+
+    /// Retrieves the inner value stored in this `DebouncedCell`.
+    /// This will return the pending value if there is one even if the quiet period has not
+    /// elapsed.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> T {
+        match self.pending_value.into_inner() {
+            Some((value, _)) => value,
+            None => self.current_value.into_inner(),
+        }
+    }
+
+    /// Borrows a immutable reference to the published value of this cell.
+    /// The cell is polled before the value is borrowed.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.poll();
+        self.current_value.borrow()
+    }
+
+    /// Borrows a mutable reference to the published value of this cell.
+    /// The cell is polled before the value is borrowed.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        self.poll();
+        self.current_value.borrow_mut()
+    }
+}
+
+impl <T: Clone> DebouncedCell<T> {
+    /// Gets the published value of this cell.
+    /// The cell is polled before the value is retrieved.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get(&self) -> T {
+        self.poll();
+        self.current_value.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::DebouncedCell;
+
+    #[test]
+    pub fn test_debounced_cell_set_not_published() {
+        let cell = DebouncedCell::new(1, Duration::from_secs(60));
+
+        cell.set(2);
+
+        assert!(cell.has_pending());
+        assert!(!cell.poll());
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_debounced_cell_set_published() {
+        let cell = DebouncedCell::new(1, Duration::from_millis(10));
+
+        cell.set(2);
+        cell.set(3);
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(3, cell.get());
+        assert!(!cell.has_pending());
+    }
+
+    #[test]
+    pub fn test_debounced_cell_poll_while_borrowed() {
+        let cell = DebouncedCell::new(1, Duration::ZERO);
+        let guard = cell.borrow();
+
+        cell.set(2);
+
+        assert_eq!(1, *guard);
+        assert_eq!(1, cell.get());
+        assert!(!cell.flush());
+        drop(guard);
+
+        assert!(cell.has_pending());
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_debounced_cell_flush() {
+        let cell = DebouncedCell::new(1, Duration::from_secs(60));
+
+        cell.set(2);
+
+        assert!(cell.flush());
+        assert!(!cell.flush());
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_debounced_cell_into_inner() {
+        let cell = DebouncedCell::new(1, Duration::from_secs(60));
+
+        cell.set(2);
+
+        assert_eq!(2, cell.into_inner());
+    }
+
+    #[test]
+    #[should_panic(expected = "Lock was poisoned")]
+    pub fn test_debounced_cell_flush_poisoned() {
+        let cell = Arc::new(DebouncedCell::new(1, Duration::from_secs(60)));
+
+        let panicked = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let _guard = cell.borrow_mut();
+                panic!("Test panic");
+            }).join()
+        };
+
+        assert!(panicked.is_err());
+        cell.set(2);
+        cell.flush();
+    }
+}
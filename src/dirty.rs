@@ -0,0 +1,181 @@
+use std::{mem::replace, sync::atomic::{AtomicBool, Ordering}};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A cell that records whether its value has changed since it was last marked as clean.
+/// Render or synchronisation loops can use this to skip work when nothing has changed.
+///
+/// # Usage
+/// ```
+/// use sync_cell::DirtyCell;
+///
+/// let cell = DirtyCell::new(1);
+///
+/// assert_eq!(None, cell.get_if_dirty());
+///
+/// cell.set(2);
+///
+/// assert_eq!(Some(2), cell.get_if_dirty());
+/// assert_eq!(None, cell.get_if_dirty());
+/// ```
+#[derive(Debug)]
+pub struct DirtyCell<T> {
+    /// The value of this cell.
+    data: SyncCell<T>,
+    /// Whether the value has changed since it was last marked as clean.
+    /// This is only set while the value is locked for writing.
+    dirty: AtomicBool,
+}
+
+impl <T> DirtyCell<T> {
+    /// Creates a new clean `DirtyCell`.
+    ///
+    /// - `data` - The initial value of the `DirtyCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            data: SyncCell::new(data),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Sets the value contained in this cell and marks it as dirty.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the value contained in this cell and marks it as dirty.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Checks if the value has changed since it was last marked as clean.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Acquire)
+    }
+
+    /// Marks the value as clean.
+    /// Returns `true` if the value was dirty.
+    pub fn mark_clean(&self) -> bool {
+        self.dirty.swap(false, Ordering::AcqRel)
+    }
+
+    /// Marks the value as dirty without changing it.
+    pub fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Release)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// This does not change whether the value is dirty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.data.borrow()
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell if it is dirty and marks it
+    /// as clean.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_if_dirty(&self) -> Option<SyncCellRef<'_, T>> {
+        let data = self.data.borrow();
+
+        // The value cannot change while it is borrowed, so it is the value that was marked clean.
+        self.mark_clean().then_some(data)
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell and marks it as dirty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        let data = self.data.borrow_mut();
+
+        self.mark_dirty();
+
+        data
+    }
+
+    /// Retrieves the inner value stored in this `DirtyCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl <T: Clone> DirtyCell<T> {
+    /// Gets the value contained in this cell.
+    /// This does not change whether the value is dirty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+
+    /// Gets the value contained in this cell if it is dirty and marks it as clean.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_if_dirty(&self) -> Option<T> {
+        self.borrow_if_dirty().map(|data| data.clone())
+    }
+}
+
+impl <T: Default> Default for DirtyCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for DirtyCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DirtyCell;
+
+    #[test]
+    pub fn test_dirty_cell_set() {
+        let cell = DirtyCell::new(1);
+        assert!(!cell.is_dirty());
+
+        cell.set(2);
+        assert!(cell.is_dirty());
+        assert_eq!(2, cell.get());
+        assert!(cell.is_dirty());
+
+        assert!(cell.mark_clean());
+        assert!(!cell.mark_clean());
+    }
+
+    #[test]
+    pub fn test_dirty_cell_borrow_mut() {
+        let cell = DirtyCell::new(vec![1]);
+
+        cell.borrow_mut().push(2);
+
+        assert_eq!(2, cell.borrow_if_dirty().unwrap().len());
+        assert!(cell.borrow_if_dirty().is_none());
+
+        cell.mark_dirty();
+        assert_eq!(Some(vec![1, 2]), cell.get_if_dirty());
+    }
+}
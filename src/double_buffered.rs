@@ -0,0 +1,179 @@
+use std::mem::swap;
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A cell containing a front buffer that is read by consumers and a back buffer that is written
+/// by producers.
+/// Producers mutate the back buffer in place while consumers continue to read the front buffer.
+/// Calling `swap` exchanges the two buffers making the changes visible to consumers.
+///
+/// Unlike `HeldSyncCell`, no values are moved or dropped when changes are applied. The previous
+/// front buffer becomes the new back buffer so that any allocations it holds can be reused.
+///
+/// # Usage
+/// ```
+/// use sync_cell::DoubleBufferedCell;
+///
+/// let cell = DoubleBufferedCell::new(vec![0; 4], vec![0; 4]);
+///
+/// cell.write_back(|back| back[0] = 1);
+/// assert_eq!(0, cell.borrow()[0]);
+///
+/// cell.swap();
+/// assert_eq!(1, cell.borrow()[0]);
+/// ```
+pub struct DoubleBufferedCell<T> {
+    /// The buffer that is read by consumers.
+    /// This lock is always acquired before `back` when both are needed.
+    front: SyncCell<T>,
+    /// The buffer that is written by producers.
+    back: SyncCell<T>,
+}
+
+impl <T> DoubleBufferedCell<T> {
+    /// Creates a new `DoubleBufferedCell`.
+    ///
+    /// - `front` - The initial value of the front buffer.
+    /// - `back` - The initial value of the back buffer.
+    pub const fn new(front: T, back: T) -> Self {
+        Self {
+            front: SyncCell::new(front),
+            back: SyncCell::new(back),
+        }
+    }
+
+    /// Borrows a immutable reference to the front buffer.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.front.borrow()
+    }
+
+    /// Borrows a immutable reference to the back buffer.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_back(&self) -> SyncCellRef<'_, T> {
+        self.back.borrow()
+    }
+
+    /// Borrows a mutable reference to the back buffer.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_back_mut(&self) -> SyncCellGuard<'_, T> {
+        self.back.borrow_mut()
+    }
+
+    /// Calls a function with a mutable reference to the back buffer.
+    ///
+    /// - `f` - The function used to modify the back buffer.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn write_back<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.back.borrow_mut())
+    }
+
+    /// Exchanges the front and back buffers.
+    /// This will block until all borrows of either buffer have been released.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn swap(&self) {
+        let mut front = self.front.borrow_mut();
+        let mut back = self.back.borrow_mut();
+
+        swap(&mut *front, &mut *back);
+    }
+
+    /// Retrieves the front and back buffers stored in this `DoubleBufferedCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> (T, T) {
+        (self.front.into_inner(), self.back.into_inner())
+    }
+}
+
+impl <T: Clone> DoubleBufferedCell<T> {
+    /// Creates a new `DoubleBufferedCell` where both buffers start with the same value.
+    ///
+    /// - `data` - The initial value of both buffers.
+    pub fn from_value(data: T) -> Self {
+        Self::new(data.clone(), data)
+    }
+
+    /// Gets the value of the front buffer.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get(&self) -> T {
+        self.front.get()
+    }
+
+    /// Copies the front buffer into the back buffer.
+    /// This is useful when the next frame is built incrementally from the previous one.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn copy_front_to_back(&self) {
+        let front = self.front.borrow();
+
+        self.back.borrow_mut().clone_from(&front);
+    }
+}
+
+impl <T: Default> Default for DoubleBufferedCell<T> {
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DoubleBufferedCell;
+
+    #[test]
+    pub fn test_double_buffered_cell_write_back() {
+        let cell = DoubleBufferedCell::new(1, 2);
+
+        cell.write_back(|back| *back = 3);
+
+        assert_eq!(1, cell.get());
+        assert_eq!(3, *cell.borrow_back());
+    }
+
+    #[test]
+    pub fn test_double_buffered_cell_swap() {
+        let cell = DoubleBufferedCell::new(1, 2);
+
+        cell.swap();
+
+        assert_eq!(2, cell.get());
+        assert_eq!(1, *cell.borrow_back());
+    }
+
+    #[test]
+    pub fn test_double_buffered_cell_reuses_buffer() {
+        let cell = DoubleBufferedCell::from_value(Vec::<u8>::with_capacity(64));
+        let front_ptr = cell.borrow().as_ptr();
+
+        cell.swap();
+
+        assert_eq!(front_ptr, cell.borrow_back().as_ptr());
+    }
+
+    #[test]
+    pub fn test_double_buffered_cell_copy_front_to_back() {
+        let cell = DoubleBufferedCell::new(vec![1], vec![]);
+
+        cell.copy_front_to_back();
+        cell.write_back(|back| back.push(2));
+        cell.swap();
+
+        assert_eq!(vec![1, 2], cell.get());
+        assert_eq!((vec![1, 2], vec![1]), cell.into_inner());
+    }
+}
@@ -0,0 +1,176 @@
+use std::ops::Deref;
+
+use crate::{HeldSyncCell, SyncCellRef};
+
+/// A held cell that starts without a current value.
+/// The first value set is published by the first call to `update`, until which reads return
+/// `None`. This is useful for resources that are loaded asynchronously but are exposed through
+/// the same interface as a `HeldSyncCell`.
+///
+/// # Usage
+/// ```
+/// use sync_cell::EmptyHeldCell;
+///
+/// let texture = EmptyHeldCell::new();
+/// assert_eq!(None, texture.try_get());
+///
+/// // Once loading finishes.
+/// texture.set(vec![0u8; 16]);
+/// assert_eq!(None, texture.try_get());
+///
+/// texture.update();
+/// assert_eq!(Some(16), texture.borrow().map(|texture| texture.len()));
+/// ```
+pub struct EmptyHeldCell<T> {
+    /// The held cell containing `None` until the first value is published.
+    inner: HeldSyncCell<Option<T>>,
+}
+
+impl <T> EmptyHeldCell<T> {
+    /// Creates a new `EmptyHeldCell` with no current or incoming value.
+    pub fn new() -> Self {
+        Self {
+            inner: HeldSyncCell::new(None),
+        }
+    }
+
+    /// Creates a new `EmptyHeldCell` whose first value is published by the first call to
+    /// `update`.
+    ///
+    /// - `value` - The value to publish on the first update.
+    pub fn pending(value: T) -> Self {
+        Self {
+            inner: HeldSyncCell::from_parts(None, Some(Some(value))),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// This value will only become available once the `update` method is called.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, value: T) {
+        self.inner.set(Some(value))
+    }
+
+    /// Checks if a new value is available that can be applied by calling `update`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn has_update(&self) -> bool {
+        self.inner.has_update()
+    }
+
+    /// Updates the internal value of this cell.
+    /// This involves replacing the current value with the incoming value if it is available.
+    /// Returns `true` if a value was applied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update(&self) -> bool {
+        self.inner.update()
+    }
+
+    /// Checks if a value has been published by calling `update`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn is_published(&self) -> bool {
+        self.inner.borrow().is_some()
+    }
+
+    /// Borrows a immutable reference to the current value of this cell.
+    /// Returns `None` if no value has been published yet.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> Option<EmptyHeldCellRef<'_, T>> {
+        let data = self.inner.borrow();
+
+        match *data {
+            Some(_) => Some(EmptyHeldCellRef {
+                data,
+            }),
+            None => None,
+        }
+    }
+
+    /// Retrieves the inner value stored in this `EmptyHeldCell`.
+    /// This will return the most up-to-date value even if `update` has not been called, or `None`
+    /// if no value has ever been set.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> Option<T> {
+        self.inner.into_inner()
+    }
+}
+
+impl <T: Clone> EmptyHeldCell<T> {
+    /// Gets the current value contained in this cell.
+    /// Returns `None` if no value has been published yet.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn try_get(&self) -> Option<T> {
+        self.inner.get()
+    }
+}
+
+impl <T> Default for EmptyHeldCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A borrow of the published value of an `EmptyHeldCell`.
+pub struct EmptyHeldCellRef<'a, T> {
+    /// The borrow of the current value of the inner cell.
+    data: SyncCellRef<'a, Option<T>>,
+}
+
+impl <T> Deref for EmptyHeldCellRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &*self.data {
+            Some(value) => value,
+            None => unreachable!("Published value was removed while borrowed."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::EmptyHeldCell;
+
+    #[test]
+    pub fn test_empty_held_cell_pending() {
+        let cell = EmptyHeldCell::pending(1);
+
+        assert!(!cell.is_published());
+        assert!(cell.borrow().is_none());
+        assert!(cell.has_update());
+
+        assert!(cell.update());
+        assert!(cell.is_published());
+        assert_eq!(1, *cell.borrow().unwrap());
+    }
+
+    #[test]
+    pub fn test_empty_held_cell_set() {
+        let cell = EmptyHeldCell::new();
+
+        assert!(!cell.update());
+        assert_eq!(None, cell.try_get());
+
+        cell.set(1);
+        cell.update();
+        cell.set(2);
+
+        assert_eq!(Some(1), cell.try_get());
+        assert_eq!(Some(2), cell.into_inner());
+    }
+}
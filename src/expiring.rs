@@ -0,0 +1,248 @@
+use std::time::{Duration, Instant};
+
+use crate::SyncCell;
+
+/// A value stored in an `ExpiringCell` along with the instant it expires.
+struct Entry<T> {
+    /// The stored value.
+    value: T,
+    /// The instant after which the value is no longer available.
+    /// This is `None` if the time to live is too long to be represented, in which case the value
+    /// never expires.
+    deadline: Option<Instant>,
+}
+
+impl <T> Entry<T> {
+    /// Checks if this entry has expired.
+    fn is_expired(&self) -> bool {
+        self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// A cell whose value expires after a fixed amount of time.
+/// Setting the value of the cell refreshes the deadline. Once the deadline has passed the value is
+/// no longer available and methods retrieving it will return `None`.
+///
+/// This is useful for cache entries that need to be recomputed periodically.
+///
+/// # Usage
+/// ```
+/// use std::time::Duration;
+/// use sync_cell::ExpiringCell;
+///
+/// let cell = ExpiringCell::new(Duration::from_secs(60));
+///
+/// // The cell starts out empty.
+/// assert_eq!(None, cell.get());
+///
+/// cell.set(1);
+/// assert_eq!(Some(1), cell.get());
+/// ```
+pub struct ExpiringCell<T> {
+    /// The amount of time a value remains available after being set.
+    ttl: Duration,
+    /// The current value of this cell if one has been set.
+    entry: SyncCell<Option<Entry<T>>>,
+}
+
+impl <T> ExpiringCell<T> {
+    /// Creates a new empty `ExpiringCell`.
+    ///
+    /// - `ttl` - The amount of time a value remains available after being set.
+    pub const fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: SyncCell::new(None),
+        }
+    }
+
+    /// Creates a new `ExpiringCell` containing a value.
+    /// The value will expire `ttl` after this method is called.
+    ///
+    /// - `data` - The initial value of the `ExpiringCell`.
+    /// - `ttl` - The amount of time a value remains available after being set.
+    pub fn with_value(data: T, ttl: Duration) -> Self {
+        let cell = Self::new(ttl);
+        cell.set(data);
+        cell
+    }
+
+    /// Gets the amount of time a value remains available after being set.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Sets the value contained in this cell and resets the expiry deadline.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        self.entry.set(Some(Entry {
+            value,
+            deadline: self.deadline(),
+        }))
+    }
+
+    /// Resets the expiry deadline of the current value without changing it.
+    ///
+    /// Returns `true` if there was an unexpired value to refresh.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn refresh(&self) -> bool {
+        match &mut *self.entry.borrow_mut() {
+            Some(entry) if !entry.is_expired() => {
+                entry.deadline = self.deadline();
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Checks if this cell does not contain an unexpired value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_expired(&self) -> bool {
+        match &*self.entry.borrow() {
+            Some(entry) => entry.is_expired(),
+            None => true,
+        }
+    }
+
+    /// Gets the amount of time until the current value expires.
+    /// Returns `None` if there is no unexpired value and `Duration::MAX` if the value never
+    /// expires.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.entry.borrow()
+            .as_ref()
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| match entry.deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::MAX,
+            })
+    }
+
+    /// Gets the deadline of a value set now.
+    /// Returns `None` if the deadline is too far in the future to be represented.
+    fn deadline(&self) -> Option<Instant> {
+        Instant::now().checked_add(self.ttl)
+    }
+
+    /// Removes the value contained in this cell.
+    /// Returns the value if it had not yet expired.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take(&self) -> Option<T> {
+        self.entry.replace(None)
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| entry.value)
+    }
+
+    /// Removes the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn clear(&self) {
+        self.entry.set(None)
+    }
+
+    /// Calls a function with a reference to the value contained in this cell.
+    /// Returns `None` without calling the function if there is no unexpired value.
+    ///
+    /// - `f` - The function to call with the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.entry.borrow()
+            .as_ref()
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| f(&entry.value))
+    }
+
+    /// Retrieves the inner value stored in this `ExpiringCell` if it has not expired.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> Option<T> {
+        self.entry.into_inner()
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| entry.value)
+    }
+}
+
+impl <T: Clone> ExpiringCell<T> {
+    /// Gets the value contained in this cell.
+    /// Returns `None` if there is no unexpired value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> Option<T> {
+        self.with(T::clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crate::ExpiringCell;
+
+    #[test]
+    pub fn test_expiring_cell_new() {
+        let cell = ExpiringCell::<i32>::new(Duration::from_secs(60));
+
+        assert!(cell.is_expired());
+        assert_eq!(None, cell.get());
+    }
+
+    #[test]
+    pub fn test_expiring_cell_set() {
+        let cell = ExpiringCell::new(Duration::from_secs(60));
+
+        cell.set(1);
+
+        assert!(!cell.is_expired());
+        assert_eq!(Some(1), cell.get());
+    }
+
+    #[test]
+    pub fn test_expiring_cell_expired() {
+        let cell = ExpiringCell::with_value(1, Duration::from_millis(10));
+
+        thread::sleep(Duration::from_millis(20));
+
+        assert!(cell.is_expired());
+        assert!(!cell.refresh());
+        assert_eq!(None, cell.get());
+        assert_eq!(None, cell.take());
+    }
+
+    #[test]
+    pub fn test_expiring_cell_never_expires() {
+        let cell = ExpiringCell::with_value(1, Duration::MAX);
+
+        assert!(cell.refresh());
+        assert_eq!(Some(1), cell.get());
+        assert_eq!(Some(Duration::MAX), cell.remaining());
+    }
+
+    #[test]
+    pub fn test_expiring_cell_set_refreshes() {
+        let cell = ExpiringCell::with_value(1, Duration::from_millis(200));
+
+        thread::sleep(Duration::from_millis(120));
+        cell.set(2);
+        thread::sleep(Duration::from_millis(120));
+
+        assert_eq!(Some(2), cell.take());
+        assert_eq!(None, cell.get());
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{RawSyncLock, SyncCell};
+
+/// Extends the collection in the cell taking the lock once per call.
+/// This lets producers add many values to a shared collection through a `&SyncCell` without
+/// borrowing the cell for each value.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncCell;
+///
+/// let cell = SyncCell::new(vec![1]);
+///
+/// (&cell).extend([2, 3]);
+///
+/// assert_eq!(vec![1, 2, 3], cell.get());
+/// ```
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <A, C: Extend<A> + ?Sized, L: RawSyncLock> Extend<A> for &SyncCell<C, L> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        self.borrow_mut().extend(iter);
+    }
+}
+
+/// Extends the collection in the cell.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <A, C: Extend<A> + ?Sized, L: RawSyncLock> Extend<A> for SyncCell<C, L> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        (&*self).extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_extend() {
+        let cell = Arc::new(SyncCell::new(HashSet::new()));
+
+        let handles: Vec<_> = (0..4).map(|i| {
+            let cell = cell.clone();
+            thread::spawn(move || (&*cell).extend(i * 10..i * 10 + 10))
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut cell = Arc::into_inner(cell).unwrap();
+        cell.extend([100]);
+
+        assert_eq!(41, cell.borrow().len());
+    }
+}
@@ -0,0 +1,182 @@
+use std::{sync::{atomic::{AtomicBool, Ordering}, Condvar, Mutex, MutexGuard, PoisonError}, time::{Duration, Instant}};
+
+/// A boolean flag that can be raised and lowered from multiple threads and waited on.
+/// Reading and changing the flag only uses an atomic, which makes this much cheaper than a
+/// `SyncCell<bool>` for flags such as shutdown or readiness signals. Threads can block until the
+/// flag is raised.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::SyncFlag;
+///
+/// let ready = Arc::new(SyncFlag::new(false));
+///
+/// let worker = {
+///     let ready = ready.clone();
+///     thread::spawn(move || ready.raise())
+/// };
+///
+/// // Blocks until the worker raises the flag.
+/// ready.wait_until_raised();
+/// assert!(ready.is_raised());
+/// # worker.join().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SyncFlag {
+    /// The state of the flag.
+    raised: AtomicBool,
+    /// The lock held while waiting for the flag and while waking waiting threads.
+    lock: Mutex<()>,
+    /// The condition notified when the flag is raised.
+    condvar: Condvar,
+}
+
+impl SyncFlag {
+    /// Creates a new `SyncFlag`.
+    ///
+    /// - `raised` - Whether the flag starts raised.
+    pub const fn new(raised: bool) -> Self {
+        Self {
+            raised: AtomicBool::new(raised),
+            lock: Mutex::new(()),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Raises this flag and wakes every thread waiting for it.
+    /// Returns `true` if the flag was already raised.
+    pub fn raise(&self) -> bool {
+        let previous = self.raised.swap(true, Ordering::AcqRel);
+
+        if !previous {
+            // Taking the lock makes sure a thread that has just seen the flag lowered is waiting
+            // on the condition before it is notified.
+            drop(self.lock());
+            self.condvar.notify_all();
+        }
+
+        previous
+    }
+
+    /// Lowers this flag.
+    /// Returns `true` if the flag was raised.
+    pub fn lower(&self) -> bool {
+        self.raised.swap(false, Ordering::AcqRel)
+    }
+
+    /// Checks if this flag is raised.
+    pub fn is_raised(&self) -> bool {
+        self.raised.load(Ordering::Acquire)
+    }
+
+    /// Blocks the current thread until this flag is raised.
+    /// Returns immediately if the flag is already raised.
+    pub fn wait_until_raised(&self) {
+        let mut lock = self.lock();
+
+        while !self.is_raised() {
+            lock = self.condvar.wait(lock).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Blocks the current thread until this flag is raised or a timeout elapses.
+    /// Returns `true` if the flag was raised.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    pub fn wait_until_raised_timeout(&self, timeout: Duration) -> bool {
+        if self.is_raised() {
+            return true;
+        }
+
+        // A timeout too long to represent never elapses.
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            self.wait_until_raised();
+            return true;
+        };
+        let mut lock = self.lock();
+
+        while !self.is_raised() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                return false;
+            }
+
+            lock = self.condvar.wait_timeout(lock, remaining).unwrap_or_else(PoisonError::into_inner).0;
+        }
+
+        true
+    }
+
+    /// Retrieves the state of this flag.
+    pub fn into_inner(self) -> bool {
+        self.raised.into_inner()
+    }
+
+    /// Takes the lock used to wait for the flag.
+    /// The lock protects no data so a poisoned lock is ignored.
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for SyncFlag {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl From<bool> for SyncFlag {
+    fn from(raised: bool) -> Self {
+        Self::new(raised)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::SyncFlag;
+
+    #[test]
+    pub fn test_sync_flag() {
+        let flag = Arc::new(SyncFlag::default());
+
+        assert!(!flag.wait_until_raised_timeout(Duration::from_millis(10)));
+
+        let waiters: Vec<_> = (0..3).map(|_| {
+            let flag = flag.clone();
+            thread::spawn(move || flag.wait_until_raised())
+        }).collect();
+
+        assert!(!flag.raise());
+        assert!(flag.raise());
+
+        for waiter in waiters {
+            waiter.join().unwrap();
+        }
+
+        assert!(flag.lower());
+        assert!(!flag.is_raised());
+    }
+
+    #[test]
+    pub fn test_sync_flag_wait_max_timeout() {
+        let flag = Arc::new(SyncFlag::new(true));
+
+        assert!(flag.wait_until_raised_timeout(Duration::MAX));
+
+        flag.lower();
+
+        let waiter = {
+            let flag = flag.clone();
+            thread::spawn(move || flag.wait_until_raised_timeout(Duration::MAX))
+        };
+
+        thread::sleep(Duration::from_millis(10));
+        flag.raise();
+
+        assert!(waiter.join().unwrap());
+    }
+}
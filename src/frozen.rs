@@ -0,0 +1,278 @@
+use std::{ops::{Deref, DerefMut}, sync::OnceLock};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A cell that can be modified until it is frozen after which it can only be read.
+/// Once frozen, reads no longer take a lock and all writes are rejected. This suits values such
+/// as configuration that are set up at start up and then never change.
+///
+/// # Usage
+/// ```
+/// use sync_cell::FrozenCell;
+///
+/// let cell = FrozenCell::new(1);
+///
+/// assert_eq!(Ok(()), cell.set(2));
+///
+/// cell.freeze();
+///
+/// assert_eq!(Err(3), cell.set(3));
+/// assert_eq!(Some(&2), cell.get_frozen());
+/// ```
+#[derive(Debug)]
+pub struct FrozenCell<T> {
+    /// The value of this cell once it has been frozen.
+    frozen: OnceLock<T>,
+    /// The value of this cell before it has been frozen.
+    /// This is `None` once the cell has been frozen.
+    data: SyncCell<Option<T>>,
+}
+
+impl <T> FrozenCell<T> {
+    /// Creates a new unfrozen `FrozenCell`.
+    ///
+    /// - `data` - The initial value of the `FrozenCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            frozen: OnceLock::new(),
+            data: SyncCell::new(Some(data)),
+        }
+    }
+
+    /// Creates a new `FrozenCell` that is already frozen.
+    ///
+    /// - `data` - The value of the `FrozenCell`.
+    pub fn new_frozen(data: T) -> Self {
+        let cell = Self {
+            frozen: OnceLock::new(),
+            data: SyncCell::new(None),
+        };
+        let _ = cell.frozen.set(data);
+        cell
+    }
+
+    /// Freezes this cell so that its value can no longer be changed.
+    /// Freezing a cell that is already frozen has no effect.
+    /// This will block until all borrows of the unfrozen value have been released.
+    ///
+    /// Returns a reference to the frozen value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn freeze(&self) -> &T {
+        if let Some(value) = self.frozen.get() {
+            return value;
+        }
+
+        let mut data = self.data.borrow_mut();
+
+        if let Some(value) = data.take() {
+            let _ = self.frozen.set(value);
+        }
+
+        self.frozen_value()
+    }
+
+    /// Checks if this cell has been frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.get().is_some()
+    }
+
+    /// Gets a reference to the value of this cell if it has been frozen.
+    /// This never takes a lock.
+    pub fn get_frozen(&self) -> Option<&T> {
+        self.frozen.get()
+    }
+
+    /// Sets the value contained in this cell.
+    /// If the cell has been frozen the value is returned as an error.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        match self.borrow_mut() {
+            Some(mut data) => {
+                *data = value;
+                Ok(())
+            },
+            None => Err(value),
+        }
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// This only takes a lock if the cell has not been frozen.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> FrozenCellRef<'_, T> {
+        if let Some(value) = self.frozen.get() {
+            return FrozenCellRef::Frozen(value);
+        }
+
+        let data = self.data.borrow();
+
+        match *data {
+            Some(_) => FrozenCellRef::Unfrozen(data),
+            None => FrozenCellRef::Frozen(self.frozen_value()),
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// Returns `None` if the cell has been frozen.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> Option<FrozenCellGuard<'_, T>> {
+        if self.is_frozen() {
+            return None;
+        }
+
+        let data = self.data.borrow_mut();
+
+        match *data {
+            Some(_) => Some(FrozenCellGuard {
+                data,
+            }),
+            None => None,
+        }
+    }
+
+    /// Retrieves the inner value stored in this `FrozenCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        match self.data.into_inner() {
+            Some(value) => value,
+            None => match self.frozen.into_inner() {
+                Some(value) => value,
+                None => unreachable!("Cell has neither a frozen nor an unfrozen value."),
+            },
+        }
+    }
+
+    /// Gets the frozen value after the unfrozen value has been observed to be taken.
+    fn frozen_value(&self) -> &T {
+        match self.frozen.get() {
+            Some(value) => value,
+            None => unreachable!("Cell has neither a frozen nor an unfrozen value."),
+        }
+    }
+}
+
+impl <T: Clone> FrozenCell<T> {
+    /// Gets the value contained in this cell.
+    /// This only takes a lock if the cell has not been frozen.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl <T: Default> Default for FrozenCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for FrozenCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl <T> From<SyncCell<T>> for FrozenCell<T> {
+    fn from(cell: SyncCell<T>) -> Self {
+        Self::new_frozen(cell.into_inner())
+    }
+}
+
+/// A borrow of the value in a `FrozenCell`.
+pub enum FrozenCellRef<'a, T> {
+    /// A reference to the value of a frozen cell.
+    Frozen(&'a T),
+    /// A lock guard over the value of a cell that has not been frozen.
+    Unfrozen(SyncCellRef<'a, Option<T>>),
+}
+
+impl <T> Deref for FrozenCellRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Self::Frozen(value) => value,
+            Self::Unfrozen(data) => match &**data {
+                Some(value) => value,
+                None => unreachable!("Cell was frozen while borrowed."),
+            },
+        }
+    }
+}
+
+/// A mutable borrow of the value in a `FrozenCell` that has not been frozen.
+/// The cell cannot be frozen while this guard exists.
+pub struct FrozenCellGuard<'a, T> {
+    /// The lock guard over the unfrozen value.
+    data: SyncCellGuard<'a, Option<T>>,
+}
+
+impl <T> Deref for FrozenCellGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &*self.data {
+            Some(value) => value,
+            None => unreachable!("Cell was frozen while borrowed."),
+        }
+    }
+}
+
+impl <T> DerefMut for FrozenCellGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match &mut *self.data {
+            Some(value) => value,
+            None => unreachable!("Cell was frozen while borrowed."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FrozenCell, SyncCell};
+
+    #[test]
+    pub fn test_frozen_cell_unfrozen() {
+        let cell = FrozenCell::new(1);
+
+        assert!(!cell.is_frozen());
+        assert_eq!(None, cell.get_frozen());
+        assert_eq!(Ok(()), cell.set(2));
+        *cell.borrow_mut().unwrap() += 1;
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_frozen_cell_freeze() {
+        let cell = FrozenCell::new(1);
+
+        assert_eq!(&1, cell.freeze());
+        assert_eq!(&1, cell.freeze());
+        assert!(cell.is_frozen());
+        assert_eq!(Err(2), cell.set(2));
+        assert!(cell.borrow_mut().is_none());
+        assert_eq!(1, *cell.borrow());
+        assert_eq!(1, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_frozen_cell_from_sync_cell() {
+        let cell = FrozenCell::from(SyncCell::new(5));
+
+        assert_eq!(Some(&5), cell.get_frozen());
+        assert_eq!(5, SyncCell::new(5).freeze().into_inner());
+    }
+}
@@ -0,0 +1,197 @@
+use std::{mem::replace, sync::atomic::{AtomicU64, Ordering}};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A token identifying the value of a `GenerationalCell` at the time it was read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Generation(u64);
+
+/// A cell that counts how many times its value has been written.
+/// Every read returns a `Generation` token that can later be compared against the cell to find
+/// out whether a copy or anything derived from the value has become stale.
+///
+/// # Usage
+/// ```
+/// use sync_cell::GenerationalCell;
+///
+/// let cell = GenerationalCell::new(1);
+///
+/// let (value, generation) = cell.get();
+/// let derived = value * 10;
+///
+/// cell.set(2);
+///
+/// if !cell.validate(generation) {
+///     // Recompute the derived value.
+/// }
+/// # assert!(!cell.validate(generation));
+/// # assert_eq!(10, derived);
+/// ```
+#[derive(Debug)]
+pub struct GenerationalCell<T> {
+    /// The value of this cell.
+    data: SyncCell<T>,
+    /// The current generation which is only changed while the value is locked for writing.
+    generation: AtomicU64,
+}
+
+impl <T> GenerationalCell<T> {
+    /// Creates a new `GenerationalCell` at the first generation.
+    ///
+    /// - `data` - The initial value of the `GenerationalCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            data: SyncCell::new(data),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Gets the current generation of this cell.
+    pub fn generation(&self) -> Generation {
+        Generation(self.generation.load(Ordering::Acquire))
+    }
+
+    /// Checks if a generation token is still current.
+    ///
+    /// - `token` - A token returned by an earlier read.
+    pub fn validate(&self, token: Generation) -> bool {
+        self.generation() == token
+    }
+
+    /// Sets the value contained in this cell and advances the generation.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) -> Generation {
+        let mut data = self.borrow_mut();
+        *data = value;
+        self.generation()
+    }
+
+    /// Replaces the value contained in this cell and advances the generation.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell along with its generation.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> (SyncCellRef<'_, T>, Generation) {
+        let data = self.data.borrow();
+        let generation = self.generation();
+
+        (data, generation)
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// The generation is advanced when the borrow is taken.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        let data = self.data.borrow_mut();
+
+        self.generation.fetch_add(1, Ordering::AcqRel);
+
+        data
+    }
+
+    /// Retrieves the inner value stored in this `GenerationalCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl <T: Clone> GenerationalCell<T> {
+    /// Gets the value contained in this cell along with its generation.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> (T, Generation) {
+        let (data, generation) = self.borrow();
+
+        (data.clone(), generation)
+    }
+
+    /// Gets the value contained in this cell if it is still at a given generation.
+    ///
+    /// - `token` - A token returned by an earlier read.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_if_current(&self, token: Generation) -> Option<T> {
+        let (data, generation) = self.borrow();
+
+        (generation == token).then(|| data.clone())
+    }
+
+    /// Gets the value contained in this cell and its generation if it has changed since a given
+    /// generation.
+    ///
+    /// - `token` - A token returned by an earlier read.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_if_changed(&self, token: Generation) -> Option<(T, Generation)> {
+        let (data, generation) = self.borrow();
+
+        (generation != token).then(|| (data.clone(), generation))
+    }
+}
+
+impl <T: Default> Default for GenerationalCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for GenerationalCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GenerationalCell;
+
+    #[test]
+    pub fn test_generational_cell_validate() {
+        let cell = GenerationalCell::new(1);
+        let (_, first) = cell.get();
+
+        assert!(cell.validate(first));
+
+        let second = cell.set(2);
+
+        assert!(!cell.validate(first));
+        assert!(cell.validate(second));
+        assert!(second > first);
+    }
+
+    #[test]
+    pub fn test_generational_cell_get_if_current() {
+        let cell = GenerationalCell::new(1);
+        let token = cell.generation();
+
+        assert_eq!(Some(1), cell.get_if_current(token));
+        assert_eq!(None, cell.get_if_changed(token));
+
+        *cell.borrow_mut() += 1;
+
+        assert_eq!(None, cell.get_if_current(token));
+        assert_eq!(Some((2, cell.generation())), cell.get_if_changed(token));
+    }
+}
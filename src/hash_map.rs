@@ -0,0 +1,344 @@
+use std::{borrow::Borrow, collections::{HashMap, hash_map::RandomState}, hash::{BuildHasher, Hash}, thread};
+
+use crate::SyncCell;
+
+/// The value hashed along with a key to choose its shard.
+const SHARD_SALT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// A hash map that splits its keys between a number of `SyncCell` shards to reduce contention.
+/// Each key is always stored in the same shard, so threads using keys in different shards do not
+/// wait for each other.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::SyncHashMap;
+///
+/// let map = Arc::new(SyncHashMap::new());
+///
+/// let handles: Vec<_> = (0..4).map(|id| {
+///     let map = map.clone();
+///     thread::spawn(move || {
+///         map.insert(id, id * 10);
+///     })
+/// }).collect();
+/// # for handle in handles { handle.join().unwrap(); }
+///
+/// assert_eq!(Some(20), map.get_cloned(&2));
+/// assert_eq!(Some(true), map.with(&3, |value| *value == 30));
+///
+/// map.retain(|key, _| key % 2 == 0);
+///
+/// assert_eq!(2, map.len());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this map will panic rather than return an error when a lock becomes poisoned.
+#[derive(Debug)]
+pub struct SyncHashMap<K, V, S = RandomState> {
+    /// The hasher used to choose the shard of a key.
+    hasher: S,
+    /// The shards of this map.
+    shards: Box<[SyncCell<HashMap<K, V, S>>]>,
+}
+
+impl <K: Eq + Hash, V> SyncHashMap<K, V> {
+    /// Creates a new empty `SyncHashMap` with four shards per available CPU.
+    pub fn new() -> Self {
+        let shards = thread::available_parallelism()
+            .map(|count| count.get() * 4)
+            .unwrap_or(16);
+
+        Self::with_shards(shards)
+    }
+
+    /// Creates a new empty `SyncHashMap` with a fixed number of shards.
+    ///
+    /// - `shards` - The number of shards. At least one shard is always created.
+    pub fn with_shards(shards: usize) -> Self {
+        Self::with_shards_and_hasher(shards, RandomState::new())
+    }
+}
+
+impl <K: Eq + Hash, V, S: BuildHasher + Clone> SyncHashMap<K, V, S> {
+    /// Creates a new empty `SyncHashMap` with a fixed number of shards that hashes keys with a
+    /// custom hasher.
+    ///
+    /// - `shards` - The number of shards. At least one shard is always created.
+    /// - `hasher` - The hasher used for the keys.
+    pub fn with_shards_and_hasher(shards: usize, hasher: S) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| SyncCell::new(HashMap::with_hasher(hasher.clone())))
+                .collect(),
+            hasher,
+        }
+    }
+}
+
+impl <K: Eq + Hash, V, S: BuildHasher> SyncHashMap<K, V, S> {
+    /// Gets the number of shards in this map.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Gets the index of the shard that stores a key.
+    fn shard_index<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        // The shards hash keys with the same hasher, so the shard is chosen from a salted hash.
+        // Otherwise every key of a shard would share the bits of its hash used by the shard.
+        let hash = self.hasher.hash_one((SHARD_SALT, key));
+
+        // Maps the hash onto the shards by its high bits.
+        ((u128::from(hash) * self.shards.len() as u128) >> 64) as usize
+    }
+
+    /// Gets the shard that stores a key.
+    fn shard<Q: Hash + ?Sized>(&self, key: &Q) -> &SyncCell<HashMap<K, V, S>> {
+        &self.shards[self.shard_index(key)]
+    }
+
+    /// Inserts a value into this map.
+    /// The previous value for the key is returned or `None` if the key was not present.
+    ///
+    /// - `key` - The key to insert the value at.
+    /// - `value` - The value to insert.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.shard(&key).insert(key, value)
+    }
+
+    /// Removes a key from this map.
+    /// The removed value is returned or `None` if the key was not present.
+    ///
+    /// - `key` - The key to remove.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn remove<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.shard(key).remove(key)
+    }
+
+    /// Checks if this map contains a key.
+    ///
+    /// - `key` - The key to look for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn contains_key<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.shard(key).contains_key(key)
+    }
+
+    /// Calls a function with a reference to the value of a key.
+    /// Returns `None` if the key is not present.
+    ///
+    /// - `key` - The key to look up.
+    /// - `f` - The function to call with the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with<Q: Eq + Hash + ?Sized, R>(&self, key: &Q, f: impl FnOnce(&V) -> R) -> Option<R> where K: Borrow<Q> {
+        self.shard(key).borrow().get(key).map(f)
+    }
+
+    /// Calls a function with a mutable reference to the value of a key.
+    /// Returns `None` if the key is not present.
+    ///
+    /// - `key` - The key to look up.
+    /// - `f` - The function to call with the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with_mut<Q: Eq + Hash + ?Sized, R>(&self, key: &Q, f: impl FnOnce(&mut V) -> R) -> Option<R> where K: Borrow<Q> {
+        self.shard(key).borrow_mut().get_mut(key).map(f)
+    }
+
+    /// Removes every entry for which a function returns `false`.
+    /// Shards are locked one at a time.
+    ///
+    /// - `f` - The function deciding which entries to keep.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn retain(&self, mut f: impl FnMut(&K, &mut V) -> bool) {
+        for shard in self.shards.iter() {
+            shard.borrow_mut().retain(&mut f);
+        }
+    }
+
+    /// Gets the number of entries in this map.
+    /// Shards are counted one at a time so the result may include some concurrent changes but not
+    /// others.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn len(&self) -> usize {
+        self.shards.iter()
+            .map(|shard| shard.borrow().len())
+            .sum()
+    }
+
+    /// Checks if this map has no entries.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.borrow().is_empty())
+    }
+
+    /// Removes every entry from this map.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            shard.borrow_mut().clear();
+        }
+    }
+
+    /// Retrieves the entries stored in this `SyncHashMap`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> HashMap<K, V, S> {
+        let mut map = HashMap::with_hasher(self.hasher);
+
+        for shard in self.shards.into_vec() {
+            map.extend(shard.into_inner());
+        }
+
+        map
+    }
+}
+
+impl <K: Eq + Hash, V: Clone, S: BuildHasher> SyncHashMap<K, V, S> {
+    /// Gets a copy of the value of a key.
+    /// Returns `None` if the key is not present.
+    ///
+    /// - `key` - The key to look up.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_cloned<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.shard(key).get_cloned(key)
+    }
+}
+
+impl <K: Eq + Hash, V> Default for SyncHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <K: Eq + Hash, V> FromIterator<(K, V)> for SyncHashMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/// Inserts the entries into the map taking the lock of each shard at most once per call.
+///
+/// # Panicking
+/// This method will panic if any of the locks become poisoned.
+impl <K: Eq + Hash, V, S: BuildHasher> Extend<(K, V)> for &SyncHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let mut groups: Vec<Vec<(K, V)>> = self.shards.iter().map(|_| Vec::new()).collect();
+
+        for (key, value) in iter {
+            groups[self.shard_index(&key)].push((key, value));
+        }
+
+        for (shard, group) in self.shards.iter().zip(groups) {
+            if !group.is_empty() {
+                shard.borrow_mut().extend(group);
+            }
+        }
+    }
+}
+
+/// Inserts the entries into the map.
+///
+/// # Panicking
+/// This method will panic if any of the locks become poisoned.
+impl <K: Eq + Hash, V, S: BuildHasher> Extend<(K, V)> for SyncHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        (&*self).extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, hash::{BuildHasher, BuildHasherDefault, DefaultHasher}, sync::Arc, thread};
+
+    use crate::SyncHashMap;
+
+    #[test]
+    pub fn test_sync_hash_map() {
+        let map = SyncHashMap::with_shards(4);
+
+        assert_eq!(None, map.insert("a", 1));
+        assert_eq!(Some(1), map.insert("a", 2));
+        assert!(map.contains_key("a"));
+        assert_eq!(Some(3), map.with_mut("a", |value| { *value += 1; *value }));
+        assert_eq!(Some(3), map.remove("a"));
+        assert!(map.is_empty());
+        assert_eq!(4, map.shard_count());
+    }
+
+    #[test]
+    pub fn test_sync_hash_map_shard_hash_bits() {
+        let hasher = BuildHasherDefault::<DefaultHasher>::default();
+        let map: SyncHashMap<i32, (), _> = SyncHashMap::with_shards_and_hasher(4, hasher.clone());
+
+        let low_bits: HashSet<_> = (0..1000)
+            .filter(|key| map.shard_index(key) == 0)
+            .map(|key| hasher.hash_one(key) % 4)
+            .collect();
+
+        // The keys of a shard are spread over the buckets of its own map.
+        assert_eq!(4, low_bits.len());
+    }
+
+    #[test]
+    pub fn test_sync_hash_map_extend() {
+        let collected: SyncHashMap<_, _> = [(1, 2)].into_iter().collect();
+        assert_eq!(Some(2), collected.get_cloned(&1));
+
+        let mut map = SyncHashMap::with_shards(3);
+
+        (&map).extend((0..10).map(|i| (i, i * 2)));
+        map.extend([(0, 1)]);
+
+        assert_eq!(10, map.len());
+        assert_eq!(Some(1), map.get_cloned(&0));
+        assert_eq!(Some(18), map.get_cloned(&9));
+    }
+
+    #[test]
+    pub fn test_sync_hash_map_threads() {
+        let map = Arc::new(SyncHashMap::with_shards(3));
+
+        let handles: Vec<_> = (0..4).map(|thread| {
+            let map = map.clone();
+            thread::spawn(move || {
+                for i in 0..25 {
+                    map.insert(thread * 25 + i, i);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(100, map.len());
+
+        map.retain(|_, value| *value < 5);
+
+        let map = Arc::into_inner(map).unwrap().into_inner();
+        assert_eq!(20, map.len());
+    }
+}
@@ -0,0 +1,232 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{HeldSyncCell, SyncCellGuard, SyncCellRef};
+
+/// A `HeldSyncCell` for unsized values such as trait objects.
+/// An update moves the incoming value into the place of the current value, and the new value of
+/// a trait object can have a different size, so both values are stored boxed. Values are set as
+/// boxes, while borrows give access to the unboxed value.
+/// `HeldSyncCell` itself keeps sized values inline, so setting one does not allocate and
+/// `HeldSyncCell::new` stays `const`.
+///
+/// Methods that are not forwarded here can be reached through `as_held`.
+///
+/// # Usage
+/// ```
+/// use sync_cell::HeldSyncBox;
+///
+/// trait Scene: Send + Sync {
+///     fn name(&self) -> &str;
+/// }
+///
+/// struct Menu;
+/// struct Level;
+///
+/// impl Scene for Menu {
+///     fn name(&self) -> &str { "menu" }
+/// }
+///
+/// impl Scene for Level {
+///     fn name(&self) -> &str { "level" }
+/// }
+///
+/// let scene: HeldSyncBox<dyn Scene> = HeldSyncBox::new(Box::new(Menu));
+///
+/// scene.set(Box::new(Level));
+/// assert_eq!("menu", scene.borrow().name());
+///
+/// scene.update();
+/// assert_eq!("level", scene.borrow().name());
+/// ```
+pub struct HeldSyncBox<T: ?Sized> {
+    /// The cell holding the boxed values.
+    cell: HeldSyncCell<Box<T>>,
+}
+
+impl <T: ?Sized> HeldSyncBox<T> {
+    /// Creates a new `HeldSyncBox`.
+    ///
+    /// - `data` - The initial value of the `HeldSyncBox`.
+    pub const fn new(data: Box<T>) -> Self {
+        Self {
+            cell: HeldSyncCell::new(data),
+        }
+    }
+
+    /// Gets the `HeldSyncCell` holding the boxed values of this cell.
+    pub fn as_held(&self) -> &HeldSyncCell<Box<T>> {
+        &self.cell
+    }
+
+    /// Sets the value contained in this cell.
+    /// This value will only become available once the `update` method is called.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, value: Box<T>) {
+        self.cell.set(value)
+    }
+
+    /// Sets the value contained in this cell if the incoming value can be locked without
+    /// blocking.
+    /// If the incoming value is borrowed or its lock is poisoned, the value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    pub fn try_set(&self, value: Box<T>) -> Result<(), Box<T>> {
+        self.cell.try_set(value)
+    }
+
+    /// Checks if a new value is available that can be applied by calling `update`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn has_update(&self) -> bool {
+        self.cell.has_update()
+    }
+
+    /// Updates the internal value of this cell.
+    /// Returns `true` if an update was applied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update(&self) -> bool {
+        self.cell.update()
+    }
+
+    /// Updates the internal value of this cell.
+    /// The replaced value is returned or `None` if no new value was available.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_take(&self) -> Option<Box<T>> {
+        self.cell.update_take()
+    }
+
+    /// Discards the incoming value of this cell without applying it.
+    /// The discarded value is returned or `None` if no new value had been set.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn cancel_update(&self) -> Option<Box<T>> {
+        self.cell.cancel_update()
+    }
+
+    /// Gets the version of this cell.
+    /// The version changes every time a value is set, applied or discarded.
+    pub fn version(&self) -> u64 {
+        self.cell.version()
+    }
+
+    /// Borrows a immutable reference to the current value of this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> HeldSyncBoxRef<'_, T> {
+        HeldSyncBoxRef {
+            data: self.cell.borrow(),
+        }
+    }
+
+    /// Borrows a mutable reference to the current value of this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_mut(&self) -> HeldSyncBoxGuard<'_, T> {
+        HeldSyncBoxGuard {
+            data: self.cell.borrow_mut(),
+        }
+    }
+
+    /// Retrieves the inner value stored in this `HeldSyncBox`.
+    /// This will return the most up-to-date value even if `update` has not been called.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> Box<T> {
+        self.cell.into_inner()
+    }
+
+    /// Splits this `HeldSyncBox` into its current value and its incoming value.
+    /// The incoming value is `None` if no new value has been set since the last call to `update`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_parts(self) -> (Box<T>, Option<Box<T>>) {
+        self.cell.into_parts()
+    }
+}
+
+impl <T: ?Sized> From<Box<T>> for HeldSyncBox<T> {
+    fn from(value: Box<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A borrow of the current value of a `HeldSyncBox`.
+pub struct HeldSyncBoxRef<'a, T: ?Sized> {
+    /// The lock guard over the current value.
+    data: SyncCellRef<'a, Box<T>>,
+}
+
+impl <T: ?Sized> Deref for HeldSyncBoxRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+/// A mutable borrow of the current value of a `HeldSyncBox`.
+pub struct HeldSyncBoxGuard<'a, T: ?Sized> {
+    /// The lock guard over the current value.
+    data: SyncCellGuard<'a, Box<T>>,
+}
+
+impl <T: ?Sized> Deref for HeldSyncBoxGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl <T: ?Sized> DerefMut for HeldSyncBoxGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::HeldSyncBox;
+
+    #[test]
+    pub fn test_held_sync_box_unsized() {
+        let cell: HeldSyncBox<[i32]> = HeldSyncBox::new(Box::new([1, 2]));
+
+        cell.set(Box::new([3, 4, 5]));
+        cell.borrow_mut()[0] = 0;
+
+        assert_eq!([0, 2], *cell.borrow());
+        assert_eq!(3, cell.as_held().peek_next().unwrap().len());
+
+        cell.update();
+
+        assert_eq!([3, 4, 5], *cell.borrow());
+        assert_eq!(Box::new([3, 4, 5]) as Box<[i32]>, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_held_sync_box_cancel() {
+        let cell: HeldSyncBox<str> = HeldSyncBox::new("a".into());
+
+        cell.set("b".into());
+
+        assert!(cell.has_update());
+        assert_eq!(Some("b".into()), cell.cancel_update());
+        assert!(!cell.update());
+        assert_eq!("a", &*cell.borrow());
+    }
+}
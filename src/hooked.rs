@@ -0,0 +1,223 @@
+use std::mem::replace;
+
+use crate::{SyncCell, SyncCellRef};
+
+/// A function run before a value is written that can transform or veto it.
+type BeforeSetFn<T> = Box<dyn Fn(&T, T) -> Result<T, T> + Send + Sync>;
+
+/// A function run after a value is written with the previous and the new value.
+type AfterSetFn<T> = Box<dyn Fn(&T, &T) + Send + Sync>;
+
+/// A cell that runs hooks around every write to its value.
+/// The hook run before a write sees the current and the incoming value and can transform the
+/// incoming value or veto the write. The hook run after a write sees the previous and the new
+/// value. This allows every change to a critical cell to be logged or validated in one place.
+///
+/// Both hooks run while the value is mutably borrowed, so they see writes in the order they are
+/// applied. Hooks must not access the cell they belong to.
+///
+/// # Usage
+/// ```
+/// use sync_cell::HookedCell;
+///
+/// let cell = HookedCell::with_hooks(
+///     0,
+///     |_, value: i32| if value < 0 { Err(value) } else { Ok(value.min(100)) },
+///     |old, new| println!("Changed from {} to {}", old, new),
+/// );
+///
+/// assert_eq!(Ok(()), cell.set(150));
+/// assert_eq!(Err(-1), cell.set(-1));
+/// assert_eq!(100, cell.get());
+/// ```
+pub struct HookedCell<T> {
+    /// The value of this cell.
+    data: SyncCell<T>,
+    /// The hook run before a value is written.
+    before_set: Option<BeforeSetFn<T>>,
+    /// The hook run after a value is written.
+    after_set: Option<AfterSetFn<T>>,
+}
+
+impl <T> HookedCell<T> {
+    /// Creates a new `HookedCell` without any hooks.
+    ///
+    /// - `data` - The initial value of the `HookedCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            data: SyncCell::new(data),
+            before_set: None,
+            after_set: None,
+        }
+    }
+
+    /// Creates a new `HookedCell` with hooks run before and after every write.
+    ///
+    /// - `data` - The initial value of the `HookedCell`.
+    /// - `before_set` - A function called with the current and the incoming value that returns
+    ///   the value to write or an error to veto the write.
+    /// - `after_set` - A function called with the previous and the new value.
+    pub fn with_hooks(data: T, before_set: impl Fn(&T, T) -> Result<T, T> + Send + Sync + 'static,
+            after_set: impl Fn(&T, &T) + Send + Sync + 'static) -> Self {
+        Self::new(data)
+            .before_set(before_set)
+            .after_set(after_set)
+    }
+
+    /// Sets the hook run before every write, replacing any previous hook.
+    ///
+    /// - `hook` - A function called with the current and the incoming value that returns the value
+    ///   to write or an error to veto the write.
+    pub fn before_set(mut self, hook: impl Fn(&T, T) -> Result<T, T> + Send + Sync + 'static) -> Self {
+        self.before_set = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets the hook run after every write, replacing any previous hook.
+    ///
+    /// - `hook` - A function called with the previous and the new value.
+    pub fn after_set(mut self, hook: impl Fn(&T, &T) + Send + Sync + 'static) -> Self {
+        self.after_set = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets the value contained in this cell.
+    /// If the write is vetoed the value is returned as an error.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.replace(value).map(drop)
+    }
+
+    /// Replaces the value contained in this cell.
+    /// The previous value is returned. If the write is vetoed the value is returned as an error.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> Result<T, T> {
+        self.write(&mut self.data.borrow_mut(), value)
+    }
+
+    /// Replaces the value contained in this cell with the result of a function.
+    /// The result passes through the hooks like any other write. If the write is vetoed the
+    /// result is returned as an error.
+    ///
+    /// The value stays mutably borrowed from computing the result until the hooks have run, so
+    /// concurrent updates are never lost.
+    ///
+    /// - `f` - The function computing the new value from the current value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn update(&self, f: impl FnOnce(&T) -> T) -> Result<(), T> {
+        let mut data = self.data.borrow_mut();
+        let value = f(&data);
+
+        self.write(&mut data, value).map(drop)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.data.borrow()
+    }
+
+    /// Retrieves the inner value stored in this `HookedCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Runs the hooks around writing a value to the mutably borrowed value of this cell.
+    /// The previous value is returned or the incoming value if the write is vetoed.
+    fn write(&self, data: &mut T, value: T) -> Result<T, T> {
+        let value = match &self.before_set {
+            Some(hook) => hook(data, value)?,
+            None => value,
+        };
+
+        let previous = replace(data, value);
+
+        if let Some(hook) = &self.after_set {
+            hook(&previous, data);
+        }
+
+        Ok(previous)
+    }
+}
+
+impl <T: Clone> HookedCell<T> {
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.data.get()
+    }
+}
+
+impl <T: Default> Default for HookedCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for HookedCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, Mutex}, thread};
+
+    use crate::HookedCell;
+
+    #[test]
+    pub fn test_hooked_cell() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let cell = {
+            let log = log.clone();
+            HookedCell::new(1)
+                .before_set(|current, value| if value == *current { Err(value) } else { Ok(value * 10) })
+                .after_set(move |old, new| log.lock().unwrap().push((*old, *new)))
+        };
+
+        assert_eq!(Ok(1), cell.replace(2));
+        assert_eq!(Err(20), cell.set(20));
+        assert_eq!(Ok(()), cell.update(|value| value + 1));
+        assert_eq!(210, cell.get());
+        assert_eq!(vec![(1, 20), (20, 210)], *log.lock().unwrap());
+    }
+
+    #[test]
+    pub fn test_hooked_cell_concurrent_update() {
+        let cell = Arc::new(HookedCell::new(0).before_set(|_, value| Ok(value)));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    cell.update(|value| value + 1).unwrap();
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(400, cell.get());
+    }
+}
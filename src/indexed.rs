@@ -0,0 +1,209 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{DefaultRawLock, RawSyncLock, SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A collection whose elements are stored contiguously and can be accessed by index in a
+/// `SyncCell`.
+/// This is implemented for slices, arrays, `Vec` and boxed slices. It can be implemented for
+/// other contiguous collections to give them the indexed helpers of `SyncCell`.
+pub trait Elements {
+    /// The type of the elements.
+    type Item;
+
+    /// Gets the elements as a slice.
+    fn as_elements(&self) -> &[Self::Item];
+
+    /// Gets the elements as a mutable slice.
+    fn as_elements_mut(&mut self) -> &mut [Self::Item];
+}
+
+impl <T> Elements for [T] {
+    type Item = T;
+
+    fn as_elements(&self) -> &[T] {
+        self
+    }
+
+    fn as_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl <T, const N: usize> Elements for [T; N] {
+    type Item = T;
+
+    fn as_elements(&self) -> &[T] {
+        self
+    }
+
+    fn as_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl <T> Elements for Vec<T> {
+    type Item = T;
+
+    fn as_elements(&self) -> &[T] {
+        self
+    }
+
+    fn as_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl <T> Elements for Box<[T]> {
+    type Item = T;
+
+    fn as_elements(&self) -> &[T] {
+        self
+    }
+
+    fn as_elements_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl <S: Elements + ?Sized, L: RawSyncLock> SyncCell<S, L> {
+    /// Sets a single element of the collection in this cell.
+    /// If the index is out of bounds the value is returned as an error.
+    ///
+    /// - `index` - The index of the element.
+    /// - `value` - The new value of the element.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(Ok(()), cell.set_index(1, 5));
+    /// assert_eq!(Err(6), cell.set_index(3, 6));
+    /// assert_eq!(Some(5), cell.get_index(1));
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set_index(&self, index: usize, value: S::Item) -> Result<(), S::Item> {
+        match self.borrow_mut().as_elements_mut().get_mut(index) {
+            Some(element) => {
+                *element = value;
+                Ok(())
+            },
+            None => Err(value),
+        }
+    }
+
+    /// Borrows a single element of the collection in this cell.
+    /// Returns `None` if the index is out of bounds. The whole collection stays immutably
+    /// borrowed while the returned reference is held.
+    ///
+    /// - `index` - The index of the element.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_index(&self, index: usize) -> Option<ElementRef<'_, S, L>> {
+        let data = self.borrow();
+
+        (index < data.as_elements().len()).then(|| ElementRef {
+            data,
+            index,
+        })
+    }
+
+    /// Mutably borrows a single element of the collection in this cell.
+    /// Returns `None` if the index is out of bounds. The whole collection stays mutably borrowed
+    /// while the returned reference is held.
+    ///
+    /// - `index` - The index of the element.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_index_mut(&self, index: usize) -> Option<ElementGuard<'_, S, L>> {
+        let data = self.borrow_mut();
+
+        (index < data.as_elements().len()).then(|| ElementGuard {
+            data,
+            index,
+        })
+    }
+}
+
+impl <S: Elements + ?Sized, L: RawSyncLock> SyncCell<S, L> where S::Item: Clone {
+    /// Gets a copy of a single element of the collection in this cell.
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// - `index` - The index of the element.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_index(&self, index: usize) -> Option<S::Item> {
+        self.borrow().as_elements().get(index).cloned()
+    }
+}
+
+/// An immutable borrow of a single element of the collection in a `SyncCell`.
+pub struct ElementRef<'a, S: Elements + ?Sized, L: RawSyncLock = DefaultRawLock> {
+    /// The borrowed collection.
+    data: SyncCellRef<'a, S, L>,
+    /// The index of the element, which is always in bounds.
+    index: usize,
+}
+
+impl <S: Elements + ?Sized, L: RawSyncLock> Deref for ElementRef<'_, S, L> {
+    type Target = S::Item;
+
+    fn deref(&self) -> &S::Item {
+        &self.data.as_elements()[self.index]
+    }
+}
+
+/// A mutable borrow of a single element of the collection in a `SyncCell`.
+pub struct ElementGuard<'a, S: Elements + ?Sized, L: RawSyncLock = DefaultRawLock> {
+    /// The borrowed collection.
+    data: SyncCellGuard<'a, S, L>,
+    /// The index of the element, which is always in bounds.
+    index: usize,
+}
+
+impl <S: Elements + ?Sized, L: RawSyncLock> Deref for ElementGuard<'_, S, L> {
+    type Target = S::Item;
+
+    fn deref(&self) -> &S::Item {
+        &self.data.as_elements()[self.index]
+    }
+}
+
+impl <S: Elements + ?Sized, L: RawSyncLock> DerefMut for ElementGuard<'_, S, L> {
+    fn deref_mut(&mut self) -> &mut S::Item {
+        &mut self.data.as_elements_mut()[self.index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_index() {
+        let cell = SyncCell::new([1, 2, 3]);
+
+        assert_eq!(Some(2), cell.get_index(1));
+        assert_eq!(None, cell.get_index(3));
+
+        *cell.borrow_index_mut(0).unwrap() += 10;
+
+        assert_eq!(11, *cell.borrow_index(0).unwrap());
+        assert!(cell.borrow_index(3).is_none());
+    }
+
+    #[test]
+    pub fn test_sync_cell_index_unsized() {
+        let cell: Box<SyncCell<[i32]>> = Box::new(SyncCell::new([1, 2]));
+
+        assert_eq!(Ok(()), cell.set_index(0, 3));
+        assert_eq!(Err(4), cell.set_index(2, 4));
+        assert_eq!(Some(3), cell.get_index(0));
+    }
+}
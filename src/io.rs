@@ -0,0 +1,167 @@
+use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+
+use crate::{RawSyncLock, SyncCell};
+
+/// Writes to the writer in the cell taking the lock once per call.
+/// This lets several threads share one writer, such as a log file, through a `&SyncCell`.
+///
+/// # Usage
+/// ```
+/// use std::io::Write;
+/// use sync_cell::SyncCell;
+///
+/// let log = SyncCell::new(Vec::new());
+///
+/// writeln!(&log, "Started").unwrap();
+///
+/// assert_eq!(b"Started\n", &log.borrow()[..]);
+/// ```
+///
+/// # Panicking
+/// Each method will panic if the lock becomes poisoned.
+impl <W: Write + ?Sized, L: RawSyncLock> Write for &SyncCell<W, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.borrow_mut().write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.borrow_mut().write_vectored(bufs)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        // Writing the whole buffer under one lock keeps writes from different threads from
+        // interleaving.
+        self.borrow_mut().write_all(buf)
+    }
+
+    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> io::Result<()> {
+        self.borrow_mut().write_fmt(args)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.borrow_mut().flush()
+    }
+}
+
+/// Writes to the writer in the cell.
+///
+/// # Panicking
+/// Each method will panic if the lock becomes poisoned.
+impl <W: Write + ?Sized, L: RawSyncLock> Write for SyncCell<W, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self).write_vectored(bufs)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        (&*self).write_all(buf)
+    }
+
+    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> io::Result<()> {
+        (&*self).write_fmt(args)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+/// Reads from the reader in the cell taking the lock once per call.
+/// This lets several threads take turns reading from one reader, such as a socket, through a
+/// `&SyncCell`.
+///
+/// # Panicking
+/// Each method will panic if the lock becomes poisoned.
+impl <R: Read + ?Sized, L: RawSyncLock> Read for &SyncCell<R, L> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.borrow_mut().read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.borrow_mut().read_vectored(bufs)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.borrow_mut().read_exact(buf)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.borrow_mut().read_to_end(buf)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.borrow_mut().read_to_string(buf)
+    }
+}
+
+/// Reads from the reader in the cell.
+///
+/// # Panicking
+/// Each method will panic if the lock becomes poisoned.
+impl <R: Read + ?Sized, L: RawSyncLock> Read for SyncCell<R, L> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self).read_vectored(bufs)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        (&*self).read_exact(buf)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (&*self).read_to_end(buf)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&*self).read_to_string(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::{Cursor, Read, Write}, sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_write() {
+        let log = Arc::new(SyncCell::new(Vec::new()));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let log = log.clone();
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    (&*log).write_all(b"line\n").unwrap();
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let log = String::from_utf8(log.get()).unwrap();
+
+        assert_eq!(40, log.lines().count());
+        assert!(log.lines().all(|line| line == "line"));
+    }
+
+    #[test]
+    pub fn test_sync_cell_read() {
+        let mut reader = SyncCell::new(Cursor::new(b"abcd".to_vec()));
+        let mut buf = [0; 2];
+
+        (&reader).read_exact(&mut buf).unwrap();
+        assert_eq!(b"ab", &buf);
+
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!("cd", rest);
+    }
+}
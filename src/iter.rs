@@ -0,0 +1,81 @@
+use std::vec::Drain;
+
+use crate::{RawSyncLock, SyncCell};
+
+/// Iterates over the values of the collection in the cell, consuming the cell.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncCell;
+///
+/// let cell = SyncCell::new(vec![1, 2, 3]);
+///
+/// let doubled: Vec<_> = cell.into_iter().map(|value| value * 2).collect();
+///
+/// assert_eq!(vec![2, 4, 6], doubled);
+/// ```
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <C: IntoIterator, L: RawSyncLock> IntoIterator for SyncCell<C, L> {
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    fn into_iter(self) -> C::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl <T, L: RawSyncLock> SyncCell<Vec<T>, L> {
+    /// Removes every value from the vector in this cell and calls a function with an iterator
+    /// over them.
+    /// The cell stays mutably borrowed while the function runs and is left empty, keeping the
+    /// capacity of the vector. Values not consumed by the function are dropped.
+    ///
+    /// - `f` - The function to call with the removed values.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let results = SyncCell::new(vec![1, 2, 3]);
+    ///
+    /// let total: i32 = results.drain_with(|values| values.sum());
+    ///
+    /// assert_eq!(6, total);
+    /// assert!(results.borrow().is_empty());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn drain_with<R>(&self, f: impl FnOnce(Drain<'_, T>) -> R) -> R {
+        f(self.borrow_mut().drain(..))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_into_iter() {
+        let cell = Arc::new(SyncCell::new(Vec::new()));
+
+        let handles: Vec<_> = (0..4).map(|i| {
+            let cell = cell.clone();
+            thread::spawn(move || cell.borrow_mut().push(i))
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(2, cell.drain_with(|values| values.take(2).count()));
+        assert!(cell.borrow().is_empty());
+
+        let set = SyncCell::new(BTreeSet::from([3, 1, 2]));
+        assert_eq!(vec![1, 2, 3], set.into_iter().collect::<Vec<_>>());
+    }
+}
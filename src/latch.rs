@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use crate::CondCell;
+
+/// The state of a `LatchCell`.
+#[derive(Debug)]
+struct Latch<T> {
+    /// The number of count downs remaining before the latch is released.
+    count: usize,
+    /// The value published when the latch is released.
+    value: Option<T>,
+}
+
+/// A countdown latch that releases waiting threads once it has been counted down a number of
+/// times.
+/// A value can be provided when counting down which is made available to waiting threads once
+/// the latch is released.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::LatchCell;
+///
+/// let latch = Arc::new(LatchCell::new(3));
+///
+/// let workers: Vec<_> = (0..3).map(|i| {
+///     let latch = latch.clone();
+///     thread::spawn(move || {
+///         latch.count_down_with(i);
+///     })
+/// }).collect();
+///
+/// // Blocks until every worker has counted down.
+/// let last = latch.wait_value();
+/// assert!(last.is_some());
+/// # for worker in workers { worker.join().unwrap(); }
+/// ```
+#[derive(Debug)]
+pub struct LatchCell<T = ()> {
+    /// The remaining count and the published value.
+    state: CondCell<Latch<T>>,
+}
+
+impl <T> LatchCell<T> {
+    /// Creates a new `LatchCell`.
+    /// A latch with a count of zero is released immediately.
+    ///
+    /// - `count` - The number of count downs needed to release the latch.
+    pub const fn new(count: usize) -> Self {
+        Self {
+            state: CondCell::new(Latch {
+                count,
+                value: None,
+            }),
+        }
+    }
+
+    /// Decrements the count of this latch releasing it if the count reaches zero.
+    /// Counting down a released latch has no effect.
+    ///
+    /// Returns `true` if this call released the latch.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn count_down(&self) -> bool {
+        let mut state = self.state.borrow_mut();
+
+        match state.count {
+            0 => false,
+            _ => {
+                state.count -= 1;
+                state.count == 0
+            },
+        }
+    }
+
+    /// Decrements the count of this latch and stores a value to publish when it is released.
+    /// The value replaces any value provided by an earlier count down. Counting down a released
+    /// latch has no effect and the value is dropped.
+    ///
+    /// Returns `true` if this call released the latch.
+    ///
+    /// - `value` - The value to publish when the latch is released.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn count_down_with(&self, value: T) -> bool {
+        let mut state = self.state.borrow_mut();
+
+        match state.count {
+            0 => false,
+            _ => {
+                state.count -= 1;
+                state.value = Some(value);
+                state.count == 0
+            },
+        }
+    }
+
+    /// Gets the number of count downs remaining before the latch is released.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn count(&self) -> usize {
+        self.state.borrow().count
+    }
+
+    /// Checks if this latch has been released.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_released(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Blocks the current thread until this latch is released.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait(&self) {
+        drop(self.state.wait_until(|state| state.count == 0));
+    }
+
+    /// Blocks the current thread until this latch is released or a timeout elapses.
+    ///
+    /// Returns `true` if the latch was released.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        self.state.wait_until_timeout(timeout, |state| state.count == 0).is_some()
+    }
+
+    /// Retrieves the published value of this latch if it has been released.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> Option<T> {
+        let state = self.state.into_inner();
+
+        match state.count {
+            0 => state.value,
+            _ => None,
+        }
+    }
+}
+
+impl <T: Clone> LatchCell<T> {
+    /// Blocks the current thread until this latch is released and returns the published value.
+    /// Returns `None` if no value was provided when counting down.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait_value(&self) -> Option<T> {
+        self.state.wait_until(|state| state.count == 0).value.clone()
+    }
+
+    /// Gets the published value of this latch without blocking.
+    /// Returns `None` if the latch has not been released or no value was provided.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> Option<T> {
+        let state = self.state.borrow();
+
+        match state.count {
+            0 => state.value.clone(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::LatchCell;
+
+    #[test]
+    pub fn test_latch_cell_count_down() {
+        let latch: LatchCell = LatchCell::new(2);
+
+        assert!(!latch.count_down());
+        assert!(!latch.is_released());
+        assert!(latch.count_down());
+        assert!(latch.is_released());
+        assert!(!latch.count_down());
+        assert_eq!(0, latch.count());
+    }
+
+    #[test]
+    pub fn test_latch_cell_value() {
+        let latch = LatchCell::new(2);
+
+        latch.count_down_with(1);
+        assert_eq!(None, latch.get());
+
+        latch.count_down_with(2);
+        assert_eq!(Some(2), latch.get());
+        assert_eq!(Some(2), latch.wait_value());
+    }
+
+    #[test]
+    pub fn test_latch_cell_wait_timeout() {
+        let latch: LatchCell = LatchCell::new(1);
+
+        assert!(!latch.wait_timeout(Duration::from_millis(10)));
+        latch.count_down();
+        assert!(latch.wait_timeout(Duration::from_millis(10)));
+    }
+
+    #[test]
+    pub fn test_latch_cell_wait() {
+        let latch: Arc<LatchCell> = Arc::new(LatchCell::new(4));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let latch = latch.clone();
+            thread::spawn(move || {
+                latch.count_down();
+            })
+        }).collect();
+
+        latch.wait();
+        assert!(latch.is_released());
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::{borrow::Borrow, collections::HashMap, fmt::{self, Debug, Formatter}, hash::Hash, sync::{Arc, OnceLock}};
+
+use crate::SyncCell;
+
+/// A map that computes the value of each key the first time it is asked for and keeps it forever.
+/// The construction function runs exactly once per key even when several threads ask for the
+/// same key at the same time. The other threads wait for the value instead of computing it again.
+/// The map is not locked while a value is computed, so values for different keys are computed in
+/// parallel.
+///
+/// Values are returned as an `Arc`, which is a cheap handle that stays valid after the map is
+/// dropped.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncLazyMap;
+///
+/// let lengths = SyncLazyMap::new(|name: &String| name.len());
+///
+/// assert_eq!(5, *lengths.get(String::from("alice")));
+/// assert!(lengths.contains_key("alice"));
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this map will panic rather than return an error when the lock becomes
+/// poisoned. If the construction function panics, the next call for the key runs it again.
+pub struct SyncLazyMap<K, V, F = fn(&K) -> V> {
+    /// The values by key, which are empty while they are being computed.
+    values: SyncCell<HashMap<K, Arc<OnceLock<Arc<V>>>>>,
+    /// The function computing the value of a key.
+    init: F,
+}
+
+impl <K: Eq + Hash + Clone, V, F: Fn(&K) -> V> SyncLazyMap<K, V, F> {
+    /// Creates a new empty `SyncLazyMap`.
+    ///
+    /// - `init` - The function computing the value of a key.
+    pub fn new(init: F) -> Self {
+        Self {
+            values: SyncCell::new(HashMap::new()),
+            init,
+        }
+    }
+
+    /// Gets the value of a key computing it if this is the first time the key is asked for.
+    ///
+    /// - `key` - The key of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self, key: K) -> Arc<V> {
+        let existing = self.values.borrow().get(&key).cloned();
+
+        let value = match existing {
+            Some(value) => value,
+            None => self.values.entry_with(key.clone(), |entry| entry.or_default().clone()),
+        };
+
+        value.get_or_init(|| Arc::new((self.init)(&key))).clone()
+    }
+
+    /// Gets the value of a key if it has already been computed.
+    ///
+    /// - `key` - The key of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_if_present<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<Arc<V>> where K: Borrow<Q> {
+        self.values.borrow().get(key)?.get().cloned()
+    }
+
+    /// Checks if the value of a key has been computed or is being computed.
+    ///
+    /// - `key` - The key to look for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn contains_key<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.values.contains_key(key)
+    }
+
+    /// Gets the number of keys that have been asked for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    /// Checks if no key has been asked for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.values.borrow().is_empty()
+    }
+}
+
+impl <K: Debug, V: Debug, F> Debug for SyncLazyMap<K, V, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncLazyMap")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, atomic::{AtomicUsize, Ordering}}, thread, time::Duration};
+
+    use crate::SyncLazyMap;
+
+    #[test]
+    pub fn test_sync_lazy_map() {
+        let map = SyncLazyMap::new(|key: &i32| key * 2);
+
+        assert_eq!(None, map.get_if_present(&1));
+        assert_eq!(2, *map.get(1));
+        assert_eq!(Some(2), map.get_if_present(&1).map(|value| *value));
+        assert_eq!(1, map.len());
+    }
+
+    #[test]
+    pub fn test_sync_lazy_map_once() {
+        let computed = Arc::new(AtomicUsize::new(0));
+
+        let map = {
+            let computed = computed.clone();
+            Arc::new(SyncLazyMap::new(move |key: &u32| {
+                computed.fetch_add(1, Ordering::Relaxed);
+                thread::sleep(Duration::from_millis(20));
+                *key
+            }))
+        };
+
+        let handles: Vec<_> = (0..8).map(|_| {
+            let map = map.clone();
+            thread::spawn(move || map.get(7))
+        }).collect();
+
+        let values: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        assert_eq!(1, computed.load(Ordering::Relaxed));
+        assert!(values.iter().all(|value| Arc::ptr_eq(value, &values[0])));
+    }
+}
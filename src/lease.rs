@@ -0,0 +1,365 @@
+use std::{error::Error, fmt::{self, Display, Formatter}, ops::Deref, time::{Duration, Instant}};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// The error returned when a lease on a `LeaseCell` is used after it has expired or been taken
+/// over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LeaseExpired;
+
+impl Display for LeaseExpired {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Lease has expired")
+    }
+}
+
+impl Error for LeaseExpired {}
+
+/// The current holder of a lease.
+#[derive(Debug)]
+struct Holder {
+    /// The identifier of the lease.
+    id: u64,
+    /// The instant the lease expires.
+    /// This is `None` if the duration is too long to be represented, in which case the lease
+    /// never expires.
+    deadline: Option<Instant>,
+}
+
+impl Holder {
+    /// Checks if the lease has not yet expired.
+    fn is_valid(&self) -> bool {
+        match self.deadline {
+            Some(deadline) => deadline > Instant::now(),
+            None => true,
+        }
+    }
+}
+
+/// The value of a `LeaseCell` and the state of its lease.
+#[derive(Debug)]
+struct LeaseState<T> {
+    /// The value of the cell.
+    value: T,
+    /// The current holder of the lease if any.
+    holder: Option<Holder>,
+    /// The identifier given to the next lease.
+    next_id: u64,
+}
+
+/// A cell whose value can only be modified by the holder of a lease that expires after a time.
+/// A lease that is not renewed in time can be taken over by another thread, after which the
+/// previous holder can no longer modify the value.
+///
+/// This is useful for watchdogs and work distribution where a stalled worker must not block
+/// others forever.
+///
+/// A worker that panics while modifying the value does not poison the cell. Its lease is
+/// released as it unwinds so that another worker can take over, and the value is left as the
+/// panicking worker left it.
+///
+/// # Usage
+/// ```
+/// use std::time::Duration;
+/// use sync_cell::{LeaseCell, LeaseExpired};
+///
+/// let cell = LeaseCell::new(0);
+///
+/// let lease = cell.lease(Duration::from_secs(10)).unwrap();
+///
+/// // Only one lease can be held at a time.
+/// assert!(cell.lease(Duration::from_secs(10)).is_none());
+///
+/// lease.with_mut(|value| *value += 1).unwrap();
+/// drop(lease);
+///
+/// assert_eq!(1, cell.get());
+/// ```
+#[derive(Debug)]
+pub struct LeaseCell<T> {
+    /// The value and the state of its lease.
+    state: SyncCell<LeaseState<T>>,
+}
+
+impl <T> LeaseCell<T> {
+    /// Creates a new `LeaseCell` with no lease held.
+    ///
+    /// - `data` - The initial value of the `LeaseCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            state: SyncCell::builder().ignore_poison().build(LeaseState {
+                value: data,
+                holder: None,
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Acquires a lease on this cell if no lease is held or the current lease has expired.
+    /// Returns `None` if another lease is still valid.
+    ///
+    /// - `duration` - How long the lease is valid for.
+    pub fn lease(&self, duration: Duration) -> Option<Lease<'_, T>> {
+        let mut state = self.state.borrow_mut();
+
+        if state.holder.as_ref().is_some_and(Holder::is_valid) {
+            return None;
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.holder = Some(Holder {
+            id,
+            deadline: Instant::now().checked_add(duration),
+        });
+
+        Some(Lease {
+            cell: self,
+            id,
+        })
+    }
+
+    /// Checks if a valid lease is currently held on this cell.
+    pub fn is_leased(&self) -> bool {
+        self.state.borrow()
+            .holder
+            .as_ref()
+            .is_some_and(Holder::is_valid)
+    }
+
+    /// Calls a function with a reference to the value of this cell.
+    /// Reading does not require a lease.
+    ///
+    /// - `f` - The function to call.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.state.borrow().value)
+    }
+
+    /// Retrieves the inner value stored in this `LeaseCell`.
+    pub fn into_inner(self) -> T {
+        self.state.into_inner().value
+    }
+
+    /// Borrows the state of this cell if a lease is still the valid holder.
+    fn borrow_held(&self, id: u64) -> Result<SyncCellGuard<'_, LeaseState<T>>, LeaseExpired> {
+        let state = self.state.borrow_mut();
+
+        match &state.holder {
+            Some(holder) if holder.id == id && holder.is_valid() => Ok(state),
+            _ => Err(LeaseExpired),
+        }
+    }
+}
+
+impl <T: Clone> LeaseCell<T> {
+    /// Gets the value contained in this cell.
+    /// Reading does not require a lease.
+    pub fn get(&self) -> T {
+        self.with(T::clone)
+    }
+}
+
+impl <T: Default> Default for LeaseCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for LeaseCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A lease granting exclusive rights to modify the value of a `LeaseCell` until it expires.
+/// The lease is released when it is dropped.
+#[derive(Debug)]
+pub struct Lease<'a, T> {
+    /// The cell this lease is held on.
+    cell: &'a LeaseCell<T>,
+    /// The identifier of this lease.
+    id: u64,
+}
+
+impl <T> Lease<'_, T> {
+    /// Checks if this lease is still valid.
+    pub fn is_valid(&self) -> bool {
+        self.remaining().is_some()
+    }
+
+    /// Gets the time remaining until this lease expires.
+    /// Returns `None` if the lease has expired or been taken over and `Duration::MAX` if the lease
+    /// never expires.
+    pub fn remaining(&self) -> Option<Duration> {
+        let state = self.cell.state.borrow();
+
+        match &state.holder {
+            Some(holder) if holder.id == self.id => match holder.deadline {
+                Some(deadline) => deadline.checked_duration_since(Instant::now())
+                    .filter(|remaining| !remaining.is_zero()),
+                None => Some(Duration::MAX),
+            },
+            _ => None,
+        }
+    }
+
+    /// Extends this lease so that it expires a given duration from now.
+    /// This fails if the lease has already expired.
+    ///
+    /// - `duration` - How long the lease is valid for from now.
+    pub fn renew(&self, duration: Duration) -> Result<(), LeaseExpired> {
+        let mut state = self.cell.borrow_held(self.id)?;
+
+        if let Some(holder) = &mut state.holder {
+            holder.deadline = Instant::now().checked_add(duration);
+        }
+
+        Ok(())
+    }
+
+    /// Calls a function with a mutable reference to the value of the cell.
+    /// This fails if the lease has expired.
+    ///
+    /// - `f` - The function to call.
+    pub fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R, LeaseExpired> {
+        let mut state = self.cell.borrow_held(self.id)?;
+
+        Ok(f(&mut state.value))
+    }
+
+    /// Sets the value of the cell.
+    /// If the lease has expired the value is returned as an error.
+    ///
+    /// - `value` - The new value of the cell.
+    pub fn commit(&self, value: T) -> Result<(), T> {
+        match self.cell.borrow_held(self.id) {
+            Ok(mut state) => {
+                state.value = value;
+                Ok(())
+            },
+            Err(_) => Err(value),
+        }
+    }
+
+    /// Borrows a immutable reference to the value of the cell.
+    /// Reading does not require the lease to be valid.
+    pub fn borrow(&self) -> LeaseRef<'_, T> {
+        LeaseRef {
+            state: self.cell.state.borrow(),
+        }
+    }
+}
+
+impl <T> Drop for Lease<'_, T> {
+    fn drop(&mut self) {
+        // The state ignores poisoning, so this cannot panic while a panicking holder unwinds.
+        let mut state = self.cell.state.borrow_mut();
+
+        if state.holder.as_ref().is_some_and(|holder| holder.id == self.id) {
+            state.holder = None;
+        }
+    }
+}
+
+/// A borrow of the value of a `LeaseCell` through a lease.
+pub struct LeaseRef<'a, T> {
+    /// The lock guard over the state of the cell.
+    state: SyncCellRef<'a, LeaseState<T>>,
+}
+
+impl <T> Deref for LeaseRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.state.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{panic::{AssertUnwindSafe, catch_unwind}, thread, time::Duration};
+
+    use crate::{LeaseCell, LeaseExpired};
+
+    #[test]
+    pub fn test_lease_cell_exclusive() {
+        let cell = LeaseCell::new(1);
+        let lease = cell.lease(Duration::from_secs(60)).unwrap();
+
+        assert!(cell.is_leased());
+        assert!(cell.lease(Duration::from_secs(60)).is_none());
+        assert_eq!(Ok(()), lease.commit(2));
+        assert_eq!(2, *lease.borrow());
+
+        drop(lease);
+
+        assert!(!cell.is_leased());
+        assert!(cell.lease(Duration::from_secs(60)).is_some());
+    }
+
+    #[test]
+    pub fn test_lease_cell_expired() {
+        let cell = LeaseCell::new(1);
+        let first = cell.lease(Duration::from_millis(10)).unwrap();
+
+        thread::sleep(Duration::from_millis(20));
+
+        assert!(!first.is_valid());
+
+        let second = cell.lease(Duration::from_secs(60)).unwrap();
+
+        assert_eq!(Err(LeaseExpired), first.with_mut(|value| *value = 5));
+        assert_eq!(Err(LeaseExpired), first.renew(Duration::from_secs(60)));
+        assert_eq!(Err(3), first.commit(3));
+
+        drop(first);
+
+        assert!(second.is_valid());
+        assert_eq!(Ok(()), second.with_mut(|value| *value = 4));
+        assert_eq!(4, cell.get());
+    }
+
+    #[test]
+    pub fn test_lease_cell_renew() {
+        let cell = LeaseCell::new(());
+        let lease = cell.lease(Duration::from_millis(100)).unwrap();
+
+        assert!(lease.renew(Duration::from_secs(60)).is_ok());
+
+        thread::sleep(Duration::from_millis(150));
+
+        assert!(lease.remaining().unwrap() > Duration::from_secs(30));
+    }
+
+    #[test]
+    pub fn test_lease_cell_never_expires() {
+        let cell = LeaseCell::new(());
+        let lease = cell.lease(Duration::MAX).unwrap();
+
+        assert!(cell.lease(Duration::from_secs(1)).is_none());
+        assert!(lease.renew(Duration::MAX).is_ok());
+        assert_eq!(Some(Duration::MAX), lease.remaining());
+    }
+
+    #[test]
+    pub fn test_lease_cell_panic_while_leased() {
+        let cell = LeaseCell::new(1);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let lease = cell.lease(Duration::from_secs(60)).unwrap();
+
+            lease.with_mut(|value| {
+                *value = 2;
+                panic!("Panicking while leased");
+            })
+        }));
+
+        assert!(result.is_err());
+        assert!(!cell.is_leased());
+
+        let lease = cell.lease(Duration::from_secs(60)).unwrap();
+
+        assert_eq!(Ok(()), lease.with_mut(|value| *value += 1));
+        assert_eq!(3, *lease.borrow());
+    }
+}
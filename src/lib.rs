@@ -3,18 +3,612 @@
 //!
 //! ## Included Types
 //! - `SyncCell` - A replacement for `std::cell::RefCell` and `std::cell::Cell` with an easier to
-//! use API than `std::sync::RwLock`.
+//!   use API than `std::sync::RwLock`.
 //! - `HeldSyncCell` - A cell that maintains a previous value until the `update` method is called
-//! at which point any changes to the value are applied.
-
-use std::{sync::{RwLock, RwLockReadGuard, RwLockWriteGuard}, cmp::Ordering, hash::{Hash, Hasher}, mem::swap};
+//!   at which point any changes to the value are applied.
+//! - `HeldSyncBox` - A `HeldSyncCell` for unsized values such as trait objects.
+//! - `UndoRedoCell` - A cell that records previous values so that changes can be undone and
+//!   redone.
+//! - `ExpiringCell` - A cell whose value expires after a fixed amount of time.
+//! - `DebouncedCell` - A cell that only publishes a new value once no other value has been set
+//!   for a period of time.
+//! - `ThrottledCell` - A cell that accepts new values at any rate but publishes them at most
+//!   once per interval.
+//! - `DoubleBufferedCell` - A cell with a front buffer for consumers and a back buffer for
+//!   producers that can be swapped.
+//! - `TripleBufferCell` - A wait-free triple buffer for exchanging values between a single
+//!   producer and a single consumer.
+//! - `SnapshotCell` - A cell that hands out cheap immutable snapshots of its value.
+//! - `TransactionalCell` - A cell that can be read and written atomically alongside other cells
+//!   using a transaction.
+//! - `PersistentCell` - A cell whose value is stored in a file so that it survives restarts.
+//!   Requires the `serde` feature.
+//! - `ShmCell` - A cell stored in named shared memory that can be accessed from multiple
+//!   processes. Requires the `shm` feature.
+//! - `CondCell` - A cell that allows threads to block until its value satisfies a condition.
+//! - `SlotCell` - A single value slot with blocking put and take operations for handing values
+//!   between threads.
+//! - `LatchCell` - A countdown latch that releases waiting threads and publishes a value once
+//!   counted down.
+//! - `AccumulatorCell` - A cell that folds items submitted from many threads into an
+//!   accumulated value.
+//! - `PoolCell` - A pool of reusable values that are returned to the pool when dropped.
+//! - `ShardedCell` - A cell split into per-thread shards to reduce contention on frequently
+//!   updated values.
+//! - `ThreadLocalCell` - A cell where each thread updates its own staging value that is later
+//!   merged into a shared value.
+//! - `ReadMostlyCell` - A cell where readers never lock and writers wait for readers of the
+//!   previous value to finish.
+//! - `CowCell` - A cell whose readers hold cheap snapshots and whose writers only clone the
+//!   value when snapshots exist.
+//! - `FrozenCell` - A cell that can be modified until it is frozen after which reads no longer
+//!   take a lock.
+//! - `TimedCell` - A cell that records when its value was last written and read.
+//! - `StatCell` - A cell that counts reads, writes and contended borrows.
+//! - `ValidatedCell` - A cell that checks an invariant every time its value is written.
+//! - `ClampedCell` - A cell whose value is kept within an inclusive range.
+//! - `StateMachineCell` - A cell holding a state that can only change through allowed
+//!   transitions.
+//! - `GenerationalCell` - A cell that hands out generation tokens to detect stale copies of its
+//!   value.
+//! - `PriorityCell` - A cell where higher priority writes replace lower priority values.
+//! - `BroadcastCell` - A cell that delivers every published value to all of its receivers.
+//! - `PromiseCell` - A cell that is fulfilled once and that readers can wait on.
+//! - `AnyCell` - A cell holding a value of any type with checked downcasts.
+//! - `TypeMapCell` - A cell holding at most one value of each type.
+//! - `DirtyCell` - A cell that records whether its value has changed since it was marked clean.
+//! - `LeaseCell` - A cell that can only be modified by the holder of an expiring lease.
+//! - `MvccCell` - A cell that keeps every version of its value that is still being read.
+//! - `QueuedSyncCell` - A held cell that queues every value set and applies them one at a time.
+//! - `UpdateGroup` - A group of held cells whose incoming values are applied together.
+//! - `AtomicHeldCell` - A lock-free held cell for a single consumer where setting and applying
+//!   a value are single atomic swaps.
+//! - `UpdateScheduler` - A list of held cells that are all updated in registration order by a
+//!   single call.
+//! - `EmptyHeldCell` - A held cell that starts without a value until the first update publishes
+//!   one.
+//! - `AsyncSyncCell` - A cell like `SyncCell` whose borrows are futures that yield to the
+//!   executor instead of blocking. Requires the `async` feature, and is backed by a
+//!   `tokio::sync::RwLock` with the `tokio` feature.
+//! - `AsyncHeldSyncCell` - A variant of `HeldSyncCell` whose waiting methods are futures.
+//!   Requires the `async` feature.
+//! - `SyncCell::into_tokio_watch` - Converts a `SyncCell` or `AsyncSyncCell` to and from a
+//!   `tokio::sync::watch` channel, with `follow_tokio_watch` keeping a cell up to date with a
+//!   channel. Requires the `tokio` feature.
+//! - `SyncCellSink` - A `futures::Sink` that sets the value of a cell, returned by
+//!   `SyncCell::sink` and `HeldSyncCell::sink`. Requires the `futures` feature.
+//! - `ArchivedSyncCell` - The `rkyv` archived form of a `SyncCell` or, as
+//!   `ArchivedHeldSyncCell`, of the current value of a `HeldSyncCell`, for zero-copy snapshots of
+//!   structs containing cells. Requires the `rkyv` feature.
+//! - `Arbitrary` - `proptest` strategies for `SyncCell` and `HeldSyncCell`, with `HeldSyncCell`
+//!   sometimes holding an incoming value. Requires the `proptest` feature.
+//! - `SyncCell::into_atomic_cell` - Converts a `SyncCell` or `HeldSyncCell` to and from a
+//!   `crossbeam_utils::atomic::AtomicCell`. Requires the `crossbeam` feature.
+//! - `Serialize` / `Deserialize` - `serde` support for `SyncCell` and `HeldSyncCell`, which
+//!   keeps the incoming value of a `HeldSyncCell` that has not been applied. Requires the `serde`
+//!   feature.
+//! - `RawSyncCell` - A variant of `SyncCell` that never poisons, for code that handles panics
+//!   itself.
+//! - `RawSyncLock` - The trait for the lock backends of `SyncCell` and `RawSyncCell`, chosen with
+//!   their second type parameter. `RwLockRawLock` keeps its state in an atomic and is the
+//!   default, `StdRawLock` lets a `LockPolicy` choose which waiting thread goes next and
+//!   `SpinRawLock` only uses atomics. With the `parking_lot` feature `parking_lot::RawRwLock` is
+//!   also a backend and becomes the default.
+//! - `CriticalSectionRawLock` - A `RawSyncLock` backend that changes its state inside a
+//!   critical section, for bare-metal targets without atomic compare-and-swap. Requires the
+//!   `critical-section` feature.
+//! - `LoomRawLock` - The default `RawSyncLock` backend when the crate is built with
+//!   `--cfg loom`, so that `loom` can check every interleaving of the threads using a cell.
+//! - `ShuttleRawLock` - The default `RawSyncLock` backend when the crate is built with
+//!   `--cfg shuttle`, so that `shuttle` can run the threads using a cell in random orders.
+//! - `MutexSyncCell` - A variant of `SyncCell` backed by a `Mutex` for cells that are written
+//!   as often as they are read.
+//! - `DetectingRawLock` - A `RawSyncLock` backend that panics with a report instead of
+//!   deadlocking when threads wait for each other in a cycle. Requires the `deadlock-detection`
+//!   feature.
+//! - `NotifyingRawLock` - A `RawSyncLock` backend that wakes waiting threads when a mutable
+//!   borrow is released, giving `SyncCell` a `wait_for` method that blocks until the value
+//!   satisfies a condition.
+//! - `ContentionStats` - Statistics about how often borrows of a `SyncCell` waited for the
+//!   lock, read with `SyncCell::contention_stats`. Requires the `contention-stats` feature.
+//! - `LockHolder` - The thread holding the mutable borrow that blocked a borrow, reported by
+//!   `SyncCellError`. Holders are tracked in debug builds with the `owner-tracking` feature,
+//!   which also names the cell and the thread that poisoned it in panic messages.
+//! - `sync_cell_borrow` - The `tracing` span of a borrow of a `SyncCell`, named by the label of
+//!   the cell and closed when the borrow is released. Requires the `tracing` feature.
+//! - `Elements` - A trait for contiguous collections that gives `SyncCell` single-element
+//!   helpers such as `get_index` and `borrow_index`. With the `rayon` feature it also gives
+//!   `par_iter_with` and `par_chunks_mut_with`, which borrow the collection once and process it
+//!   in parallel.
+//! - `SharedSyncCell` - A cell that wraps a shared `Arc<RwLock<T>>` for use alongside code that
+//!   holds its own handle.
+//! - `SyncVec` - A vector whose elements are locked individually so that threads can work on
+//!   different elements at the same time.
+//! - `SyncHashMap` - A hash map that splits its keys between several `SyncCell` shards so that
+//!   threads using different keys rarely wait for each other.
+//! - `SyncQueue` - A first in first out queue shared between threads whose pops block until a
+//!   value is available, with an optional capacity.
+//! - `SyncStack` - A last in first out stack shared between threads, such as a free list or
+//!   undo stack.
+//! - `SyncBTreeMap` - An ordered map shared between threads with range queries that run under a
+//!   single borrow.
+//! - `SyncSlab` - A collection that stores values at stable keys with a lock for each value,
+//!   such as a registry of entities.
+//! - `SyncRingBuffer` - A buffer of fixed capacity that either overwrites its oldest value or
+//!   blocks when full, such as a shared log buffer.
+//! - `SyncBitSet` - A fixed size set of flags stored in atomic words that can be changed from
+//!   multiple threads without a lock.
+//! - `SyncLruCache` - A cache of a fixed number of values that evicts the least recently used
+//!   value and computes missing values once.
+//! - `SyncCellArray` - A fixed size array of independent `SyncCell`s, such as one slot per
+//!   worker thread.
+//! - `SyncString` - A string that many threads can append to at the same time without their
+//!   text being interleaved.
+//! - `SyncLazyMap` - A map that computes the value of each key exactly once the first time it
+//!   is asked for and never evicts it.
+//! - `PoisonPolicy` - What a `SyncCell` does when its lock is poisoned, set with
+//!   `SyncCell::with_poison_policy` or `SyncCellBuilder::poison_policy`. Resetting the value
+//!   requires it to implement `Default`.
+//! - `TransactionGuard` - A mutable borrow of a `SyncCell` that restores the previous value
+//!   unless it is committed, returned by `SyncCell::borrow_mut_transactional`.
+//! - `lock_all` - Locks several `SyncCell`s and changes them together, keeping every change or
+//!   none of them.
+//! - `set_guard_watchdog` - Reports borrows of a `SyncCell`, `HeldSyncCell` or `RawSyncCell`
+//!   that are held for longer than a threshold with a backtrace of where they were taken. Only
+//!   available in debug builds.
+//! - `HookedCell` - A cell that runs hooks before and after every write, which can observe,
+//!   transform or veto incoming values.
+//! - `PaddedSyncCell` - A `SyncCell` aligned to a cache line to avoid false sharing between
+//!   neighbouring cells.
+//! - `LocalCache` - A thread's copy of the value of a `SyncCell` that `SyncCell::get_cached`
+//!   refreshes only when the cell has changed. Requires the `local-cache` feature, which makes
+//!   every `SyncCell` count its mutable borrows.
+//! - `SyncCounter` - A counter backed by an atomic integer that can wrap or saturate on
+//!   overflow.
+//! - `SyncFlag` - A boolean flag backed by an atomic that threads can wait to be raised.
+//! - `sync_cell!` / `held_sync_cell!` - Create cells or declare `static` cells whose initial values
+//!   are computed on first use or, with `const { ... }`, at compile time.
+//! - `SyncCellBuilder` - A builder that configures the label, poison policy and padding of a
+//!   `SyncCell`, returned by `SyncCell::builder`.
+//! - `SyncFields` - A derive macro generating a companion struct that stores each field of a
+//!   struct in its own `SyncCell`. Requires the `derive` feature.
+//! - `ProjectedCell` - A handle to part of the value of a `SyncCell` that shares its lock,
+//!   returned by `SyncCell::project`.
+//! - `zip` - Reads the values of two cells together as a consistent snapshot, with `merge`
+//!   combining them with a function.
+//! - `MappedView` - A read-only view of a value derived from a `SyncCell`, returned by
+//!   `SyncCell::map_view`.
+//! - `RecordedCell` - A cell that records its most recent writes with when they happened and
+//!   which thread made them. Requires the `record` feature.
+//!
+//! ## Platform Support
+//! On targets without threads such as `wasm32-unknown-unknown`, the standard library replaces
+//! its locks with single-threaded versions, so `SyncCell` and `HeldSyncCell` compile unchanged
+//! and behave like a `RefCell`. A borrow that would block on such a target can never be released
+//! and aborts instead of deadlocking. The timeout borrows of `AsyncSyncCell` measure time on a
+//! spawned thread and are not usable there.
+//!
+//! Without the default `std` feature the crate is `#![no_std]` and only needs `alloc`.
+//! `SyncCell` and `HeldSyncCell` are then backed by `SpinRawLock`, and the types and methods
+//! that need the standard library, such as timeouts and the other cells, are left out. Panics
+//! cannot be detected without the standard library, so cells are never poisoned.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{sync::atomic::{AtomicBool, Ordering as AtomicOrdering}, cmp::Ordering, error::Error, fmt::{self, Debug, Display, Formatter}, hash::{Hash, Hasher}, mem::{replace, swap, take}, ops::{Deref, DerefMut}, panic::{RefUnwindSafe, UnwindSafe}};
+#[cfg(feature = "std")]
+use std::{sync::{Condvar, Mutex, MutexGuard}, thread, time::{Duration, Instant}};
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicUsize;
+
+use lock_result::{LockResult, PoisonError, TryLockError, TryLockResult};
+use version::VersionCounter;
+
+#[cfg(feature = "std")]
+mod undo_redo;
+#[cfg(feature = "std")]
+mod expiring;
+#[cfg(feature = "std")]
+mod debounced;
+#[cfg(feature = "std")]
+mod throttled;
+#[cfg(feature = "std")]
+mod double_buffered;
+#[cfg(feature = "std")]
+mod triple_buffer;
+#[cfg(feature = "std")]
+mod snapshot;
+#[cfg(feature = "std")]
+mod transactional;
+#[cfg(feature = "serde")]
+mod persistent;
+#[cfg(feature = "shm")]
+mod shm;
+#[cfg(feature = "std")]
+mod cond;
+#[cfg(feature = "std")]
+mod slot;
+#[cfg(feature = "std")]
+mod latch;
+#[cfg(feature = "std")]
+mod accumulator;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod sharded;
+#[cfg(feature = "std")]
+mod thread_local;
+#[cfg(feature = "std")]
+mod read_mostly;
+#[cfg(feature = "std")]
+mod cow;
+#[cfg(feature = "std")]
+mod frozen;
+#[cfg(feature = "std")]
+mod timed;
+#[cfg(feature = "std")]
+mod stat;
+#[cfg(feature = "std")]
+mod validated;
+#[cfg(feature = "std")]
+mod clamped;
+#[cfg(feature = "std")]
+mod state_machine;
+#[cfg(feature = "std")]
+mod generational;
+#[cfg(feature = "std")]
+mod priority;
+#[cfg(feature = "std")]
+mod broadcast;
+#[cfg(feature = "std")]
+mod promise;
+#[cfg(feature = "std")]
+mod any;
+#[cfg(feature = "std")]
+mod type_map;
+#[cfg(feature = "std")]
+mod dirty;
+#[cfg(feature = "std")]
+mod lease;
+#[cfg(feature = "std")]
+mod mvcc;
+#[cfg(feature = "std")]
+mod queued;
+#[cfg(feature = "std")]
+mod update_group;
+#[cfg(feature = "std")]
+mod atomic_held;
+#[cfg(feature = "std")]
+mod scheduler;
+#[cfg(feature = "std")]
+mod empty_held;
+#[cfg(feature = "async")]
+mod async_cell;
+#[cfg(feature = "async")]
+mod async_held;
+#[cfg(feature = "async")]
+mod block_on;
+#[cfg(feature = "async")]
+mod timer;
+#[cfg(feature = "tokio")]
+mod tokio_watch;
+#[cfg(feature = "futures")]
+mod sink;
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "proptest")]
+mod arbitrary;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "crossbeam")]
+mod crossbeam;
+#[cfg(feature = "serde")]
+mod serialize;
+mod raw_lock;
+#[cfg(all(feature = "std", loom))]
+mod loom_shims;
+#[cfg(all(feature = "std", shuttle))]
+mod shuttle_shims;
+#[cfg(feature = "std")]
+mod mutex;
+#[cfg(feature = "deadlock-detection")]
+mod deadlock;
+#[cfg(feature = "std")]
+mod notify;
+#[cfg(feature = "contention-stats")]
+mod contention;
+#[cfg(feature = "tracing")]
+mod trace;
+mod owner;
+mod lock_result;
+mod version;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+mod map;
+#[cfg(feature = "std")]
+mod indexed;
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "std")]
+mod vec;
+#[cfg(feature = "std")]
+mod hash_map;
+#[cfg(feature = "std")]
+mod queue;
+#[cfg(feature = "std")]
+mod stack;
+#[cfg(feature = "std")]
+mod btree_map;
+#[cfg(feature = "std")]
+mod slab;
+#[cfg(feature = "std")]
+mod ring_buffer;
+#[cfg(feature = "std")]
+mod bit_set;
+#[cfg(feature = "std")]
+mod lru;
+#[cfg(feature = "std")]
+mod cell_array;
+#[cfg(feature = "std")]
+mod string;
+#[cfg(feature = "std")]
+mod lazy_map;
+mod poison;
+#[cfg(feature = "std")]
+mod unwind;
+#[cfg(feature = "std")]
+mod optimistic;
+#[cfg(feature = "std")]
+mod transaction;
+#[cfg(feature = "std")]
+mod lock_all;
+mod watchdog;
+#[cfg(feature = "std")]
+mod hooked;
+mod padded;
+#[cfg(feature = "local-cache")]
+mod cached;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod extend;
+#[cfg(feature = "std")]
+mod iter;
+#[cfg(feature = "std")]
+mod counter;
+#[cfg(feature = "std")]
+mod flag;
+mod macros;
+mod builder;
+#[cfg(feature = "std")]
+mod projected;
+#[cfg(feature = "std")]
+mod zip;
+#[cfg(feature = "std")]
+mod mapped;
+#[cfg(feature = "record")]
+mod recorded;
+#[cfg(feature = "std")]
+mod held_box;
+
+#[cfg(feature = "std")]
+pub use undo_redo::UndoRedoCell;
+#[cfg(feature = "std")]
+pub use expiring::ExpiringCell;
+#[cfg(feature = "std")]
+pub use debounced::DebouncedCell;
+#[cfg(feature = "std")]
+pub use throttled::ThrottledCell;
+#[cfg(feature = "std")]
+pub use double_buffered::DoubleBufferedCell;
+#[cfg(feature = "std")]
+pub use triple_buffer::{TripleBufferCell, TripleBufferReader, TripleBufferWriter};
+#[cfg(feature = "std")]
+pub use snapshot::SnapshotCell;
+#[cfg(feature = "std")]
+pub use transactional::{transaction, Transaction, TransactionalCell};
+#[cfg(feature = "serde")]
+pub use persistent::PersistentCell;
+#[cfg(feature = "shm")]
+pub use shm::{ShmCell, ShmReadGuard, ShmValue, ShmWriteGuard};
+#[cfg(feature = "std")]
+pub use cond::{CondCell, CondCellGuard, CondCellRef};
+#[cfg(feature = "std")]
+pub use slot::SlotCell;
+#[cfg(feature = "std")]
+pub use latch::LatchCell;
+#[cfg(feature = "std")]
+pub use accumulator::AccumulatorCell;
+#[cfg(feature = "std")]
+pub use pool::{PoolCell, PoolGuard};
+#[cfg(feature = "std")]
+pub use sharded::ShardedCell;
+#[cfg(feature = "std")]
+pub use thread_local::ThreadLocalCell;
+#[cfg(feature = "std")]
+pub use read_mostly::{ReadMostlyCell, ReadMostlyGuard};
+#[cfg(feature = "std")]
+pub use cow::{CowCell, CowCellGuard};
+#[cfg(feature = "std")]
+pub use frozen::{FrozenCell, FrozenCellGuard, FrozenCellRef};
+#[cfg(feature = "std")]
+pub use timed::TimedCell;
+#[cfg(feature = "std")]
+pub use stat::{CellStats, StatCell};
+#[cfg(feature = "std")]
+pub use validated::{ValidatedCell, ValidatedCellGuard, ValidationPolicy};
+#[cfg(feature = "std")]
+pub use clamped::{ClampMode, ClampedCell};
+#[cfg(feature = "std")]
+pub use state_machine::StateMachineCell;
+#[cfg(feature = "std")]
+pub use generational::{Generation, GenerationalCell};
+#[cfg(feature = "std")]
+pub use priority::PriorityCell;
+#[cfg(feature = "std")]
+pub use broadcast::{BroadcastCell, BroadcastReceiver};
+#[cfg(feature = "std")]
+pub use promise::PromiseCell;
+#[cfg(feature = "async")]
+pub use promise::PromiseWait;
+#[cfg(feature = "std")]
+pub use any::{AnyCell, AnyCellGuard, AnyCellRef};
+#[cfg(feature = "std")]
+pub use type_map::TypeMapCell;
+#[cfg(feature = "std")]
+pub use dirty::DirtyCell;
+#[cfg(feature = "std")]
+pub use lease::{Lease, LeaseCell, LeaseExpired, LeaseRef};
+#[cfg(feature = "std")]
+pub use mvcc::{MvccCell, MvccReadGuard};
+#[cfg(feature = "std")]
+pub use queued::QueuedSyncCell;
+#[cfg(feature = "std")]
+pub use update_group::UpdateGroup;
+#[cfg(feature = "std")]
+pub use atomic_held::{AtomicHeldCell, AtomicHeldSetter};
+#[cfg(feature = "std")]
+pub use scheduler::UpdateScheduler;
+#[cfg(feature = "std")]
+pub use empty_held::{EmptyHeldCell, EmptyHeldCellRef};
+#[cfg(feature = "async")]
+pub use async_cell::{AsyncBorrow, AsyncBorrowMut, AsyncChanged, AsyncSyncCell, AsyncSyncCellGuard, AsyncSyncCellRef, AsyncValueStream, SyncCellBorrow, SyncCellBorrowMut};
+#[cfg(feature = "async")]
+pub use async_held::{AsyncHeldSyncCell, WaitForPending};
+#[cfg(feature = "futures")]
+pub use sink::{HeldSyncCellSink, SyncCellSink};
+#[cfg(feature = "rkyv")]
+pub use archive::{ArchivedHeldSyncCell, ArchivedSyncCell};
+pub use raw_lock::{DefaultRawLock, RawSyncCell, RawSyncCellGuard, RawSyncCellRef, RawSyncLock, SpinRawLock};
+#[cfg(feature = "std")]
+pub use raw_lock::{LockPolicy, RwLockRawLock, StdRawLock};
+#[cfg(feature = "critical-section")]
+pub use raw_lock::CriticalSectionRawLock;
+#[cfg(all(feature = "std", loom))]
+pub use loom_shims::LoomRawLock;
+#[cfg(all(feature = "std", shuttle))]
+pub use shuttle_shims::ShuttleRawLock;
+#[cfg(feature = "std")]
+pub use mutex::MutexSyncCell;
+#[cfg(feature = "deadlock-detection")]
+pub use deadlock::DetectingRawLock;
+#[cfg(feature = "std")]
+pub use notify::NotifyingRawLock;
+#[cfg(feature = "contention-stats")]
+use contention::ContentionCounters;
+use owner::LockOwner;
+pub use owner::LockHolder;
+use poison::PoisonHandler;
+#[cfg(feature = "contention-stats")]
+pub use contention::{ContentionStats, global_contention_stats};
+#[cfg(feature = "std")]
+pub use indexed::{ElementGuard, ElementRef, Elements};
+#[cfg(feature = "std")]
+pub use shared::SharedSyncCell;
+#[cfg(feature = "std")]
+pub use vec::SyncVec;
+#[cfg(feature = "std")]
+pub use hash_map::SyncHashMap;
+#[cfg(feature = "std")]
+pub use queue::SyncQueue;
+#[cfg(feature = "std")]
+pub use stack::SyncStack;
+#[cfg(feature = "std")]
+pub use btree_map::SyncBTreeMap;
+#[cfg(feature = "std")]
+pub use slab::{SlabGuard, SlabKey, SlabRef, SyncSlab};
+#[cfg(feature = "std")]
+pub use ring_buffer::{OverflowPolicy, SyncRingBuffer};
+#[cfg(feature = "std")]
+pub use bit_set::SyncBitSet;
+#[cfg(feature = "std")]
+pub use lru::SyncLruCache;
+#[cfg(feature = "std")]
+pub use cell_array::SyncCellArray;
+#[cfg(feature = "std")]
+pub use string::SyncString;
+#[cfg(feature = "std")]
+pub use lazy_map::SyncLazyMap;
+pub use poison::PoisonPolicy;
+#[cfg(feature = "std")]
+pub use transaction::TransactionGuard;
+#[cfg(feature = "std")]
+pub use lock_all::{LockCell, LockedCells, lock_all};
+pub use watchdog::{WatchdogAction, set_guard_watchdog};
+#[cfg(feature = "std")]
+pub use hooked::HookedCell;
+pub use padded::PaddedSyncCell;
+#[cfg(feature = "local-cache")]
+pub use cached::LocalCache;
+#[cfg(feature = "std")]
+pub use counter::{CounterMode, CounterValue, SyncCounter};
+#[cfg(feature = "std")]
+pub use flag::SyncFlag;
+pub use builder::SyncCellBuilder;
+/// Generates a companion struct that stores each field of a struct in its own `SyncCell`.
+/// For a struct `Name` this generates `NameCells` with a method for each field returning the cell
+/// of that field. Threads using different fields then never wait for each other, unlike a
+/// `SyncCell` holding the whole struct.
+///
+/// Only structs with named fields and no generic parameters are supported. This macro is only
+/// available with the `derive` feature.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncFields;
+///
+/// #[derive(SyncFields)]
+/// pub struct Config {
+///     pub name: String,
+///     pub retries: u32,
+/// }
+///
+/// let config = ConfigCells::new(Config {
+///     name: String::from("server"),
+///     retries: 3,
+/// });
+///
+/// config.retries().set(5);
+///
+/// assert_eq!("server", config.name().get());
+/// assert_eq!(5, config.into_inner().retries);
+/// ```
+#[cfg(feature = "derive")]
+pub use sync_cell_derive::SyncFields;
+#[cfg(feature = "std")]
+pub use projected::{ProjectedCell, ProjectedGuard, ProjectedRef};
+#[cfg(feature = "std")]
+pub use zip::{Merged, Zip, merge, zip};
+#[cfg(feature = "std")]
+pub use mapped::MappedView;
+#[cfg(feature = "record")]
+pub use recorded::{RecordedCell, RecordedGuard, WriteRecord};
+#[cfg(feature = "std")]
+pub use held_box::{HeldSyncBox, HeldSyncBoxGuard, HeldSyncBoxRef};
+
+// Lets the code generated by the derive macros refer to this crate by name within its own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as sync_cell;
 
 /// A mutable memory location that can be modified safely from multiple threads.
 /// This structure is similar to `std::cell::Cell` or `std::cell::RefCell`
 /// while being thread-safe.
-/// It functions as a thin wrapper around `std::sync::RwLock` while assuming that poisoned locks
+/// It functions as a thin wrapper around a reader-writer lock while assuming that poisoned locks
 /// indicate an unrecoverable error. This makes it more ergonomic to use than `RwLock` at the cost
 /// of some stability.
+///
+/// The lock is provided by a `RawSyncLock` backend chosen with the second type parameter, which
+/// defaults to `DefaultRawLock`. Cells with another backend are created with `with_lock`. Like
+/// `std::sync::RwLock`, a cell is poisoned when a thread panics while mutably borrowing it,
+/// whichever backend is used.
+///
+/// Releasing a borrow does not wake other threads unless the cell uses a `NotifyingRawLock`. A
+/// cell with that backend has a `wait_for` method that blocks until the value satisfies a
+/// condition and is checked again every time a mutable borrow is released.
 /// 
 /// # As a `Cell` replacement.
 /// `SyncCell` can be used to replace the functionality of a `std::cell::Cell` in contexts where
@@ -77,13 +671,37 @@ use std::{sync::{RwLock, RwLockReadGuard, RwLockWriteGuard}, cmp::Ordering, hash
 /// println!("{:?}", borrowed);
 /// ```
 ///
+/// # Debugging
+/// The value is held in a `RawSyncCell`, so its debug checks apply to every `SyncCell` and
+/// `HeldSyncCell`. In debug builds, a thread that borrows a cell in a way that would deadlock on
+/// a borrow it already holds panics instead, and `set_guard_watchdog` reports borrows that are
+/// held for too long. Deadlocks between threads are detected by creating the cell with
+/// `with_lock` and a `DetectingRawLock`.
+///
+/// With the `tracing` feature each borrow is a trace level `sync_cell_borrow` span with the
+/// label of the cell as its `cell` field and whether it is `mutable`. The span is closed when the
+/// borrow is released, so its duration is how long the lock was held, and an `acquired` event in
+/// the span records how long the borrow waited for the lock.
+///
 /// # Panicking
 /// Unlike `std::sync::RwLock`, `SyncCell` will panic rather than return an error when the lock
-/// becomes poisoned.
-#[derive(Debug)]
-pub struct SyncCell<T: ?Sized> {
-    /// The internal lock holding the data of this cell.
-    data: RwLock<T>,
+/// becomes poisoned. In debug builds, borrowing a cell that the current thread has already
+/// borrowed in a conflicting way also panics.
+pub struct SyncCell<T: ?Sized, L = DefaultRawLock> {
+    /// The statistics of borrows that had to wait for the lock.
+    #[cfg(feature = "contention-stats")]
+    contention: ContentionCounters,
+    /// Information used to explain poisoned locks.
+    owner: LockOwner,
+    /// How a poisoned lock is handled.
+    poison: PoisonHandler,
+    /// Whether a thread panicked while mutably borrowing this cell.
+    poisoned: AtomicBool,
+    /// The number of mutable borrows of this cell, used to check cached copies of its value.
+    #[cfg(feature = "local-cache")]
+    version: VersionCounter,
+    /// The value of this cell and the lock protecting it.
+    data: RawSyncCell<T, L>,
 }
 
 impl <T> SyncCell<T> {
@@ -91,9 +709,50 @@ impl <T> SyncCell<T> {
     ///
     /// - `data` - The initial value of the `SyncCell`.
     pub const fn new(data: T) -> Self {
-        Self {
-            data: RwLock::new(data)
-        }
+        SyncCellBuilder::new().build(data)
+    }
+
+    /// Creates a new `SyncCell` with a label that identifies it in panic messages.
+    /// In debug builds with the `owner-tracking` feature, the message of a poisoned lock names
+    /// the cell and the thread that poisoned it. With the `tracing` feature the label also names
+    /// the spans of borrows of the cell. Labels are otherwise discarded.
+    ///
+    /// - `data` - The initial value of the `SyncCell`.
+    /// - `label` - The label of the cell, such as where it was created.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// static CONFIG: SyncCell<u32> = SyncCell::with_label(0, "CONFIG");
+    ///
+    /// CONFIG.set(1);
+    /// assert_eq!(1, CONFIG.get());
+    /// ```
+    pub const fn with_label(data: T, label: &'static str) -> Self {
+        SyncCellBuilder::new().label(label).build(data)
+    }
+}
+
+impl <T, L: RawSyncLock> SyncCell<T, L> {
+    /// Creates a new `SyncCell` protected by an existing lock.
+    /// This chooses the backend of the cell, including backends that are configured at
+    /// construction, such as a `StdRawLock` with a `LockPolicy`.
+    ///
+    /// - `data` - The initial value of the `SyncCell`.
+    /// - `lock` - The unlocked lock protecting the value.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::{RawSyncLock, SpinRawLock, SyncCell};
+    ///
+    /// static HITS: SyncCell<u32, SpinRawLock> = SyncCell::with_lock(0, SpinRawLock::INIT);
+    ///
+    /// *HITS.borrow_mut() += 1;
+    /// assert_eq!(1, HITS.get());
+    /// ```
+    pub const fn with_lock(data: T, lock: L) -> Self {
+        SyncCellBuilder::new().build_with_lock(data, lock)
     }
 
     /// Sets the value contained in this cell.
@@ -103,9 +762,9 @@ impl <T> SyncCell<T> {
     /// # Panicking
     /// This method will panic if the lock becomes poisoned.
     pub fn set(&self, value: T) {
-        match self.data.write() {
+        match self.write_data() {
             Ok(mut data) => *data = value,
-            Err(err) => panic!("Failed to set cell value. Lock was poisoned: {}", err),
+            Err(err) => self.owner.poisoned("set", err),
         }
     }
 
@@ -114,9 +773,19 @@ impl <T> SyncCell<T> {
     /// # Panicking
     /// This method will panic if the lock becomes poisoned.
     pub fn into_inner(self) -> T {
-        match self.data.into_inner() {
-            Ok(data) => data,
-            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        let mut data = self.data.into_inner();
+
+        if !self.poisoned.into_inner() {
+            return data;
+        }
+
+        match self.poison {
+            PoisonHandler::Ignore => data,
+            handler @ PoisonHandler::Reset(_) => {
+                handler.reset(&mut data);
+                data
+            },
+            PoisonHandler::Panic => self.owner.poisoned("get", PoisonError::new(data)),
         }
     }
 
@@ -128,25 +797,37 @@ impl <T> SyncCell<T> {
     /// # Panicking
     /// This method will panic if the lock becomes poisoned.
     pub fn replace(&self, mut value: T) -> T {
-        match self.data.write() {
+        match self.write_data() {
             Ok(mut data) => {
                 swap(&mut *data, &mut value);
                 value
             },
-            Err(err) => panic!("Failed to set cell value. Lock was poisoned: {}", err),
+            Err(err) => self.owner.poisoned("set", err),
         }
     }
+
+    /// Converts this cell into a `FrozenCell` that has already been frozen.
+    /// Reads of the frozen cell do not take a lock and writes are rejected.
+    ///
+    /// This method is only available with the `std` feature.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    #[cfg(feature = "std")]
+    pub fn freeze(self) -> FrozenCell<T> {
+        FrozenCell::new_frozen(self.into_inner())
+    }
 }
 
-impl <T: ?Sized> SyncCell<T> {
+impl <T: ?Sized, L: RawSyncLock> SyncCell<T, L> {
     /// Borrows a immutable reference to the data stored in this cell.
     ///
     /// # Panicking
     /// This method will panic if the lock becomes poisoned.
-    pub fn borrow(&self) -> RwLockReadGuard<T> {
-        match self.data.read() {
+    pub fn borrow(&self) -> SyncCellRef<'_, T, L> {
+        match self.read_data() {
             Ok(data) => data,
-            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+            Err(err) => self.owner.poisoned("get", err),
         }
     }
     
@@ -154,72 +835,468 @@ impl <T: ?Sized> SyncCell<T> {
     ///
     /// # Panicking
     /// This method will panic if the lock becomes poisoned.
-    pub fn borrow_mut(&self) -> RwLockWriteGuard<T> {
-        match self.data.write() {
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T, L> {
+        match self.write_data() {
             Ok(data) => data,
-            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+            Err(err) => self.owner.poisoned("get", err),
+        }
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell without blocking.
+    /// This fails if the value is mutably borrowed or the lock is poisoned.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::{SyncCell, SyncCellError};
+    ///
+    /// let cell = SyncCell::new(1);
+    /// let guard = cell.borrow_mut();
+    ///
+    /// assert!(matches!(cell.try_borrow(), Err(SyncCellError::WouldBlock(_))));
+    /// drop(guard);
+    /// assert_eq!(1, *cell.try_borrow().unwrap());
+    /// ```
+    pub fn try_borrow(&self) -> Result<SyncCellRef<'_, T, L>, SyncCellError> {
+        match self.try_read_data() {
+            Err(TryLockError::Poisoned(err)) => Ok(self.recover_read(Err(err))?),
+            result => result.map_err(|err| self.lock_error(err)),
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell without blocking.
+    /// This fails if the value is borrowed or the lock is poisoned.
+    pub fn try_borrow_mut(&self) -> Result<SyncCellGuard<'_, T, L>, SyncCellError> {
+        let data = match self.try_write_data() {
+            Err(TryLockError::Poisoned(err)) => self.recover_write(Err(err))?,
+            result => result.map_err(|err| self.lock_error(err))?,
+        };
+        self.bump_version();
+
+        Ok(data)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell, blocking for at most
+    /// `timeout`.
+    /// This fails with `SyncCellError::Timeout` if the value is still mutably borrowed once the
+    /// timeout has elapsed.
+    ///
+    /// This method is only available with the `std` feature.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    #[cfg(feature = "std")]
+    pub fn borrow_timeout(&self, timeout: Duration) -> Result<SyncCellRef<'_, T, L>, SyncCellError> {
+        retry_timeout(timeout, || self.try_borrow())
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell, blocking for at most
+    /// `timeout`.
+    /// This fails with `SyncCellError::Timeout` if the value is still borrowed once the timeout
+    /// has elapsed.
+    ///
+    /// This method is only available with the `std` feature.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    #[cfg(feature = "std")]
+    pub fn borrow_mut_timeout(&self, timeout: Duration) -> Result<SyncCellGuard<'_, T, L>, SyncCellError> {
+        retry_timeout(timeout, || self.try_borrow_mut())
+    }
+
+    /// Gets a raw pointer to the value of this cell.
+    /// This allows the value to be handed to foreign code, such as a C callback that receives a
+    /// `void*` context. Creating the pointer does not lock the cell, and the address stays the
+    /// same for the lifetime of the cell.
+    ///
+    /// Dereferencing the pointer is only sound while the caller holds a borrow of this cell that
+    /// permits the access: a `SyncCellRef` for reads or a `SyncCellGuard` for writes.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(1);
+    /// let pointer = cell.data_ptr();
+    ///
+    /// let borrowed = cell.borrow_mut();
+    /// // SAFETY: The mutable borrow is held while the value is written.
+    /// unsafe { *pointer = 2 };
+    /// drop(borrowed);
+    ///
+    /// assert_eq!(2, cell.get());
+    /// ```
+    pub fn data_ptr(&self) -> *mut T {
+        self.data.data_ptr()
+    }
+
+    /// Gets statistics about how often borrows of this cell had to wait for the lock.
+    /// This method is only available with the `contention-stats` feature.
+    #[cfg(feature = "contention-stats")]
+    pub fn contention_stats(&self) -> ContentionStats {
+        self.contention.stats()
+    }
+
+    /// Takes a read lock of the data of this cell.
+    fn read_data(&self) -> LockResult<SyncCellRef<'_, T, L>> {
+        #[cfg(feature = "tracing")]
+        let start = Instant::now();
+
+        #[cfg(feature = "contention-stats")]
+        let result = self.contention.acquire(|| self.try_read_data(), || self.read_lock());
+
+        #[cfg(not(feature = "contention-stats"))]
+        let result = self.read_lock();
+
+        #[cfg(feature = "tracing")]
+        if let Ok(data) = &result {
+            trace::acquired(&data.span, start.elapsed());
+        }
+
+        self.recover_read(result)
+    }
+
+    /// Takes a write lock of the data of this cell.
+    fn write_data(&self) -> LockResult<SyncCellGuard<'_, T, L>> {
+        #[cfg(feature = "tracing")]
+        let start = Instant::now();
+
+        #[cfg(feature = "contention-stats")]
+        let result = self.contention.acquire(|| self.try_write_data(), || self.write_lock());
+
+        #[cfg(not(feature = "contention-stats"))]
+        let result = self.write_lock();
+
+        #[cfg(feature = "tracing")]
+        if let Ok(data) = &result {
+            trace::acquired(&data.span, start.elapsed());
+        }
+
+        let result = self.recover_write(result);
+
+        if result.is_ok() {
+            self.bump_version();
+        }
+
+        result
+    }
+
+    /// Takes a read lock of the data of this cell without applying the poison policy.
+    pub(crate) fn read_lock(&self) -> LockResult<SyncCellRef<'_, T, L>> {
+        self.check_poison(SyncCellRef {
+            data: self.data.borrow(),
+            #[cfg(feature = "tracing")]
+            span: self.owner.span(false),
+        })
+    }
+
+    /// Takes a write lock of the data of this cell without applying the poison policy.
+    pub(crate) fn write_lock(&self) -> LockResult<SyncCellGuard<'_, T, L>> {
+        self.check_poison(SyncCellGuard::new(self.data.borrow_mut(), &self.poisoned, &self.owner))
+    }
+
+    /// Attempts to take a read lock of the data of this cell without blocking or applying the
+    /// poison policy.
+    pub(crate) fn try_read_data(&self) -> TryLockResult<SyncCellRef<'_, T, L>> {
+        match self.data.try_borrow() {
+            Some(data) => Ok(self.check_poison(SyncCellRef {
+                data,
+                #[cfg(feature = "tracing")]
+                span: self.owner.span(false),
+            })?),
+            None => Err(TryLockError::WouldBlock),
+        }
+    }
+
+    /// Attempts to take a write lock of the data of this cell without blocking or applying the
+    /// poison policy.
+    pub(crate) fn try_write_data(&self) -> TryLockResult<SyncCellGuard<'_, T, L>> {
+        match self.data.try_borrow_mut() {
+            Some(data) => Ok(self.check_poison(SyncCellGuard::new(data, &self.poisoned, &self.owner))?),
+            None => Err(TryLockError::WouldBlock),
+        }
+    }
+
+    /// Converts an error from trying to lock this cell into a `SyncCellError`.
+    /// If the lock is contended, the error names the thread holding a mutable borrow of the cell.
+    ///
+    /// - `err` - The error returned by the lock.
+    pub(crate) fn lock_error<G>(&self, err: TryLockError<G>) -> SyncCellError {
+        match err {
+            TryLockError::WouldBlock => SyncCellError::WouldBlock(self.owner.holder()),
+            TryLockError::Poisoned(_) => SyncCellError::Poisoned,
         }
     }
+
+    /// Wraps a borrow in an error if this cell is poisoned.
+    fn check_poison<G>(&self, guard: G) -> LockResult<G> {
+        match self.poisoned.load(AtomicOrdering::Acquire) {
+            true => Err(PoisonError::new(guard)),
+            false => Ok(guard),
+        }
+    }
+
+    /// Records that the value of this cell may be about to change.
+    /// This must be called while holding the write lock. Versions are only counted with the
+    /// `local-cache` feature.
+    pub(crate) fn bump_version(&self) {
+        #[cfg(feature = "local-cache")]
+        self.version.increment(AtomicOrdering::Release);
+    }
+
+    /// Clears the poisoning of this cell.
+    pub(crate) fn clear_poison(&self) {
+        self.poisoned.store(false, AtomicOrdering::Release);
+    }
 }
 
-impl <T: Clone> SyncCell<T> {
+impl <T: Clone, L: RawSyncLock> SyncCell<T, L> {
     /// Gets the value contained in this cell.
     ///
     /// # Panicking
     /// This method will panic if the lock becomes poisoned.
     pub fn get(&self) -> T {
-        match self.data.read() {
+        match self.read_data() {
             Ok(data) => data.clone(),
-            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+            Err(err) => self.owner.poisoned("get", err),
         }
     }
 }
 
-impl <T: Clone> Clone for SyncCell<T> {
+impl <I: Iterator + ?Sized, L: RawSyncLock> SyncCell<I, L> {
+    /// Advances the iterator in this cell and returns the next item.
+    /// The lock is only held while the iterator is advanced, so the cell can serve as a simple
+    /// work queue shared by several threads.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::{sync::Arc, thread};
+    /// use sync_cell::SyncCell;
+    ///
+    /// let jobs = Arc::new(SyncCell::new(0..100));
+    ///
+    /// let workers: Vec<_> = (0..4).map(|_| {
+    ///     let jobs = jobs.clone();
+    ///     thread::spawn(move || {
+    ///         let mut done = 0;
+    ///
+    ///         while let Some(_job) = jobs.next_item() {
+    ///             done += 1;
+    ///         }
+    ///
+    ///         done
+    ///     })
+    /// }).collect();
+    ///
+    /// let done: i32 = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+    /// assert_eq!(100, done);
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn next_item(&self) -> Option<I::Item> {
+        self.borrow_mut().next()
+    }
+}
+
+// A thread that panics while mutably borrowing a cell poisons it, like `std::sync::RwLock`.
+impl <T: ?Sized, L> UnwindSafe for SyncCell<T, L> {}
+impl <T: ?Sized, L> RefUnwindSafe for SyncCell<T, L> {}
+
+impl <T: Clone, L: RawSyncLock> Clone for SyncCell<T, L> {
     fn clone(&self) -> Self {
-        Self::new(self.get())
+        Self::with_lock(self.get(), L::INIT)
     }
 }
 
-impl <T: Default> Default for SyncCell<T> {
+impl <T: Default, L: RawSyncLock> Default for SyncCell<T, L> {
     fn default() -> Self {
-        Self::new(T::default())
+        Self::with_lock(T::default(), L::INIT)
     }
 }
 
-impl <T: PartialEq + ?Sized> PartialEq for SyncCell<T> {
+impl <T: PartialEq + ?Sized, L: RawSyncLock> PartialEq for SyncCell<T, L> {
     fn eq(&self, other: &Self) -> bool {
         self.borrow().eq(&*other.borrow())
     }
 }
 
-impl <T: Eq + ?Sized> Eq for SyncCell<T> {
+impl <T: Eq + ?Sized, L: RawSyncLock> Eq for SyncCell<T, L> {
+}
+
+impl <T: PartialEq + ?Sized, L: RawSyncLock> PartialEq<T> for SyncCell<T, L> {
+    /// Compares the value of this cell with a plain value.
+    fn eq(&self, other: &T) -> bool {
+        self.borrow().eq(other)
+    }
 }
 
-impl <T: PartialOrd + ?Sized> PartialOrd for SyncCell<T> {
+impl <T: PartialOrd + ?Sized, L: RawSyncLock> PartialOrd for SyncCell<T, L> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.borrow().partial_cmp(&*other.borrow())
     }
 }
 
-impl <T: Ord + ?Sized> Ord for SyncCell<T> {
+impl <T: Ord + ?Sized, L: RawSyncLock> Ord for SyncCell<T, L> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.borrow().cmp(&*other.borrow())
     }
 }
 
-impl <T: Hash + ?Sized> Hash for SyncCell<T> {
+impl <T: Hash + ?Sized, L: RawSyncLock> Hash for SyncCell<T, L> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.borrow().hash(state)
     }
 }
 
+impl <T: ?Sized + Debug, L: RawSyncLock> Debug for SyncCell<T, L> {
+    /// Formats the value of this cell.
+    /// A value that is locked for writing is not waited for and is shown as `<locked>`, so a
+    /// value can be formatted while its own cell is mutably borrowed.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("SyncCell");
+
+        match self.try_read_data() {
+            Ok(data) => debug.field("data", &&*data),
+            Err(TryLockError::Poisoned(err)) => debug.field("data", &&**err.get_ref()),
+            Err(TryLockError::WouldBlock) => debug.field("data", &format_args!("<locked>")),
+        };
+
+        debug.finish_non_exhaustive()
+    }
+}
+
 impl <T> From<T> for SyncCell<T> {
     fn from(value: T) -> Self {
         Self::new(value)
     }
 }
 
+impl <A, C: FromIterator<A>, L: RawSyncLock> FromIterator<A> for SyncCell<C, L> {
+    /// Collects the values into a collection stored in a new `SyncCell`.
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+        Self::with_lock(iter.into_iter().collect(), L::INIT)
+    }
+}
+
+/// An immutable borrow of the value of a `SyncCell`.
+pub struct SyncCellRef<'a, T: ?Sized, L: RawSyncLock = DefaultRawLock> {
+    /// The borrow of the value and its lock.
+    data: RawSyncCellRef<'a, T, L>,
+    /// The span of this borrow, which is closed after the lock is released.
+    #[cfg(feature = "tracing")]
+    span: trace::BorrowSpan,
+}
+
+impl <T: ?Sized, L: RawSyncLock> Deref for SyncCellRef<'_, T, L> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl <T: ?Sized + Debug, L: RawSyncLock> Debug for SyncCellRef<'_, T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl <T: ?Sized + Display, L: RawSyncLock> Display for SyncCellRef<'_, T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+/// A mutable borrow of the value of a `SyncCell`.
+/// The cell is poisoned if the thread holding this borrow panics.
+pub struct SyncCellGuard<'a, T: ?Sized, L: RawSyncLock = DefaultRawLock> {
+    /// The borrow of the value and its lock.
+    data: RawSyncCellGuard<'a, T, L>,
+    /// The poisoning of the borrowed cell.
+    poisoned: &'a AtomicBool,
+    /// The owner information of the borrowed cell.
+    owner: &'a LockOwner,
+    /// Whether the thread was already panicking when the borrow was taken, in which case the cell
+    /// is not poisoned when it is released.
+    panicking: bool,
+    /// The span of this borrow, which is closed after the lock is released.
+    #[cfg(feature = "tracing")]
+    span: trace::BorrowSpan,
+}
+
+impl <'a, T: ?Sized, L: RawSyncLock> SyncCellGuard<'a, T, L> {
+    /// Creates a mutable borrow of a `SyncCell`.
+    ///
+    /// - `data` - The borrow of the value and its lock.
+    /// - `poisoned` - The poisoning of the borrowed cell.
+    /// - `owner` - The owner information of the borrowed cell.
+    fn new(data: RawSyncCellGuard<'a, T, L>, poisoned: &'a AtomicBool, owner: &'a LockOwner) -> Self {
+        owner.acquire();
+
+        Self {
+            data,
+            poisoned,
+            owner,
+            panicking: panicking(),
+            #[cfg(feature = "tracing")]
+            span: owner.span(true),
+        }
+    }
+}
+
+/// Checks if the current thread is panicking.
+/// Without the `std` feature a panic cannot be detected while unwinding, so this is always `false`
+/// and cells are never poisoned.
+#[cfg(feature = "std")]
+fn panicking() -> bool {
+    thread::panicking()
+}
+
+/// Checks if the current thread is panicking.
+/// Without the `std` feature a panic cannot be detected while unwinding, so this is always `false`
+/// and cells are never poisoned.
+#[cfg(not(feature = "std"))]
+fn panicking() -> bool {
+    false
+}
+
+impl <T: ?Sized, L: RawSyncLock> Deref for SyncCellGuard<'_, T, L> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> DerefMut for SyncCellGuard<'_, T, L> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> Drop for SyncCellGuard<'_, T, L> {
+    fn drop(&mut self) {
+        // The lock is released after this, so the next borrow sees the poisoning.
+        let poisoned = !self.panicking && panicking();
+
+        if poisoned {
+            self.poisoned.store(true, AtomicOrdering::Release);
+        }
+
+        self.owner.release(poisoned);
+    }
+}
+
+impl <T: ?Sized + Debug, L: RawSyncLock> Debug for SyncCellGuard<'_, T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl <T: ?Sized + Display, L: RawSyncLock> Display for SyncCellGuard<'_, T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
 /// A cell that holds a value until any changes made are applied by use of the `update` method.
 /// Getting the value or obtaining a reference to the value in this cell will return the value
 /// immediately following the last call to `update`. This allows for mutably altering a value while
@@ -227,6 +1304,9 @@ impl <T> From<T> for SyncCell<T> {
 /// This is useful when you want a method in a structure to be able to modify the structure it is
 /// being called from such as when changing the scene in a game engine.
 ///
+/// The current and incoming values are stored inline, so they must be sized. To hold unsized
+/// values such as trait objects use `HeldSyncBox` instead, which boxes both.
+///
 /// # Usage
 /// ```
 /// use sync_cell::HeldSyncCell;
@@ -245,390 +1325,2336 @@ impl <T> From<T> for SyncCell<T> {
 pub struct HeldSyncCell<T> {
     /// The current value that is made available.
     current_value: SyncCell<T>,
+    /// The incoming value and everything else that changes when values are set.
+    staged: SyncCell<Staged<T>>,
+    /// The function combining a newly set value with an incoming value that has not been applied.
+    merge: Option<MergeFn<T>>,
+    /// The functions called when an incoming value is applied.
+    listeners: SyncCell<Listeners<T>>,
+    /// When incoming values are applied without calling `update`.
+    policy: UpdatePolicy,
+    /// The number of times a value has been set, applied or discarded.
+    /// This is only changed while the incoming value is locked.
+    version: VersionCounter,
+    /// The number of threads waiting for an update.
+    /// Setting a value only notifies `staged_signal` while this is not zero.
+    #[cfg(feature = "std")]
+    staged_waiters: AtomicUsize,
+    /// The number of values set or functions deferred while a thread was waiting, used to wake
+    /// threads waiting for an update.
+    #[cfg(feature = "std")]
+    staged_count: Mutex<u64>,
+    /// The condition variable notified when a value is set or a function is deferred while a
+    /// thread is waiting.
+    #[cfg(feature = "std")]
+    staged_signal: Condvar,
+}
+
+/// The state of a `HeldSyncCell` that changes when values are set.
+/// This is kept behind a single lock so that setting a value only takes one lock and updates see
+/// a consistent incoming state.
+/// The current value is kept behind its own lock so that values can still be set while it is
+/// borrowed.
+struct Staged<T> {
     /// The value to use next.
-    next_value: SyncCell<Option<T>>,
+    value: Option<T>,
+    /// The functions to apply to the current value on the next update.
+    deferred: Deferred<T>,
+    /// The number of values set since the last update.
+    sets: usize,
+    /// The instant the first value was set after the last update.
+    #[cfg(feature = "std")]
+    first_set_at: Option<Instant>,
+    /// A previous value kept by `update_swap` so that its allocation can be reused.
+    spare: Option<T>,
 }
 
-impl <T> HeldSyncCell<T> {
-    /// Creates a new `HeldSyncCell`.
-    ///
-    /// - `data` - The initial value of the `HeldSyncCell`.
-    pub const fn new(data: T) -> Self {
-        Self {
-            current_value: SyncCell::new(data),
-            next_value: SyncCell::new(None),
-        }
+impl <T> Staged<T> {
+    /// Checks if an incoming value or deferred function is waiting to be applied.
+    fn has_update(&self) -> bool {
+        self.value.is_some() || !self.deferred.is_empty()
     }
 
-    /// Sets the value contained in this cell.
-    /// This value will only become available once the `update` method is called.
-    /// 
-    /// In the case that multiple threads call this method simultaniously,
-    /// the order in which the calls are processed is not defined. However, the final result will
-    /// be the value specified by one of the method calls.
-    ///
-    /// - `value` - The new value of the cell.
-    ///
-    /// # Panicking
-    /// This method will panic if any of the locks become poisoned.
-    pub fn set(&self, value: T) {
-        self.next_value.set(Some(value))
+    /// Gets the functions to apply to the current value on the next update.
+    fn deferred(&mut self) -> &mut Vec<DeferredFn<T>> {
+        &mut self.deferred.functions
     }
+}
 
-    /// Retrieves the inner value stored in this `HeldSyncCell`. 
-    /// This will return the most up-to-date value even if `update` has not been called.
-    ///
-    /// # Panicking
-    /// This method will panic if any of the locks become poisoned.
-    pub fn into_inner(self) -> T {
-        self.next_value.into_inner()
-            .unwrap_or(self.current_value.into_inner())
-    }
+/// The functions deferred on a `HeldSyncCell`.
+/// The functions are only `Send`, so they can only be reached through a mutable reference.
+struct Deferred<T> {
+    /// The functions to apply to the current value on the next update.
+    functions: Vec<DeferredFn<T>>,
+}
 
-    /// Borrows a immutable reference to the data stored in this cell.
-    /// This is a reference to the current value of the cell.
-    ///
-    /// # Panicking
-    /// This method will panic if any of the locks become poisoned.
-    pub fn borrow(&self) -> RwLockReadGuard<T> {
-        self.current_value.borrow()
-    }
-    
-    /// Borrows a mutable reference to the data stored in this cell.
-    /// This is a reference to the current value of the cell not the incoming value. Any changes to
-    /// the value will update the current value.
-    ///
-    /// # Panicking
-    /// This method will panic if any of the locks become poisoned.
-    pub fn borrow_mut(&self) -> RwLockWriteGuard<T> {
-        self.current_value.borrow_mut()
+impl <T> Deferred<T> {
+    /// Checks if there are no functions waiting to be applied.
+    fn is_empty(&self) -> bool {
+        self.functions.is_empty()
     }
+}
 
-    /// Checks if a new nalue is available that can be applied by calling `update`.
-    ///
-    /// # Panicking
-    /// This method will panic if any of the locks become poisoned.
-    pub fn has_update(&self) -> bool {
-        self.next_value.borrow().is_some()
-    }
+// SAFETY: A shared reference only gives access to the number of functions, so no function is
+// ever shared between threads. Functions are only taken or called through a mutable reference.
+unsafe impl <T> Sync for Deferred<T> {}
+
+/// When a `HeldSyncCell` applies its incoming value without `update` being called.
+/// Policies are checked lazily whenever the current value of the cell is accessed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UpdatePolicy {
+    /// Incoming values are only applied by calling `update`.
+    #[default]
+    Manual,
+    /// The incoming value is applied once at least this many values have been set since the last
+    /// update.
+    AfterSets(usize),
+    /// The incoming value is applied once this much time has passed since the first value was set
+    /// after the last update.
+    /// This policy is only available with the `std` feature.
+    #[cfg(feature = "std")]
+    AfterDelay(Duration),
+}
 
-    /// Updates the internal value of this cell.
-    /// This involves replacing the current value with the incoming value if it is available.
-    ///
-    /// # Panicking
-    /// This method will panic if any of the locks become poisoned.
-    pub fn update(&self) {
-        if let Some(next) = self.next_value.replace(None) {
-            self.current_value.set(next);
-        }
-    } 
+/// The result of `HeldSyncCell::update_if`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpdateOutcome {
+    /// The incoming value was applied.
+    Applied,
+    /// The condition rejected the incoming value, which is kept so that a later update can apply
+    /// it. It can be discarded with `HeldSyncCell::cancel_update`.
+    Rejected,
+    /// No incoming value was waiting to be applied.
+    NoUpdate,
 }
 
-impl <T: Clone> HeldSyncCell<T> {
-    /// Gets the value contained in this cell.
-    ///
-    /// # Panicking
-    /// This method will panic if any of the locks become poisoned.
-    pub fn get(&self) -> T {
-        self.current_value.get()
+impl UpdateOutcome {
+    /// Checks if the incoming value was applied.
+    pub fn is_applied(self) -> bool {
+        self == Self::Applied
     }
 }
 
-impl <T: Clone> Clone for HeldSyncCell<T> {
-    fn clone(&self) -> Self {
-        Self::new(self.get())
-    }
+/// A function merging a newly set value into the incoming value of a `HeldSyncCell`.
+type MergeFn<T> = Arc<dyn Fn(&mut T, T) + Send + Sync>;
+
+/// A function mutating the current value of a `HeldSyncCell` when it is updated.
+type DeferredFn<T> = Box<dyn FnOnce(&mut T) + Send>;
+
+/// A function called with the previous and the new value when a `HeldSyncCell` is updated.
+type ListenerFn<T> = Box<dyn FnMut(&T, &T) + Send + Sync>;
+
+/// The listeners registered on a `HeldSyncCell`.
+struct Listeners<T> {
+    /// The identifier given to the next listener.
+    next_id: u64,
+    /// The registered listeners in the order they were registered.
+    list: Vec<(u64, ListenerFn<T>)>,
 }
 
-impl <T: Default> Default for HeldSyncCell<T> {
-    fn default() -> Self {
-        Self::new(T::default())
+/// A handle identifying a listener registered on a `HeldSyncCell` that can be used to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+/// The error returned when a versioned set on a `HeldSyncCell` is based on an outdated version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VersionMismatch {
+    /// The version the writer expected the cell to be at.
+    pub expected: u64,
+    /// The version the cell was actually at.
+    pub actual: u64,
+}
+
+impl Display for VersionMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected cell version {} but found version {}", self.expected, self.actual)
     }
 }
 
-impl <T: PartialEq> PartialEq for HeldSyncCell<T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.borrow().eq(&*other.borrow())
+impl Error for VersionMismatch {}
+
+/// The error returned by the fallible methods of `SyncCell`.
+/// This collects the errors of the crate into one type so that they can be passed on with `?`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyncCellError {
+    /// The lock was poisoned by a thread that panicked while holding it.
+    Poisoned,
+    /// The lock is held by another borrow.
+    /// In debug builds with the `owner-tracking` feature, this holds the thread with a mutable
+    /// borrow of the cell if there is one.
+    WouldBlock(Option<LockHolder>),
+    /// The lock was still held by another borrow once the timeout had elapsed.
+    /// In debug builds with the `owner-tracking` feature, this holds the thread with a mutable
+    /// borrow of the cell if there is one.
+    Timeout(Option<LockHolder>),
+    /// The operation was rejected because the cell was not in a valid state for it, such as a
+    /// version mismatch or an expired lease.
+    Invalid,
+}
+
+impl Display for SyncCellError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Poisoned => write!(f, "Lock was poisoned"),
+            Self::WouldBlock(Some(holder)) => write!(f, "Cell is already borrowed mutably by {}", holder),
+            Self::WouldBlock(None) => write!(f, "Cell is already borrowed"),
+            Self::Timeout(Some(holder)) => write!(f, "Timed out waiting for {} to release the cell", holder),
+            Self::Timeout(None) => write!(f, "Timed out waiting for the cell to be released"),
+            Self::Invalid => write!(f, "Cell was not in a valid state for the operation"),
+        }
+    }
+}
+
+impl Error for SyncCellError {}
+
+impl <G> From<TryLockError<G>> for SyncCellError {
+    fn from(err: TryLockError<G>) -> Self {
+        match err {
+            TryLockError::WouldBlock => Self::WouldBlock(None),
+            TryLockError::Poisoned(_) => Self::Poisoned,
+        }
+    }
+}
+
+impl <G> From<PoisonError<G>> for SyncCellError {
+    fn from(_: PoisonError<G>) -> Self {
+        Self::Poisoned
+    }
+}
+
+impl From<VersionMismatch> for SyncCellError {
+    fn from(_: VersionMismatch) -> Self {
+        Self::Invalid
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<LeaseExpired> for SyncCellError {
+    fn from(_: LeaseExpired) -> Self {
+        Self::Invalid
+    }
+}
+
+/// The shortest delay before a contended borrow of a `SyncCell` is retried.
+#[cfg(feature = "std")]
+pub(crate) const MIN_BACKOFF: Duration = Duration::from_micros(50);
+
+/// The longest delay before a contended borrow of a `SyncCell` is retried.
+#[cfg(feature = "std")]
+pub(crate) const MAX_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Retries a non-blocking borrow until it succeeds, fails for a reason other than blocking or the
+/// timeout elapses.
+/// `SyncCellError::Timeout` is returned if the borrow would still block once the timeout has
+/// elapsed.
+///
+/// Releasing a lock does not notify anyone, so after yielding once the thread sleeps between
+/// retries for a delay that doubles with every attempt instead of spinning.
+#[cfg(feature = "std")]
+fn retry_timeout<R>(timeout: Duration, mut f: impl FnMut() -> Result<R, SyncCellError>) -> Result<R, SyncCellError> {
+    // A timeout too long to represent never elapses.
+    let deadline = Instant::now().checked_add(timeout);
+    let mut delay = Duration::ZERO;
+
+    loop {
+        match f() {
+            Err(SyncCellError::WouldBlock(holder)) => {
+                let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+                if remaining.is_some_and(|remaining| remaining.is_zero()) {
+                    return Err(SyncCellError::Timeout(holder));
+                }
+
+                if delay.is_zero() {
+                    thread::yield_now();
+                    delay = MIN_BACKOFF;
+                } else {
+                    thread::sleep(remaining.map_or(delay, |remaining| delay.min(remaining)));
+                    delay = (delay * 2).min(MAX_BACKOFF);
+                }
+            },
+            result => return result,
+        }
     }
 }
 
-impl <T: Eq> Eq for HeldSyncCell<T> {
-}
+impl <T> HeldSyncCell<T> {
+    /// Creates a new `HeldSyncCell`.
+    ///
+    /// - `data` - The initial value of the `HeldSyncCell`.
+    pub const fn new(data: T) -> Self {
+        Self::create(data, None, UpdatePolicy::Manual)
+    }
+
+    /// Creates a new `HeldSyncCell` with an incoming value that has not been applied yet.
+    /// This allows a cell to be restored along with any staged value, such as when loading a
+    /// saved state.
+    ///
+    /// - `current` - The current value of the `HeldSyncCell`.
+    /// - `pending` - The incoming value that will be applied by the next call to `update`.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::from_parts(0, Some(1));
+    /// assert_eq!(0, cell.get());
+    ///
+    /// cell.update();
+    /// assert_eq!(1, cell.get());
+    /// ```
+    pub fn from_parts(current: T, pending: Option<T>) -> Self {
+        let cell = Self::new(current);
+
+        if let Some(pending) = pending {
+            cell.set(pending);
+        }
+
+        cell
+    }
+
+    /// Creates a new `HeldSyncCell` that applies incoming values automatically according to a
+    /// policy.
+    /// The policy is checked whenever the current value is accessed, so a borrow of the current
+    /// value must not be held while the cell is accessed again on the same thread.
+    ///
+    /// - `data` - The initial value of the `HeldSyncCell`.
+    /// - `policy` - When incoming values are applied without calling `update`.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::{HeldSyncCell, UpdatePolicy};
+    ///
+    /// let cell = HeldSyncCell::with_update_policy(0, UpdatePolicy::AfterSets(2));
+    ///
+    /// cell.set(1);
+    /// assert_eq!(0, cell.get());
+    ///
+    /// cell.set(2);
+    /// assert_eq!(2, cell.get());
+    /// ```
+    pub const fn with_update_policy(data: T, policy: UpdatePolicy) -> Self {
+        Self::create(data, None, policy)
+    }
+
+    /// Creates a new `HeldSyncCell` that merges values set before `update` is called instead of
+    /// keeping only the last one.
+    /// Values set with `set`, `try_set`, `set_with` and `set_versioned` are merged. `set_if_absent`
+    /// only sets a value when none is incoming, so it has nothing to merge with, and
+    /// `borrow_next_mut` changes the incoming value in place.
+    ///
+    /// - `data` - The initial value of the `HeldSyncCell`.
+    /// - `merge` - A function merging a newly set value into the incoming value that has not been
+    ///   applied yet.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::with_merge(0, |pending, incoming| *pending += incoming);
+    ///
+    /// cell.set(1);
+    /// cell.set(2);
+    /// cell.update();
+    ///
+    /// assert_eq!(3, cell.get());
+    /// ```
+    pub fn with_merge(data: T, merge: impl Fn(&mut T, T) + Send + Sync + 'static) -> Self {
+        Self::create(data, Some(Arc::new(merge)), UpdatePolicy::Manual)
+    }
+
+    /// Creates a new `HeldSyncCell` with an optional merge function and an update policy.
+    const fn create(data: T, merge: Option<MergeFn<T>>, policy: UpdatePolicy) -> Self {
+        Self {
+            current_value: SyncCell::new(data),
+            staged: SyncCell::new(Staged {
+                value: None,
+                deferred: Deferred {
+                    functions: Vec::new(),
+                },
+                sets: 0,
+                #[cfg(feature = "std")]
+                first_set_at: None,
+                spare: None,
+            }),
+            merge,
+            listeners: SyncCell::new(Listeners {
+                next_id: 0,
+                list: Vec::new(),
+            }),
+            policy,
+            version: VersionCounter::new(),
+            #[cfg(feature = "std")]
+            staged_waiters: AtomicUsize::new(0),
+            #[cfg(feature = "std")]
+            staged_count: Mutex::new(0),
+            #[cfg(feature = "std")]
+            staged_signal: Condvar::new(),
+        }
+    }
+
+    /// Gets the policy used by this cell to apply incoming values automatically.
+    pub fn update_policy(&self) -> UpdatePolicy {
+        self.policy
+    }
+
+    /// Applies the incoming value if the update policy of this cell requires it.
+    /// This is called automatically whenever the current value is accessed.
+    /// Returns `true` if a value was applied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn poll(&self) -> bool {
+        let due = match self.policy {
+            UpdatePolicy::Manual => false,
+            _ => self.is_due(&self.staged.borrow()),
+        };
+
+        due && self.update()
+    }
+
+    /// Applies the incoming value if the update policy of this cell requires it and the locks
+    /// can be acquired without blocking.
+    /// Nothing is applied if a lock is held, which also covers a borrow held by the calling thread.
+    fn try_poll(&self) -> Result<bool, SyncCellError> {
+        let due = match self.policy {
+            UpdatePolicy::Manual => false,
+            _ => match self.staged.try_borrow() {
+                Ok(staged) => self.is_due(&staged),
+                Err(SyncCellError::WouldBlock(_)) => false,
+                Err(err) => return Err(err),
+            },
+        };
+
+        if !due {
+            return Ok(false);
+        }
+
+        match self.try_update() {
+            Err(SyncCellError::WouldBlock(_)) => Ok(false),
+            result => result,
+        }
+    }
+
+    /// Checks if the update policy of this cell requires the locked incoming value to be applied.
+    fn is_due(&self, staged: &Staged<T>) -> bool {
+        match self.policy {
+            UpdatePolicy::Manual => false,
+            UpdatePolicy::AfterSets(count) => staged.sets >= count,
+            #[cfg(feature = "std")]
+            UpdatePolicy::AfterDelay(delay) => staged.first_set_at
+                .is_some_and(|first_set_at| first_set_at.elapsed() >= delay),
+        }
+    }
+
+    /// Applies the incoming value if the update policy of this cell requires it without blocking
+    /// before the current value is borrowed.
+    fn poll_before_borrow(&self) {
+        if let Err(err) = self.try_poll() {
+            panic!("Failed to get cell value. Lock was poisoned: {}", err);
+        }
+    }
+
+    /// Records that a value has been set in the locked incoming state.
+    fn record_set(&self, staged: &mut Staged<T>) {
+        self.version.increment(AtomicOrdering::AcqRel);
+
+        staged.sets += 1;
+
+        #[cfg(feature = "std")]
+        staged.first_set_at.get_or_insert_with(Instant::now);
+
+        self.notify_staged();
+    }
+
+    /// Resets the locked incoming state after the incoming value has been applied or discarded.
+    fn reset_staged(&self, staged: &mut Staged<T>) {
+        self.version.increment(AtomicOrdering::AcqRel);
+
+        staged.sets = 0;
+
+        #[cfg(feature = "std")]
+        {
+            staged.first_set_at = None;
+        }
+    }
+
+    /// Stores a value in the locked incoming value, merging it if the cell has a merge function.
+    fn stage(&self, staged: &mut Staged<T>, value: T) {
+        match (&mut staged.value, &self.merge) {
+            (Some(pending), Some(merge)) => merge(pending, value),
+            _ => staged.value = Some(value),
+        }
+
+        self.record_set(staged);
+    }
+
+    /// Gets the version of this cell.
+    /// The version changes every time a value is set, applied or discarded.
+    pub fn version(&self) -> u64 {
+        self.version.load(AtomicOrdering::Acquire)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// This is a reference to the current value of the cell.
+    /// An incoming value due under the update policy of this cell is applied first unless the
+    /// current or incoming value is already borrowed, in which case it stays incoming.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.poll_before_borrow();
+        self.current_value.borrow()
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// This is a reference to the current value of the cell not the incoming value. Any changes to
+    /// the value will update the current value.
+    /// An incoming value due under the update policy of this cell is applied first unless the
+    /// current or incoming value is already borrowed, in which case it stays incoming.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        self.poll_before_borrow();
+        self.current_value.borrow_mut()
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell without blocking.
+    /// This fails if the current value is mutably borrowed or its lock is poisoned.
+    pub fn try_borrow(&self) -> Result<SyncCellRef<'_, T>, SyncCellError> {
+        self.try_poll()?;
+
+        self.current_value.try_read_data().map_err(|err| self.current_value.lock_error(err))
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell without blocking.
+    /// This fails if the current value is borrowed or its lock is poisoned.
+    pub fn try_borrow_mut(&self) -> Result<SyncCellGuard<'_, T>, SyncCellError> {
+        self.try_poll()?;
+
+        self.current_value.try_write_data().map_err(|err| self.current_value.lock_error(err))
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell, blocking for at most
+    /// `timeout`.
+    /// This fails with `SyncCellError::Timeout` if the current value is still mutably borrowed
+    /// once the timeout has elapsed, or if its lock is poisoned. The thread sleeps between
+    /// attempts, so waiting does not keep a core busy.
+    ///
+    /// This method is only available with the `std` feature.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    #[cfg(feature = "std")]
+    pub fn borrow_timeout(&self, timeout: Duration) -> Result<SyncCellRef<'_, T>, SyncCellError> {
+        retry_timeout(timeout, || self.try_borrow())
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell, blocking for at most
+    /// `timeout`.
+    /// This fails with `SyncCellError::Timeout` if the current value is still borrowed once the
+    /// timeout has elapsed, or if its lock is poisoned. The thread sleeps between attempts, so
+    /// waiting does not keep a core busy.
+    ///
+    /// This method is only available with the `std` feature.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    #[cfg(feature = "std")]
+    pub fn borrow_mut_timeout(&self, timeout: Duration) -> Result<SyncCellGuard<'_, T>, SyncCellError> {
+        retry_timeout(timeout, || self.try_borrow_mut())
+    }
+
+    /// Checks if a new nalue is available that can be applied by calling `update`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn has_update(&self) -> bool {
+        self.staged.borrow().has_update()
+    }
+
+    /// Updates the internal value of this cell.
+    /// This involves replacing the current value with the incoming value if it is available.
+    /// Returns `true` if an incoming value or deferred function was applied.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::new(0);
+    ///
+    /// cell.set(1);
+    /// assert!(cell.update());
+    /// assert!(!cell.update());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update(&self) -> bool {
+        self.apply_update().is_some()
+    }
+
+    /// Updates the internal value of this cell if a condition on the current and the incoming
+    /// value holds.
+    /// If the condition does not hold, `UpdateOutcome::Rejected` is returned and the incoming
+    /// value is kept so that it can be applied by a later update or discarded with
+    /// `cancel_update`. `UpdateOutcome::NoUpdate` is returned without calling the condition if no
+    /// incoming value is waiting. Deferred functions are only applied along with an incoming
+    /// value.
+    ///
+    /// Both values are locked while the condition is checked, so it must not access this cell.
+    ///
+    /// - `condition` - A function given the current and the incoming value that returns `true` if
+    ///   the incoming value should be applied.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::{HeldSyncCell, UpdateOutcome};
+    ///
+    /// let cell = HeldSyncCell::new(1);
+    /// cell.set(5);
+    ///
+    /// // Only allow the value to grow by at most 2 in a single update.
+    /// assert_eq!(UpdateOutcome::Rejected, cell.update_if(|current, next| next - current <= 2));
+    /// assert_eq!(Some(5), cell.get_next());
+    ///
+    /// cell.set(3);
+    /// assert_eq!(UpdateOutcome::Applied, cell.update_if(|current, next| next - current <= 2));
+    /// assert_eq!(3, cell.get());
+    ///
+    /// assert_eq!(UpdateOutcome::NoUpdate, cell.update_if(|_, _| true));
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_if(&self, condition: impl FnOnce(&T, &T) -> bool) -> UpdateOutcome {
+        let (mut staged, mut current) = self.lock_update();
+
+        match &staged.value {
+            None => return UpdateOutcome::NoUpdate,
+            Some(next) if !condition(&current, next) => return UpdateOutcome::Rejected,
+            Some(_) => {},
+        }
+
+        self.apply_staged(&mut staged, &mut current);
+
+        UpdateOutcome::Applied
+    }
+
+    /// Updates the internal value of this cell.
+    /// This involves replacing the current value with the incoming value if it is available.
+    /// The replaced value is returned or `None` if no new value was available.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_take(&self) -> Option<T> {
+        self.apply_update().flatten()
+    }
+
+    /// Applies the incoming value and any deferred functions.
+    /// Returns `None` if there was nothing to apply, or the replaced value if an incoming value
+    /// was applied.
+    fn apply_update(&self) -> Option<Option<T>> {
+        let mut staged = self.staged.borrow_mut();
+
+        if !staged.has_update() {
+            return None;
+        }
+
+        let mut current = self.current_value.borrow_mut();
+
+        Some(self.apply_staged(&mut staged, &mut current))
+    }
+
+    /// Updates the internal value of this cell without blocking.
+    /// Returns `Ok(true)` if an incoming value or deferred function was applied. This fails if
+    /// the incoming or the current value is borrowed or either lock is poisoned, in which case
+    /// nothing is applied.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::{HeldSyncCell, SyncCellError};
+    ///
+    /// let cell = HeldSyncCell::new(0);
+    /// cell.set(1);
+    ///
+    /// let borrowed = cell.borrow();
+    /// assert_eq!(Err(SyncCellError::WouldBlock(None)), cell.try_update());
+    /// drop(borrowed);
+    ///
+    /// assert_eq!(Ok(true), cell.try_update());
+    /// assert_eq!(1, cell.get());
+    /// ```
+    pub fn try_update(&self) -> Result<bool, SyncCellError> {
+        let mut staged = self.staged.try_write_data().map_err(|err| self.staged.lock_error(err))?;
+
+        if !staged.has_update() {
+            return Ok(false);
+        }
+
+        let mut current = self.current_value.try_write_data().map_err(|err| self.current_value.lock_error(err))?;
+
+        self.apply_staged(&mut staged, &mut current);
+
+        Ok(true)
+    }
+
+    /// Queues a function that mutates the current value when `update` is next called.
+    /// Deferred functions are applied in the order they were queued after any incoming value has
+    /// been applied. Listeners are not called for deferred functions.
+    ///
+    /// - `f` - The function to apply to the current value.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::new((0, 0));
+    ///
+    /// cell.defer(|value| value.0 += 1);
+    /// cell.defer(|value| value.1 += 2);
+    /// assert_eq!((0, 0), cell.get());
+    ///
+    /// cell.update();
+    /// assert_eq!((1, 2), cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn defer(&self, f: impl FnOnce(&mut T) + Send + 'static) {
+        self.staged.borrow_mut().deferred().push(Box::new(f));
+        self.notify_staged();
+    }
+
+    /// Wakes any threads waiting for an update.
+    /// Nothing is locked or notified unless a thread is waiting.
+    fn notify_staged(&self) {
+        #[cfg(feature = "std")]
+        {
+            // A waiter registers itself before checking for an update, so either it sees the
+            // update or this sees the waiter.
+            if self.staged_waiters.load(AtomicOrdering::SeqCst) == 0 {
+                return;
+            }
+
+            *self.lock_staged_count() += 1;
+            self.staged_signal.notify_all();
+        }
+    }
+
+    /// Locks the number of values set or functions deferred.
+    #[cfg(feature = "std")]
+    fn lock_staged_count(&self) -> MutexGuard<'_, u64> {
+        match self.staged_count.lock() {
+            Ok(count) => count,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Blocks the current thread until an update is available that can be applied by calling
+    /// `update`.
+    /// This returns immediately if `has_update` would return `true`.
+    /// This method is only available with the `std` feature.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::{sync::Arc, thread};
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = Arc::new(HeldSyncCell::new(0));
+    /// let producer = cell.clone();
+    ///
+    /// thread::spawn(move || producer.set(1));
+    ///
+    /// cell.wait_for_pending();
+    /// assert!(cell.has_update());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    #[cfg(feature = "std")]
+    pub fn wait_for_pending(&self) {
+        /// Unregisters a thread waiting for an update, even if waiting panics.
+        struct Waiting<'a>(&'a AtomicUsize);
+
+        impl Drop for Waiting<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, AtomicOrdering::SeqCst);
+            }
+        }
+
+        self.staged_waiters.fetch_add(1, AtomicOrdering::SeqCst);
+        let _waiting = Waiting(&self.staged_waiters);
+
+        loop {
+            let seen = *self.lock_staged_count();
+
+            // The value is checked without holding the count so that the lock order with setters
+            // is never reversed. Anything set after the count was read changes it.
+            if self.has_update() {
+                return;
+            }
+
+            let count = self.lock_staged_count();
+
+            if let Err(err) = self.staged_signal.wait_while(count, |count| *count == seen) {
+                panic!("Failed to get cell value. Lock was poisoned: {}", err);
+            }
+        }
+    }
+
+    /// Blocks the current thread until an update is available and then applies it.
+    /// Returns `true` if an update was applied, which may not be the case if another thread
+    /// applied it first.
+    /// This method is only available with the `std` feature.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    #[cfg(feature = "std")]
+    pub fn wait_and_update(&self) -> bool {
+        self.wait_for_pending();
+        self.update()
+    }
+
+    /// Applies the locked incoming value and then all deferred functions to the locked current
+    /// value.
+    /// The replaced value is returned or `None` if there was no incoming value.
+    pub(crate) fn apply_staged(&self, staged: &mut Staged<T>, current: &mut T) -> Option<T> {
+        let previous = staged.value.take()
+            .map(|next| self.apply(staged, current, next));
+
+        for f in take(staged.deferred()) {
+            f(current);
+        }
+
+        previous
+    }
+
+    /// Locks both the incoming state and the current value so that an update can be applied
+    /// later.
+    /// The incoming state is always locked first.
+    pub(crate) fn lock_update(&self) -> (SyncCellGuard<'_, Staged<T>>, SyncCellGuard<'_, T>) {
+        let staged = self.staged.borrow_mut();
+        let current = self.current_value.borrow_mut();
+
+        (staged, current)
+    }
+
+    /// Replaces the locked current value with an incoming value and notifies the listeners.
+    /// The replaced value is returned.
+    fn apply(&self, staged: &mut Staged<T>, current: &mut T, next: T) -> T {
+        let previous = replace(current, next);
+
+        self.reset_staged(staged);
+
+        for (_, listener) in self.listeners.borrow_mut().list.iter_mut() {
+            listener(&previous, current);
+        }
+
+        previous
+    }
+
+    /// Registers a listener that is called with the previous and the new value every time
+    /// `update` applies an incoming value.
+    /// Listeners are called in the order they were registered while the current value is locked,
+    /// so they must not access this cell.
+    ///
+    /// Returns a handle that can be passed to `remove_listener` to remove the listener.
+    ///
+    /// - `listener` - The function to call.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn on_update(&self, listener: impl FnMut(&T, &T) + Send + Sync + 'static) -> ListenerId {
+        let mut listeners = self.listeners.borrow_mut();
+        let id = listeners.next_id;
+
+        listeners.next_id += 1;
+        listeners.list.push((id, Box::new(listener)));
+
+        ListenerId(id)
+    }
+
+    /// Removes a listener registered with `on_update`.
+    /// Returns `true` if the listener was registered on this cell.
+    ///
+    /// - `id` - The handle returned when the listener was registered.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn remove_listener(&self, id: ListenerId) -> bool {
+        let mut listeners = self.listeners.borrow_mut();
+        let count = listeners.list.len();
+
+        listeners.list.retain(|(listener, _)| *listener != id.0);
+        listeners.list.len() != count
+    }
+
+    /// Discards the incoming value of this cell and any deferred functions without applying them.
+    /// The discarded value is returned or `None` if no new value had been set, in which case the
+    /// version of the cell is left unchanged.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn cancel_update(&self) -> Option<T> {
+        let mut staged = self.staged.borrow_mut();
+        let next = staged.value.take();
+
+        staged.deferred().clear();
+
+        if next.is_some() {
+            self.reset_staged(&mut staged);
+        }
+
+        next
+    }
+
+    /// Borrows a immutable reference to the incoming value of this cell.
+    /// Returns `None` if no new value has been set since the last call to `update`.
+    /// The incoming value cannot be changed or applied while the reference is held.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn peek_next(&self) -> Option<NextValueRef<'_, T>> {
+        let staged = self.staged.borrow();
+
+        match staged.value {
+            Some(_) => Some(NextValueRef {
+                data: staged,
+            }),
+            None => None,
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// This value will only become available once the `update` method is called.
+    ///
+    /// In the case that multiple threads call this method simultaniously,
+    /// the order in which the calls are processed is not defined. However, the final result will
+    /// be the value specified by one of the method calls. If the cell was created with
+    /// `with_merge`, the value is instead merged into any incoming value that has not been
+    /// applied yet.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, value: T) {
+        self.stage(&mut self.staged.borrow_mut(), value);
+    }
+
+    /// Sets the value contained in this cell if the incoming value can be locked without
+    /// blocking.
+    /// This value will only become available once the `update` method is called. If the incoming
+    /// value is borrowed or its lock is poisoned, the value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    pub fn try_set(&self, value: T) -> Result<(), T> {
+        match self.staged.try_write_data() {
+            Ok(mut staged) => {
+                self.stage(&mut staged, value);
+                Ok(())
+            },
+            Err(_) => Err(value),
+        }
+    }
+
+    /// Sets the value contained in this cell only if no incoming value is waiting to be applied.
+    /// This makes the first value set before an update win instead of the last one. Returns `true`
+    /// if the value was set. The merge function of a cell created with `with_merge` is never
+    /// called, since there is no incoming value to merge into.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::new(0);
+    ///
+    /// assert!(cell.set_if_absent(1));
+    /// assert!(!cell.set_if_absent(2));
+    ///
+    /// cell.update();
+    /// assert_eq!(1, cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set_if_absent(&self, value: T) -> bool {
+        let mut staged = self.staged.borrow_mut();
+
+        if staged.value.is_some() {
+            return false;
+        }
+
+        self.stage(&mut staged, value);
+
+        true
+    }
+
+    /// Sets the value contained in this cell if the cell is still at an expected version.
+    /// This value will only become available once the `update` method is called.
+    ///
+    /// Writers can read the version along with the current value and pass it here so that a
+    /// value computed from an outdated state does not replace a newer incoming value. Like
+    /// `set`, the value is merged into any incoming value if the cell was created with
+    /// `with_merge`.
+    ///
+    /// - `value` - The new value of the cell.
+    /// - `expected_version` - The version returned by `version` when the value was computed.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set_versioned(&self, value: T, expected_version: u64) -> Result<(), VersionMismatch> {
+        let mut staged = self.staged.borrow_mut();
+        let actual = self.version();
+
+        if actual != expected_version {
+            return Err(VersionMismatch {
+                expected: expected_version,
+                actual,
+            });
+        }
+
+        self.stage(&mut staged, value);
+
+        Ok(())
+    }
+
+    /// Sets the value contained in this cell to a value computed from the current value.
+    /// This value will only become available once the `update` method is called.
+    ///
+    /// The incoming value is locked while the new value is computed, so no other thread can set
+    /// a value in between. Like `set`, the new value is merged into any incoming value if the
+    /// cell was created with `with_merge`.
+    ///
+    /// - `f` - The function used to compute the new value from the current value.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set_with(&self, f: impl FnOnce(&T) -> T) {
+        let mut staged = self.staged.borrow_mut();
+        let value = f(&self.current_value.borrow());
+
+        self.stage(&mut staged, value);
+    }
+
+    /// Retrieves the inner value stored in this `HeldSyncCell`.
+    /// This will return the most up-to-date value even if `update` has not been called. Like
+    /// `update`, any deferred functions are applied to the incoming value, or to the current value
+    /// if no new value has been set. Listeners are not called.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::new(vec![0]);
+    ///
+    /// cell.set(vec![1]);
+    /// cell.defer(|value| value.push(2));
+    ///
+    /// assert_eq!(vec![1, 2], cell.into_inner());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> T {
+        let mut staged = self.staged.into_inner();
+        let mut value = staged.value
+            .take()
+            .unwrap_or_else(|| self.current_value.into_inner());
+
+        for f in take(staged.deferred()) {
+            f(&mut value);
+        }
+
+        value
+    }
+
+    /// Splits this `HeldSyncCell` into its current value and its incoming value.
+    /// The incoming value is `None` if no new value has been set since the last call to `update`.
+    /// Deferred functions that have not been applied are dropped.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::new(0);
+    /// cell.set(1);
+    ///
+    /// assert_eq!((0, Some(1)), cell.into_parts());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_parts(self) -> (T, Option<T>) {
+        (self.current_value.into_inner(), self.staged.into_inner().value)
+    }
+
+    /// Updates the internal value of this cell keeping the replaced value as a spare instead of
+    /// dropping it.
+    /// The spare can be retrieved with `take_spare` so that a producer can reuse its allocation
+    /// for the next value. Any previous spare is dropped. Returns `true` if an incoming value or
+    /// deferred function was applied, like `update`. If only deferred functions were applied, no
+    /// value was replaced, so no spare is produced and any previous spare is kept.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::HeldSyncCell;
+    ///
+    /// let cell = HeldSyncCell::new(vec![0u8; 1024]);
+    ///
+    /// cell.set(vec![1u8; 1024]);
+    /// cell.update_swap();
+    ///
+    /// // Reuse the buffer of the old frame for the next one.
+    /// let mut frame = cell.take_spare().unwrap_or_default();
+    /// frame.fill(2);
+    /// cell.set(frame);
+    /// # cell.update();
+    /// # assert_eq!(2, cell.get()[0]);
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_swap(&self) -> bool {
+        // The incoming state stays locked until the spare is stored, so a value set in between
+        // cannot be overwritten or see an outdated spare.
+        let mut staged = self.staged.borrow_mut();
+
+        if !staged.has_update() {
+            return false;
+        }
+
+        let previous = self.apply_staged(&mut staged, &mut self.current_value.borrow_mut());
+
+        if let Some(previous) = previous {
+            staged.spare = Some(previous);
+        }
+
+        true
+    }
+
+    /// Takes the value kept as a spare by the last call to `update_swap`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn take_spare(&self) -> Option<T> {
+        self.staged.borrow_mut()
+            .spare
+            .take()
+    }
+}
+
+impl <T: Clone> HeldSyncCell<T> {
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+
+    /// Updates the internal value of this cell and gets the resulting current value.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_and_get(&self) -> T {
+        self.update();
+        self.get()
+    }
+
+    /// Borrows a mutable reference to the incoming value of this cell so that it can be built up
+    /// in place.
+    /// If no new value has been set since the last call to `update`, the incoming value is first
+    /// initialised with a copy of the current value.
+    /// This counts as setting a value for the update policy of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_next_mut(&self) -> NextValueGuard<'_, T> {
+        let mut staged = self.staged.borrow_mut();
+
+        if staged.value.is_none() {
+            staged.value = Some(self.current_value.borrow().clone());
+        }
+
+        self.record_set(&mut staged);
+
+        NextValueGuard {
+            data: staged,
+        }
+    }
+
+    /// Gets the incoming value of this cell.
+    /// Returns `None` if no new value has been set since the last call to `update`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get_next(&self) -> Option<T> {
+        self.staged.borrow().value.clone()
+    }
+}
+
+impl <T: Clone> Clone for HeldSyncCell<T> {
+    fn clone(&self) -> Self {
+        Self::create(self.get(), self.merge.clone(), self.policy)
+    }
+}
+
+impl <T: Default> Default for HeldSyncCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T: PartialEq> PartialEq for HeldSyncCell<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.borrow().eq(&*other.borrow())
+    }
+}
+
+impl <T: Eq> Eq for HeldSyncCell<T> {
+}
+
+impl <T: PartialEq> PartialEq<T> for HeldSyncCell<T> {
+    /// Compares the current value of this cell with a plain value.
+    fn eq(&self, other: &T) -> bool {
+        self.borrow().eq(other)
+    }
+}
+
+impl <T: PartialEq> PartialEq<SyncCell<T>> for HeldSyncCell<T> {
+    /// Compares the current value of this cell with the value of a `SyncCell`.
+    fn eq(&self, other: &SyncCell<T>) -> bool {
+        self.borrow().eq(&*other.borrow())
+    }
+}
+
+impl <T: PartialEq> PartialEq<HeldSyncCell<T>> for SyncCell<T> {
+    /// Compares the value of this cell with the current value of a `HeldSyncCell`.
+    fn eq(&self, other: &HeldSyncCell<T>) -> bool {
+        self.borrow().eq(&*other.borrow())
+    }
+}
+
+impl <T: PartialOrd> PartialOrd<SyncCell<T>> for HeldSyncCell<T> {
+    /// Compares the current value of this cell with the value of a `SyncCell`.
+    fn partial_cmp(&self, other: &SyncCell<T>) -> Option<Ordering> {
+        self.borrow().partial_cmp(&*other.borrow())
+    }
+}
+
+impl <T: PartialOrd> PartialOrd<HeldSyncCell<T>> for SyncCell<T> {
+    /// Compares the value of this cell with the current value of a `HeldSyncCell`.
+    fn partial_cmp(&self, other: &HeldSyncCell<T>) -> Option<Ordering> {
+        self.borrow().partial_cmp(&*other.borrow())
+    }
+}
+
+impl <T: PartialOrd> PartialOrd for HeldSyncCell<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.borrow().partial_cmp(&*other.borrow())
+    }
+}
+
+impl <T: Ord> Ord for HeldSyncCell<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.borrow().cmp(&*other.borrow())
+    }
+}
+
+impl <T: Hash> Hash for HeldSyncCell<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.borrow().hash(state)
+    }
+}
+
+impl <T: Debug> Debug for HeldSyncCell<T> {
+    /// Formats the current value and whether an update is available.
+    /// Values that are locked for writing are not waited for and are shown as `<locked>`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("HeldSyncCell");
+
+        match self.current_value.try_read_data() {
+            Ok(current) => debug.field("current", &*current),
+            Err(TryLockError::Poisoned(err)) => debug.field("current", &**err.get_ref()),
+            Err(TryLockError::WouldBlock) => debug.field("current", &format_args!("<locked>")),
+        };
+
+        match self.staged.try_read_data() {
+            Ok(staged) => debug.field("has_update", &staged.has_update()),
+            Err(TryLockError::Poisoned(err)) => debug.field("has_update", &err.get_ref().has_update()),
+            Err(TryLockError::WouldBlock) => debug.field("has_update", &format_args!("<locked>")),
+        };
+
+        debug.field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl <T: Display> Display for HeldSyncCell<T> {
+    /// Formats the current value.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.borrow().fmt(f)
+    }
+}
+
+impl <T> From<T> for HeldSyncCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A borrow of the incoming value of a `HeldSyncCell`.
+pub struct NextValueRef<'a, T> {
+    /// The lock guard over the incoming value.
+    data: SyncCellRef<'a, Staged<T>>,
+}
+
+impl <T> Deref for NextValueRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &self.data.value {
+            Some(value) => value,
+            None => unreachable!("Incoming value was taken while borrowed."),
+        }
+    }
+}
+
+/// A mutable borrow of the incoming value of a `HeldSyncCell`.
+pub struct NextValueGuard<'a, T> {
+    /// The lock guard over the incoming value.
+    data: SyncCellGuard<'a, Staged<T>>,
+}
+
+impl <T> Deref for NextValueGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &self.data.value {
+            Some(value) => value,
+            None => unreachable!("Incoming value was taken while borrowed."),
+        }
+    }
+}
+
+impl <T> DerefMut for NextValueGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match &mut self.data.value {
+            Some(value) => value,
+            None => unreachable!("Incoming value was taken while borrowed."),
+        }
+    }
+}
+
+#[cfg(test)]
+// Some tests compare booleans with `assert_eq!` to keep their expected values explicit.
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use core::panic;
+    use std::{thread, sync::{Arc, atomic::{AtomicUsize, Ordering as AtomicOrdering}}, time::Duration};
+
+    use crate::{SyncCell, HeldSyncCell, LeaseExpired, LockPolicy, RawSyncLock, SpinRawLock, StdRawLock, SyncCellError, UpdateOutcome, UpdatePolicy, VersionMismatch, retry_timeout};
+
+    #[test]
+    pub fn test_sync_cell_new() {
+        let _cell = SyncCell::new(1);
+    } 
+
+    #[test]
+    pub fn test_sync_cell_set() {
+        let cell = SyncCell::new(2);
+
+        cell.set(3);
+
+        assert_eq!(3, cell.get())
+    }
+    
+    #[test]
+    pub fn test_sync_cell_get() {
+        let cell = SyncCell::new(4);
+
+        assert_eq!(4, cell.get())
+    }
+    
+    #[test]
+    pub fn test_sync_cell_replace() {
+        let cell = SyncCell::new(2);
+
+        let old = cell.replace(3);
+        
+        assert_eq!(2, old);
+        assert_eq!(3, cell.get())
+    }
+    
+    #[test]
+    #[should_panic]
+    pub fn test_sync_cell_replace_poisoned() {
+        let cell = Arc::new(SyncCell::new(4));
+
+        let cell2 = cell.clone();
+
+        let _ = thread::spawn(move || {
+            let _borrow = cell2.borrow();
+
+            panic!("Intentional panic.");
+        }).join();
+
+        let old = cell.replace(3);
+        
+        assert_ne!(2, old);
+        assert_ne!(3, cell.get())
+    }
+    
+    #[test]
+    pub fn test_sync_cell_try_borrow() {
+        let cell = Arc::new(SyncCell::new(1));
+        let guard = cell.borrow();
+
+        assert!(cell.try_borrow().is_ok());
+        assert_eq!(Some(SyncCellError::WouldBlock(None)), cell.try_borrow_mut().err());
+        assert_eq!(Some(SyncCellError::Timeout(None)), cell.borrow_mut_timeout(Duration::from_millis(10)).err());
+        drop(guard);
+
+        let _ = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let _guard = cell.borrow_mut();
+                panic!("Poisoning the lock");
+            }).join()
+        };
+
+        assert_eq!(Some(SyncCellError::Poisoned), cell.borrow_timeout(Duration::from_millis(10)).err());
+    }
+
+    #[test]
+    pub fn test_sync_cell_with_lock() {
+        let cell = Arc::new(SyncCell::with_lock(1, SpinRawLock::INIT));
+        let guard = cell.borrow();
+
+        assert!(cell.try_borrow().is_ok());
+        assert_eq!(Some(SyncCellError::WouldBlock(None)), cell.try_borrow_mut().err());
+        drop(guard);
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    *cell.borrow_mut() += 1;
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(401, cell.get());
+
+        let fair = SyncCell::with_lock(1, StdRawLock::with_policy(LockPolicy::Fair));
+        fair.set(2);
+        assert_eq!(2, fair.into_inner());
+    }
+
+    #[test]
+    pub fn test_sync_cell_with_lock_poisoned() {
+        let cell = Arc::new(SyncCell::with_lock(1, SpinRawLock::INIT));
+
+        let _ = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let _guard = cell.borrow();
+                panic!("Immutable borrows do not poison the lock");
+            }).join()
+        };
+
+        assert_eq!(1, cell.get());
+
+        let _ = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let mut guard = cell.borrow_mut();
+                *guard = 2;
+                panic!("Poisoning the lock");
+            }).join()
+        };
+
+        assert_eq!(Some(SyncCellError::Poisoned), cell.try_borrow().err());
+        assert!(std::panic::catch_unwind(|| cell.get()).is_err());
+    }
+
+    #[test]
+    pub fn test_sync_cell_data_ptr() {
+        let cell = SyncCell::new(1);
+        let pointer = cell.data_ptr();
+
+        {
+            let borrowed = cell.borrow();
+            assert_eq!(&*borrowed as *const i32, pointer as *const i32);
+        }
+
+        {
+            let _borrowed = cell.borrow_mut();
+            // SAFETY: The mutable borrow is held while the value is written.
+            unsafe { *pointer = 2 };
+        }
+
+        assert_eq!(2, cell.get());
+        assert_eq!(pointer, cell.data_ptr());
+    }
+
+    #[test]
+    pub fn test_sync_cell_error_from() {
+        fn expire() -> Result<(), SyncCellError> {
+            Err(LeaseExpired)?
+        }
+
+        assert_eq!(Err(SyncCellError::Invalid), expire());
+    }
+
+    #[test]
+    pub fn test_sync_cell_into_inner() {
+        let cell = SyncCell::new(4);
+
+        assert_eq!(4, cell.into_inner())
+    }
+    
+    #[test]
+    pub fn test_sync_cell_mutable_borrow() {
+        let cell = SyncCell::new(4);
+
+        let mut borrow = cell.borrow_mut();
+
+        *borrow = 5;
+
+        drop(borrow);
+
+        assert_eq!(5, cell.get())
+    }
+    
+    #[test]
+    #[should_panic]
+    pub fn test_sync_cell_mutable_borrow_poisoned() {
+        let cell = Arc::new(SyncCell::new(4));
+
+        let cell2 = cell.clone();
+
+        let _ = thread::spawn(move || {
+            let _borrow = cell2.borrow();
+
+            panic!("Intentional panic.");
+        }).join();
+
+        let mut borrow = cell.borrow_mut();
+
+        *borrow = 5;
+
+        drop(borrow);
+
+        assert_ne!(5, cell.get())
+    }
+    
+    #[test]
+    #[should_panic]
+    pub fn test_sync_cell_get_poisoned() {
+        let cell = Arc::new(SyncCell::new(4));
+
+        let cell2 = cell.clone();
+
+        let _ = thread::spawn(move || {
+            let _borrow = cell2.borrow();
+
+            panic!("Intentional panic.");
+        }).join();
+
+        assert_ne!(4, cell.get())
+    }
+    
+    #[test]
+    #[should_panic]
+    pub fn test_sync_cell_set_poisoned() {
+        let cell = Arc::new(SyncCell::new(4));
+
+        let cell2 = cell.clone();
+
+        let _ = thread::spawn(move || {
+            let _borrow = cell2.borrow();
+
+            panic!("Intentional panic.");
+        }).join();
+
+        cell.set(5);
+
+        assert_ne!(5, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_new() {
+        let _cell = HeldSyncCell::new(0);
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_get() {
+        let cell = HeldSyncCell::new(1);
+
+        assert_eq!(1, cell.get())
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_set_no_update() {
+        let cell = HeldSyncCell::new(1);
+
+        cell.set(2);
+
+        assert_eq!(true, cell.has_update());
+        assert_eq!(1, cell.get())
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_set_update() {
+        let cell = HeldSyncCell::new(1);
+
+        cell.set(2);
+        cell.update();
+
+        assert_eq!(false, cell.has_update());
+        assert_eq!(2, cell.get())
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_set_double_update() {
+        let cell = HeldSyncCell::new(1);
+
+        cell.set(2);
+        cell.update();
+        cell.update();
+
+        assert_eq!(false, cell.has_update());
+        assert_eq!(2, cell.get())
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_no_set_update() {
+        let cell = HeldSyncCell::new(1);
+
+        cell.update();
+
+        assert_eq!(false, cell.has_update());
+        assert_eq!(1, cell.get())
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_no_set() {
+        let cell = HeldSyncCell::new(1);
+
+        assert_eq!(false, cell.has_update());
+        assert_eq!(1, cell.get())
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_into_inner() {
+        let cell = HeldSyncCell::new(4);
+
+        assert_eq!(4, cell.into_inner())
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_set_into_inner() {
+        let cell = HeldSyncCell::new(4);
+
+        cell.set(5);
+
+        assert_eq!(5, cell.into_inner())
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_defer_into_inner() {
+        let cell = HeldSyncCell::new(4);
+
+        cell.defer(|value| *value *= 10);
+
+        assert_eq!(40, cell.into_inner());
+
+        let cell = HeldSyncCell::new(4);
+
+        cell.set(5);
+        cell.defer(|value| *value += 1);
+
+        assert_eq!(6, cell.into_inner());
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_set_update_into_inner() {
+        let cell = HeldSyncCell::new(4);
+
+        cell.set(5);
+        cell.update();
+
+        assert_eq!(5, cell.into_inner())
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_mutable_borrow() {
+        let cell = HeldSyncCell::new(4);
+
+        let mut borrow = cell.borrow_mut();
+
+        *borrow = 5;
+
+        drop(borrow);
+
+        assert_eq!(5, cell.get())
+    }
+    
+    #[test]
+    pub fn test_held_sync_cell_mutable_borrow_set() {
+        let cell = HeldSyncCell::new(4);
+
+        let mut borrow = cell.borrow_mut();
+
+        *borrow = 5;
+
+        cell.set(6);
+
+        drop(borrow);
+
+        assert_eq!(5, cell.get());
+        cell.update();
+        assert_eq!(6, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_peek_next() {
+        let cell = HeldSyncCell::new(1);
+
+        assert!(cell.peek_next().is_none());
+
+        cell.set(2);
+
+        assert_eq!(2, *cell.peek_next().unwrap());
+        assert_eq!(Some(2), cell.get_next());
+        assert_eq!(1, cell.get());
+
+        cell.update();
+
+        assert_eq!(None, cell.get_next());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_cancel_update() {
+        let cell = HeldSyncCell::new(1);
+        let version = cell.version();
+
+        // Cancelling nothing does not change the version seen by versioned sets.
+        assert_eq!(None, cell.cancel_update());
+        assert_eq!(version, cell.version());
+
+        cell.set(2);
+
+        assert_eq!(Some(2), cell.cancel_update());
+        assert!(!cell.has_update());
+
+        cell.update();
+
+        assert_eq!(1, cell.get());
+
+        // Deferred functions are discarded along with the incoming value.
+        cell.defer(|value| *value += 10);
+
+        assert_eq!(None, cell.cancel_update());
+        assert!(!cell.has_update());
+        assert!(!cell.update());
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_update_take() {
+        let cell = HeldSyncCell::new(1);
+
+        assert_eq!(None, cell.update_take());
+
+        cell.set(2);
+
+        assert_eq!(Some(1), cell.update_take());
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_update_and_get() {
+        let cell = HeldSyncCell::new(1);
+
+        assert_eq!(1, cell.update_and_get());
+
+        cell.set(2);
+
+        assert_eq!(2, cell.update_and_get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_set_with() {
+        let cell = HeldSyncCell::new(1);
+
+        cell.set_with(|value| value + 1);
+
+        assert_eq!(1, cell.get());
+        assert_eq!(Some(2), cell.get_next());
+
+        cell.update();
+        cell.set_with(|value| value * 10);
+        cell.update();
+
+        assert_eq!(20, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_with_merge() {
+        let cell = HeldSyncCell::with_merge(vec![0], |pending: &mut Vec<i32>, incoming| pending.extend(incoming));
+
+        cell.set(vec![1]);
+        cell.set(vec![2, 3]);
+
+        assert_eq!(vec![0], cell.get());
+
+        cell.update();
+        cell.set(vec![4]);
+        cell.update();
+
+        assert_eq!(vec![4], cell.clone().get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_with_merge_set_with() {
+        let cell = HeldSyncCell::with_merge(1, |pending: &mut i32, incoming| *pending += incoming);
+
+        cell.set(2);
+        cell.set_with(|current| current * 10);
+        assert_eq!(Ok(()), cell.set_versioned(3, cell.version()));
+        assert!(!cell.set_if_absent(100));
+
+        cell.update();
+        assert_eq!(15, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_on_update() {
+        let cell = HeldSyncCell::new(0);
+        let seen = Arc::new(SyncCell::new(Vec::new()));
+
+        let id = {
+            let seen = seen.clone();
+            cell.on_update(move |previous, next| seen.borrow_mut().push((*previous, *next)))
+        };
+
+        cell.update();
+        cell.set(1);
+        cell.update();
+
+        assert!(cell.remove_listener(id));
+        assert!(!cell.remove_listener(id));
+
+        cell.set(2);
+        cell.update();
+
+        assert_eq!(vec![(0, 1)], seen.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_update_policy_after_sets() {
+        let cell = HeldSyncCell::with_update_policy(0, UpdatePolicy::AfterSets(3));
+
+        cell.set(1);
+        cell.set(2);
+        assert_eq!(0, cell.get());
+
+        cell.set(3);
+        assert_eq!(3, cell.get());
+
+        cell.set(4);
+        cell.cancel_update();
+        cell.set(5);
+        cell.set(6);
+        assert!(!cell.poll());
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_nested_borrow_with_update_policy() {
+        let cell = HeldSyncCell::with_update_policy(0, UpdatePolicy::AfterSets(1));
+
+        cell.set(1);
+        let outer = cell.borrow();
 
-impl <T: PartialOrd> PartialOrd for HeldSyncCell<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.borrow().partial_cmp(&*other.borrow())
-    }
-}
+        // The incoming value cannot be applied while the current value is borrowed.
+        cell.set(2);
+        assert_eq!(1, *cell.borrow());
+        assert_eq!(1, *outer);
+        drop(outer);
 
-impl <T: Ord> Ord for HeldSyncCell<T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.borrow().cmp(&*other.borrow())
-    }
-}
+        // Nor while the incoming value is borrowed.
+        let mut next = cell.borrow_next_mut();
+        *next += 1;
+        assert_eq!(1, *cell.borrow());
+        drop(next);
 
-impl <T: Hash> Hash for HeldSyncCell<T> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.borrow().hash(state)
+        assert_eq!(3, *cell.borrow_mut());
     }
-}
 
-impl <T> From<T> for HeldSyncCell<T> {
-    fn from(value: T) -> Self {
-        Self::new(value)
+    #[test]
+    pub fn test_held_sync_cell_update_policy_after_delay() {
+        let cell = HeldSyncCell::with_update_policy(0, UpdatePolicy::AfterDelay(Duration::from_millis(10)));
+
+        cell.set(1);
+        assert_eq!(0, cell.get());
+
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(1, cell.get());
+        assert_eq!(UpdatePolicy::AfterDelay(Duration::from_millis(10)), cell.update_policy());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use core::panic;
-    use std::{thread, sync::Arc};
+    #[test]
+    pub fn test_held_sync_cell_borrow_next_mut() {
+        let cell = HeldSyncCell::new(vec![1]);
+
+        cell.borrow_next_mut().push(2);
+        cell.borrow_next_mut().push(3);
+
+        assert_eq!(vec![1], cell.get());
 
-    use crate::{SyncCell, HeldSyncCell};
+        cell.update();
+
+        assert_eq!(vec![1, 2, 3], cell.get());
+    }
 
     #[test]
-    pub fn test_sync_cell_new() {
-        let _cell = SyncCell::new(1);
-    } 
+    pub fn test_held_sync_cell_update_swap() {
+        let cell = HeldSyncCell::new(vec![1]);
+
+        assert!(!cell.update_swap());
+        assert_eq!(None, cell.take_spare());
+
+        cell.set(vec![2]);
+
+        assert!(cell.update_swap());
+        assert!(!cell.has_update());
+        assert_eq!(Some(vec![1]), cell.take_spare());
+        assert_eq!(None, cell.take_spare());
+        assert_eq!(vec![2], cell.get());
+
+        cell.set(vec![3]);
+        cell.update_swap();
+        cell.defer(|value| value.push(4));
+
+        // Deferred functions are applied in place, so the spare from before is kept.
+        assert!(cell.update_swap());
+        assert!(!cell.has_update());
+        assert_eq!(vec![3, 4], cell.get());
+        assert_eq!(Some(vec![2]), cell.take_spare());
+    }
 
     #[test]
-    pub fn test_sync_cell_set() {
-        let cell = SyncCell::new(2);
+    pub fn test_held_sync_cell_set_versioned() {
+        let cell = HeldSyncCell::new(1);
+        let version = cell.version();
 
-        cell.set(3);
+        assert_eq!(Ok(()), cell.set_versioned(2, version));
 
-        assert_eq!(3, cell.get())
+        let result = cell.set_versioned(3, version);
+
+        assert_eq!(Err(VersionMismatch { expected: version, actual: version + 1 }), result);
+        assert_eq!(Some(2), cell.get_next());
+
+        cell.update();
+
+        assert_eq!(version + 2, cell.version());
     }
-    
+
     #[test]
-    pub fn test_sync_cell_get() {
-        let cell = SyncCell::new(4);
+    pub fn test_held_sync_cell_defer() {
+        let cell = HeldSyncCell::new(vec![1]);
 
-        assert_eq!(4, cell.get())
+        cell.defer(|value| value.push(2));
+        assert!(cell.has_update());
+
+        cell.set(vec![3]);
+        cell.defer(|value| value.push(4));
+
+        assert_eq!(Some(vec![1]), cell.update_take());
+        assert_eq!(vec![3, 2, 4], cell.get());
+        assert!(!cell.has_update());
     }
-    
+
     #[test]
-    pub fn test_sync_cell_replace() {
-        let cell = SyncCell::new(2);
+    pub fn test_held_sync_cell_try_borrow() {
+        let cell = HeldSyncCell::new(1);
+        let borrowed = cell.borrow_mut();
 
-        let old = cell.replace(3);
-        
-        assert_eq!(2, old);
-        assert_eq!(3, cell.get())
+        assert!(matches!(cell.borrow_timeout(Duration::from_millis(10)), Err(SyncCellError::Timeout(_))));
+        assert!(cell.borrow_timeout(Duration::from_millis(10)).is_err());
+        assert_eq!(Ok(()), cell.try_set(2));
+        assert!(matches!(cell.try_update(), Err(SyncCellError::WouldBlock(_))));
+
+        drop(borrowed);
+
+        let next = cell.peek_next();
+
+        assert_eq!(Err(3), cell.try_set(3));
+        assert_eq!(1, *cell.try_borrow().unwrap());
+
+        drop(next);
+
+        assert_eq!(Ok(true), cell.try_update());
+        assert_eq!(Ok(false), cell.try_update());
+        assert_eq!(2, *cell.borrow_mut_timeout(Duration::from_millis(10)).unwrap());
     }
-    
+
     #[test]
-    #[should_panic]
-    pub fn test_sync_cell_replace_poisoned() {
-        let cell = Arc::new(SyncCell::new(4));
+    pub fn test_held_sync_cell_borrow_timeout_sleeps() {
+        let cell = Arc::new(HeldSyncCell::new(1));
+        let borrowed = cell.borrow_mut();
+        let attempts = AtomicUsize::new(0);
+
+        let result = retry_timeout(Duration::from_millis(50), || {
+            attempts.fetch_add(1, AtomicOrdering::Relaxed);
+            cell.try_borrow().map(drop)
+        });
+
+        // Spinning would retry many thousands of times within the timeout.
+        assert!(matches!(result, Err(SyncCellError::Timeout(_))));
+        assert!(attempts.into_inner() < 100);
+
+        drop(borrowed);
+        assert_eq!(1, *cell.borrow_timeout(Duration::MAX).unwrap());
+    }
 
-        let cell2 = cell.clone();
+    #[test]
+    pub fn test_held_sync_cell_try_borrow_poisoned() {
+        let cell = Arc::new(HeldSyncCell::new(1));
+        let poisoner = cell.clone();
 
         let _ = thread::spawn(move || {
-            let _borrow = cell2.borrow();
-
-            panic!("Intentional panic.");
+            let _borrowed = poisoner.borrow_mut();
+            panic!("Poisoning the cell.");
         }).join();
 
-        let old = cell.replace(3);
-        
-        assert_ne!(2, old);
-        assert_ne!(3, cell.get())
+        assert_eq!(Err(SyncCellError::Poisoned), cell.try_borrow().map(|value| *value));
+        assert_eq!(Err(SyncCellError::Poisoned), cell.try_borrow_mut().map(|value| *value));
     }
-    
+
     #[test]
-    pub fn test_sync_cell_into_inner() {
-        let cell = SyncCell::new(4);
+    pub fn test_held_sync_cell_from_parts() {
+        let cell = HeldSyncCell::from_parts(1, None);
+        assert!(!cell.has_update());
 
-        assert_eq!(4, cell.into_inner())
+        let cell = HeldSyncCell::from_parts(1, Some(2));
+        assert_eq!(Some(2), cell.get_next());
+        assert_eq!(2, cell.update_and_get());
     }
-    
-    #[test]
-    pub fn test_sync_cell_mutable_borrow() {
-        let cell = SyncCell::new(4);
 
-        let mut borrow = cell.borrow_mut();
+    #[test]
+    pub fn test_held_sync_cell_update_applied() {
+        let cell = HeldSyncCell::new(1);
 
-        *borrow = 5;
+        assert!(!cell.update());
 
-        drop(borrow);
+        cell.set(2);
+        assert!(cell.update());
+        assert!(!cell.update());
 
-        assert_eq!(5, cell.get())
+        cell.defer(|value| *value += 1);
+        assert!(cell.update());
+        assert_eq!(3, cell.get());
     }
-    
-    #[test]
-    #[should_panic]
-    pub fn test_sync_cell_mutable_borrow_poisoned() {
-        let cell = Arc::new(SyncCell::new(4));
 
-        let cell2 = cell.clone();
+    #[test]
+    pub fn test_held_sync_cell_update_if() {
+        let cell = HeldSyncCell::new(1);
 
-        let _ = thread::spawn(move || {
-            let _borrow = cell2.borrow();
+        assert_eq!(UpdateOutcome::NoUpdate, cell.update_if(|_, _| true));
 
-            panic!("Intentional panic.");
-        }).join();
+        cell.set(2);
+        cell.defer(|value| *value *= 10);
 
-        let mut borrow = cell.borrow_mut();
+        assert_eq!(UpdateOutcome::Rejected, cell.update_if(|current, next| *current > *next));
+        assert!(cell.has_update());
+        assert_eq!(1, cell.get());
 
-        *borrow = 5;
+        assert!(cell.update_if(|current, next| *current < *next).is_applied());
+        assert_eq!(20, cell.get());
+        assert!(!cell.has_update());
 
-        drop(borrow);
+        cell.set(3);
 
-        assert_ne!(5, cell.get())
+        assert_eq!(UpdateOutcome::Rejected, cell.update_if(|_, _| false));
+        assert_eq!(Some(3), cell.cancel_update());
+        assert_eq!(UpdateOutcome::NoUpdate, cell.update_if(|_, _| true));
     }
-    
+
     #[test]
-    #[should_panic]
-    pub fn test_sync_cell_get_poisoned() {
-        let cell = Arc::new(SyncCell::new(4));
+    pub fn test_held_sync_cell_notify_only_waiters() {
+        let cell = Arc::new(HeldSyncCell::new(0));
 
-        let cell2 = cell.clone();
+        cell.set(1);
+        cell.defer(|value| *value += 1);
+        assert_eq!(0, *cell.lock_staged_count());
 
-        let _ = thread::spawn(move || {
-            let _borrow = cell2.borrow();
+        cell.update();
 
-            panic!("Intentional panic.");
-        }).join();
+        let waiter = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.wait_for_pending())
+        };
 
-        assert_ne!(4, cell.get())
+        while cell.staged_waiters.load(AtomicOrdering::SeqCst) == 0 {
+            thread::yield_now();
+        }
+
+        cell.set(2);
+        waiter.join().unwrap();
+
+        assert_eq!(1, *cell.lock_staged_count());
+        assert_eq!(0, cell.staged_waiters.load(AtomicOrdering::SeqCst));
     }
-    
-    #[test]
-    #[should_panic]
-    pub fn test_sync_cell_set_poisoned() {
-        let cell = Arc::new(SyncCell::new(4));
 
-        let cell2 = cell.clone();
+    #[test]
+    pub fn test_held_sync_cell_wait_for_pending() {
+        let cell = Arc::new(HeldSyncCell::new(0));
+        let producer = cell.clone();
+
+        let handle = thread::spawn(move || {
+            for i in 1..=3 {
+                thread::sleep(Duration::from_millis(5));
+                producer.set(i);
+            }
+        });
+
+        let mut last = 0;
+
+        while last != 3 {
+            assert!(cell.wait_and_update());
+            last = cell.get();
+        }
 
-        let _ = thread::spawn(move || {
-            let _borrow = cell2.borrow();
+        handle.join().unwrap();
 
-            panic!("Intentional panic.");
-        }).join();
+        cell.defer(|value| *value = 4);
+        cell.wait_for_pending();
+        assert!(cell.wait_and_update());
+        assert_eq!(4, cell.get());
+    }
 
-        cell.set(5);
+    #[test]
+    pub fn test_sync_cell_from_iter() {
+        let cell: SyncCell<Vec<_>> = (1..=3).collect();
 
-        assert_ne!(5, cell.get());
+        assert_eq!(vec![1, 2, 3], cell.get());
     }
 
+    #[cfg(feature = "derive")]
     #[test]
-    pub fn test_held_sync_cell_new() {
-        let _cell = HeldSyncCell::new(0);
+    pub fn test_sync_fields() {
+        use std::collections::HashMap;
+
+        use crate::SyncFields;
+
+        #[derive(SyncFields)]
+        struct State {
+            /// The handlers of each event.
+            handlers: HashMap<String, fn(u32) -> u32>,
+            pub(crate) count: u32,
+        }
+
+        let state = StateCells::from(State {
+            handlers: HashMap::new(),
+            count: 1,
+        });
+
+        state.handlers().borrow_mut().insert(String::from("double"), |value| value * 2);
+        let double = state.handlers().borrow()["double"];
+        state.count().set(double(state.count().get()));
+
+        let state = State::from(state);
+        assert_eq!(2, state.count);
+        assert_eq!(1, state.handlers.len());
     }
-    
+
     #[test]
-    pub fn test_held_sync_cell_get() {
-        let cell = HeldSyncCell::new(1);
+    pub fn test_sync_cell_debug() {
+        let cell = SyncCell::new(1);
 
-        assert_eq!(1, cell.get())
+        assert_eq!("SyncCell { data: 1, .. }", format!("{:?}", cell));
+
+        let borrowed = cell.borrow_mut();
+        assert_eq!("SyncCell { data: <locked>, .. }", format!("{:?}", cell));
+        drop(borrowed);
     }
-    
+
     #[test]
-    pub fn test_held_sync_cell_set_no_update() {
-        let cell = HeldSyncCell::new(1);
+    pub fn test_sync_cell_eq_value() {
+        let cell = SyncCell::new(5);
+        let held = HeldSyncCell::new(String::from("a"));
 
-        cell.set(2);
+        assert_eq!(cell, 5);
+        assert_ne!(cell, 6);
+        assert_eq!(cell, SyncCell::new(5));
 
-        assert_eq!(true, cell.has_update());
-        assert_eq!(1, cell.get())
+        held.set(String::from("b"));
+        assert_eq!(held, String::from("a"));
     }
-    
+
     #[test]
-    pub fn test_held_sync_cell_set_update() {
-        let cell = HeldSyncCell::new(1);
+    pub fn test_sync_cell_cmp_held_sync_cell() {
+        let cell = SyncCell::new(1);
+        let held = HeldSyncCell::new(1);
 
-        cell.set(2);
-        cell.update();
+        assert!(cell == held);
+        assert!(held == cell);
 
-        assert_eq!(false, cell.has_update());
-        assert_eq!(2, cell.get())
+        held.set(2);
+        assert!(cell == held);
+
+        held.update();
+        assert!(cell < held);
+        assert!(held > cell);
+        assert!(held != cell);
     }
-    
+
     #[test]
-    pub fn test_held_sync_cell_set_double_update() {
+    pub fn test_held_sync_cell_debug() {
         let cell = HeldSyncCell::new(1);
 
+        assert_eq!("HeldSyncCell { current: 1, has_update: false, policy: Manual, .. }", format!("{:?}", cell));
+
         cell.set(2);
-        cell.update();
-        cell.update();
+        assert_eq!("HeldSyncCell { current: 1, has_update: true, policy: Manual, .. }", format!("{:?}", cell));
 
-        assert_eq!(false, cell.has_update());
-        assert_eq!(2, cell.get())
+        let borrowed = cell.borrow_mut();
+        assert_eq!("HeldSyncCell { current: <locked>, has_update: true, policy: Manual, .. }", format!("{:?}", cell));
+        drop(borrowed);
+
+        assert_eq!("1", cell.to_string());
     }
 
     #[test]
-    pub fn test_held_sync_cell_no_set_update() {
-        let cell = HeldSyncCell::new(1);
+    pub fn test_held_sync_cell_set_while_borrowed() {
+        let cell = HeldSyncCell::with_update_policy(vec![1], UpdatePolicy::AfterSets(2));
+        let borrowed = cell.borrow();
 
-        cell.update();
+        cell.set(vec![2]);
+        cell.defer(|value| value.push(3));
+        cell.set_with(|current| current.iter().map(|value| value * 10).collect());
 
-        assert_eq!(false, cell.has_update());
-        assert_eq!(1, cell.get())
+        assert_eq!(vec![1], *borrowed);
+        assert_eq!(Some(vec![10]), cell.get_next());
+
+        drop(borrowed);
+
+        assert_eq!(vec![10, 3], cell.get());
+        assert!(!cell.has_update());
     }
 
     #[test]
-    pub fn test_held_sync_cell_no_set() {
+    pub fn test_held_sync_cell_into_parts() {
         let cell = HeldSyncCell::new(1);
+        assert_eq!((1, None), cell.into_parts());
 
-        assert_eq!(false, cell.has_update());
-        assert_eq!(1, cell.get())
+        let cell = HeldSyncCell::from_parts(1, Some(2));
+        assert_eq!((1, Some(2)), cell.into_parts());
     }
-    
+
     #[test]
-    pub fn test_held_sync_cell_into_inner() {
-        let cell = HeldSyncCell::new(4);
+    pub fn test_held_sync_cell_set_if_absent() {
+        let cell = HeldSyncCell::with_update_policy(0, UpdatePolicy::AfterSets(2));
 
-        assert_eq!(4, cell.into_inner())
+        assert!(cell.set_if_absent(1));
+        assert!(!cell.set_if_absent(2));
+        assert_eq!(0, cell.get());
+
+        cell.set(3);
+        assert_eq!(3, cell.get());
+        assert!(cell.set_if_absent(4));
     }
-    
+
+    #[cfg(all(debug_assertions, feature = "owner-tracking"))]
     #[test]
-    pub fn test_held_sync_cell_set_into_inner() {
-        let cell = HeldSyncCell::new(4);
+    #[should_panic(expected = "Failed to get cell 'counter' value. Lock was poisoned by thread 'writer'")]
+    pub fn test_sync_cell_with_label_poisoned() {
+        let cell = Arc::new(SyncCell::with_label(4, "counter"));
 
-        cell.set(5);
+        let cell2 = cell.clone();
 
-        assert_eq!(5, cell.into_inner())
+        let _ = thread::Builder::new().name(String::from("writer")).spawn(move || {
+            let _borrow = cell2.borrow_mut();
+
+            panic!("Intentional panic.");
+        }).unwrap().join();
+
+        cell.get();
     }
-    
+
+    #[cfg(debug_assertions)]
     #[test]
-    pub fn test_held_sync_cell_set_update_into_inner() {
-        let cell = HeldSyncCell::new(4);
+    #[should_panic(expected = "already borrowed by the current thread")]
+    pub fn test_sync_cell_reentrant_borrow() {
+        let cell = SyncCell::new(1);
+        let _borrowed = cell.borrow();
 
-        cell.set(5);
-        cell.update();
+        cell.set(2);
+    }
 
-        assert_eq!(5, cell.into_inner())
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "already borrowed by the current thread")]
+    pub fn test_held_sync_cell_reentrant_borrow() {
+        let cell = HeldSyncCell::new(1);
+        let _borrowed = cell.borrow_mut();
+
+        cell.get();
     }
-    
+
+    #[cfg(all(debug_assertions, feature = "owner-tracking"))]
     #[test]
-    pub fn test_held_sync_cell_mutable_borrow() {
-        let cell = HeldSyncCell::new(4);
+    pub fn test_sync_cell_error_holder() {
+        use std::sync::Barrier;
 
-        let mut borrow = cell.borrow_mut();
+        let cell = Arc::new(SyncCell::new(1));
+        let (borrowed, release) = (Arc::new(Barrier::new(2)), Arc::new(Barrier::new(2)));
 
-        *borrow = 5;
+        let handle = {
+            let (cell, borrowed, release) = (cell.clone(), borrowed.clone(), release.clone());
+            thread::Builder::new().name(String::from("holder")).spawn(move || {
+                let _borrow = cell.borrow_mut();
+                borrowed.wait();
+                release.wait();
+            }).unwrap()
+        };
 
-        drop(borrow);
+        borrowed.wait();
 
-        assert_eq!(5, cell.get())
-    }
-    
-    #[test]
-    pub fn test_held_sync_cell_mutable_borrow_set() {
-        let cell = HeldSyncCell::new(4);
+        let holder = match cell.try_borrow().err() {
+            Some(SyncCellError::WouldBlock(Some(holder))) => holder,
+            err => panic!("Expected the holder of the borrow, found {:?}", err),
+        };
 
-        let mut borrow = cell.borrow_mut();
+        assert_eq!(Some("holder"), holder.thread().as_ref().and_then(|thread| thread.name()));
+        assert_eq!("Cell is already borrowed mutably by thread 'holder'", cell.try_borrow_mut().unwrap_err().to_string());
+        assert_eq!(Some(SyncCellError::Timeout(Some(holder))), cell.borrow_timeout(Duration::from_millis(10)).err());
 
-        *borrow = 5;
+        release.wait();
+        handle.join().unwrap();
 
-        cell.set(6);
+        assert!(cell.try_borrow_mut().is_ok());
+        assert!(holder.thread().is_none());
+    }
 
-        drop(borrow);
+    #[test]
+    pub fn test_sync_cell_next_item() {
+        let cell = SyncCell::new(vec![1, 2].into_iter());
 
-        assert_eq!(5, cell.get());
-        cell.update();
-        assert_eq!(6, cell.get());
+        assert_eq!(Some(1), cell.next_item());
+        assert_eq!(Some(2), cell.next_item());
+        assert_eq!(None, cell.next_item());
     }
 }
-
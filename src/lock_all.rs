@@ -0,0 +1,232 @@
+use std::{any::TypeId, marker::PhantomData};
+
+use crate::{SyncCell, TransactionGuard};
+
+/// A cell that can be locked together with other cells by `lock_all`.
+/// This is implemented for every `SyncCell` with a value that can be cloned.
+pub trait LockCell {
+    /// Identifies the cell so that cells can be locked in a consistent order.
+    #[doc(hidden)]
+    fn lock_key(&self) -> (usize, TypeId);
+
+    /// Mutably borrows the cell until `lock_all` returns.
+    #[doc(hidden)]
+    fn lock_begin(&self) -> LockedBorrow<'_>;
+}
+
+/// A value borrowed by `lock_all` whose changes can be committed.
+trait RollbackGuard {
+    /// Gets a pointer to the borrowed value.
+    fn data(&mut self) -> *mut ();
+
+    /// Keeps the changes made to the value.
+    fn commit(&mut self);
+}
+
+impl <T> RollbackGuard for TransactionGuard<'_, T> {
+    fn data(&mut self) -> *mut () {
+        (&mut **self as *mut T).cast::<()>()
+    }
+
+    fn commit(&mut self) {
+        self.backup = None;
+    }
+}
+
+/// A cell mutably borrowed by `lock_all`.
+pub struct LockedBorrow<'a> {
+    /// The key of the borrowed cell.
+    key: (usize, TypeId),
+    /// The borrow of the cell, which restores the value when it is dropped unless committed.
+    guard: Box<dyn RollbackGuard + 'a>,
+}
+
+impl <T: Clone + 'static> LockCell for SyncCell<T> {
+    fn lock_key(&self) -> (usize, TypeId) {
+        (self as *const Self as usize, TypeId::of::<T>())
+    }
+
+    fn lock_begin(&self) -> LockedBorrow<'_> {
+        LockedBorrow {
+            key: self.lock_key(),
+            guard: Box::new(self.borrow_mut_transactional()),
+        }
+    }
+}
+
+/// The cells mutably borrowed by a call to `lock_all`.
+pub struct LockedCells<'a> {
+    /// The borrowed cells sorted by key.
+    entries: Vec<LockedBorrow<'a>>,
+    /// Keeps the borrows on the thread that holds the locks.
+    _unsend: PhantomData<*const ()>,
+}
+
+impl LockedCells<'_> {
+    /// Gets a reference to the value of one of the locked cells.
+    ///
+    /// - `cell` - The cell, which must have been passed to `lock_all`.
+    ///
+    /// # Panicking
+    /// This method will panic if the cell was not passed to `lock_all`.
+    pub fn get<T: Clone + 'static>(&mut self, cell: &SyncCell<T>) -> &T {
+        self.get_mut(cell)
+    }
+
+    /// Gets a mutable reference to the value of one of the locked cells.
+    /// Changes are only kept if the function passed to `lock_all` returns `Ok`.
+    ///
+    /// - `cell` - The cell, which must have been passed to `lock_all`.
+    ///
+    /// # Panicking
+    /// This method will panic if the cell was not passed to `lock_all`.
+    pub fn get_mut<T: Clone + 'static>(&mut self, cell: &SyncCell<T>) -> &mut T {
+        let key = cell.lock_key();
+
+        let entry = match self.entries.binary_search_by_key(&key, |entry| entry.key) {
+            Ok(index) => &mut self.entries[index],
+            Err(_) => panic!("Cell was not locked by lock_all."),
+        };
+
+        // SAFETY: The key includes the address and value type of the cell, so the entry borrows
+        // this cell and its value is a `T`. The value is borrowed for as long as the cells are
+        // locked.
+        unsafe { &mut *entry.guard.data().cast::<T>() }
+    }
+}
+
+/// Mutably borrows several cells at once and runs a function that changes them together.
+/// If the function returns `Ok` every change is kept. If it returns `Err` or panics, every cell is
+/// restored to the value it had before, so the cells never show only some of the changes.
+///
+/// This is one of three ways to change cells all or nothing:
+/// - `SyncCell::borrow_mut_transactional` rolls back a change to a single `SyncCell`.
+/// - `lock_all` locks several `SyncCell`s for the whole call and rolls all of them back on
+///   failure.
+/// - `transaction` buffers changes to `TransactionalCell`s without holding locks while the
+///   function runs and retries it on conflicts. Use it for cells that are changed together often.
+///
+/// Cells are always locked in the same order no matter the order they are passed in, so two
+/// calls over the same cells cannot deadlock each other. A cell passed more than once is
+/// only locked once.
+///
+/// - `cells` - The cells that can be accessed by the function.
+/// - `f` - The function changing the cells through the `LockedCells`.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{SyncCell, lock_all};
+///
+/// let from = SyncCell::new(10);
+/// let to = SyncCell::new(0);
+///
+/// let transfer = |amount| lock_all(&[&from, &to], |locked| {
+///     *locked.get_mut(&to) += amount;
+///
+///     let balance = locked.get_mut(&from);
+///     if *balance < amount {
+///         return Err("Insufficient funds");
+///     }
+///
+///     *balance -= amount;
+///     Ok(())
+/// });
+///
+/// assert_eq!(Ok(()), transfer(4));
+/// assert_eq!(Err("Insufficient funds"), transfer(20));
+/// assert_eq!((6, 4), (from.get(), to.get()));
+/// ```
+///
+/// # Panicking
+/// This function will panic if any of the locks become poisoned.
+pub fn lock_all<R, E>(cells: &[&dyn LockCell], f: impl FnOnce(&mut LockedCells<'_>) -> Result<R, E>) -> Result<R, E> {
+    let mut cells = cells.to_vec();
+    cells.sort_by_key(|cell| cell.lock_key());
+    cells.dedup_by_key(|cell| cell.lock_key());
+
+    let mut locked = LockedCells {
+        entries: cells.iter().map(|cell| cell.lock_begin()).collect(),
+        _unsend: PhantomData,
+    };
+
+    let result = f(&mut locked);
+
+    if result.is_ok() {
+        for entry in locked.entries.iter_mut() {
+            entry.guard.commit();
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::{SyncCell, lock_all};
+
+    #[test]
+    pub fn test_lock_all() {
+        let a = SyncCell::new(1);
+        let b = SyncCell::new(String::from("b"));
+
+        let result: Result<(), ()> = lock_all(&[&b, &a, &a], |locked| {
+            *locked.get_mut(&a) += 1;
+            locked.get_mut(&b).push('!');
+            Err(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!((1, String::from("b")), (a.get(), b.get()));
+
+        let len = lock_all::<_, ()>(&[&a, &b], |locked| {
+            *locked.get_mut(&a) += 1;
+            Ok(locked.get(&b).len())
+        });
+
+        assert_eq!(Ok(1), len);
+        assert_eq!(2, a.get());
+    }
+
+    #[test]
+    pub fn test_lock_all_threads() {
+        let cells = Arc::new((SyncCell::new(100), SyncCell::new(0)));
+
+        // Threads pass the cells in opposite orders, which would deadlock without a consistent
+        // lock order.
+        let handles: Vec<_> = (0..4).map(|thread| {
+            let cells = cells.clone();
+            thread::spawn(move || {
+                for _ in 0..25 {
+                    let (a, b) = &*cells;
+                    let order: [&dyn crate::LockCell; 2] = if thread % 2 == 0 { [a, b] } else { [b, a] };
+
+                    lock_all::<_, ()>(&order, |locked| {
+                        *locked.get_mut(a) -= 1;
+                        *locked.get_mut(b) += 1;
+                        Ok(())
+                    }).unwrap();
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!((0, 100), (cells.0.get(), cells.1.get()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cell was not locked by lock_all.")]
+    pub fn test_lock_all_missing_cell() {
+        let a = SyncCell::new(1);
+        let b = SyncCell::new(2);
+
+        let _ = lock_all::<(), ()>(&[&a], |locked| {
+            locked.get_mut(&b);
+            Ok(())
+        });
+    }
+}
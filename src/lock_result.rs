@@ -0,0 +1,78 @@
+//! The results of locking a `SyncCell`.
+//! These are the types of `std::sync` with the `std` feature, and minimal copies of them
+//! otherwise so that poisoning is reported the same way without the standard library.
+
+#[cfg(feature = "std")]
+pub use std::sync::{LockResult, PoisonError, TryLockError, TryLockResult};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::{LockResult, PoisonError, TryLockError, TryLockResult};
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use core::fmt::{self, Debug, Display, Formatter};
+
+    /// The error returned when a lock was poisoned, holding the borrow that was taken anyway.
+    pub struct PoisonError<T> {
+        /// The borrow taken from the poisoned lock.
+        guard: T,
+    }
+
+    impl <T> PoisonError<T> {
+        /// Creates a new `PoisonError` for a borrow of a poisoned lock.
+        ///
+        /// - `guard` - The borrow taken from the poisoned lock.
+        pub fn new(guard: T) -> Self {
+            Self {
+                guard,
+            }
+        }
+
+        /// Gets the borrow taken from the poisoned lock.
+        pub fn into_inner(self) -> T {
+            self.guard
+        }
+
+        /// Gets a reference to the borrow taken from the poisoned lock.
+        pub fn get_ref(&self) -> &T {
+            &self.guard
+        }
+
+        /// Gets a mutable reference to the borrow taken from the poisoned lock.
+        pub fn get_mut(&mut self) -> &mut T {
+            &mut self.guard
+        }
+    }
+
+    impl <T> Debug for PoisonError<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.debug_struct("PoisonError").finish_non_exhaustive()
+        }
+    }
+
+    impl <T> Display for PoisonError<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "poisoned lock: another task failed inside")
+        }
+    }
+
+    /// The error returned when a lock could not be taken without blocking.
+    pub enum TryLockError<T> {
+        /// The lock was poisoned.
+        Poisoned(PoisonError<T>),
+        /// The lock is held by another borrow.
+        WouldBlock,
+    }
+
+    impl <T> From<PoisonError<T>> for TryLockError<T> {
+        fn from(err: PoisonError<T>) -> Self {
+            Self::Poisoned(err)
+        }
+    }
+
+    /// The result of taking a lock that may be poisoned.
+    pub type LockResult<T> = Result<T, PoisonError<T>>;
+
+    /// The result of trying to take a lock without blocking.
+    pub type TryLockResult<T> = Result<T, TryLockError<T>>;
+}
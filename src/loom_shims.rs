@@ -0,0 +1,133 @@
+//! The replacements used when the crate is built with `--cfg loom`, so that `loom` can explore
+//! every interleaving of the threads using a `SyncCell` or `HeldSyncCell`.
+//! Every lock taken by a cell is then a `loom` lock, and the thread locals used to detect
+//! reentrant borrows are kept for each `loom` thread instead of each thread of the test.
+
+use std::sync::OnceLock;
+
+use loom::sync::{Condvar, Mutex, MutexGuard};
+
+use crate::RawSyncLock;
+
+#[cfg(debug_assertions)]
+pub(crate) use loom::thread_local;
+
+/// The borrows held of a `LoomRawLock`.
+#[derive(Debug)]
+struct LoomLockState {
+    /// The number of shared locks held.
+    readers: usize,
+    /// Whether an exclusive lock is held.
+    writer: bool,
+}
+
+/// The `loom` primitives of a `LoomRawLock`.
+#[derive(Debug)]
+struct LoomLock {
+    /// The borrows currently held.
+    state: Mutex<LoomLockState>,
+    /// The condition variable used to wake threads waiting for the lock.
+    released: Condvar,
+}
+
+/// The backend of `SyncCell` and `RawSyncCell` when the crate is built with `--cfg loom`.
+/// This is the `DefaultRawLock` in that case, so `HeldSyncCell` and the other cells are modelled by
+/// `loom` as well.
+///
+/// The primitives of `loom` can only be created while a model is running, so they are created
+/// when the lock is first used, which lets `INIT` stay a constant.
+/// They are boxed so that the lock stays as small as the other backends.
+#[derive(Debug)]
+pub struct LoomRawLock {
+    /// The primitives of the lock, created when it is first used.
+    lock: OnceLock<Box<LoomLock>>,
+}
+
+impl LoomRawLock {
+    /// Locks the borrow counts of this lock.
+    fn state(&self) -> (MutexGuard<'_, LoomLockState>, &Condvar) {
+        let lock = self.lock.get_or_init(|| Box::new(LoomLock {
+            state: Mutex::new(LoomLockState {
+                readers: 0,
+                writer: false,
+            }),
+            released: Condvar::new(),
+        }));
+
+        // A panic never leaves the counts inconsistent, so a poisoned lock can be used.
+        let state = match lock.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        };
+
+        (state, &lock.released)
+    }
+
+    /// Blocks while `condition` holds for the locked borrow counts and then changes them.
+    fn wait(&self, condition: impl Fn(&LoomLockState) -> bool, change: impl FnOnce(&mut LoomLockState)) {
+        let (mut state, released) = self.state();
+
+        while condition(&state) {
+            state = match released.wait(state) {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+        }
+
+        change(&mut state);
+    }
+}
+
+unsafe impl RawSyncLock for LoomRawLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        lock: OnceLock::new(),
+    };
+
+    fn lock_shared(&self) {
+        self.wait(|state| state.writer, |state| state.readers += 1);
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        let (mut state, _) = self.state();
+
+        if state.writer {
+            return false;
+        }
+
+        state.readers += 1;
+        true
+    }
+
+    unsafe fn unlock_shared(&self) {
+        let (mut state, released) = self.state();
+
+        state.readers -= 1;
+
+        if state.readers == 0 {
+            released.notify_all();
+        }
+    }
+
+    fn lock_exclusive(&self) {
+        self.wait(|state| state.writer || state.readers > 0, |state| state.writer = true);
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        let (mut state, _) = self.state();
+
+        if state.writer || state.readers > 0 {
+            return false;
+        }
+
+        state.writer = true;
+        true
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        let (mut state, released) = self.state();
+
+        state.writer = false;
+        released.notify_all();
+    }
+}
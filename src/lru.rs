@@ -0,0 +1,267 @@
+use std::{borrow::Borrow, collections::{BTreeMap, HashMap}, hash::Hash, sync::{Arc, Mutex, MutexGuard, OnceLock}};
+
+/// An entry of a `SyncLruCache`.
+#[derive(Debug)]
+struct LruEntry<V> {
+    /// The tick at which the entry was last used.
+    used: u64,
+    /// The value of the entry, which is empty while it is being computed.
+    value: Arc<OnceLock<V>>,
+}
+
+/// The entries of a `SyncLruCache`.
+#[derive(Debug)]
+struct LruState<K, V> {
+    /// The entries by key.
+    entries: HashMap<K, LruEntry<V>>,
+    /// The keys of the entries by the tick at which they were last used.
+    order: BTreeMap<u64, K>,
+    /// The tick given to the next use of an entry.
+    tick: u64,
+}
+
+impl <K: Eq + Hash + Clone, V> LruState<K, V> {
+    /// Marks an entry as the most recently used.
+    fn touch<Q: Eq + Hash + ?Sized>(&mut self, key: &Q) -> Option<Arc<OnceLock<V>>> where K: Borrow<Q> {
+        let entry = self.entries.get_mut(key)?;
+
+        let key = match self.order.remove(&entry.used) {
+            Some(key) => key,
+            None => unreachable!("Cache entry was missing from the use order."),
+        };
+        entry.used = self.tick;
+        self.order.insert(self.tick, key);
+        self.tick += 1;
+
+        Some(entry.value.clone())
+    }
+
+    /// Adds an entry as the most recently used, evicting the least recently used entries to stay
+    /// within a capacity.
+    fn add(&mut self, key: K, value: Arc<OnceLock<V>>, capacity: usize) {
+        if let Some(entry) = self.entries.remove(&key) {
+            self.order.remove(&entry.used);
+        }
+
+        while self.entries.len() >= capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => self.entries.remove(&oldest),
+                None => break,
+            };
+        }
+
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(key, LruEntry {
+            used: self.tick,
+            value,
+        });
+        self.tick += 1;
+    }
+}
+
+/// A cache of a fixed number of values that can be shared between threads.
+/// When the cache is full the least recently used value is evicted to make space.
+///
+/// `get_or_insert_with` computes a missing value at most once even when several threads ask for
+/// the same key at the same time. The other threads wait for the value instead of computing it
+/// again. The cache is not locked while a value is computed.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncLruCache;
+///
+/// let cache = SyncLruCache::new(2);
+///
+/// assert_eq!(4, cache.get_or_insert_with(2, || 2 * 2));
+/// assert_eq!(9, cache.get_or_insert_with(3, || 3 * 3));
+///
+/// // The value of 2 is already cached so it is not computed again.
+/// assert_eq!(4, cache.get_or_insert_with(2, || unreachable!()));
+///
+/// // 3 is the least recently used so it is evicted.
+/// cache.insert(4, 16);
+/// assert_eq!(None, cache.get(&3));
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this cache will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct SyncLruCache<K, V> {
+    /// The entries of this cache.
+    data: Mutex<LruState<K, V>>,
+    /// The maximum number of entries in this cache.
+    capacity: usize,
+}
+
+impl <K: Eq + Hash + Clone, V> SyncLruCache<K, V> {
+    /// Creates a new empty `SyncLruCache`.
+    ///
+    /// - `capacity` - The maximum number of values. A capacity of zero is treated as one.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data: Mutex::new(LruState {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                tick: 0,
+            }),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Gets the maximum number of values in this cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets the number of values in this cache including any that are still being computed.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Checks if this cache has no values.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Inserts a value into this cache as the most recently used value.
+    /// Any previous value for the key is replaced.
+    ///
+    /// - `key` - The key of the value.
+    /// - `value` - The value to insert.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn insert(&self, key: K, value: V) {
+        self.lock().add(key, Arc::new(OnceLock::from(value)), self.capacity);
+    }
+
+    /// Removes a value from this cache.
+    /// Returns whether the key was present.
+    ///
+    /// - `key` - The key of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn remove<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        let mut data = self.lock();
+
+        match data.entries.remove(key) {
+            Some(entry) => {
+                data.order.remove(&entry.used);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Removes every value from this cache.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn clear(&self) {
+        let mut data = self.lock();
+        data.entries.clear();
+        data.order.clear();
+    }
+
+    /// Locks the entries of this cache.
+    fn lock(&self) -> MutexGuard<'_, LruState<K, V>> {
+        match self.data.lock() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+impl <K: Eq + Hash + Clone, V: Clone> SyncLruCache<K, V> {
+    /// Gets a copy of a value in this cache and marks it as the most recently used.
+    /// Returns `None` if the key is not present or its value is still being computed.
+    ///
+    /// - `key` - The key of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.lock().touch(key)?.get().cloned()
+    }
+
+    /// Gets a copy of a value in this cache, computing and inserting it if the key is not present.
+    /// If another thread is already computing the value this waits for it rather than computing it
+    /// again. If the computing thread panics, one of the waiting threads computes it instead.
+    ///
+    /// - `key` - The key of the value.
+    /// - `f` - The function computing the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V {
+        let value = {
+            let mut data = self.lock();
+
+            match data.touch(&key) {
+                Some(value) => value,
+                None => {
+                    let value = Arc::new(OnceLock::new());
+                    data.add(key, value.clone(), self.capacity);
+                    value
+                },
+            }
+        };
+
+        value.get_or_init(f).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, atomic::{AtomicUsize, Ordering}}, thread, time::Duration};
+
+    use crate::SyncLruCache;
+
+    #[test]
+    pub fn test_sync_lru_cache_evict() {
+        let cache = SyncLruCache::new(2);
+
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(Some(1), cache.get("a"));
+
+        cache.insert("c", 3);
+
+        assert_eq!(2, cache.len());
+        assert_eq!(None, cache.get("b"));
+        assert!(cache.remove("a"));
+        assert_eq!(Some(3), cache.get("c"));
+    }
+
+    #[test]
+    pub fn test_sync_lru_cache_compute_once() {
+        let cache = Arc::new(SyncLruCache::new(4));
+        let computed = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..8).map(|_| {
+            let cache = cache.clone();
+            let computed = computed.clone();
+            thread::spawn(move || {
+                cache.get_or_insert_with(1, || {
+                    computed.fetch_add(1, Ordering::Relaxed);
+                    thread::sleep(Duration::from_millis(20));
+                    10
+                })
+            })
+        }).collect();
+
+        for handle in handles {
+            assert_eq!(10, handle.join().unwrap());
+        }
+
+        assert_eq!(1, computed.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,126 @@
+/// Creates a `SyncCell` or declares `static` cells whose initial values are computed on first
+/// use.
+///
+/// Given an expression this is the same as `SyncCell::new`. Given `static` declarations each one
+/// becomes a `LazyLock<SyncCell<T>>` so the initial value can be built by any code, such as a
+/// builder or a file read, instead of having to be a constant. The static dereferences to the
+/// cell so every method of `SyncCell` can be used directly.
+///
+/// An initial value written as `const { ... }` is evaluated at compile time instead, like in
+/// `thread_local!`. The static is then a plain `SyncCell<T>`, which does not need to check
+/// whether the value has been created yet.
+///
+/// # Usage
+/// ```
+/// use sync_cell::sync_cell;
+///
+/// sync_cell! {
+///     /// The names of the connected users.
+///     static USERS: Vec<String> = vec![String::from("admin")];
+///     pub static LIMIT: usize = "16".parse().unwrap();
+///     static RETRIES: u32 = const { 3 };
+/// }
+///
+/// USERS.borrow_mut().push(String::from("guest"));
+///
+/// assert_eq!(2, USERS.borrow().len());
+/// assert_eq!(16, LIMIT.get());
+/// assert_eq!(3, RETRIES.get());
+///
+/// let cell = sync_cell!(5);
+/// assert_eq!(5, cell.get());
+/// ```
+#[macro_export]
+macro_rules! sync_cell {
+    () => {};
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = const $value:block; $($rest:tt)*) => {
+        $(#[$attr])*
+        $vis static $name: $crate::SyncCell<$t> = $crate::SyncCell::new($value);
+        $crate::sync_cell!($($rest)*);
+    };
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $value:expr; $($rest:tt)*) => {
+        $(#[$attr])*
+        $vis static $name: ::std::sync::LazyLock<$crate::SyncCell<$t>> = ::std::sync::LazyLock::new(|| $crate::SyncCell::new($value));
+        $crate::sync_cell!($($rest)*);
+    };
+    ($value:expr $(,)?) => {
+        $crate::SyncCell::new($value)
+    };
+}
+
+/// Creates a `HeldSyncCell` or declares `static` held cells whose initial values are computed on
+/// first use.
+///
+/// Given an expression this is the same as `HeldSyncCell::new`. Given `static` declarations each
+/// one becomes a `LazyLock<HeldSyncCell<T>>` so the initial value can be built by any code.
+///
+/// An initial value written as `const { ... }` is evaluated at compile time instead, like in
+/// `thread_local!`. The static is then a plain `HeldSyncCell<T>`, which does not need to check
+/// whether the value has been created yet.
+///
+/// # Usage
+/// ```
+/// use sync_cell::held_sync_cell;
+///
+/// held_sync_cell! {
+///     static STATE: u32 = const { 1 };
+///     static NAME: String = String::from("menu");
+/// }
+///
+/// STATE.set(2);
+/// assert_eq!(1, STATE.get());
+///
+/// STATE.update();
+/// assert_eq!(2, STATE.get());
+/// assert_eq!("menu", NAME.get());
+/// ```
+#[macro_export]
+macro_rules! held_sync_cell {
+    () => {};
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = const $value:block; $($rest:tt)*) => {
+        $(#[$attr])*
+        $vis static $name: $crate::HeldSyncCell<$t> = $crate::HeldSyncCell::new($value);
+        $crate::held_sync_cell!($($rest)*);
+    };
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $value:expr; $($rest:tt)*) => {
+        $(#[$attr])*
+        $vis static $name: ::std::sync::LazyLock<$crate::HeldSyncCell<$t>> = ::std::sync::LazyLock::new(|| $crate::HeldSyncCell::new($value));
+        $crate::held_sync_cell!($($rest)*);
+    };
+    ($value:expr $(,)?) => {
+        $crate::HeldSyncCell::new($value)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    sync_cell! {
+        static MAP: HashMap<u32, &'static str> = HashMap::from([(1, "a")]);
+    }
+
+    held_sync_cell! {
+        static HELD: Vec<u32> = Vec::new();
+        static CONST_HELD: Vec<u32> = const { Vec::new() };
+    }
+
+    #[test]
+    pub fn test_sync_cell_macros() {
+        MAP.borrow_mut().insert(2, "b");
+        assert_eq!(2, MAP.borrow().len());
+
+        HELD.set(vec![1]);
+        assert!(HELD.update());
+        assert_eq!(vec![1], HELD.get());
+
+        // A constant initial value declares a plain static.
+        let held: &'static crate::HeldSyncCell<Vec<u32>> = &CONST_HELD;
+        held.set(vec![2]);
+        held.update();
+        assert_eq!(vec![2], held.get());
+
+        let cell = held_sync_cell!(1);
+        assert_eq!(1, cell.get());
+    }
+}
@@ -0,0 +1,130 @@
+use std::{borrow::Borrow, collections::{HashMap, hash_map::Entry}, hash::{BuildHasher, Hash}};
+
+use crate::{RawSyncLock, SyncCell};
+
+impl <K: Eq + Hash, V, S: BuildHasher, L: RawSyncLock> SyncCell<HashMap<K, V, S>, L> {
+    /// Inserts a value into the map in this cell.
+    /// The previous value for the key is returned or `None` if the key was not present.
+    ///
+    /// - `key` - The key to insert the value at.
+    /// - `value` - The value to insert.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::collections::HashMap;
+    /// use sync_cell::SyncCell;
+    ///
+    /// let scores = SyncCell::new(HashMap::new());
+    ///
+    /// assert_eq!(None, scores.insert("alice", 1));
+    /// assert_eq!(Some(1), scores.insert("alice", 2));
+    /// assert_eq!(Some(2), scores.get_cloned("alice"));
+    /// assert_eq!(Some(2), scores.remove("alice"));
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.borrow_mut().insert(key, value)
+    }
+
+    /// Removes a key from the map in this cell.
+    /// The removed value is returned or `None` if the key was not present.
+    ///
+    /// - `key` - The key to remove.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn remove<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.borrow_mut().remove(key)
+    }
+
+    /// Checks if the map in this cell contains a key.
+    ///
+    /// - `key` - The key to look for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn contains_key<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.borrow().contains_key(key)
+    }
+
+    /// Runs a function on the entry for a key in the map in this cell.
+    /// The whole operation is performed under a single mutable borrow, so no other thread can
+    /// change the entry between looking it up and modifying it. The result of the function is
+    /// returned.
+    ///
+    /// - `key` - The key of the entry.
+    /// - `f` - The function to run on the entry.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::collections::HashMap;
+    /// use sync_cell::SyncCell;
+    ///
+    /// let counts = SyncCell::new(HashMap::new());
+    ///
+    /// for word in ["a", "b", "a"] {
+    ///     counts.entry_with(word, |entry| *entry.or_insert(0) += 1);
+    /// }
+    ///
+    /// assert_eq!(Some(2), counts.get_cloned("a"));
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn entry_with<R>(&self, key: K, f: impl FnOnce(Entry<'_, K, V>) -> R) -> R {
+        f(self.borrow_mut().entry(key))
+    }
+}
+
+impl <K: Eq + Hash, V: Clone, S: BuildHasher, L: RawSyncLock> SyncCell<HashMap<K, V, S>, L> {
+    /// Gets a copy of the value for a key in the map in this cell.
+    /// Returns `None` if the key is not present.
+    ///
+    /// - `key` - The key to look up.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_cloned<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.borrow().get(key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_map() {
+        let cell = SyncCell::new(HashMap::new());
+
+        assert_eq!(None, cell.insert(1, "a"));
+        assert!(cell.contains_key(&1));
+        assert_eq!(Some("a"), cell.get_cloned(&1));
+        assert_eq!(Some("a"), cell.remove(&1));
+        assert_eq!(None, cell.get_cloned(&1));
+    }
+
+    #[test]
+    pub fn test_sync_cell_map_entry_with() {
+        let cell = Arc::new(SyncCell::new(HashMap::new()));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    cell.entry_with(i % 10, |entry| *entry.or_insert(0) += 1);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!((0..10).all(|key| cell.get_cloned(&key) == Some(40)));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{fmt::{self, Debug, Formatter}, sync::Arc};
+
+use crate::{RawSyncLock, SyncCell};
+
+impl <T: ?Sized + Send + Sync, L: RawSyncLock + Sync> SyncCell<T, L> {
+    /// Creates a read-only view of a value derived from the value of this cell.
+    /// The derived value is computed by the view each time it is read while this cell is
+    /// immutably borrowed. This allows derived data such as a count or a summary to be handed out
+    /// without exposing the whole cell.
+    ///
+    /// - `f` - The function computing the derived value.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let users = SyncCell::new(vec!["admin"]);
+    /// let count = users.map_view(|users| users.len());
+    ///
+    /// users.borrow_mut().push("guest");
+    ///
+    /// assert_eq!(2, count.get());
+    /// ```
+    pub fn map_view<'a, U>(&'a self, f: impl Fn(&T) -> U + Send + Sync + 'a) -> MappedView<'a, U> {
+        MappedView {
+            get: Arc::new(move || f(&self.borrow())),
+        }
+    }
+}
+
+/// A read-only view of a value derived from a `SyncCell`, returned by `SyncCell::map_view`.
+/// Views are cheap to clone and do not reveal the type of the cell they read from.
+pub struct MappedView<'a, U> {
+    /// The function reading the cell and computing the derived value.
+    get: Arc<dyn Fn() -> U + Send + Sync + 'a>,
+}
+
+impl <'a, U> MappedView<'a, U> {
+    /// Computes the derived value from the current value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> U {
+        (self.get)()
+    }
+
+    /// Creates a view of a value derived from the value of this view.
+    ///
+    /// - `f` - The function computing the new derived value.
+    pub fn map<V>(self, f: impl Fn(U) -> V + Send + Sync + 'a) -> MappedView<'a, V> where U: 'a {
+        MappedView {
+            get: Arc::new(move || f(self.get())),
+        }
+    }
+}
+
+impl <U> Clone for MappedView<'_, U> {
+    fn clone(&self) -> Self {
+        Self {
+            get: self.get.clone(),
+        }
+    }
+}
+
+impl <U> Debug for MappedView<'_, U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedView").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_map_view() {
+        let cell = SyncCell::new(vec![1, 2, 3]);
+        let total = cell.map_view(|values| values.iter().sum::<i32>());
+        let doubled = total.clone().map(|total| total * 2);
+
+        thread::scope(|scope| {
+            scope.spawn(|| cell.borrow_mut().push(4)).join().unwrap();
+            scope.spawn(|| assert_eq!(10, total.get()));
+        });
+
+        assert_eq!(20, doubled.get());
+    }
+}
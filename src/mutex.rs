@@ -0,0 +1,188 @@
+use std::{mem::replace, sync::{Mutex, MutexGuard, TryLockError}};
+
+/// A variant of `SyncCell` that is backed by a `std::sync::Mutex` instead of an `RwLock`.
+/// Every borrow is exclusive, which is cheaper than an `RwLock` for cells that are written about
+/// as often as they are read.
+///
+/// `borrow` and `borrow_mut` are both provided so that this cell can replace a `SyncCell`
+/// without changing any call sites, but they take the same lock.
+///
+/// # Usage
+/// ```
+/// use sync_cell::MutexSyncCell;
+///
+/// let cell = MutexSyncCell::new(0);
+///
+/// cell.set(1);
+/// *cell.borrow_mut() += 1;
+///
+/// assert_eq!(2, cell.get());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this cell will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct MutexSyncCell<T: ?Sized> {
+    /// The internal lock holding the data of this cell.
+    data: Mutex<T>,
+}
+
+impl <T> MutexSyncCell<T> {
+    /// Creates a new `MutexSyncCell`.
+    ///
+    /// - `data` - The initial value of the `MutexSyncCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            data: Mutex::new(data),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the internal value contained in this cell.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Retrieves the inner value stored in this `MutexSyncCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        match self.data.into_inner() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+impl <T: ?Sized> MutexSyncCell<T> {
+    /// Borrows a reference to the data stored in this cell.
+    /// This takes the same exclusive lock as `borrow_mut`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> MutexGuard<'_, T> {
+        self.borrow_mut()
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> MutexGuard<'_, T> {
+        match self.data.lock() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell without blocking.
+    /// Returns `None` if the value is already borrowed.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn try_borrow(&self) -> Option<MutexGuard<'_, T>> {
+        match self.data.try_lock() {
+            Ok(data) => Some(data),
+            Err(TryLockError::WouldBlock) => None,
+            Err(TryLockError::Poisoned(err)) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Gets a mutable reference to the value of this cell.
+    /// No locking is required as the cell is borrowed mutably.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get_mut(&mut self) -> &mut T {
+        match self.data.get_mut() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+impl <T: Clone> MutexSyncCell<T> {
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl <T: Clone> Clone for MutexSyncCell<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.get())
+    }
+}
+
+impl <T: Default> Default for MutexSyncCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for MutexSyncCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::MutexSyncCell;
+
+    #[test]
+    pub fn test_mutex_sync_cell_borrow() {
+        let cell = MutexSyncCell::new(1);
+        let borrowed = cell.borrow();
+
+        assert!(cell.try_borrow().is_none());
+
+        drop(borrowed);
+
+        assert_eq!(1, cell.replace(2));
+        assert_eq!(Some(2), cell.try_borrow().map(|value| *value));
+        assert_eq!(2, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_mutex_sync_cell_threads() {
+        let cell = Arc::new(MutexSyncCell::new(0));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    *cell.borrow_mut() += 1;
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(400, cell.get());
+    }
+}
@@ -0,0 +1,280 @@
+use std::{mem::replace, ops::Deref, sync::{Arc, Weak}};
+
+use crate::SyncCell;
+
+/// A version of the value in an `MvccCell`.
+#[derive(Debug)]
+struct Version<T> {
+    /// The number of this version.
+    number: u64,
+    /// The value at this version.
+    value: T,
+}
+
+/// The versions of an `MvccCell`.
+#[derive(Debug)]
+struct Versions<T> {
+    /// The newest version.
+    current: Arc<Version<T>>,
+    /// The older versions that may still be pinned by readers, oldest first.
+    history: Vec<Weak<Version<T>>>,
+}
+
+/// A cell that keeps every version of its value that is still being read.
+/// Each write creates a new version. Readers pin the newest version when they begin reading and
+/// keep seeing it for as long as they hold the read, without blocking writers or being affected
+/// by later writes. Old versions are freed once no reader has them pinned.
+///
+/// # Usage
+/// ```
+/// use sync_cell::MvccCell;
+///
+/// let cell = MvccCell::new(vec![1]);
+///
+/// let read = cell.begin_read();
+///
+/// cell.update(|value| {
+///     let mut value = value.clone();
+///     value.push(2);
+///     value
+/// });
+///
+/// // The pinned version is unaffected by the write.
+/// assert_eq!(vec![1], *read);
+/// assert_eq!(vec![1, 2], *cell.begin_read());
+/// assert_eq!(0, read.version());
+/// assert_eq!(1, cell.version());
+/// ```
+#[derive(Debug)]
+pub struct MvccCell<T> {
+    /// The versions of this cell.
+    versions: SyncCell<Versions<T>>,
+}
+
+impl <T> MvccCell<T> {
+    /// Creates a new `MvccCell` at version 0.
+    ///
+    /// - `data` - The initial value of the `MvccCell`.
+    pub fn new(data: T) -> Self {
+        Self {
+            versions: SyncCell::new(Versions {
+                current: Arc::new(Version {
+                    number: 0,
+                    value: data,
+                }),
+                history: Vec::new(),
+            }),
+        }
+    }
+
+    /// Pins the newest version of the value for reading.
+    /// The pinned version is kept until the returned guard is dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn begin_read(&self) -> MvccReadGuard<T> {
+        MvccReadGuard {
+            version: self.versions.borrow().current.clone(),
+        }
+    }
+
+    /// Pins an older version of the value for reading.
+    /// Returns `None` if the version does not exist or has already been freed.
+    ///
+    /// - `version` - The number of the version to read.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn read_version(&self, version: u64) -> Option<MvccReadGuard<T>> {
+        let versions = self.versions.borrow();
+
+        if versions.current.number == version {
+            return Some(MvccReadGuard {
+                version: versions.current.clone(),
+            });
+        }
+
+        versions.history.iter()
+            .filter_map(Weak::upgrade)
+            .find(|pinned| pinned.number == version)
+            .map(|version| MvccReadGuard {
+                version,
+            })
+    }
+
+    /// Writes a new version of the value.
+    /// Readers that have already pinned a version are unaffected. The number of the new version
+    /// is returned.
+    ///
+    /// - `value` - The value of the new version.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn write(&self, value: T) -> u64 {
+        let mut versions = self.versions.borrow_mut();
+        Self::push(&mut versions, value)
+    }
+
+    /// Writes a new version of the value computed from the newest version.
+    /// Writers are serialised so concurrent updates are applied one after another. The number of
+    /// the new version is returned.
+    ///
+    /// - `f` - The function used to compute the new value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn update(&self, f: impl FnOnce(&T) -> T) -> u64 {
+        let mut versions = self.versions.borrow_mut();
+        let value = f(&versions.current.value);
+        Self::push(&mut versions, value)
+    }
+
+    /// Gets the number of the newest version.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn version(&self) -> u64 {
+        self.versions.borrow().current.number
+    }
+
+    /// Gets the number of versions that are still alive, including the newest version.
+    /// Freed versions are removed from the history while counting.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn live_versions(&self) -> usize {
+        let mut versions = self.versions.borrow_mut();
+        Self::collect(&mut versions);
+        versions.history.len() + 1
+    }
+
+    /// Makes a version the newest version and remembers the previous newest version while it is
+    /// pinned.
+    fn push(versions: &mut Versions<T>, value: T) -> u64 {
+        let number = versions.current.number + 1;
+        let previous = replace(&mut versions.current, Arc::new(Version {
+            number,
+            value,
+        }));
+
+        Self::collect(versions);
+
+        if Arc::strong_count(&previous) > 1 {
+            versions.history.push(Arc::downgrade(&previous));
+        }
+
+        number
+    }
+
+    /// Removes versions that are no longer pinned from the history.
+    fn collect(versions: &mut Versions<T>) {
+        versions.history.retain(|version| version.strong_count() > 0);
+    }
+}
+
+impl <T: Clone> MvccCell<T> {
+    /// Gets a copy of the newest version of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        T::clone(&self.begin_read())
+    }
+
+    /// Retrieves the newest version of the value stored in this `MvccCell`.
+    /// The value is cloned if a reader still has it pinned.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        let version = self.versions.into_inner().current;
+
+        match Arc::try_unwrap(version) {
+            Ok(version) => version.value,
+            Err(version) => version.value.clone(),
+        }
+    }
+}
+
+impl <T: Default> Default for MvccCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for MvccCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A pinned version of the value in an `MvccCell`.
+/// The version is kept alive until this guard is dropped.
+#[derive(Debug)]
+pub struct MvccReadGuard<T> {
+    /// The pinned version.
+    version: Arc<Version<T>>,
+}
+
+impl <T> MvccReadGuard<T> {
+    /// Gets the number of the pinned version.
+    pub fn version(&self) -> u64 {
+        self.version.number
+    }
+}
+
+impl <T> Clone for MvccReadGuard<T> {
+    fn clone(&self) -> Self {
+        Self {
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl <T> Deref for MvccReadGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.version.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MvccCell;
+
+    #[test]
+    pub fn test_mvcc_cell_pinned_read() {
+        let cell = MvccCell::new(1);
+        let read = cell.begin_read();
+
+        assert_eq!(1, cell.write(2));
+        assert_eq!(2, cell.update(|value| value + 1));
+
+        assert_eq!(1, *read);
+        assert_eq!(0, read.version());
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_mvcc_cell_garbage_collection() {
+        let cell = MvccCell::new(0);
+        let first = cell.begin_read();
+
+        cell.write(1);
+        let second = cell.begin_read();
+        cell.write(2);
+        cell.write(3);
+
+        assert_eq!(3, cell.live_versions());
+        assert_eq!(Some(1), cell.read_version(1).map(|read| *read));
+        assert!(cell.read_version(2).is_none());
+
+        drop(first);
+        assert_eq!(2, cell.live_versions());
+
+        drop(second);
+        assert_eq!(1, cell.live_versions());
+        assert_eq!(3, cell.into_inner());
+    }
+}
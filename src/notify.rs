@@ -0,0 +1,205 @@
+use std::sync::{Condvar, Mutex, MutexGuard, atomic::{AtomicUsize, Ordering}};
+
+use crate::{DefaultRawLock, RawSyncLock, SyncCell, SyncCellRef};
+
+/// A `RawSyncLock` backend that wakes the threads blocked in `SyncCell::wait_for` whenever an
+/// exclusive lock is released.
+/// Every change to the value of a cell, including `set`, ends by releasing an exclusive lock, so
+/// waiting threads check their condition again after each change. Nothing is locked or notified
+/// on release unless a thread is waiting. It wraps another backend that does the actual locking.
+///
+/// This type is only available with the `std` feature.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::{NotifyingRawLock, RawSyncLock, SyncCell};
+///
+/// let cell: Arc<SyncCell<i32, NotifyingRawLock>> = Arc::new(SyncCell::with_lock(0, NotifyingRawLock::INIT));
+///
+/// let producer = {
+///     let cell = cell.clone();
+///     thread::spawn(move || {
+///         for i in 1..=5 {
+///             cell.set(i);
+///         }
+///     })
+/// };
+///
+/// let value = cell.wait_for(|value| *value == 5);
+/// assert_eq!(5, *value);
+/// # drop(value);
+/// # producer.join().unwrap();
+/// ```
+pub struct NotifyingRawLock<L = DefaultRawLock> {
+    /// The lock doing the actual locking.
+    inner: L,
+    /// The number of threads waiting in `wait_for`.
+    /// Releasing an exclusive lock only notifies `released` while this is not zero.
+    waiters: AtomicUsize,
+    /// The number of exclusive locks released while a thread was waiting.
+    releases: Mutex<u64>,
+    /// The condition variable notified when an exclusive lock is released while a thread is
+    /// waiting.
+    released: Condvar,
+}
+
+impl <L: RawSyncLock> NotifyingRawLock<L> {
+    /// Creates a new `NotifyingRawLock` wrapping an existing lock.
+    ///
+    /// - `inner` - The unlocked lock doing the actual locking.
+    pub const fn new(inner: L) -> Self {
+        Self {
+            inner,
+            waiters: AtomicUsize::new(0),
+            releases: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Locks the number of exclusive locks released while a thread was waiting.
+    fn lock_releases(&self) -> MutexGuard<'_, u64> {
+        match self.releases.lock() {
+            Ok(releases) => releases,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+unsafe impl <L: RawSyncLock> RawSyncLock for NotifyingRawLock<L> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self::new(L::INIT);
+
+    fn lock_shared(&self) {
+        self.inner.lock_shared();
+    }
+
+    fn lock_shared_recursive(&self) {
+        self.inner.lock_shared_recursive();
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        self.inner.try_lock_shared()
+    }
+
+    unsafe fn unlock_shared(&self) {
+        // SAFETY: The caller holds a shared lock, which was acquired from the inner lock.
+        unsafe { self.inner.unlock_shared() }
+    }
+
+    fn lock_exclusive(&self) {
+        self.inner.lock_exclusive();
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        self.inner.try_lock_exclusive()
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        // SAFETY: The caller holds an exclusive lock, which was acquired from the inner lock.
+        unsafe { self.inner.unlock_exclusive() }
+
+        // A waiter registers itself before checking the value, so either it sees the change or
+        // this sees the waiter.
+        if self.waiters.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+
+        *self.lock_releases() += 1;
+        self.released.notify_all();
+    }
+}
+
+/// Unregisters a thread waiting in `wait_for`, even if its condition panics.
+struct Waiting<'a>(&'a AtomicUsize);
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> SyncCell<T, NotifyingRawLock<L>> {
+    /// Blocks the current thread until the value of this cell satisfies a condition.
+    /// The condition is checked immediately and then again every time a mutable borrow of the
+    /// value is released. The returned borrow holds the lock, so the value cannot change until it
+    /// is dropped.
+    /// This method is only available with the `std` feature.
+    ///
+    /// - `condition` - The condition to wait for.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait_for(&self, mut condition: impl FnMut(&T) -> bool) -> SyncCellRef<'_, T, NotifyingRawLock<L>> {
+        let lock = self.data.raw_lock();
+
+        lock.waiters.fetch_add(1, Ordering::SeqCst);
+        let _waiting = Waiting(&lock.waiters);
+
+        loop {
+            let seen = *lock.lock_releases();
+
+            // The value is checked without holding the count so that releasing a mutable borrow
+            // never waits for a condition. Any change made after the count was read changes it.
+            let value = self.borrow();
+
+            if condition(&value) {
+                return value;
+            }
+
+            drop(value);
+
+            let releases = lock.lock_releases();
+
+            if let Err(err) = lock.released.wait_while(releases, |releases| *releases == seen) {
+                panic!("Failed to get cell value. Lock was poisoned: {}", err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, atomic::Ordering}, thread, time::Duration};
+
+    use crate::{NotifyingRawLock, RawSyncLock, SyncCell};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Phase {
+        Loading,
+        Ready,
+    }
+
+    #[test]
+    pub fn test_sync_cell_wait_for() {
+        let cell: Arc<SyncCell<Phase, NotifyingRawLock>> = Arc::new(SyncCell::with_lock(Phase::Loading, NotifyingRawLock::INIT));
+        let loader = cell.clone();
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            *loader.borrow_mut() = Phase::Ready;
+        });
+
+        assert_eq!(Phase::Ready, *cell.wait_for(|phase| *phase == Phase::Ready));
+        handle.join().unwrap();
+
+        assert_eq!(0, cell.data.raw_lock().waiters.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    pub fn test_sync_cell_wait_for_satisfied() {
+        let cell: SyncCell<i32, NotifyingRawLock> = SyncCell::with_lock(1, NotifyingRawLock::INIT);
+
+        assert_eq!(1, *cell.wait_for(|value| *value == 1));
+    }
+
+    #[test]
+    pub fn test_sync_cell_wait_for_notify_only_waiters() {
+        let cell: SyncCell<i32, NotifyingRawLock> = SyncCell::with_lock(0, NotifyingRawLock::INIT);
+
+        cell.set(1);
+        cell.set(2);
+
+        assert_eq!(0, *cell.data.raw_lock().lock_releases());
+    }
+}
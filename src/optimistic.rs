@@ -0,0 +1,112 @@
+use crate::{RawSyncLock, SyncCell};
+
+impl <T: PartialEq, L: RawSyncLock> SyncCell<T, L> {
+    /// Sets the value contained in this cell only if it is equal to an expected value.
+    /// If the value is different the new value is returned as an error.
+    ///
+    /// - `current` - The value the cell is expected to contain.
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(1);
+    ///
+    /// assert_eq!(Ok(()), cell.compare_and_set(&1, 2));
+    /// assert_eq!(Err(3), cell.compare_and_set(&1, 3));
+    /// assert_eq!(2, cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn compare_and_set(&self, current: &T, value: T) -> Result<(), T> {
+        let mut data = self.borrow_mut();
+
+        if *data != *current {
+            return Err(value);
+        }
+
+        *data = value;
+        Ok(())
+    }
+}
+
+impl <T: Clone + PartialEq, L: RawSyncLock> SyncCell<T, L> {
+    /// Replaces the value contained in this cell with the result of a function without holding
+    /// the write lock while the function runs.
+    /// The value is copied, the function computes a replacement and the replacement is stored
+    /// with `compare_and_set`. If another thread changed the value in the meantime the function
+    /// is run again with the new value. This keeps write locks very short when the function is
+    /// expensive, but the function may be run more than once.
+    ///
+    /// The new value is returned.
+    ///
+    /// - `f` - The function computing the new value from the current value.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(2);
+    ///
+    /// assert_eq!(4, cell.update_optimistic(|value| value * value));
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn update_optimistic(&self, mut f: impl FnMut(T) -> T) -> T {
+        let mut current = self.get();
+
+        loop {
+            let value = f(current.clone());
+
+            // The replacement is only stored if the value has not changed since it was read.
+            // Otherwise the current value is read under the same lock and the function retried.
+            let mut data = self.borrow_mut();
+
+            if *data == current {
+                *data = value.clone();
+                return value;
+            }
+
+            current = data.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_compare_and_set() {
+        let cell = SyncCell::new("a");
+
+        assert_eq!(Err("c"), cell.compare_and_set(&"b", "c"));
+        assert_eq!(Ok(()), cell.compare_and_set(&"a", "c"));
+        assert_eq!("c", cell.get());
+    }
+
+    #[test]
+    pub fn test_sync_cell_update_optimistic() {
+        let cell = Arc::new(SyncCell::new(0));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    cell.update_optimistic(|value| value + 1);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(400, cell.get());
+    }
+}
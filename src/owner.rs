@@ -0,0 +1,238 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, sync::Mutex, thread::Thread};
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+use std::{cell::Cell, fmt::Write, sync::atomic::{AtomicU64, Ordering as AtomicOrdering}, thread};
+#[cfg(all(debug_assertions, feature = "owner-tracking", loom))]
+use crate::loom_shims::thread_local;
+#[cfg(all(debug_assertions, feature = "owner-tracking", shuttle))]
+use crate::shuttle_shims::thread_local;
+
+/// The id given to the next thread that borrows a cell mutably.
+/// Ids start at one so that zero can mean that no thread holds a borrow.
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The threads that have been given an id, used to name the holders of locks.
+/// Threads are removed when they exit unless they poisoned a cell.
+#[cfg(feature = "std")]
+static THREADS: Mutex<BTreeMap<u64, Thread>> = Mutex::new(BTreeMap::new());
+
+/// The id of a thread, which is registered with its name while the thread is running.
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+struct ThreadEntry {
+    /// The id of the thread.
+    id: u64,
+    /// Whether the thread poisoned a cell, in which case it stays registered after it exits so
+    /// that the poisoned cell can name it.
+    poisoned: Cell<bool>,
+}
+
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+impl ThreadEntry {
+    /// Gives the current thread an id and registers it.
+    fn new() -> Self {
+        let id = NEXT_THREAD_ID.fetch_add(1, AtomicOrdering::Relaxed);
+
+        match THREADS.lock() {
+            Ok(mut threads) => threads.insert(id, thread::current()),
+            Err(err) => err.into_inner().insert(id, thread::current()),
+        };
+
+        Self {
+            id,
+            poisoned: Cell::new(false),
+        }
+    }
+}
+
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+impl Drop for ThreadEntry {
+    fn drop(&mut self) {
+        if self.poisoned.get() {
+            return;
+        }
+
+        match THREADS.lock() {
+            Ok(mut threads) => threads.remove(&self.id),
+            Err(err) => err.into_inner().remove(&self.id),
+        };
+    }
+}
+
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+thread_local! {
+    /// The id of the current thread.
+    static THREAD: ThreadEntry = ThreadEntry::new();
+}
+
+/// Gets the id of the current thread, or zero if the thread is exiting.
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+fn current_thread_id() -> u64 {
+    THREAD.try_with(|thread| thread.id).unwrap_or(0)
+}
+
+/// Gets the id of the current thread and keeps it registered after it exits, since it has
+/// poisoned a cell.
+#[cfg(all(debug_assertions, feature = "owner-tracking"))]
+fn poisoning_thread_id() -> u64 {
+    THREAD.try_with(|thread| {
+        thread.poisoned.set(true);
+        thread.id
+    }).unwrap_or(0)
+}
+
+/// The thread holding the mutable borrow of a `SyncCell` that another borrow was blocked by.
+/// This is reported by `SyncCellError::WouldBlock` and `SyncCellError::Timeout`.
+/// Holders are only tracked in debug builds with the `owner-tracking` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LockHolder {
+    /// The id of the holding thread.
+    id: u64,
+}
+
+impl LockHolder {
+    /// Gets the id given to the holding thread by this crate.
+    /// Ids are unique for the run of the program.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Gets the holding thread, or `None` if it has exited since the error was returned.
+    /// Threads that poisoned a cell are kept after they exit.
+    /// This method is only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn thread(&self) -> Option<Thread> {
+        match THREADS.lock() {
+            Ok(threads) => threads.get(&self.id).cloned(),
+            Err(err) => err.into_inner().get(&self.id).cloned(),
+        }
+    }
+}
+
+impl Display for LockHolder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "std")]
+        if let Some(thread) = self.thread() {
+            return match thread.name() {
+                Some(name) => write!(f, "thread '{}'", name),
+                None => write!(f, "thread {:?}", thread.id()),
+            };
+        }
+
+        write!(f, "thread #{}", self.id)
+    }
+}
+
+/// Information about a `SyncCell` used to explain blocked borrows and poisoned locks.
+/// This is only tracked in debug builds with the `owner-tracking` feature and is empty otherwise,
+/// apart from the label kept for the `tracing` feature.
+#[derive(Debug)]
+pub(crate) struct LockOwner {
+    /// The label given to the cell when it was created.
+    #[cfg(any(all(debug_assertions, feature = "owner-tracking"), feature = "tracing"))]
+    label: Option<&'static str>,
+    /// The id of the thread holding a mutable borrow of the cell, or zero if there is none.
+    #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+    writer: AtomicU64,
+    /// The id of the thread that poisoned the cell by panicking while holding a mutable borrow,
+    /// or zero if the cell has not been poisoned.
+    #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+    poisoner: AtomicU64,
+}
+
+impl LockOwner {
+    /// Creates the owner information of a cell.
+    ///
+    /// - `label` - The label of the cell, which is discarded unless it is tracked or the `tracing`
+    ///   feature is enabled.
+    #[cfg_attr(not(any(all(debug_assertions, feature = "owner-tracking"), feature = "tracing")), allow(unused_variables))]
+    pub(crate) const fn new(label: Option<&'static str>) -> Self {
+        Self {
+            #[cfg(any(all(debug_assertions, feature = "owner-tracking"), feature = "tracing"))]
+            label,
+            #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+            writer: AtomicU64::new(0),
+            #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+            poisoner: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates the span of a borrow of the cell, which is named by the label of the cell.
+    ///
+    /// - `mutable` - Whether the borrow is mutable.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn span(&self, mutable: bool) -> crate::trace::BorrowSpan {
+        crate::trace::borrow_span(self.label, mutable)
+    }
+
+    /// Records that the current thread has taken a mutable borrow of the cell.
+    pub(crate) fn acquire(&self) {
+        #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+        self.writer.store(current_thread_id(), AtomicOrdering::Relaxed);
+    }
+
+    /// Records that the current thread is releasing its mutable borrow of the cell.
+    ///
+    /// - `poisoned` - Whether the thread is panicking and so poisons the cell.
+    #[cfg_attr(not(all(debug_assertions, feature = "owner-tracking")), allow(unused_variables))]
+    pub(crate) fn release(&self, poisoned: bool) {
+        #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+        {
+            if poisoned {
+                self.poisoner.store(poisoning_thread_id(), AtomicOrdering::Relaxed);
+            }
+
+            self.writer.store(0, AtomicOrdering::Relaxed);
+        }
+    }
+
+    /// Gets the thread holding a mutable borrow of the cell.
+    /// This is always `None` in release builds and without the `owner-tracking` feature.
+    pub(crate) fn holder(&self) -> Option<LockHolder> {
+        #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+        match self.writer.load(AtomicOrdering::Relaxed) {
+            0 => None,
+            id => Some(LockHolder {
+                id,
+            }),
+        }
+
+        #[cfg(not(all(debug_assertions, feature = "owner-tracking")))]
+        None
+    }
+
+    /// Panics with a message describing a poisoned lock.
+    ///
+    /// - `action` - What was being done to the cell, such as `get` or `set`.
+    /// - `err` - The error returned by the lock.
+    pub(crate) fn poisoned(&self, action: &str, err: impl Display) -> ! {
+        #[cfg(all(debug_assertions, feature = "owner-tracking"))]
+        {
+            let mut cell = String::from("cell");
+
+            if let Some(label) = self.label {
+                let _ = write!(cell, " '{}'", label);
+            }
+
+            let poisoner = match self.poisoner.load(AtomicOrdering::Relaxed) {
+                0 => None,
+                id => LockHolder {
+                    id,
+                }.thread(),
+            };
+
+            if let Some(poisoner) = poisoner {
+                match poisoner.name() {
+                    Some(name) => panic!("Failed to {} {} value. Lock was poisoned by thread '{}': {}", action, cell, name, err),
+                    None => panic!("Failed to {} {} value. Lock was poisoned by thread {:?}: {}", action, cell, poisoner.id(), err),
+                }
+            }
+
+            panic!("Failed to {} {} value. Lock was poisoned: {}", action, cell, err);
+        }
+
+        #[cfg(not(all(debug_assertions, feature = "owner-tracking")))]
+        panic!("Failed to {} cell value. Lock was poisoned: {}", action, err);
+    }
+}
@@ -0,0 +1,115 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::SyncCell;
+
+/// A `SyncCell` aligned to a cache line to avoid false sharing.
+/// Small cells placed next to each other, such as the elements of an array, share a cache line
+/// so writes to one cell slow down every thread using its neighbours. Padding each cell to its
+/// own cache line keeps them independent at the cost of memory.
+///
+/// This dereferences to a `SyncCell` so every method of `SyncCell` can be used directly.
+///
+/// # Usage
+/// ```
+/// use sync_cell::PaddedSyncCell;
+///
+/// let counters: [PaddedSyncCell<u32>; 4] = Default::default();
+///
+/// counters[1].set(2);
+///
+/// assert_eq!(2, counters[1].get());
+/// assert_eq!(128, std::mem::align_of::<PaddedSyncCell<u32>>());
+/// ```
+#[derive(Debug)]
+#[repr(align(128))]
+pub struct PaddedSyncCell<T: ?Sized> {
+    /// The padded cell.
+    cell: SyncCell<T>,
+}
+
+impl <T> PaddedSyncCell<T> {
+    /// Creates a new `PaddedSyncCell`.
+    ///
+    /// - `data` - The initial value of the `PaddedSyncCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            cell: SyncCell::new(data),
+        }
+    }
+
+    /// Pads an existing cell.
+    ///
+    /// - `cell` - The cell to pad.
+    pub(crate) const fn from_cell(cell: SyncCell<T>) -> Self {
+        Self {
+            cell,
+        }
+    }
+
+    /// Retrieves the inner value stored in this `PaddedSyncCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.cell.into_inner()
+    }
+}
+
+impl <T> SyncCell<T> {
+    /// Creates a new `SyncCell` aligned to a cache line.
+    ///
+    /// - `data` - The initial value of the `SyncCell`.
+    pub const fn new_padded(data: T) -> PaddedSyncCell<T> {
+        PaddedSyncCell::new(data)
+    }
+}
+
+impl <T: ?Sized> Deref for PaddedSyncCell<T> {
+    type Target = SyncCell<T>;
+
+    fn deref(&self) -> &SyncCell<T> {
+        &self.cell
+    }
+}
+
+impl <T: ?Sized> DerefMut for PaddedSyncCell<T> {
+    fn deref_mut(&mut self) -> &mut SyncCell<T> {
+        &mut self.cell
+    }
+}
+
+impl <T: Default> Default for PaddedSyncCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for PaddedSyncCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl <T> From<SyncCell<T>> for PaddedSyncCell<T> {
+    fn from(cell: SyncCell<T>) -> Self {
+        Self::from_cell(cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use crate::{PaddedSyncCell, SyncCell};
+
+    #[test]
+    pub fn test_padded_sync_cell() {
+        let cells = [SyncCell::new_padded(1u8), SyncCell::new_padded(2u8)];
+
+        *cells[0].borrow_mut() += 1;
+
+        assert_eq!(2, cells[0].get());
+        assert_eq!(256, size_of::<[PaddedSyncCell<u8>; 2]>());
+        assert_eq!(0, (&cells[1] as *const _ as usize) % 128);
+    }
+}
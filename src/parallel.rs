@@ -0,0 +1,116 @@
+use rayon::{prelude::*, slice::{Chunks, ChunksMut, Iter, IterMut}};
+
+use crate::{Elements, RawSyncLock, SyncCell};
+
+impl <S: Elements + ?Sized, L: RawSyncLock> SyncCell<S, L> {
+    /// Borrows the collection in this cell once and passes a rayon parallel iterator over its
+    /// elements to `f`.
+    /// The cell stays immutably borrowed until `f` returns, so the collection does not need to be
+    /// copied out of the cell to be processed in parallel.
+    /// This method is only available with the `rayon` feature.
+    ///
+    /// - `f` - The function given the parallel iterator.
+    ///
+    /// # Usage
+    /// ```
+    /// use rayon::prelude::*;
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new((1..=100).collect::<Vec<u64>>());
+    ///
+    /// assert_eq!(5050, cell.par_iter_with(|iter| iter.sum::<u64>()));
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn par_iter_with<R>(&self, f: impl FnOnce(Iter<'_, S::Item>) -> R) -> R where S::Item: Sync {
+        f(self.borrow().as_elements().par_iter())
+    }
+
+    /// Mutably borrows the collection in this cell once and passes a rayon parallel iterator over
+    /// its elements to `f`.
+    /// The cell stays mutably borrowed until `f` returns, so the closures run by rayon must not
+    /// borrow the cell themselves.
+    /// This method is only available with the `rayon` feature.
+    ///
+    /// - `f` - The function given the parallel iterator.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn par_iter_mut_with<R>(&self, f: impl FnOnce(IterMut<'_, S::Item>) -> R) -> R where S::Item: Send {
+        f(self.borrow_mut().as_elements_mut().par_iter_mut())
+    }
+
+    /// Borrows the collection in this cell once and passes a rayon parallel iterator over chunks
+    /// of its elements to `f`.
+    /// This method is only available with the `rayon` feature.
+    ///
+    /// - `chunk_size` - The number of elements in each chunk. The last chunk may be shorter.
+    /// - `f` - The function given the parallel iterator.
+    ///
+    /// # Panicking
+    /// This method will panic if `chunk_size` is zero or the lock becomes poisoned.
+    pub fn par_chunks_with<R>(&self, chunk_size: usize, f: impl FnOnce(Chunks<'_, S::Item>) -> R) -> R where S::Item: Sync {
+        f(self.borrow().as_elements().par_chunks(chunk_size))
+    }
+
+    /// Mutably borrows the collection in this cell once and passes a rayon parallel iterator over
+    /// mutable chunks of its elements to `f`.
+    /// The cell stays mutably borrowed until `f` returns, so the closures run by rayon must not
+    /// borrow the cell themselves.
+    /// This method is only available with the `rayon` feature.
+    ///
+    /// - `chunk_size` - The number of elements in each chunk. The last chunk may be shorter.
+    /// - `f` - The function given the parallel iterator.
+    ///
+    /// # Usage
+    /// ```
+    /// use rayon::prelude::*;
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(vec![0; 10]);
+    ///
+    /// cell.par_chunks_mut_with(4, |chunks| chunks.enumerate().for_each(|(index, chunk)| chunk.fill(index)));
+    /// assert_eq!(vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2], cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if `chunk_size` is zero or the lock becomes poisoned.
+    pub fn par_chunks_mut_with<R>(&self, chunk_size: usize, f: impl FnOnce(ChunksMut<'_, S::Item>) -> R) -> R where S::Item: Send {
+        f(self.borrow_mut().as_elements_mut().par_chunks_mut(chunk_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_par_iter_with() {
+        let cell = SyncCell::new((0..1000).collect::<Vec<u32>>());
+
+        let evens = cell.par_iter_with(|iter| iter.filter(|value| *value % 2 == 0).count());
+
+        assert_eq!(500, evens);
+    }
+
+    #[test]
+    pub fn test_sync_cell_par_iter_mut_with() {
+        let cell = SyncCell::new([1, 2, 3, 4]);
+
+        cell.par_iter_mut_with(|iter| iter.for_each(|value| *value *= 10));
+
+        assert_eq!([10, 20, 30, 40], cell.get());
+    }
+
+    #[test]
+    pub fn test_sync_cell_par_chunks_with() {
+        let cell = SyncCell::new((1..=10).collect::<Box<[u32]>>());
+
+        let sums: Vec<u32> = cell.par_chunks_with(3, |chunks| chunks.map(|chunk| chunk.iter().sum()).collect());
+
+        assert_eq!(vec![6, 15, 24, 10], sums);
+    }
+}
@@ -0,0 +1,256 @@
+use std::{fs::{self, File}, io::{self, ErrorKind, Write}, path::{Path, PathBuf}};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A cell whose value is stored in a file so that it survives restarts.
+/// The value is loaded from the file when the cell is opened and written back whenever it is set
+/// or when `flush` is called. Values are stored as JSON using their `serde` impls, so any value
+/// that can be serialized and deserialized can be stored, including structs and collections.
+/// This type is only available with the `serde` feature.
+///
+/// Writes are atomic. The value is first written to a temporary file next to the target file which
+/// is then renamed over the target. A crash part way through a write will leave the previous value
+/// intact.
+///
+/// # Usage
+/// ```
+/// use sync_cell::PersistentCell;
+///
+/// let path = std::env::temp_dir().join("sync_cell_persistent_cell_doc.json");
+/// # let _ = std::fs::remove_file(&path);
+///
+/// let cell = PersistentCell::open(&path, || 0).unwrap();
+/// cell.set(5).unwrap();
+/// drop(cell);
+///
+/// let cell: PersistentCell<i32> = PersistentCell::open(&path, || 0).unwrap();
+/// assert_eq!(5, cell.get());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct PersistentCell<T> {
+    /// The file the value is stored in.
+    path: PathBuf,
+    /// The current value of this cell.
+    data: SyncCell<T>,
+}
+
+impl <T: Serialize + DeserializeOwned> PersistentCell<T> {
+    /// Opens a `PersistentCell` stored at a path.
+    /// If the file does not exist the cell starts with the value returned by `default`. The file
+    /// is not created until the value is first set or flushed.
+    ///
+    /// - `path` - The file the value is stored in.
+    /// - `default` - A function returning the initial value if the file does not exist.
+    ///
+    /// # Errors
+    /// An error is returned if the file exists but cannot be read or does not contain a valid
+    /// value.
+    pub fn open(path: impl Into<PathBuf>, default: impl FnOnce() -> T) -> io::Result<Self> {
+        let path = path.into();
+
+        let data = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => default(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Self {
+            path,
+            data: SyncCell::new(data),
+        })
+    }
+
+    /// Writes a value to the file and then sets it as the value contained in this cell.
+    /// The value of the cell is left unchanged if writing the file fails.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Errors
+    /// An error is returned if the value cannot be serialized or the file cannot be written.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) -> io::Result<()> {
+        let mut data = self.data.borrow_mut();
+
+        self.write(&value)?;
+        *data = value;
+
+        Ok(())
+    }
+
+    /// Writes the current value of this cell to the file.
+    /// This is needed to persist changes made through `borrow_mut`.
+    ///
+    /// # Errors
+    /// An error is returned if the value cannot be serialized or the file cannot be written.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn flush(&self) -> io::Result<()> {
+        // A write lock prevents concurrent flushes from renaming over each other.
+        let data = self.data.borrow_mut();
+
+        self.write(&data)
+    }
+
+    /// Writes a value to the file using a temporary file and a rename.
+    fn write(&self, value: &T) -> io::Result<()> {
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+
+        let bytes = serde_json::to_vec(value)?;
+
+        let mut file = File::create(&temp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+
+        fs::rename(&temp_path, &self.path)
+    }
+}
+
+impl <T> PersistentCell<T> {
+    /// Gets the path of the file the value is stored in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.data.borrow()
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// Changes made through this reference are not written to the file until `flush` is called.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        self.data.borrow_mut()
+    }
+
+    /// Retrieves the inner value stored in this `PersistentCell`.
+    /// Unflushed changes are not written to the file.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl <T: Clone> PersistentCell<T> {
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.data.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, io::ErrorKind, path::PathBuf};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::PersistentCell;
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+    struct Settings {
+        name: String,
+        volume: Option<u8>,
+        keys: Vec<char>,
+    }
+
+    /// Gets a unique path in the temporary directory for a test.
+    fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("sync_cell_{}_{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    pub fn test_persistent_cell_default() {
+        let path = temp_path("persistent_default");
+
+        let cell = PersistentCell::open(&path, || 3).unwrap();
+
+        assert_eq!(3, cell.get());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    pub fn test_persistent_cell_set_reopen() {
+        let path = temp_path("persistent_set_reopen");
+
+        let cell = PersistentCell::open(&path, || 3).unwrap();
+        cell.set(4).unwrap();
+
+        let cell: PersistentCell<i32> = PersistentCell::open(&path, || 3).unwrap();
+        assert_eq!(4, cell.get());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    pub fn test_persistent_cell_set_failed() {
+        let path = temp_path("persistent_set_failed").join("missing_dir").join("value.json");
+
+        let cell = PersistentCell::open(&path, || 3).unwrap();
+
+        assert!(cell.set(4).is_err());
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_persistent_cell_flush() {
+        let path = temp_path("persistent_flush");
+
+        let cell = PersistentCell::open(&path, String::new).unwrap();
+        cell.borrow_mut().push_str("hello");
+        cell.flush().unwrap();
+
+        assert_eq!("\"hello\"", fs::read_to_string(&path).unwrap());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    pub fn test_persistent_cell_struct() {
+        let path = temp_path("persistent_struct");
+
+        let settings = Settings {
+            name: String::from("player"),
+            volume: None,
+            keys: vec!['w', 'a', 's', 'd'],
+        };
+
+        let cell = PersistentCell::open(&path, || settings.clone()).unwrap();
+        cell.borrow_mut().volume = Some(80);
+        cell.flush().unwrap();
+
+        let cell: PersistentCell<Settings> = PersistentCell::open(&path, || unreachable!()).unwrap();
+        assert_eq!(Settings { volume: Some(80), ..settings }, cell.get());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    pub fn test_persistent_cell_invalid() {
+        let path = temp_path("persistent_invalid");
+        fs::write(&path, "not a number").unwrap();
+
+        let result = PersistentCell::<i32>::open(&path, || 0);
+
+        assert_eq!(ErrorKind::InvalidData, result.err().unwrap().kind());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{lock_result::LockResult, RawSyncLock, SyncCell, SyncCellGuard, SyncCellRef};
+
+/// What a `SyncCell` does when its lock has been poisoned by a thread that panicked while
+/// mutably borrowing it.
+/// The policy applies to every method of the cell, so a panicking writer in one part of a program
+/// does not have to cause panics in unrelated readers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PoisonPolicy {
+    /// The thread accessing the cell panics.
+    #[default]
+    Panic,
+    /// The poisoning is ignored and the value is used as the panicking thread left it.
+    Ignore,
+    /// The value is reset to its default and the poisoning is cleared.
+    /// This policy can only be chosen for values that implement `Default`.
+    ClearAndDefault,
+}
+
+/// Resets a value to its default.
+///
+/// # Safety
+/// `value` must point to a valid `T`.
+unsafe fn reset_default<T: Default>(value: *mut ()) {
+    // SAFETY: The caller guarantees that the pointer is to a valid `T`.
+    unsafe { *value.cast::<T>() = T::default() }
+}
+
+/// How a `SyncCell` handles its lock being poisoned.
+/// This does not depend on the type of the value so that a `SyncCell` can still be coerced to an
+/// unsized type.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PoisonHandler {
+    /// The thread accessing the cell panics.
+    Panic,
+    /// The poisoning is ignored.
+    Ignore,
+    /// The value is reset by a function taking a pointer to the value the cell was created with
+    /// and the poisoning is cleared.
+    Reset(unsafe fn(*mut ())),
+}
+
+impl PoisonHandler {
+    /// Creates a handler for a policy.
+    ///
+    /// - `policy` - The policy to apply to a value of type `T`.
+    pub(crate) const fn new<T: Default>(policy: PoisonPolicy) -> Self {
+        match policy {
+            PoisonPolicy::Panic => Self::Panic,
+            PoisonPolicy::Ignore => Self::Ignore,
+            PoisonPolicy::ClearAndDefault => Self::Reset(reset_default::<T>),
+        }
+    }
+
+    /// Gets the policy of this handler.
+    fn policy(self) -> PoisonPolicy {
+        match self {
+            Self::Panic => PoisonPolicy::Panic,
+            Self::Ignore => PoisonPolicy::Ignore,
+            Self::Reset(_) => PoisonPolicy::ClearAndDefault,
+        }
+    }
+
+    /// Resets a value to its default.
+    ///
+    /// - `value` - The value of the cell this handler belongs to.
+    pub(crate) fn reset<T: ?Sized>(self, value: &mut T) {
+        if let Self::Reset(reset) = self {
+            // SAFETY: A reset function is only created by `new` for the type of its value. If the cell has since been coerced to an unsized type, the value
+            // behind the pointer is still the sized value the cell was created with.
+            unsafe { reset((value as *mut T).cast::<()>()) }
+        }
+    }
+}
+
+impl <T: Default> SyncCell<T> {
+    /// Creates a new `SyncCell` that handles its lock being poisoned according to a policy.
+    /// The value must implement `Default` so that it can be reset by
+    /// `PoisonPolicy::ClearAndDefault`. Other values can ignore poisoning with
+    /// `SyncCellBuilder::ignore_poison`.
+    ///
+    /// - `data` - The initial value of the `SyncCell`.
+    /// - `policy` - What to do when the lock is poisoned.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::{sync::Arc, thread};
+    /// use sync_cell::{PoisonPolicy, SyncCell};
+    ///
+    /// let cell = Arc::new(SyncCell::with_poison_policy(5, PoisonPolicy::ClearAndDefault));
+    ///
+    /// let _ = {
+    ///     let cell = cell.clone();
+    ///     thread::spawn(move || {
+    ///         let _guard = cell.borrow_mut();
+    ///         panic!("Poisoning the lock");
+    ///     }).join()
+    /// };
+    ///
+    /// assert_eq!(PoisonPolicy::ClearAndDefault, cell.poison_policy());
+    /// assert_eq!(0, cell.get());
+    /// ```
+    pub const fn with_poison_policy(data: T, policy: PoisonPolicy) -> Self {
+        let mut cell = Self::new(data);
+        cell.poison = PoisonHandler::new::<T>(policy);
+        cell
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> SyncCell<T, L> {
+    /// Gets what this cell does when its lock has been poisoned.
+    pub fn poison_policy(&self) -> PoisonPolicy {
+        self.poison.policy()
+    }
+
+    /// Applies the poison policy of this cell to the result of taking a read lock.
+    /// An error is only returned if the policy is to panic.
+    pub(crate) fn recover_read<'a>(&'a self, result: LockResult<SyncCellRef<'a, T, L>>) -> LockResult<SyncCellRef<'a, T, L>> {
+        match result {
+            Ok(data) => Ok(data),
+            Err(err) => match self.poison {
+                PoisonHandler::Panic => Err(err),
+                PoisonHandler::Ignore => Ok(err.into_inner()),
+                PoisonHandler::Reset(_) => {
+                    // The value can only be reset through a write lock, after which it is read
+                    // again.
+                    drop(err);
+                    drop(self.recover_write(self.write_lock()));
+
+                    self.recover_read(self.read_lock())
+                },
+            },
+        }
+    }
+
+    /// Applies the poison policy of this cell to the result of taking a write lock.
+    /// An error is only returned if the policy is to panic.
+    pub(crate) fn recover_write<'a>(&'a self, result: LockResult<SyncCellGuard<'a, T, L>>) -> LockResult<SyncCellGuard<'a, T, L>> {
+        match result {
+            Ok(data) => Ok(data),
+            Err(err) => match self.poison {
+                PoisonHandler::Panic => Err(err),
+                PoisonHandler::Ignore => Ok(err.into_inner()),
+                PoisonHandler::Reset(_) => {
+                    let mut data = err.into_inner();
+                    self.poison.reset(&mut *data);
+                    self.clear_poison();
+                    self.bump_version();
+
+                    Ok(data)
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::{PoisonPolicy, SyncCell};
+
+    /// Poisons the lock of a cell by panicking while it is mutably borrowed.
+    fn poison(cell: &Arc<SyncCell<i32>>) {
+        let cell = cell.clone();
+
+        let _ = thread::spawn(move || {
+            let mut data = cell.borrow_mut();
+            *data = 10;
+            panic!("Poisoning the lock");
+        }).join();
+    }
+
+    #[test]
+    pub fn test_sync_cell_poison_ignore() {
+        let cell = Arc::new(SyncCell::with_poison_policy(1, PoisonPolicy::Ignore));
+
+        poison(&cell);
+
+        assert_eq!(PoisonPolicy::Ignore, cell.poison_policy());
+        assert_eq!(10, cell.get());
+        assert_eq!(10, cell.replace(2));
+        assert_eq!(2, *cell.borrow());
+    }
+
+    #[test]
+    pub fn test_sync_cell_poison_clear_and_default() {
+        let cell = Arc::new(SyncCell::with_poison_policy(1, PoisonPolicy::ClearAndDefault));
+
+        poison(&cell);
+        assert_eq!(0, *cell.borrow());
+
+        poison(&cell);
+        cell.set(3);
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_sync_cell_poison_policy_without_default() {
+        struct NoDefault(i32);
+
+        let cell = SyncCell::builder().ignore_poison().build(NoDefault(1));
+
+        assert_eq!(PoisonPolicy::Ignore, cell.poison_policy());
+        assert_eq!(1, cell.borrow().0);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_sync_cell_poison_panic() {
+        let cell = Arc::new(SyncCell::with_poison_policy(1, PoisonPolicy::Panic));
+
+        poison(&cell);
+        cell.get();
+    }
+}
@@ -0,0 +1,219 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::SyncCell;
+
+/// A function creating new values for a pool.
+type FactoryFn<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+/// A pool of reusable values shared between threads.
+/// Acquiring a value from the pool takes an idle value if one is available and otherwise creates
+/// a new one using a factory function. The value is returned to the pool when the guard is
+/// dropped so that it can be reused by a later acquisition.
+///
+/// This is useful for reusing buffers or other values that are expensive to allocate.
+///
+/// # Usage
+/// ```
+/// use sync_cell::PoolCell;
+///
+/// let pool = PoolCell::new(|| Vec::<u8>::with_capacity(1024));
+///
+/// {
+///     let mut buffer = pool.acquire();
+///     buffer.extend_from_slice(b"hello");
+/// }
+///
+/// // The buffer was returned to the pool and is reused.
+/// assert_eq!(1, pool.idle_count());
+/// assert_eq!(b"hello", &pool.acquire()[..]);
+/// ```
+pub struct PoolCell<T> {
+    /// The values waiting to be acquired.
+    idle: SyncCell<Vec<T>>,
+    /// The function creating new values when the pool is empty.
+    factory: FactoryFn<T>,
+}
+
+impl <T> PoolCell<T> {
+    /// Creates a new empty `PoolCell`.
+    ///
+    /// - `factory` - A function creating new values when the pool is empty.
+    pub fn new(factory: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self::with_values(Vec::new(), factory)
+    }
+
+    /// Creates a new `PoolCell` containing some initial values.
+    ///
+    /// - `values` - The initial idle values of the pool.
+    /// - `factory` - A function creating new values when the pool is empty.
+    pub fn with_values(values: Vec<T>, factory: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self {
+            idle: SyncCell::new(values),
+            factory: Box::new(factory),
+        }
+    }
+
+    /// Acquires a value from the pool creating a new one if there are no idle values.
+    /// The value is returned to the pool when the guard is dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn acquire(&self) -> PoolGuard<'_, T> {
+        let value = self.try_take().unwrap_or_else(|| (self.factory)());
+
+        PoolGuard {
+            pool: self,
+            value: Some(value),
+        }
+    }
+
+    /// Acquires an idle value from the pool without creating a new one.
+    /// Returns `None` if there are no idle values.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn try_acquire(&self) -> Option<PoolGuard<'_, T>> {
+        self.try_take()
+            .map(|value| PoolGuard {
+                pool: self,
+                value: Some(value),
+            })
+    }
+
+    /// Adds a value to the pool.
+    ///
+    /// - `value` - The value to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn put(&self, value: T) {
+        self.idle.borrow_mut().push(value)
+    }
+
+    /// Gets the number of values waiting to be acquired.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn idle_count(&self) -> usize {
+        self.idle.borrow().len()
+    }
+
+    /// Drops all idle values in the pool.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn clear(&self) {
+        self.idle.borrow_mut().clear()
+    }
+
+    /// Retrieves the idle values stored in this `PoolCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> Vec<T> {
+        self.idle.into_inner()
+    }
+
+    /// Takes an idle value from the pool.
+    fn try_take(&self) -> Option<T> {
+        self.idle.borrow_mut().pop()
+    }
+}
+
+/// A value acquired from a `PoolCell`.
+/// The value is returned to the pool when this guard is dropped.
+pub struct PoolGuard<'a, T> {
+    /// The pool the value is returned to.
+    pool: &'a PoolCell<T>,
+    /// The acquired value. This is only `None` once the value has been detached.
+    value: Option<T>,
+}
+
+impl <T> PoolGuard<'_, T> {
+    /// Takes ownership of the acquired value so that it is not returned to the pool.
+    pub fn detach(mut self) -> T {
+        match self.value.take() {
+            Some(value) => value,
+            None => unreachable!("Pool value was already detached."),
+        }
+    }
+}
+
+impl <T> Deref for PoolGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &self.value {
+            Some(value) => value,
+            None => unreachable!("Pool value was already detached."),
+        }
+    }
+}
+
+impl <T> DerefMut for PoolGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match &mut self.value {
+            Some(value) => value,
+            None => unreachable!("Pool value was already detached."),
+        }
+    }
+}
+
+impl <T> Drop for PoolGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.pool.put(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+
+    use crate::PoolCell;
+
+    #[test]
+    pub fn test_pool_cell_acquire_creates() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let counter = created.clone();
+        let pool = PoolCell::new(move || counter.fetch_add(1, Ordering::SeqCst));
+
+        let a = pool.acquire();
+        let b = pool.acquire();
+
+        assert_eq!(0, *a);
+        assert_eq!(1, *b);
+        assert_eq!(2, created.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    pub fn test_pool_cell_reuse() {
+        let pool = PoolCell::new(|| 0);
+
+        *pool.acquire() = 5;
+
+        assert_eq!(1, pool.idle_count());
+        assert_eq!(5, *pool.acquire());
+    }
+
+    #[test]
+    pub fn test_pool_cell_try_acquire() {
+        let pool = PoolCell::with_values(vec![1], || 0);
+
+        let guard = pool.try_acquire();
+        assert_eq!(Some(1), guard.as_deref().copied());
+        assert!(pool.try_acquire().is_none());
+    }
+
+    #[test]
+    pub fn test_pool_cell_detach() {
+        let pool = PoolCell::new(|| 1);
+
+        let value = pool.acquire().detach();
+
+        assert_eq!(1, value);
+        assert_eq!(0, pool.idle_count());
+        assert!(pool.into_inner().is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use crate::SyncCell;
+
+/// A cell where a write only replaces the stored value if its priority is at least as high as
+/// the priority of the stored value.
+/// Taking the value out of the cell resets it so that a write of any priority is accepted again.
+///
+/// This is useful for aggregating alarms or status messages where the most important one should
+/// be shown.
+///
+/// # Usage
+/// ```
+/// use sync_cell::PriorityCell;
+///
+/// let status = PriorityCell::new();
+///
+/// status.set("Saving", 1);
+/// status.set("Disk full", 10);
+/// status.set("Saved", 1);
+///
+/// assert_eq!(Some("Disk full"), status.take());
+/// assert_eq!(None, status.take());
+/// ```
+#[derive(Debug)]
+pub struct PriorityCell<T, P: Ord = u32> {
+    /// The stored value and its priority.
+    data: SyncCell<Option<(T, P)>>,
+}
+
+impl <T, P: Ord> PriorityCell<T, P> {
+    /// Creates a new empty `PriorityCell`.
+    pub const fn new() -> Self {
+        Self {
+            data: SyncCell::new(None),
+        }
+    }
+
+    /// Creates a new `PriorityCell` containing a value.
+    ///
+    /// - `value` - The initial value of the `PriorityCell`.
+    /// - `priority` - The priority of the initial value.
+    pub const fn with_value(value: T, priority: P) -> Self {
+        Self {
+            data: SyncCell::new(Some((value, priority))),
+        }
+    }
+
+    /// Sets the value contained in this cell if its priority is greater than or equal to the
+    /// priority of the stored value. Returns `true` if the value was stored.
+    ///
+    /// - `value` - The new value of the cell.
+    /// - `priority` - The priority of the new value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T, priority: P) -> bool {
+        let mut data = self.data.borrow_mut();
+
+        let replace = match &*data {
+            Some((_, current)) => priority >= *current,
+            None => true,
+        };
+
+        if replace {
+            *data = Some((value, priority));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes the value out of this cell, resetting it so that the next write is always accepted.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take(&self) -> Option<T> {
+        self.take_with_priority().map(|(value, _)| value)
+    }
+
+    /// Takes the value and its priority out of this cell, resetting it so that the next write is
+    /// always accepted.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take_with_priority(&self) -> Option<(T, P)> {
+        self.data.borrow_mut().take()
+    }
+
+    /// Checks if this cell contains a value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.data.borrow().is_none()
+    }
+
+    /// Calls a function with a reference to the stored value and its priority.
+    /// Returns `None` if the cell is empty.
+    ///
+    /// - `f` - The function to call.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with<R>(&self, f: impl FnOnce(&T, &P) -> R) -> Option<R> {
+        self.data.borrow()
+            .as_ref()
+            .map(|(value, priority)| f(value, priority))
+    }
+
+    /// Retrieves the inner value stored in this `PriorityCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> Option<T> {
+        self.data.into_inner().map(|(value, _)| value)
+    }
+}
+
+impl <T, P: Ord + Clone> PriorityCell<T, P> {
+    /// Gets the priority of the stored value.
+    /// Returns `None` if the cell is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn priority(&self) -> Option<P> {
+        self.with(|_, priority| priority.clone())
+    }
+}
+
+impl <T: Clone, P: Ord> PriorityCell<T, P> {
+    /// Gets the value contained in this cell.
+    /// Returns `None` if the cell is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> Option<T> {
+        self.with(|value, _| value.clone())
+    }
+}
+
+impl <T, P: Ord> Default for PriorityCell<T, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PriorityCell;
+
+    #[test]
+    pub fn test_priority_cell_set() {
+        let cell = PriorityCell::new();
+
+        assert!(cell.set(1, 5));
+        assert!(!cell.set(2, 4));
+        assert!(cell.set(3, 5));
+
+        assert_eq!(Some(3), cell.get());
+        assert_eq!(Some(5), cell.priority());
+    }
+
+    #[test]
+    pub fn test_priority_cell_take_resets() {
+        let cell = PriorityCell::with_value("high", 10);
+
+        assert_eq!(Some(("high", 10)), cell.take_with_priority());
+        assert!(cell.is_empty());
+        assert!(cell.set("low", 0));
+        assert_eq!(Some("low"), cell.into_inner());
+    }
+}
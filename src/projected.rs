@@ -0,0 +1,191 @@
+use std::{mem::replace, ops::{Deref, DerefMut}};
+
+use crate::{DefaultRawLock, RawSyncLock, SyncCell, SyncCellGuard, SyncCellRef};
+
+impl <T: ?Sized, L: RawSyncLock> SyncCell<T, L> {
+    /// Creates a handle to part of the value of this cell, such as one field of a struct.
+    /// The handle shares the lock of this cell but only gives access to the projected part, so a
+    /// subsystem can be handed just the state it needs.
+    ///
+    /// - `read` - A function getting the part from a reference to the value.
+    /// - `write` - A function getting the part from a mutable reference to the value.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// struct State {
+    ///     score: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// let state = SyncCell::new(State {
+    ///     score: 0,
+    ///     name: String::from("player"),
+    /// });
+    ///
+    /// let score = state.project(|state| &state.score, |state| &mut state.score);
+    ///
+    /// *score.borrow_mut() += 10;
+    ///
+    /// assert_eq!(10, score.get());
+    /// assert_eq!(10, state.borrow().score);
+    /// # assert_eq!("player", state.borrow().name);
+    /// ```
+    pub fn project<F: ?Sized>(&self, read: fn(&T) -> &F, write: fn(&mut T) -> &mut F) -> ProjectedCell<'_, T, F, L> {
+        ProjectedCell {
+            cell: self,
+            read,
+            write,
+        }
+    }
+}
+
+/// A handle to part of the value of a `SyncCell`, returned by `SyncCell::project`.
+/// Borrowing the handle borrows the whole cell, but only the projected part can be accessed.
+pub struct ProjectedCell<'a, T: ?Sized, F: ?Sized, L: RawSyncLock = DefaultRawLock> {
+    /// The cell holding the whole value.
+    cell: &'a SyncCell<T, L>,
+    /// The function getting the part from a reference to the value.
+    read: fn(&T) -> &F,
+    /// The function getting the part from a mutable reference to the value.
+    write: fn(&mut T) -> &mut F,
+}
+
+impl <'a, T: ?Sized, F: ?Sized, L: RawSyncLock> ProjectedCell<'a, T, F, L> {
+    /// Borrows a immutable reference to the projected part of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> ProjectedRef<'a, T, F, L> {
+        ProjectedRef {
+            data: self.cell.borrow(),
+            read: self.read,
+        }
+    }
+
+    /// Borrows a mutable reference to the projected part of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> ProjectedGuard<'a, T, F, L> {
+        ProjectedGuard {
+            data: self.cell.borrow_mut(),
+            read: self.read,
+            write: self.write,
+        }
+    }
+}
+
+impl <T: ?Sized, F, L: RawSyncLock> ProjectedCell<'_, T, F, L> {
+    /// Sets the projected part of the value.
+    ///
+    /// - `value` - The new value of the part.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: F) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the projected part of the value.
+    /// The previous value of the part is returned.
+    ///
+    /// - `value` - The new value of the part.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: F) -> F {
+        replace(&mut *self.borrow_mut(), value)
+    }
+}
+
+impl <T: ?Sized, F: Clone, L: RawSyncLock> ProjectedCell<'_, T, F, L> {
+    /// Gets a copy of the projected part of the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> F {
+        self.borrow().clone()
+    }
+}
+
+impl <T: ?Sized, F: ?Sized, L: RawSyncLock> Clone for ProjectedCell<'_, T, F, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <T: ?Sized, F: ?Sized, L: RawSyncLock> Copy for ProjectedCell<'_, T, F, L> {
+}
+
+/// An immutable borrow of the projected part of the value of a `SyncCell`.
+pub struct ProjectedRef<'a, T: ?Sized, F: ?Sized, L: RawSyncLock = DefaultRawLock> {
+    /// The borrowed value.
+    data: SyncCellRef<'a, T, L>,
+    /// The function getting the part from a reference to the value.
+    read: fn(&T) -> &F,
+}
+
+impl <T: ?Sized, F: ?Sized, L: RawSyncLock> Deref for ProjectedRef<'_, T, F, L> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        (self.read)(&self.data)
+    }
+}
+
+/// A mutable borrow of the projected part of the value of a `SyncCell`.
+pub struct ProjectedGuard<'a, T: ?Sized, F: ?Sized, L: RawSyncLock = DefaultRawLock> {
+    /// The borrowed value.
+    data: SyncCellGuard<'a, T, L>,
+    /// The function getting the part from a reference to the value.
+    read: fn(&T) -> &F,
+    /// The function getting the part from a mutable reference to the value.
+    write: fn(&mut T) -> &mut F,
+}
+
+impl <T: ?Sized, F: ?Sized, L: RawSyncLock> Deref for ProjectedGuard<'_, T, F, L> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        (self.read)(&self.data)
+    }
+}
+
+impl <T: ?Sized, F: ?Sized, L: RawSyncLock> DerefMut for ProjectedGuard<'_, T, F, L> {
+    fn deref_mut(&mut self) -> &mut F {
+        (self.write)(&mut self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_project() {
+        let cell = Arc::new(SyncCell::new((0u32, String::new())));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let count = cell.project(|value| &value.0, |value| &mut value.0);
+                let text = cell.project(|value| value.1.as_str(), |value| value.1.as_mut_str());
+
+                *count.borrow_mut() += 1;
+                assert!(text.borrow().is_empty());
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let count = cell.project(|value| &value.0, |value| &mut value.0);
+        assert_eq!(4, count.replace(0));
+        assert_eq!(0, cell.borrow().0);
+    }
+}
@@ -0,0 +1,314 @@
+use std::{sync::{Condvar, Mutex, MutexGuard, OnceLock}, time::Duration};
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin, task::{Context, Poll, Waker}};
+
+/// A cell that is fulfilled exactly once and that readers can wait on until it is fulfilled.
+/// Once the value is available, reading it never takes a lock.
+///
+/// This suits start up handshakes where one thread produces a value that other threads need
+/// before they can continue.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::PromiseCell;
+///
+/// let address = Arc::new(PromiseCell::new());
+///
+/// let server = {
+///     let address = address.clone();
+///     thread::spawn(move || {
+///         address.fulfill(String::from("127.0.0.1:8080")).unwrap();
+///     })
+/// };
+///
+/// assert_eq!("127.0.0.1:8080", address.wait());
+/// # server.join().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PromiseCell<T> {
+    /// The value once the promise has been fulfilled.
+    value: OnceLock<T>,
+    /// The lock waiting threads hold while checking whether the promise has been fulfilled.
+    lock: Mutex<()>,
+    /// The condition variable used to wake waiting threads.
+    fulfilled: Condvar,
+    /// The wakers of tasks waiting for the promise to be fulfilled.
+    #[cfg(feature = "async")]
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl <T> PromiseCell<T> {
+    /// Creates a new unfulfilled `PromiseCell`.
+    pub const fn new() -> Self {
+        Self {
+            value: OnceLock::new(),
+            lock: Mutex::new(()),
+            fulfilled: Condvar::new(),
+            #[cfg(feature = "async")]
+            wakers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fulfills this promise and wakes all waiting threads.
+    /// If the promise has already been fulfilled the value is returned as an error.
+    ///
+    /// - `value` - The value of the promise.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn fulfill(&self, value: T) -> Result<(), T> {
+        self.value.set(value)?;
+
+        // Taking the lock ensures no waiting thread is between its check and its wait.
+        drop(self.lock());
+        self.fulfilled.notify_all();
+
+        #[cfg(feature = "async")]
+        for waker in self.lock_wakers().drain(..) {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+
+    /// Checks if this promise has been fulfilled.
+    pub fn is_fulfilled(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Gets a reference to the value of this promise if it has been fulfilled.
+    /// This never blocks.
+    pub fn try_get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    /// Blocks the current thread until this promise is fulfilled and returns a reference to its
+    /// value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait(&self) -> &T {
+        if let Some(value) = self.value.get() {
+            return value;
+        }
+
+        let guard = self.lock();
+
+        match self.fulfilled.wait_while(guard, |_| self.value.get().is_none()) {
+            Ok(_) => self.fulfilled_value(),
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Blocks the current thread until this promise is fulfilled or a timeout elapses.
+    /// Returns `None` if the timeout elapsed before the promise was fulfilled.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn wait_timeout(&self, timeout: Duration) -> Option<&T> {
+        if let Some(value) = self.value.get() {
+            return Some(value);
+        }
+
+        let guard = self.lock();
+
+        match self.fulfilled.wait_timeout_while(guard, timeout, |_| self.value.get().is_none()) {
+            Ok((_, result)) if result.timed_out() => None,
+            Ok(_) => Some(self.fulfilled_value()),
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Waits until this promise is fulfilled from async code and returns a reference to its value.
+    /// The returned future yields to the executor instead of blocking the thread, so a promise
+    /// fulfilled by a thread can be awaited by a task. This method is only available with the
+    /// `async` feature.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::PromiseCell;
+    ///
+    /// # async fn example(address: &PromiseCell<String>) {
+    /// println!("{}", address.wait_async().await);
+    /// # }
+    /// ```
+    ///
+    /// # Panicking
+    /// The returned future will panic if the lock becomes poisoned.
+    #[cfg(feature = "async")]
+    pub fn wait_async(&self) -> PromiseWait<'_, T> {
+        PromiseWait {
+            cell: self,
+        }
+    }
+
+    /// Retrieves the value of this promise if it has been fulfilled.
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+
+    /// Takes the lock used by waiting threads.
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        match self.lock.lock() {
+            Ok(guard) => guard,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Locks the wakers of tasks waiting for the promise.
+    #[cfg(feature = "async")]
+    fn lock_wakers(&self) -> MutexGuard<'_, Vec<Waker>> {
+        match self.wakers.lock() {
+            Ok(guard) => guard,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Gets the value after the promise has been observed to be fulfilled.
+    fn fulfilled_value(&self) -> &T {
+        match self.value.get() {
+            Some(value) => value,
+            None => unreachable!("Promise was woken before being fulfilled."),
+        }
+    }
+}
+
+impl <T: Clone> PromiseCell<T> {
+    /// Blocks the current thread until this promise is fulfilled and returns a copy of its value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.wait().clone()
+    }
+
+    /// Waits until this promise is fulfilled from async code and returns a copy of its value.
+    /// This method is only available with the `async` feature.
+    ///
+    /// # Panicking
+    /// The returned future will panic if the lock becomes poisoned.
+    #[cfg(feature = "async")]
+    pub async fn get_async(&self) -> T {
+        self.wait_async().await.clone()
+    }
+}
+
+impl <T> Default for PromiseCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> From<T> for PromiseCell<T> {
+    fn from(value: T) -> Self {
+        Self {
+            value: OnceLock::from(value),
+            lock: Mutex::new(()),
+            fulfilled: Condvar::new(),
+            #[cfg(feature = "async")]
+            wakers: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+/// A future that resolves to the value of a `PromiseCell` once it is fulfilled.
+#[cfg(feature = "async")]
+pub struct PromiseWait<'a, T> {
+    /// The promise being waited on.
+    cell: &'a PromiseCell<T>,
+}
+
+#[cfg(feature = "async")]
+impl <'a, T> Future for PromiseWait<'a, T> {
+    type Output = &'a T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cell = self.cell;
+
+        if let Some(value) = cell.value.get() {
+            return Poll::Ready(value);
+        }
+
+        // The value is checked again while holding the wakers so that fulfilling the promise
+        // after the check always finds the waker registered.
+        let mut wakers = cell.lock_wakers();
+
+        if let Some(value) = cell.value.get() {
+            return Poll::Ready(value);
+        }
+
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::PromiseCell;
+
+    #[test]
+    pub fn test_promise_cell_fulfill() {
+        let cell = PromiseCell::new();
+
+        assert!(!cell.is_fulfilled());
+        assert_eq!(None, cell.try_get());
+        assert_eq!(Ok(()), cell.fulfill(1));
+        assert_eq!(Err(2), cell.fulfill(2));
+        assert_eq!(1, cell.get());
+        assert_eq!(Some(1), cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_promise_cell_wait() {
+        let cell = Arc::new(PromiseCell::new());
+
+        let waiters: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || cell.get())
+        }).collect();
+
+        thread::sleep(Duration::from_millis(10));
+        cell.fulfill(5).unwrap();
+
+        for waiter in waiters {
+            assert_eq!(5, waiter.join().unwrap());
+        }
+    }
+
+    #[test]
+    pub fn test_promise_cell_wait_timeout() {
+        let cell = PromiseCell::<i32>::new();
+
+        assert_eq!(None, cell.wait_timeout(Duration::from_millis(10)));
+
+        let cell = PromiseCell::from(3);
+
+        assert_eq!(Some(&3), cell.wait_timeout(Duration::from_millis(10)));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    pub fn test_promise_cell_wait_async() {
+        use crate::block_on::block_on;
+
+        let cell = Arc::new(PromiseCell::new());
+        let producer = cell.clone();
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            producer.fulfill(4).unwrap();
+        });
+
+        assert_eq!(4, block_on(cell.get_async()));
+        assert_eq!(&4, block_on(cell.wait_async()));
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+use std::{collections::VecDeque, sync::{Condvar, Mutex, MutexGuard}, time::Duration};
+
+/// A first in first out queue that can be shared between any number of producer and consumer
+/// threads.
+/// Popping from an empty queue blocks until a value is pushed. A bounded queue also blocks pushes
+/// while it is full.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::SyncQueue;
+///
+/// let queue = Arc::new(SyncQueue::bounded(2));
+///
+/// let producer = {
+///     let queue = queue.clone();
+///     thread::spawn(move || {
+///         for i in 0..5 {
+///             queue.push(i);
+///         }
+///     })
+/// };
+///
+/// let values: Vec<_> = (0..5).map(|_| queue.pop()).collect();
+/// assert_eq!(vec![0, 1, 2, 3, 4], values);
+/// # producer.join().unwrap();
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this queue will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct SyncQueue<T> {
+    /// The values in the queue.
+    data: Mutex<VecDeque<T>>,
+    /// The maximum number of values in the queue if it is bounded.
+    capacity: Option<usize>,
+    /// The condition variable used to wake threads waiting for a value.
+    pushed: Condvar,
+    /// The condition variable used to wake threads waiting for space.
+    popped: Condvar,
+}
+
+impl <T> SyncQueue<T> {
+    /// Creates a new unbounded `SyncQueue`.
+    pub const fn new() -> Self {
+        Self::create(None)
+    }
+
+    /// Creates a new `SyncQueue` that holds at most a number of values.
+    ///
+    /// - `capacity` - The maximum number of values. A capacity of zero is treated as one.
+    pub const fn bounded(capacity: usize) -> Self {
+        Self::create(Some(if capacity == 0 { 1 } else { capacity }))
+    }
+
+    /// Creates a new `SyncQueue` with an optional capacity.
+    const fn create(capacity: Option<usize>) -> Self {
+        Self {
+            data: Mutex::new(VecDeque::new()),
+            capacity,
+            pushed: Condvar::new(),
+            popped: Condvar::new(),
+        }
+    }
+
+    /// Gets the maximum number of values in this queue or `None` if it is unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Gets the number of values in this queue.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Checks if this queue has no values.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Adds a value to the back of this queue.
+    /// If the queue is bounded and full this blocks until space is available.
+    ///
+    /// - `value` - The value to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push(&self, value: T) {
+        let data = self.lock();
+
+        let mut data = match self.popped.wait_while(data, |data| self.is_full(data)) {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        };
+
+        data.push_back(value);
+        self.pushed.notify_one();
+    }
+
+    /// Adds a value to the back of this queue without blocking.
+    /// If the queue is bounded and full the value is returned as an error.
+    ///
+    /// - `value` - The value to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn try_push(&self, value: T) -> Result<(), T> {
+        let mut data = self.lock();
+
+        if self.is_full(&data) {
+            return Err(value);
+        }
+
+        data.push_back(value);
+        self.pushed.notify_one();
+
+        Ok(())
+    }
+
+    /// Removes the value at the front of this queue.
+    /// If the queue is empty this blocks until a value is pushed.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn pop(&self) -> T {
+        let data = self.lock();
+
+        let mut data = match self.pushed.wait_while(data, |data| data.is_empty()) {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        };
+
+        self.take(&mut data)
+    }
+
+    /// Removes the value at the front of this queue without blocking.
+    /// Returns `None` if the queue is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn try_pop(&self) -> Option<T> {
+        let mut data = self.lock();
+
+        (!data.is_empty()).then(|| self.take(&mut data))
+    }
+
+    /// Removes the value at the front of this queue, blocking for at most `timeout`.
+    /// Returns `None` if the queue is still empty once the timeout has elapsed.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<T> {
+        let data = self.lock();
+
+        let mut data = match self.pushed.wait_timeout_while(data, timeout, |data| data.is_empty()) {
+            Ok((data, _)) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        };
+
+        (!data.is_empty()).then(|| self.take(&mut data))
+    }
+
+    /// Retrieves the values stored in this `SyncQueue` from front to back.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> VecDeque<T> {
+        match self.data.into_inner() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Locks the values in this queue.
+    fn lock(&self) -> MutexGuard<'_, VecDeque<T>> {
+        match self.data.lock() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Checks if the queue has reached its capacity.
+    fn is_full(&self, data: &VecDeque<T>) -> bool {
+        self.capacity.is_some_and(|capacity| data.len() >= capacity)
+    }
+
+    /// Removes the front value of a non-empty queue and wakes a thread waiting for space.
+    fn take(&self, data: &mut VecDeque<T>) -> T {
+        let value = match data.pop_front() {
+            Some(value) => value,
+            None => unreachable!("Queue was empty when taking a value."),
+        };
+        self.popped.notify_one();
+        value
+    }
+}
+
+impl <T> Default for SyncQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> From<VecDeque<T>> for SyncQueue<T> {
+    fn from(values: VecDeque<T>) -> Self {
+        Self {
+            data: Mutex::new(values),
+            ..Self::new()
+        }
+    }
+}
+
+impl <T> FromIterator<T> for SyncQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<VecDeque<T>>())
+    }
+}
+
+/// Adds the values to the back of the queue taking the lock once per call.
+/// If the queue is bounded this blocks whenever it is full until space is available.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <T> Extend<T> for &SyncQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut data = self.lock();
+
+        for value in iter {
+            data = match self.popped.wait_while(data, |data| self.is_full(data)) {
+                Ok(data) => data,
+                Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+            };
+
+            data.push_back(value);
+            self.pushed.notify_one();
+        }
+    }
+}
+
+/// Adds the values to the back of the queue.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <T> Extend<T> for SyncQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        (&*self).extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::SyncQueue;
+
+    #[test]
+    pub fn test_sync_queue() {
+        let queue: SyncQueue<_> = (0..2).collect();
+
+        queue.push(2);
+
+        assert_eq!(3, queue.len());
+        assert_eq!(0, queue.pop());
+        assert_eq!(Some(1), queue.try_pop());
+        assert_eq!(Some(2), queue.pop_timeout(Duration::from_millis(10)));
+        assert_eq!(None, queue.try_pop());
+        assert_eq!(None, queue.pop_timeout(Duration::from_millis(10)));
+    }
+
+    #[test]
+    pub fn test_sync_queue_bounded() {
+        let queue = Arc::new(SyncQueue::bounded(1));
+
+        assert_eq!(Ok(()), queue.try_push(1));
+        assert_eq!(Err(2), queue.try_push(2));
+
+        let producer = {
+            let queue = queue.clone();
+            thread::spawn(move || queue.push(2))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(1, queue.len());
+        assert_eq!(1, queue.pop());
+
+        producer.join().unwrap();
+        assert_eq!(2, queue.pop());
+    }
+
+    #[test]
+    pub fn test_sync_queue_extend_bounded() {
+        let queue = Arc::new(SyncQueue::bounded(2));
+
+        let producer = {
+            let queue = queue.clone();
+            thread::spawn(move || (&*queue).extend(0..5))
+        };
+
+        let values: Vec<_> = (0..5).map(|_| queue.pop()).collect();
+
+        producer.join().unwrap();
+        assert_eq!(vec![0, 1, 2, 3, 4], values);
+    }
+
+    #[test]
+    pub fn test_sync_queue_threads() {
+        let queue = Arc::new(SyncQueue::new());
+
+        let producers: Vec<_> = (0..4).map(|_| {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                for i in 0..25 {
+                    queue.push(i);
+                }
+            })
+        }).collect();
+
+        let sum: u32 = (0..100).map(|_| queue.pop()).sum();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        assert_eq!(4 * 300, sum);
+    }
+}
@@ -0,0 +1,239 @@
+use std::{collections::VecDeque, mem::{replace, take}};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A cell like `HeldSyncCell` that queues every value set instead of keeping only the last one.
+/// Each call to `update` applies exactly one queued value in the order the values were set, so
+/// no intermediate value is lost.
+///
+/// # Usage
+/// ```
+/// use sync_cell::QueuedSyncCell;
+///
+/// let cell = QueuedSyncCell::new(0);
+///
+/// cell.set(1);
+/// cell.set(2);
+///
+/// assert!(cell.update());
+/// assert_eq!(1, cell.get());
+///
+/// assert!(cell.update());
+/// assert_eq!(2, cell.get());
+///
+/// assert!(!cell.update());
+/// ```
+#[derive(Debug, Default)]
+pub struct QueuedSyncCell<T> {
+    /// The current value that is made available.
+    current_value: SyncCell<T>,
+    /// The values to apply next, oldest first.
+    queue: SyncCell<VecDeque<T>>,
+}
+
+impl <T> QueuedSyncCell<T> {
+    /// Creates a new `QueuedSyncCell`.
+    ///
+    /// - `data` - The initial value of the `QueuedSyncCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            current_value: SyncCell::new(data),
+            queue: SyncCell::new(VecDeque::new()),
+        }
+    }
+
+    /// Queues a value to be applied by a later call to `update`.
+    ///
+    /// - `value` - The value to queue.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        self.queue.borrow_mut().push_back(value)
+    }
+
+    /// Checks if any queued values are waiting to be applied.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn has_update(&self) -> bool {
+        !self.queue.borrow().is_empty()
+    }
+
+    /// Gets the number of queued values waiting to be applied.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn pending(&self) -> usize {
+        self.queue.borrow().len()
+    }
+
+    /// Applies the oldest queued value.
+    /// Returns `true` if a value was applied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update(&self) -> bool {
+        self.update_take().is_some()
+    }
+
+    /// Applies the oldest queued value.
+    /// The replaced value is returned or `None` if no value was queued.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_take(&self) -> Option<T> {
+        let next = self.queue.borrow_mut().pop_front();
+
+        next.map(|next| self.current_value.replace(next))
+    }
+
+    /// Applies every queued value in order.
+    /// Returns the number of values applied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_all(&self) -> usize {
+        self.drain_updates(|_, _| {})
+    }
+
+    /// Applies every queued value in order, calling a function with the replaced and the new
+    /// value after each one is applied.
+    /// Returns the number of values applied.
+    ///
+    /// The values queued at the time of the call are applied while the current value is locked,
+    /// so the function must not access this cell. Values queued while the function runs are left
+    /// for a later update.
+    ///
+    /// - `f` - The function to call for each applied value.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn drain_updates(&self, mut f: impl FnMut(&T, &T)) -> usize {
+        let queue = take(&mut *self.queue.borrow_mut());
+        let mut current = self.current_value.borrow_mut();
+        let count = queue.len();
+
+        for next in queue {
+            let previous = replace(&mut *current, next);
+            f(&previous, &current);
+        }
+
+        count
+    }
+
+    /// Discards all queued values without applying them.
+    /// The discarded values are returned oldest first.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn clear_updates(&self) -> Vec<T> {
+        self.queue.borrow_mut().drain(..).collect()
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// This is a reference to the current value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.current_value.borrow()
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// This is a reference to the current value of the cell not any queued value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        self.current_value.borrow_mut()
+    }
+
+    /// Retrieves the inner value stored in this `QueuedSyncCell`.
+    /// This will return the most recently queued value if any values have not been applied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> T {
+        match self.queue.into_inner().pop_back() {
+            Some(value) => value,
+            None => self.current_value.into_inner(),
+        }
+    }
+}
+
+impl <T: Clone> QueuedSyncCell<T> {
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.current_value.get()
+    }
+}
+
+impl <T> From<T> for QueuedSyncCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::QueuedSyncCell;
+
+    #[test]
+    pub fn test_queued_sync_cell_update_in_order() {
+        let cell = QueuedSyncCell::new(0);
+
+        for i in 1..=3 {
+            cell.set(i);
+        }
+
+        assert_eq!(3, cell.pending());
+        assert_eq!(Some(0), cell.update_take());
+        assert_eq!(Some(1), cell.update_take());
+        assert_eq!(2, cell.get());
+        assert!(cell.has_update());
+    }
+
+    #[test]
+    pub fn test_queued_sync_cell_clear_updates() {
+        let cell = QueuedSyncCell::new(0);
+
+        cell.set(1);
+        cell.set(2);
+
+        assert_eq!(vec![1, 2], cell.clear_updates());
+        assert!(!cell.update());
+        assert_eq!(0, cell.get());
+    }
+
+    #[test]
+    pub fn test_queued_sync_cell_into_inner() {
+        let cell = QueuedSyncCell::new(0);
+
+        cell.set(1);
+        cell.set(2);
+
+        assert_eq!(2, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_queued_sync_cell_drain_updates() {
+        let cell = QueuedSyncCell::new(0);
+        let mut seen = Vec::new();
+
+        cell.set(1);
+        cell.set(2);
+
+        assert_eq!(2, cell.drain_updates(|previous, next| seen.push((*previous, *next))));
+        assert_eq!(vec![(0, 1), (1, 2)], seen);
+
+        cell.set(3);
+
+        assert_eq!(1, cell.update_all());
+        assert_eq!(0, cell.update_all());
+        assert_eq!(3, cell.get());
+    }
+}
@@ -0,0 +1,1237 @@
+use core::{cell::UnsafeCell, fmt::{self, Debug, Formatter}, hint, marker::PhantomData, mem::replace, ops::{Deref, DerefMut}, sync::atomic::{AtomicUsize, Ordering}};
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicU32;
+#[cfg(feature = "std")]
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+#[cfg(all(debug_assertions, feature = "std"))]
+use std::cell::RefCell;
+#[cfg(all(debug_assertions, feature = "std", loom))]
+use crate::loom_shims::thread_local;
+#[cfg(all(debug_assertions, feature = "std", shuttle))]
+use crate::shuttle_shims::thread_local;
+
+use crate::watchdog::Watch;
+
+/// The backend used by `SyncCell` and `RawSyncCell` when no other is chosen.
+/// This is `RwLockRawLock`, `parking_lot::RawRwLock` with the `parking_lot` feature or
+/// `SpinRawLock` without the `std` feature.
+#[cfg(all(feature = "std", not(feature = "parking_lot"), not(loom), not(shuttle)))]
+pub type DefaultRawLock = RwLockRawLock;
+
+/// The backend used by `SyncCell` and `RawSyncCell` when no other is chosen.
+/// This is `RwLockRawLock`, `parking_lot::RawRwLock` with the `parking_lot` feature or
+/// `SpinRawLock` without the `std` feature.
+#[cfg(all(feature = "parking_lot", not(loom), not(shuttle)))]
+pub type DefaultRawLock = parking_lot::RawRwLock;
+
+/// The backend used by `SyncCell` and `RawSyncCell` when no other is chosen.
+/// This is `RwLockRawLock`, `parking_lot::RawRwLock` with the `parking_lot` feature or
+/// `SpinRawLock` without the `std` feature.
+#[cfg(not(feature = "std"))]
+pub type DefaultRawLock = SpinRawLock;
+
+/// The backend used by `SyncCell` and `RawSyncCell` when the crate is built with `--cfg loom`.
+#[cfg(all(feature = "std", loom))]
+pub type DefaultRawLock = crate::loom_shims::LoomRawLock;
+
+/// The backend used by `SyncCell` and `RawSyncCell` when the crate is built with `--cfg shuttle`.
+#[cfg(all(feature = "std", shuttle))]
+pub type DefaultRawLock = crate::shuttle_shims::ShuttleRawLock;
+
+/// A raw reader-writer lock that protects the value of a `SyncCell` or `RawSyncCell`.
+/// Implementing this trait allows a cell to be backed by a different locking strategy without
+/// changing any code that uses the cell.
+///
+/// Locks implementing this trait never poison. A panic while a borrow is held releases the
+/// lock as normal, and `SyncCell` tracks poisoning itself.
+///
+/// # Safety
+/// Implementations must ensure that no other lock is held while an exclusive lock is held and
+/// that no exclusive lock is held while a shared lock is held.
+pub unsafe trait RawSyncLock {
+    /// An unlocked lock.
+    const INIT: Self;
+
+    /// Acquires a shared lock blocking the current thread until it is available.
+    fn lock_shared(&self);
+
+    /// Attempts to acquire a shared lock without blocking.
+    /// Returns `true` if the lock was acquired.
+    fn try_lock_shared(&self) -> bool;
+
+    /// Acquires a shared lock that is granted whenever no exclusive lock is held, even if other
+    /// threads are waiting for the lock.
+    /// A thread that already holds a shared lock uses this to take another without deadlocking
+    /// behind a queued writer. Backends that never hold back readers can use the default, which
+    /// calls `lock_shared`.
+    fn lock_shared_recursive(&self) {
+        self.lock_shared();
+    }
+
+    /// Releases a shared lock.
+    ///
+    /// # Safety
+    /// A shared lock must be held by the caller.
+    unsafe fn unlock_shared(&self);
+
+    /// Acquires an exclusive lock blocking the current thread until it is available.
+    fn lock_exclusive(&self);
+
+    /// Attempts to acquire an exclusive lock without blocking.
+    /// Returns `true` if the lock was acquired.
+    fn try_lock_exclusive(&self) -> bool;
+
+    /// Releases an exclusive lock.
+    ///
+    /// # Safety
+    /// An exclusive lock must be held by the caller.
+    unsafe fn unlock_exclusive(&self);
+}
+
+/// How a `StdRawLock` chooses between waiting readers and writers.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LockPolicy {
+    /// A shared lock is granted whenever no exclusive lock is held.
+    /// This gives the most read throughput but writers can starve under heavy read load.
+    #[default]
+    ReaderPreferring,
+    /// New shared locks wait while any thread is waiting for an exclusive lock.
+    /// Readers can starve under heavy write load.
+    WriterPreferring,
+    /// Locks are granted in the order they were requested.
+    /// Consecutive readers still share the lock.
+    Fair,
+}
+
+/// The borrows held of a `StdRawLock`.
+#[cfg(feature = "std")]
+struct LockCount {
+    /// The number of shared locks held.
+    readers: u32,
+    /// Whether an exclusive lock is held.
+    writer: bool,
+    /// The number of threads waiting for an exclusive lock.
+    writers_waiting: u32,
+    /// The ticket given to the next thread to request the lock under `LockPolicy::Fair`.
+    /// Tickets wrap around, which is harmless as they are only compared for equality.
+    next_ticket: u32,
+    /// The ticket of the thread whose turn it is under `LockPolicy::Fair`.
+    serving: u32,
+}
+
+#[cfg(feature = "std")]
+impl LockCount {
+    /// Takes the next ticket if no other thread is queued for its turn.
+    fn take_turn(&mut self) -> bool {
+        if self.next_ticket != self.serving {
+            return false;
+        }
+
+        self.next_ticket = self.next_ticket.wrapping_add(1);
+        self.serving = self.serving.wrapping_add(1);
+        true
+    }
+}
+
+/// A backend of `SyncCell` and `RawSyncCell` implemented with a `std::sync::Mutex` and `Condvar`.
+/// Every lock and unlock takes the mutex, so this is slower than `RwLockRawLock` and is only
+/// worth using when a particular `LockPolicy` is needed.
+/// This type is only available with the `std` feature.
+/// The order in which waiting threads are granted the lock is chosen by a `LockPolicy`, which is
+/// `LockPolicy::ReaderPreferring` for `StdRawLock::INIT`.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{LockPolicy, RawSyncCell, StdRawLock};
+///
+/// let cell = RawSyncCell::with_lock(0, StdRawLock::with_policy(LockPolicy::WriterPreferring));
+///
+/// cell.set(1);
+/// assert_eq!(1, cell.get());
+/// ```
+#[cfg(feature = "std")]
+pub struct StdRawLock {
+    /// The borrows currently held.
+    state: Mutex<LockCount>,
+    /// The condition variable used to wake threads waiting for the lock.
+    released: Condvar,
+    /// How waiting threads are granted the lock.
+    policy: LockPolicy,
+}
+
+#[cfg(feature = "std")]
+impl StdRawLock {
+    /// Creates a new unlocked `StdRawLock`.
+    ///
+    /// - `policy` - How waiting threads are granted the lock.
+    pub const fn with_policy(policy: LockPolicy) -> Self {
+        Self {
+            state: Mutex::new(LockCount {
+                readers: 0,
+                writer: false,
+                writers_waiting: 0,
+                next_ticket: 0,
+                serving: 0,
+            }),
+            released: Condvar::new(),
+            policy,
+        }
+    }
+
+    /// Gets the policy used to grant this lock to waiting threads.
+    pub fn policy(&self) -> LockPolicy {
+        self.policy
+    }
+
+    /// Locks the borrow counts of this lock.
+    fn state(&self) -> MutexGuard<'_, LockCount> {
+        // The counts are never left inconsistent while locked, so a poisoned lock can be used.
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        }
+    }
+
+    /// Blocks while `condition` holds for the locked borrow counts.
+    fn wait<'a>(&self, state: MutexGuard<'a, LockCount>, condition: impl FnMut(&mut LockCount) -> bool) -> MutexGuard<'a, LockCount> {
+        match self.released.wait_while(state, condition) {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl RawSyncLock for StdRawLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self::with_policy(LockPolicy::ReaderPreferring);
+
+    fn lock_shared(&self) {
+        let mut state = self.state();
+
+        match self.policy {
+            LockPolicy::ReaderPreferring => {
+                state = self.wait(state, |state| state.writer);
+            },
+            LockPolicy::WriterPreferring => {
+                state = self.wait(state, |state| state.writer || state.writers_waiting > 0);
+            },
+            LockPolicy::Fair => {
+                let ticket = state.next_ticket;
+                state.next_ticket = state.next_ticket.wrapping_add(1);
+                state = self.wait(state, |state| state.writer || state.serving != ticket);
+
+                // The next thread in line may be a reader that can share the lock.
+                state.serving = state.serving.wrapping_add(1);
+                self.released.notify_all();
+            },
+        }
+
+        state.readers += 1;
+    }
+
+    fn lock_shared_recursive(&self) {
+        let state = self.state();
+
+        self.wait(state, |state| state.writer).readers += 1;
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        let mut state = self.state();
+
+        let available = match self.policy {
+            LockPolicy::ReaderPreferring => !state.writer,
+            LockPolicy::WriterPreferring => !state.writer && state.writers_waiting == 0,
+            LockPolicy::Fair => !state.writer && state.take_turn(),
+        };
+
+        if available {
+            state.readers += 1;
+        }
+
+        available
+    }
+
+    unsafe fn unlock_shared(&self) {
+        let mut state = self.state();
+
+        state.readers -= 1;
+
+        if state.readers == 0 {
+            self.released.notify_all();
+        }
+    }
+
+    fn lock_exclusive(&self) {
+        let mut state = self.state();
+
+        match self.policy {
+            LockPolicy::ReaderPreferring => {
+                state = self.wait(state, |state| state.writer || state.readers > 0);
+            },
+            LockPolicy::WriterPreferring => {
+                state.writers_waiting += 1;
+                state = self.wait(state, |state| state.writer || state.readers > 0);
+                state.writers_waiting -= 1;
+            },
+            LockPolicy::Fair => {
+                let ticket = state.next_ticket;
+                state.next_ticket = state.next_ticket.wrapping_add(1);
+                state = self.wait(state, |state| state.writer || state.readers > 0 || state.serving != ticket);
+                state.serving = state.serving.wrapping_add(1);
+            },
+        }
+
+        state.writer = true;
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        let mut state = self.state();
+
+        if state.writer || state.readers > 0 {
+            return false;
+        }
+
+        if self.policy == LockPolicy::Fair && !state.take_turn() {
+            return false;
+        }
+
+        state.writer = true;
+        true
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        self.state().writer = false;
+        self.released.notify_all();
+    }
+}
+
+/// The state of a `RwLockRawLock` while an exclusive lock is held.
+#[cfg(feature = "std")]
+const RW_WRITER: u32 = 1;
+
+/// The state of a `RwLockRawLock` while a thread is waiting for an exclusive lock.
+/// New shared locks wait while it is set, so that writers are not starved by readers.
+#[cfg(feature = "std")]
+const RW_WRITER_WAITING: u32 = 1 << 1;
+
+/// The state of a `RwLockRawLock` while a thread is parked waiting for the lock.
+#[cfg(feature = "std")]
+const RW_PARKED: u32 = 1 << 2;
+
+/// The state of a `RwLockRawLock` counting one shared lock.
+#[cfg(feature = "std")]
+const RW_READER: u32 = 1 << 3;
+
+/// The bits of the state of a `RwLockRawLock` counting the shared locks held.
+#[cfg(feature = "std")]
+const RW_READERS: u32 = !(RW_READER - 1);
+
+/// A reader-writer backend of `SyncCell` and `RawSyncCell` that keeps its state in a single atomic.
+/// Locks that are not contended are taken and released without a system call. Threads that have
+/// to wait are parked on a `std::sync::Mutex` and `Condvar`, which are only used while a thread is
+/// waiting.
+/// This is the `DefaultRawLock` unless the `parking_lot` feature is enabled.
+/// This type is only available with the `std` feature.
+///
+/// New shared locks wait while a thread is waiting for an exclusive lock, so a nested immutable
+/// borrow must use `RawSyncCell::borrow_recursive`.
+/// `StdRawLock` can be used instead where a particular `LockPolicy` is needed.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{RawSyncCell, RwLockRawLock};
+///
+/// let cell: RawSyncCell<i32, RwLockRawLock> = RawSyncCell::new(1);
+///
+/// *cell.borrow_mut() += 1;
+/// assert_eq!(2, cell.get());
+/// ```
+#[cfg(feature = "std")]
+pub struct RwLockRawLock {
+    /// The number of shared locks held in the high bits and the `RW_WRITER`,
+    /// `RW_WRITER_WAITING` and `RW_PARKED` flags in the low bits.
+    state: AtomicU32,
+    /// The mutex held by threads while they park.
+    parked: Mutex<()>,
+    /// The condition variable used to wake parked threads.
+    released: Condvar,
+}
+
+#[cfg(feature = "std")]
+impl RwLockRawLock {
+    /// Takes a shared lock if it is only blocked by `blocked` flags that are not set.
+    fn try_lock_shared_unless(&self, blocked: u32) -> bool {
+        self.state.fetch_update(Ordering::Acquire, Ordering::Relaxed, |state| {
+            (state & blocked == 0 && state & RW_READERS != RW_READERS).then_some(state + RW_READER)
+        }).is_ok()
+    }
+
+    /// Parks the thread until `try_lock` succeeds.
+    ///
+    /// - `waiting` - The flags to set while the thread is parked.
+    fn park(&self, try_lock: impl Fn(&Self) -> bool, waiting: u32) {
+        while !try_lock(self) {
+            // The mutex only protects `()`, so a poisoned mutex can be used.
+            let parked = self.parked.lock().unwrap_or_else(PoisonError::into_inner);
+
+            // The flags are set while the mutex is held, so the thread that releases the lock
+            // next either sees them and wakes this thread or released it before it is tried again.
+            self.state.fetch_or(waiting, Ordering::Relaxed);
+
+            if try_lock(self) {
+                return;
+            }
+
+            drop(self.released.wait(parked).unwrap_or_else(PoisonError::into_inner));
+        }
+    }
+
+    /// Wakes the parked threads if the lock was released from `previous`, a state with
+    /// `RW_PARKED` set.
+    fn unpark(&self, previous: u32) {
+        if previous & RW_PARKED == 0 {
+            return;
+        }
+
+        let _parked = self.parked.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Threads that still have to wait set the flags again before they park.
+        self.state.fetch_and(!(RW_PARKED | RW_WRITER_WAITING), Ordering::Relaxed);
+        self.released.notify_all();
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl RawSyncLock for RwLockRawLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        state: AtomicU32::new(0),
+        parked: Mutex::new(()),
+        released: Condvar::new(),
+    };
+
+    fn lock_shared(&self) {
+        self.park(Self::try_lock_shared, RW_PARKED);
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        self.try_lock_shared_unless(RW_WRITER | RW_WRITER_WAITING)
+    }
+
+    fn lock_shared_recursive(&self) {
+        // Share the lock with the shared lock already held instead of waiting behind a queued writer.
+        self.park(|lock| lock.try_lock_shared_unless(RW_WRITER), RW_PARKED);
+    }
+
+    unsafe fn unlock_shared(&self) {
+        let previous = self.state.fetch_sub(RW_READER, Ordering::Release);
+
+        // Only the last shared lock released lets a waiting thread take the lock.
+        if previous & RW_READERS == RW_READER {
+            self.unpark(previous);
+        }
+    }
+
+    fn lock_exclusive(&self) {
+        self.park(Self::try_lock_exclusive, RW_PARKED | RW_WRITER_WAITING);
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        self.state.fetch_update(Ordering::Acquire, Ordering::Relaxed, |state| {
+            (state & (RW_WRITER | RW_READERS) == 0).then_some(state | RW_WRITER)
+        }).is_ok()
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        let previous = self.state.fetch_and(!RW_WRITER, Ordering::Release);
+
+        self.unpark(previous);
+    }
+}
+
+/// The state of a `SpinRawLock` while an exclusive lock is held.
+const SPIN_WRITER: usize = usize::MAX;
+
+/// A backend of `SyncCell` and `RawSyncCell` that spins on a single atomic instead of parking the thread.
+/// It only uses `core` atomics, so it also works where no operating system locks are
+/// available. Spinning wastes processor time, so this suits locks that are only held briefly.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{RawSyncCell, SpinRawLock};
+///
+/// static COUNTER: RawSyncCell<u32, SpinRawLock> = RawSyncCell::new(0);
+///
+/// *COUNTER.borrow_mut() += 1;
+/// assert_eq!(1, COUNTER.get());
+/// ```
+pub struct SpinRawLock {
+    /// The number of shared locks held or `SPIN_WRITER` if an exclusive lock is held.
+    state: AtomicUsize,
+}
+
+impl SpinRawLock {
+    /// Spins until `try_lock` succeeds.
+    fn spin(&self, try_lock: impl Fn(&Self) -> bool) {
+        while !try_lock(self) {
+            hint::spin_loop();
+        }
+    }
+}
+
+unsafe impl RawSyncLock for SpinRawLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        state: AtomicUsize::new(0),
+    };
+
+    fn lock_shared(&self) {
+        self.spin(Self::try_lock_shared);
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        self.state.fetch_update(Ordering::Acquire, Ordering::Relaxed, |state| {
+            (state < SPIN_WRITER - 1).then_some(state + 1)
+        }).is_ok()
+    }
+
+    unsafe fn unlock_shared(&self) {
+        self.state.fetch_sub(1, Ordering::Release);
+    }
+
+    fn lock_exclusive(&self) {
+        self.spin(Self::try_lock_exclusive);
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        self.state.compare_exchange(0, SPIN_WRITER, Ordering::Acquire, Ordering::Relaxed).is_ok()
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        self.state.store(0, Ordering::Release);
+    }
+}
+
+/// The lock of `parking_lot`, which is faster than `StdRawLock` and is fair under contention.
+/// This is the `DefaultRawLock` with the `parking_lot` feature.
+#[cfg(feature = "parking_lot")]
+unsafe impl RawSyncLock for parking_lot::RawRwLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = <Self as lock_api::RawRwLock>::INIT;
+
+    fn lock_shared(&self) {
+        lock_api::RawRwLock::lock_shared(self);
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        lock_api::RawRwLock::try_lock_shared(self)
+    }
+
+    fn lock_shared_recursive(&self) {
+        lock_api::RawRwLockRecursive::lock_shared_recursive(self);
+    }
+
+    unsafe fn unlock_shared(&self) {
+        // SAFETY: The caller guarantees that a shared lock is held.
+        unsafe { lock_api::RawRwLock::unlock_shared(self) }
+    }
+
+    fn lock_exclusive(&self) {
+        lock_api::RawRwLock::lock_exclusive(self);
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        lock_api::RawRwLock::try_lock_exclusive(self)
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        // SAFETY: The caller guarantees that an exclusive lock is held.
+        unsafe { lock_api::RawRwLock::unlock_exclusive(self) }
+    }
+}
+
+/// A backend of `SyncCell` and `RawSyncCell` whose state is only changed inside a critical
+/// section from the `critical-section` crate.
+/// This suits bare-metal targets without atomic compare-and-swap, where the critical section
+/// is provided by the platform, for example by disabling interrupts. Waiting borrows spin outside
+/// of the critical section so that interrupts can release the lock.
+/// This type is only available with the `critical-section` feature.
+///
+/// # Usage
+/// ```
+/// # // The `std` feature of `critical-section` provides the critical section for the tests.
+/// # extern crate critical_section;
+/// use sync_cell::{CriticalSectionRawLock, RawSyncLock, SyncCell};
+///
+/// static TICKS: SyncCell<u32, CriticalSectionRawLock> = SyncCell::with_lock(0, CriticalSectionRawLock::INIT);
+///
+/// *TICKS.borrow_mut() += 1;
+/// assert_eq!(1, TICKS.get());
+/// ```
+#[cfg(feature = "critical-section")]
+pub struct CriticalSectionRawLock {
+    /// The number of shared locks held or `SPIN_WRITER` if an exclusive lock is held.
+    state: critical_section::Mutex<core::cell::Cell<usize>>,
+}
+
+#[cfg(feature = "critical-section")]
+impl CriticalSectionRawLock {
+    /// Changes the state of this lock inside a critical section.
+    /// Returns `true` if `change` accepted the state.
+    ///
+    /// - `change` - Gets the new state from the current one, or `None` to leave it unchanged.
+    fn change(&self, change: impl FnOnce(usize) -> Option<usize>) -> bool {
+        critical_section::with(|cs| {
+            let state = self.state.borrow(cs);
+
+            match change(state.get()) {
+                Some(new) => {
+                    state.set(new);
+                    true
+                },
+                None => false,
+            }
+        })
+    }
+
+    /// Spins until `try_lock` succeeds.
+    fn spin(&self, try_lock: impl Fn(&Self) -> bool) {
+        while !try_lock(self) {
+            hint::spin_loop();
+        }
+    }
+}
+
+#[cfg(feature = "critical-section")]
+unsafe impl RawSyncLock for CriticalSectionRawLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        state: critical_section::Mutex::new(core::cell::Cell::new(0)),
+    };
+
+    fn lock_shared(&self) {
+        self.spin(Self::try_lock_shared);
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        self.change(|state| (state < SPIN_WRITER - 1).then_some(state + 1))
+    }
+
+    unsafe fn unlock_shared(&self) {
+        self.change(|state| Some(state - 1));
+    }
+
+    fn lock_exclusive(&self) {
+        self.spin(Self::try_lock_exclusive);
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        self.change(|state| (state == 0).then_some(SPIN_WRITER))
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        self.change(|_| Some(0));
+    }
+}
+
+#[cfg(all(debug_assertions, feature = "std", not(loom)))]
+thread_local! {
+    /// The cells borrowed by the current thread and whether each borrow is mutable.
+    static HELD: RefCell<Vec<(usize, bool)>> = const { RefCell::new(Vec::new()) };
+}
+
+// The thread locals of `loom` cannot be initialized in a `const` block.
+#[cfg(all(debug_assertions, feature = "std", loom))]
+thread_local! {
+    /// The cells borrowed by the current thread and whether each borrow is mutable.
+    static HELD: RefCell<Vec<(usize, bool)>> = RefCell::new(Vec::new());
+}
+
+/// Panics if the current thread holds a borrow of a cell that would stop it from being borrowed
+/// again.
+/// This is only checked in debug builds.
+///
+/// - `key` - The key of the cell being borrowed.
+/// - `exclusive` - Whether the new borrow is mutable.
+fn check_reentrant(key: usize, exclusive: bool) {
+    #[cfg(all(debug_assertions, feature = "std"))]
+    {
+        let reentrant = HELD.with(|held| held.borrow().iter()
+            .any(|&(held_key, held_exclusive)| held_key == key && (exclusive || held_exclusive)));
+
+        if reentrant {
+            panic!("Cell is already borrowed by the current thread. This borrow would deadlock.");
+        }
+    }
+
+    #[cfg(not(all(debug_assertions, feature = "std")))]
+    let _ = (key, exclusive);
+}
+
+/// Records a borrow taken by the current thread.
+/// This is only recorded in debug builds.
+fn enter_borrow(key: usize, exclusive: bool) {
+    #[cfg(all(debug_assertions, feature = "std"))]
+    HELD.with(|held| held.borrow_mut().push((key, exclusive)));
+
+    #[cfg(not(all(debug_assertions, feature = "std")))]
+    let _ = (key, exclusive);
+}
+
+/// Removes a borrow released by the current thread.
+fn leave_borrow(key: usize, exclusive: bool) {
+    #[cfg(all(debug_assertions, feature = "std"))]
+    HELD.with(|held| {
+        let mut held = held.borrow_mut();
+
+        if let Some(index) = held.iter().rposition(|&borrow| borrow == (key, exclusive)) {
+            held.swap_remove(index);
+        }
+    });
+
+    #[cfg(not(all(debug_assertions, feature = "std")))]
+    let _ = (key, exclusive);
+}
+
+/// A variant of `SyncCell` that never poisons.
+/// The lock is provided by a `RawSyncLock` backend chosen with the second type parameter, which
+/// defaults to `DefaultRawLock`. Because backends never poison, none of the methods of this cell
+/// panic, except that debug builds panic when a thread borrows a cell in a way that would
+/// deadlock on a borrow it already holds. `SyncCell` is built on this cell.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{RawSyncCell, StdRawLock};
+///
+/// let cell: RawSyncCell<i32, StdRawLock> = RawSyncCell::new(1);
+///
+/// *cell.borrow_mut() += 1;
+/// assert_eq!(2, cell.get());
+/// ```
+pub struct RawSyncCell<T: ?Sized, L = DefaultRawLock> {
+    /// The lock protecting the value.
+    lock: L,
+    /// The value of this cell.
+    data: UnsafeCell<T>,
+}
+
+// SAFETY: The value is only accessed through borrows that hold the lock, which the backend
+// guarantees are exclusive for mutable borrows.
+unsafe impl <T: ?Sized + Send + Sync, L: RawSyncLock + Sync> Sync for RawSyncCell<T, L> {}
+
+impl <T, L: RawSyncLock> RawSyncCell<T, L> {
+    /// Creates a new `RawSyncCell`.
+    ///
+    /// - `data` - The initial value of the `RawSyncCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            lock: L::INIT,
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Creates a new `RawSyncCell` protected by an existing lock.
+    /// This allows backends that are configured at construction, such as a `StdRawLock` with a
+    /// `LockPolicy`.
+    ///
+    /// - `data` - The initial value of the `RawSyncCell`.
+    /// - `lock` - The unlocked lock protecting the value.
+    pub const fn with_lock(data: T, lock: L) -> Self {
+        Self {
+            lock,
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    ///
+    /// - `value` - The new value of the cell.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the internal value contained in this cell.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    pub fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Retrieves the inner value stored in this `RawSyncCell`.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> RawSyncCell<T, L> {
+    /// Borrows a immutable reference to the data stored in this cell.
+    pub fn borrow(&self) -> RawSyncCellRef<'_, T, L> {
+        check_reentrant(self.key(), false);
+        self.lock.lock_shared();
+
+        RawSyncCellRef::new(self)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell while the current thread
+    /// already holds another immutable borrow.
+    /// Unlike `borrow`, this does not wait behind threads that are queued for a mutable borrow,
+    /// which would deadlock as they are themselves waiting for the existing borrow to be released.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::{LockPolicy, RawSyncCell, StdRawLock};
+    ///
+    /// let cell = RawSyncCell::with_lock(1, StdRawLock::with_policy(LockPolicy::Fair));
+    ///
+    /// let outer = cell.borrow();
+    /// let inner = cell.borrow_recursive();
+    ///
+    /// assert_eq!(*outer, *inner);
+    /// ```
+    pub fn borrow_recursive(&self) -> RawSyncCellRef<'_, T, L> {
+        check_reentrant(self.key(), false);
+        self.lock.lock_shared_recursive();
+
+        RawSyncCellRef::new(self)
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    pub fn borrow_mut(&self) -> RawSyncCellGuard<'_, T, L> {
+        check_reentrant(self.key(), true);
+        self.lock.lock_exclusive();
+
+        RawSyncCellGuard::new(self)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell without blocking.
+    /// Returns `None` if the value is mutably borrowed.
+    pub fn try_borrow(&self) -> Option<RawSyncCellRef<'_, T, L>> {
+        if !self.lock.try_lock_shared() {
+            return None;
+        }
+
+        Some(RawSyncCellRef::new(self))
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell without blocking.
+    /// Returns `None` if the value is borrowed.
+    pub fn try_borrow_mut(&self) -> Option<RawSyncCellGuard<'_, T, L>> {
+        if !self.lock.try_lock_exclusive() {
+            return None;
+        }
+
+        Some(RawSyncCellGuard::new(self))
+    }
+
+    /// Gets a mutable reference to the value of this cell.
+    /// No locking is required as the cell is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    /// Gets a raw pointer to the value of this cell.
+    /// This allows the value to be handed to foreign code, such as a C callback that receives a
+    /// `void*` context. Creating the pointer does not lock the cell.
+    ///
+    /// Dereferencing the pointer is only sound while the caller holds a borrow of this cell that
+    /// permits the access: a `RawSyncCellRef` for reads or a `RawSyncCellGuard` for writes.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::RawSyncCell;
+    ///
+    /// let cell: RawSyncCell<i32> = RawSyncCell::new(1);
+    /// let pointer = cell.data_ptr();
+    ///
+    /// let borrowed = cell.borrow_mut();
+    /// // SAFETY: The mutable borrow is held while the value is written.
+    /// unsafe { *pointer = 2 };
+    /// drop(borrowed);
+    ///
+    /// assert_eq!(2, cell.get());
+    /// ```
+    pub fn data_ptr(&self) -> *mut T {
+        self.data.get()
+    }
+
+    /// Gets the key identifying this cell in the borrows held by a thread.
+    fn key(&self) -> usize {
+        self as *const Self as *const () as usize
+    }
+
+    /// Gets the lock protecting the value of this cell.
+    #[cfg(feature = "std")]
+    pub(crate) fn raw_lock(&self) -> &L {
+        &self.lock
+    }
+}
+
+impl <T: Clone, L: RawSyncLock> RawSyncCell<T, L> {
+    /// Gets the value contained in this cell.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl <T: Clone, L: RawSyncLock> Clone for RawSyncCell<T, L> {
+    fn clone(&self) -> Self {
+        Self::new(self.get())
+    }
+}
+
+impl <T: Default, L: RawSyncLock> Default for RawSyncCell<T, L> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T, L: RawSyncLock> From<T> for RawSyncCell<T, L> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl <T: ?Sized + Debug, L: RawSyncLock> Debug for RawSyncCell<T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.try_borrow() {
+            Some(value) => f.debug_struct("RawSyncCell").field("data", &&*value).finish(),
+            None => f.debug_struct("RawSyncCell").field("data", &format_args!("<locked>")).finish(),
+        }
+    }
+}
+
+/// An immutable borrow of the value of a `RawSyncCell`.
+pub struct RawSyncCellRef<'a, T: ?Sized, L: RawSyncLock> {
+    /// The borrowed cell.
+    cell: &'a RawSyncCell<T, L>,
+    /// When and where this borrow was taken for the guard watchdog.
+    watch: Watch,
+    /// Keeps the borrow on the thread that took it, as some backends require.
+    _unsend: PhantomData<*const ()>,
+}
+
+// SAFETY: The borrow only gives shared access to the value, like a reference.
+unsafe impl <T: ?Sized + Sync, L: RawSyncLock> Sync for RawSyncCellRef<'_, T, L> {}
+
+impl <T: ?Sized, L: RawSyncLock> Deref for RawSyncCellRef<'_, T, L> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: A shared lock is held for as long as this borrow exists.
+        unsafe { &*self.cell.data.get() }
+    }
+}
+
+impl <'a, T: ?Sized, L: RawSyncLock> RawSyncCellRef<'a, T, L> {
+    /// Creates a borrow of a cell whose shared lock has been acquired.
+    fn new(cell: &'a RawSyncCell<T, L>) -> Self {
+        enter_borrow(cell.key(), false);
+
+        Self {
+            cell,
+            watch: Watch::start(),
+            _unsend: PhantomData,
+        }
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> Drop for RawSyncCellRef<'_, T, L> {
+    fn drop(&mut self) {
+        leave_borrow(self.cell.key(), false);
+
+        // SAFETY: The shared lock was acquired when this borrow was created.
+        unsafe { self.cell.lock.unlock_shared() }
+
+        self.watch.finish(false);
+    }
+}
+
+/// A mutable borrow of the value of a `RawSyncCell`.
+pub struct RawSyncCellGuard<'a, T: ?Sized, L: RawSyncLock> {
+    /// The borrowed cell.
+    cell: &'a RawSyncCell<T, L>,
+    /// When and where this borrow was taken for the guard watchdog.
+    watch: Watch,
+    /// Keeps the borrow on the thread that took it, as some backends require.
+    _unsend: PhantomData<*const ()>,
+}
+
+// SAFETY: Sharing the borrow only gives shared access to the value, like a reference.
+unsafe impl <T: ?Sized + Sync, L: RawSyncLock> Sync for RawSyncCellGuard<'_, T, L> {}
+
+impl <T: ?Sized, L: RawSyncLock> Deref for RawSyncCellGuard<'_, T, L> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: An exclusive lock is held for as long as this borrow exists.
+        unsafe { &*self.cell.data.get() }
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> DerefMut for RawSyncCellGuard<'_, T, L> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: An exclusive lock is held for as long as this borrow exists.
+        unsafe { &mut *self.cell.data.get() }
+    }
+}
+
+impl <'a, T: ?Sized, L: RawSyncLock> RawSyncCellGuard<'a, T, L> {
+    /// Creates a borrow of a cell whose exclusive lock has been acquired.
+    fn new(cell: &'a RawSyncCell<T, L>) -> Self {
+        enter_borrow(cell.key(), true);
+
+        Self {
+            cell,
+            watch: Watch::start(),
+            _unsend: PhantomData,
+        }
+    }
+}
+
+impl <T: ?Sized, L: RawSyncLock> Drop for RawSyncCellGuard<'_, T, L> {
+    fn drop(&mut self) {
+        leave_borrow(self.cell.key(), true);
+
+        // SAFETY: The exclusive lock was acquired when this borrow was created.
+        unsafe { self.cell.lock.unlock_exclusive() }
+
+        self.watch.finish(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::{LockPolicy, RawSyncCell, RwLockRawLock, SpinRawLock, StdRawLock};
+    #[cfg(feature = "critical-section")]
+    use crate::CriticalSectionRawLock;
+
+    #[test]
+    pub fn test_raw_sync_cell_borrow() {
+        let cell: RawSyncCell<i32> = RawSyncCell::new(1);
+        let borrowed = cell.borrow();
+
+        assert!(cell.try_borrow().is_some());
+        assert!(cell.try_borrow_mut().is_none());
+
+        drop(borrowed);
+
+        let borrowed = cell.borrow_mut();
+
+        assert!(cell.try_borrow().is_none());
+
+        drop(borrowed);
+
+        assert_eq!(1, cell.replace(2));
+        assert_eq!(2, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_raw_sync_cell_threads() {
+        let cell: Arc<RawSyncCell<i32>> = Arc::new(RawSyncCell::new(0));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    *cell.borrow_mut() += 1;
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(400, cell.get());
+    }
+
+    #[test]
+    pub fn test_spin_raw_lock() {
+        let cell: Arc<RawSyncCell<i32, SpinRawLock>> = Arc::new(RawSyncCell::new(0));
+        let borrowed = cell.borrow();
+
+        assert!(cell.try_borrow().is_some());
+        assert!(cell.try_borrow_mut().is_none());
+
+        drop(borrowed);
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    *cell.borrow_mut() += 1;
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(400, cell.get());
+    }
+
+    #[test]
+    pub fn test_std_raw_lock_policy() {
+        for policy in [LockPolicy::ReaderPreferring, LockPolicy::WriterPreferring, LockPolicy::Fair] {
+            let cell = Arc::new(RawSyncCell::with_lock(0, StdRawLock::with_policy(policy)));
+            let borrowed = cell.borrow();
+
+            assert!(cell.try_borrow().is_some());
+            assert!(cell.try_borrow_mut().is_none());
+
+            let handles: Vec<_> = (0..4).map(|i| {
+                let cell = cell.clone();
+                thread::spawn(move || {
+                    for _ in 0..50 {
+                        if i % 2 == 0 {
+                            *cell.borrow_mut() += 1;
+                        } else {
+                            assert!(*cell.borrow() >= 0);
+                        }
+                    }
+                })
+            }).collect();
+
+            drop(borrowed);
+
+            for handle in handles {
+                handle.join().unwrap();
+            }
+
+            assert_eq!(100, cell.get());
+        }
+    }
+
+    #[test]
+    pub fn test_raw_sync_cell_borrow_recursive() {
+        let cell = Arc::new(RawSyncCell::with_lock(1, StdRawLock::with_policy(LockPolicy::WriterPreferring)));
+        let outer = cell.borrow();
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || *cell.borrow_mut() = 2)
+        };
+
+        thread::sleep(Duration::from_millis(20));
+
+        // A plain borrow would now wait for the queued writer.
+        assert!(cell.try_borrow().is_none());
+        assert_eq!(1, *cell.borrow_recursive());
+
+        drop(outer);
+        writer.join().unwrap();
+
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_rw_lock_raw_lock() {
+        let cell: Arc<RawSyncCell<i32, RwLockRawLock>> = Arc::new(RawSyncCell::new(0));
+        let borrowed = cell.borrow();
+
+        assert!(cell.try_borrow().is_some());
+        assert!(cell.try_borrow_mut().is_none());
+
+        let handles: Vec<_> = (0..4).map(|i| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    if i % 2 == 0 {
+                        *cell.borrow_mut() += 1;
+                    } else {
+                        assert!(*cell.borrow() >= 0);
+                    }
+                }
+            })
+        }).collect();
+
+        drop(borrowed);
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(100, cell.get());
+
+        // A panic while the cell is mutably borrowed releases the lock.
+        let panicked = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let _guard = cell.borrow_mut();
+                panic!("Test panic");
+            }).join()
+        };
+
+        assert!(panicked.is_err());
+        cell.set(1);
+        assert_eq!(1, *cell.borrow());
+    }
+
+    #[test]
+    pub fn test_rw_lock_raw_lock_borrow_recursive() {
+        let cell: Arc<RawSyncCell<i32, RwLockRawLock>> = Arc::new(RawSyncCell::new(1));
+        let outer = cell.borrow();
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || *cell.borrow_mut() = 2)
+        };
+
+        thread::sleep(Duration::from_millis(20));
+
+        let inner = cell.borrow_recursive();
+        assert_eq!(1, *inner);
+
+        drop(outer);
+        assert_eq!(1, *inner);
+        drop(inner);
+
+        writer.join().unwrap();
+
+        assert_eq!(2, cell.get());
+    }
+
+    #[cfg(feature = "critical-section")]
+    #[test]
+    pub fn test_critical_section_raw_lock() {
+        let cell: Arc<RawSyncCell<i32, CriticalSectionRawLock>> = Arc::new(RawSyncCell::new(0));
+        let borrowed = cell.borrow();
+
+        assert!(cell.try_borrow().is_some());
+        assert!(cell.try_borrow_mut().is_none());
+
+        drop(borrowed);
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    *cell.borrow_mut() += 1;
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(400, cell.get());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "already borrowed by the current thread")]
+    pub fn test_raw_sync_cell_reentrant_borrow() {
+        let cell: RawSyncCell<i32> = RawSyncCell::new(1);
+        let _borrowed = cell.borrow();
+
+        let _ = cell.borrow_recursive();
+        cell.set(2);
+    }
+}
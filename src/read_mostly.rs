@@ -0,0 +1,277 @@
+use std::{ops::Deref, sync::{Mutex, atomic::{AtomicPtr, AtomicUsize, Ordering}}, thread};
+
+use crate::sharded::thread_shard;
+
+/// A reader counter aligned to a cache line so that readers on different threads do not contend.
+#[repr(align(128))]
+struct ReaderCount {
+    /// The number of readers in this shard.
+    count: AtomicUsize,
+}
+
+/// A cell optimised for values that are read far more often than they are written.
+/// Readers never take a lock. They only increment a counter that is unlikely to be shared with
+/// readers on other threads. Writers replace the value with a new allocation and then wait for all
+/// readers of the previous value to finish before freeing it.
+///
+/// Readers are tracked in two epochs. A writer switches the epoch readers register in and then
+/// waits for the readers of the previous epoch to leave, so a constant stream of new readers
+/// cannot starve a writer.
+///
+/// # Usage
+/// ```
+/// use sync_cell::ReadMostlyCell;
+///
+/// let cell = ReadMostlyCell::new(String::from("config"));
+///
+/// assert_eq!("config", &*cell.read());
+///
+/// cell.set(String::from("new config"));
+/// assert_eq!("new config", cell.get());
+/// ```
+pub struct ReadMostlyCell<T> {
+    /// The current value of this cell.
+    current: AtomicPtr<T>,
+    /// The epoch new readers register in.
+    epoch: AtomicUsize,
+    /// The reader counters for each epoch.
+    readers: [Box<[ReaderCount]>; 2],
+    /// The lock serialising writers.
+    writer: Mutex<()>,
+}
+
+// SAFETY: The value is shared between threads through references so it must be `Sync`. Writers
+// may drop values on any thread so it must also be `Send`.
+unsafe impl <T: Send + Sync> Send for ReadMostlyCell<T> {}
+unsafe impl <T: Send + Sync> Sync for ReadMostlyCell<T> {}
+
+impl <T> ReadMostlyCell<T> {
+    /// Creates a new `ReadMostlyCell` with one reader counter per available CPU.
+    ///
+    /// - `data` - The initial value of the `ReadMostlyCell`.
+    pub fn new(data: T) -> Self {
+        let shards = thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(4);
+
+        let counters = || (0..shards)
+            .map(|_| ReaderCount {
+                count: AtomicUsize::new(0),
+            })
+            .collect();
+
+        Self {
+            current: AtomicPtr::new(Box::into_raw(Box::new(data))),
+            epoch: AtomicUsize::new(0),
+            readers: [counters(), counters()],
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Borrows a immutable reference to the current value of this cell without locking.
+    /// Writers will wait for the guard to be dropped before freeing the value it refers to, so
+    /// guards should not be held for long periods of time.
+    pub fn read(&self) -> ReadMostlyGuard<'_, T> {
+        let shard = thread_shard() % self.readers[0].len();
+
+        loop {
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            let counter = &self.readers[epoch][shard].count;
+
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            // If a writer switched epochs in the meantime it may not wait for this reader.
+            if self.epoch.load(Ordering::SeqCst) == epoch {
+                return ReadMostlyGuard {
+                    value: self.current.load(Ordering::SeqCst),
+                    counter,
+                    _cell: self,
+                };
+            }
+
+            counter.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// This blocks until all readers of the previous value have finished.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the writer lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        drop(self.replace(value));
+    }
+
+    /// Replaces the value contained in this cell.
+    /// This blocks until all readers of the previous value have finished. The previous value is
+    /// returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the writer lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        let _writer = match self.writer.lock() {
+            Ok(writer) => writer,
+            Err(err) => panic!("Failed to set cell value. Lock was poisoned: {}", err),
+        };
+
+        self.replace_locked(value)
+    }
+
+    /// Replaces the value of this cell with the result of a function applied to the current
+    /// value. Writers are serialised so concurrent updates are applied one after another.
+    ///
+    /// - `f` - The function used to compute the new value.
+    ///
+    /// # Panicking
+    /// This method will panic if the writer lock becomes poisoned.
+    pub fn update(&self, f: impl FnOnce(&T) -> T) {
+        let _writer = match self.writer.lock() {
+            Ok(writer) => writer,
+            Err(err) => panic!("Failed to set cell value. Lock was poisoned: {}", err),
+        };
+
+        // SAFETY: Only writers free values and the writer lock is held.
+        let value = f(unsafe { &*self.current.load(Ordering::SeqCst) });
+        drop(self.replace_locked(value));
+    }
+
+    /// Retrieves the inner value stored in this `ReadMostlyCell`.
+    pub fn into_inner(self) -> T {
+        let pointer = self.current.swap(std::ptr::null_mut(), Ordering::SeqCst);
+
+        // SAFETY: The pointer was created by `Box::into_raw` and ownership of the cell guarantees
+        // no readers exist.
+        *unsafe { Box::from_raw(pointer) }
+    }
+
+    /// Replaces the current value and waits for readers of the old value to finish.
+    /// The writer lock must be held.
+    fn replace_locked(&self, value: T) -> T {
+        let old = self.current.swap(Box::into_raw(Box::new(value)), Ordering::SeqCst);
+
+        let epoch = self.epoch.load(Ordering::SeqCst);
+        self.epoch.store(1 - epoch, Ordering::SeqCst);
+
+        // Readers registering in the new epoch load the pointer after the swap so only readers of
+        // the previous epoch can still refer to the old value.
+        for reader in self.readers[epoch].iter() {
+            while reader.count.load(Ordering::SeqCst) != 0 {
+                thread::yield_now();
+            }
+        }
+
+        // SAFETY: The pointer was created by `Box::into_raw` and all readers that could have
+        // loaded it have finished.
+        *unsafe { Box::from_raw(old) }
+    }
+}
+
+impl <T: Clone> ReadMostlyCell<T> {
+    /// Gets the value contained in this cell without locking.
+    pub fn get(&self) -> T {
+        self.read().clone()
+    }
+}
+
+impl <T: Default> Default for ReadMostlyCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for ReadMostlyCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl <T> Drop for ReadMostlyCell<T> {
+    fn drop(&mut self) {
+        let pointer = *self.current.get_mut();
+
+        if !pointer.is_null() {
+            // SAFETY: The pointer was created by `Box::into_raw` and no readers exist.
+            drop(unsafe { Box::from_raw(pointer) });
+        }
+    }
+}
+
+/// A lock-free borrow of the value in a `ReadMostlyCell`.
+pub struct ReadMostlyGuard<'a, T> {
+    /// The value being read.
+    value: *const T,
+    /// The reader counter this reader is registered in.
+    counter: &'a AtomicUsize,
+    /// The cell being read.
+    _cell: &'a ReadMostlyCell<T>,
+}
+
+impl <T> Deref for ReadMostlyGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: The value is not freed while this reader is registered.
+        unsafe { &*self.value }
+    }
+}
+
+impl <T> Drop for ReadMostlyGuard<'_, T> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::ReadMostlyCell;
+
+    #[test]
+    pub fn test_read_mostly_cell_read() {
+        let cell = ReadMostlyCell::new(1);
+
+        assert_eq!(1, *cell.read());
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_read_mostly_cell_set() {
+        let cell = ReadMostlyCell::new(1);
+
+        cell.set(2);
+        assert_eq!(2, cell.replace(3));
+        cell.update(|value| value * 2);
+
+        assert_eq!(6, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_read_mostly_cell_threads() {
+        let cell = Arc::new(ReadMostlyCell::new(vec![0; 16]));
+
+        let readers: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    let value = cell.read();
+                    assert!(value.iter().all(|item| *item == value[0]));
+                }
+            })
+        }).collect();
+
+        for i in 1..=100 {
+            cell.set(vec![i; 16]);
+        }
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        assert_eq!(vec![100; 16], cell.get());
+    }
+}
@@ -0,0 +1,267 @@
+use std::{collections::VecDeque, fmt::{Debug, Write}, ops::{Deref, DerefMut}, sync::{Mutex, MutexGuard, PoisonError}, thread::{self, ThreadId}, time::SystemTime};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A write to a `RecordedCell`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteRecord<T> {
+    /// The value of the cell after the write.
+    pub value: T,
+    /// When the write happened.
+    pub time: SystemTime,
+    /// The thread that made the write.
+    pub thread: ThreadId,
+    /// The name of the thread that made the write, if it has one.
+    pub thread_name: Option<String>,
+}
+
+/// A cell that records the most recent writes to its value for debugging.
+/// Each write stores a copy of the new value along with when it happened and the thread that
+/// made it. Only a fixed number of writes are kept, with the oldest being discarded first. This
+/// helps find out where an unexpected value came from after a race.
+///
+/// This type is only available with the `record` feature.
+///
+/// # Usage
+/// ```
+/// use sync_cell::RecordedCell;
+///
+/// let cell = RecordedCell::new(0, 2);
+///
+/// cell.set(1);
+/// *cell.borrow_mut() += 1;
+/// cell.set(3);
+///
+/// let history = cell.history();
+///
+/// assert_eq!(vec![2, 3], history.iter().map(|record| record.value).collect::<Vec<_>>());
+/// println!("{}", cell.history_dump());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this cell will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct RecordedCell<T: Clone> {
+    /// The value of this cell.
+    data: SyncCell<T>,
+    /// The most recent writes with the oldest first.
+    history: Mutex<VecDeque<WriteRecord<T>>>,
+    /// The maximum number of writes kept.
+    capacity: usize,
+}
+
+impl <T: Clone> RecordedCell<T> {
+    /// Creates a new `RecordedCell`.
+    ///
+    /// - `data` - The initial value of the `RecordedCell`, which is not recorded.
+    /// - `capacity` - The maximum number of writes to keep.
+    pub fn new(data: T, capacity: usize) -> Self {
+        Self {
+            data: SyncCell::new(data),
+            history: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Sets the value contained in this cell and records the write.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the value contained in this cell and records the write.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        std::mem::replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.data.get()
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.data.borrow()
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// The value is recorded once the borrow is dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> RecordedGuard<'_, T> {
+        RecordedGuard {
+            cell: self,
+            data: self.data.borrow_mut(),
+        }
+    }
+
+    /// Gets the recorded writes with the oldest first.
+    pub fn history(&self) -> Vec<WriteRecord<T>> {
+        self.lock_history().iter().cloned().collect()
+    }
+
+    /// Removes every recorded write.
+    pub fn clear_history(&self) {
+        self.lock_history().clear();
+    }
+
+    /// Gets the maximum number of writes kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retrieves the inner value stored in this `RecordedCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Records a write to this cell.
+    ///
+    /// - `value` - The value of the cell after the write.
+    fn record(&self, value: &T) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let thread = thread::current();
+        let record = WriteRecord {
+            value: value.clone(),
+            time: SystemTime::now(),
+            thread: thread.id(),
+            thread_name: thread.name().map(String::from),
+        };
+
+        let mut history = self.lock_history();
+
+        if history.len() >= self.capacity {
+            history.pop_front();
+        }
+
+        history.push_back(record);
+    }
+
+    /// Takes the lock of the recorded writes.
+    /// Writes are only ever added whole, so a poisoned lock is ignored.
+    fn lock_history(&self) -> MutexGuard<'_, VecDeque<WriteRecord<T>>> {
+        self.history.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl <T: Clone + Debug> RecordedCell<T> {
+    /// Formats the recorded writes with the oldest first, one per line.
+    /// Each line shows the time of the write as the duration since the Unix epoch, the thread
+    /// and the value.
+    pub fn history_dump(&self) -> String {
+        let mut dump = String::new();
+
+        for record in self.lock_history().iter() {
+            let time = record.time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+
+            let _ = match &record.thread_name {
+                Some(name) => writeln!(dump, "[{:?}] thread '{}': {:?}", time, name, record.value),
+                None => writeln!(dump, "[{:?}] thread {:?}: {:?}", time, record.thread, record.value),
+            };
+        }
+
+        dump
+    }
+}
+
+impl <T: Clone + Default> Default for RecordedCell<T> {
+    /// Creates a cell with the default value that keeps the last 64 writes.
+    fn default() -> Self {
+        Self::new(T::default(), 64)
+    }
+}
+
+/// A mutable borrow of a `RecordedCell` that records the value once it is dropped.
+pub struct RecordedGuard<'a, T: Clone> {
+    /// The cell being borrowed.
+    cell: &'a RecordedCell<T>,
+    /// The borrowed value.
+    data: SyncCellGuard<'a, T>,
+}
+
+impl <T: Clone> Deref for RecordedGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl <T: Clone> DerefMut for RecordedGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl <T: Clone> Drop for RecordedGuard<'_, T> {
+    fn drop(&mut self) {
+        // The value is recorded before the lock is released so writes are recorded in order.
+        self.cell.record(&self.data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::RecordedCell;
+
+    #[test]
+    pub fn test_recorded_cell() {
+        let cell = RecordedCell::new(String::new(), 3);
+
+        cell.set(String::from("a"));
+        cell.borrow_mut().push('b');
+        cell.replace(String::from("c"));
+        cell.set(String::from("d"));
+
+        let history = cell.history();
+        assert_eq!(vec!["ab", "c", "d"], history.iter().map(|record| record.value.as_str()).collect::<Vec<_>>());
+
+        cell.clear_history();
+        cell.set(String::from("e"));
+
+        let dump = cell.history_dump();
+        assert_eq!(1, dump.lines().count());
+        assert!(dump.ends_with("\"e\"\n"));
+    }
+
+    #[test]
+    pub fn test_recorded_cell_thread() {
+        let cell = RecordedCell::new(0, 4);
+
+        thread::scope(|scope| {
+            thread::Builder::new()
+                .name(String::from("writer"))
+                .spawn_scoped(scope, || cell.set(1))
+                .unwrap();
+        });
+
+        assert_eq!(Some("writer"), cell.history()[0].thread_name.as_deref());
+        assert!(cell.history_dump().contains("thread 'writer': 1"));
+    }
+}
@@ -0,0 +1,232 @@
+use std::{collections::VecDeque, sync::{Condvar, Mutex, MutexGuard}, time::Duration};
+
+/// What a `SyncRingBuffer` does when a value is pushed while it is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// The oldest value is removed to make space for the new one.
+    #[default]
+    Overwrite,
+    /// The pushing thread blocks until a consumer makes space.
+    Block,
+}
+
+/// A buffer of fixed capacity shared between producer and consumer threads, such as a shared log
+/// or telemetry buffer.
+/// Values are consumed in the order they were pushed. When the buffer is full a push either
+/// overwrites the oldest value or blocks depending on the `OverflowPolicy` of the buffer.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{OverflowPolicy, SyncRingBuffer};
+///
+/// let buffer = SyncRingBuffer::new(3, OverflowPolicy::Overwrite);
+///
+/// for i in 0..5 {
+///     buffer.push(i);
+/// }
+///
+/// assert_eq!(vec![2, 3, 4], buffer.drain());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this buffer will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct SyncRingBuffer<T> {
+    /// The values in the buffer from oldest to newest.
+    data: Mutex<VecDeque<T>>,
+    /// The maximum number of values in the buffer.
+    capacity: usize,
+    /// What to do when a value is pushed while the buffer is full.
+    policy: OverflowPolicy,
+    /// The condition variable used to wake threads waiting for space.
+    popped: Condvar,
+}
+
+impl <T> SyncRingBuffer<T> {
+    /// Creates a new empty `SyncRingBuffer`.
+    ///
+    /// - `capacity` - The maximum number of values. A capacity of zero is treated as one.
+    /// - `policy` - What to do when a value is pushed while the buffer is full.
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            data: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            policy,
+            popped: Condvar::new(),
+        }
+    }
+
+    /// Gets the maximum number of values in this buffer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets what this buffer does when a value is pushed while it is full.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Gets the number of values in this buffer.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Checks if this buffer has no values.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Adds a value to this buffer.
+    /// If the buffer is full this either removes and returns the oldest value or blocks until
+    /// space is available, depending on the policy of the buffer.
+    ///
+    /// - `value` - The value to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push(&self, value: T) -> Option<T> {
+        let mut data = self.lock();
+
+        let overwritten = match self.policy {
+            OverflowPolicy::Overwrite if data.len() >= self.capacity => data.pop_front(),
+            OverflowPolicy::Overwrite => None,
+            OverflowPolicy::Block => {
+                data = match self.popped.wait_while(data, |data| data.len() >= self.capacity) {
+                    Ok(data) => data,
+                    Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+                };
+
+                None
+            },
+        };
+
+        data.push_back(value);
+        overwritten
+    }
+
+    /// Adds a value to this buffer, blocking for at most `timeout` if the policy of the buffer is
+    /// `OverflowPolicy::Block`.
+    /// If the buffer is still full once the timeout has elapsed the value is returned as an
+    /// error. Buffers that overwrite never fail.
+    ///
+    /// - `value` - The value to add.
+    /// - `timeout` - The maximum amount of time to wait.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push_timeout(&self, value: T, timeout: Duration) -> Result<(), T> {
+        if self.policy == OverflowPolicy::Overwrite {
+            self.push(value);
+            return Ok(());
+        }
+
+        let data = self.lock();
+
+        let mut data = match self.popped.wait_timeout_while(data, timeout, |data| data.len() >= self.capacity) {
+            Ok((data, _)) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        };
+
+        if data.len() >= self.capacity {
+            return Err(value);
+        }
+
+        data.push_back(value);
+        Ok(())
+    }
+
+    /// Removes the oldest value in this buffer.
+    /// Returns `None` if the buffer is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn pop(&self) -> Option<T> {
+        let value = self.lock().pop_front();
+
+        if value.is_some() {
+            self.popped.notify_one();
+        }
+
+        value
+    }
+
+    /// Removes every value in this buffer from oldest to newest.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn drain(&self) -> Vec<T> {
+        let values: Vec<_> = self.lock().drain(..).collect();
+
+        if !values.is_empty() {
+            self.popped.notify_all();
+        }
+
+        values
+    }
+
+    /// Retrieves the values stored in this `SyncRingBuffer` from oldest to newest.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> VecDeque<T> {
+        match self.data.into_inner() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Locks the values in this buffer.
+    fn lock(&self) -> MutexGuard<'_, VecDeque<T>> {
+        match self.data.lock() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::{OverflowPolicy, SyncRingBuffer};
+
+    #[test]
+    pub fn test_sync_ring_buffer_overwrite() {
+        let buffer = SyncRingBuffer::new(2, OverflowPolicy::Overwrite);
+
+        assert_eq!(None, buffer.push(1));
+        assert_eq!(None, buffer.push(2));
+        assert_eq!(Some(1), buffer.push(3));
+        assert_eq!(Ok(()), buffer.push_timeout(4, Duration::ZERO));
+        assert_eq!(Some(3), buffer.pop());
+        assert_eq!(buffer.into_inner(), [4]);
+    }
+
+    #[test]
+    pub fn test_sync_ring_buffer_block() {
+        let buffer = Arc::new(SyncRingBuffer::new(1, OverflowPolicy::Block));
+
+        buffer.push(1);
+        assert_eq!(Err(2), buffer.push_timeout(2, Duration::from_millis(10)));
+
+        let producer = {
+            let buffer = buffer.clone();
+            thread::spawn(move || buffer.push(2))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(vec![1], buffer.drain());
+
+        assert_eq!(None, producer.join().unwrap());
+        assert_eq!(Some(2), buffer.pop());
+    }
+}
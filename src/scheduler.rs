@@ -0,0 +1,152 @@
+use std::sync::{Arc, Weak};
+
+use crate::{HeldSyncCell, SyncCell};
+
+/// A type-erased `HeldSyncCell` that has been registered in an `UpdateScheduler`.
+trait Scheduled: Send + Sync {
+    /// Applies the incoming value of this cell if one is available.
+    /// Returns `true` if anything was applied.
+    fn update(&self) -> bool;
+}
+
+impl <T: Send + Sync> Scheduled for HeldSyncCell<T> {
+    fn update(&self) -> bool {
+        HeldSyncCell::update(self)
+    }
+}
+
+/// A list of `HeldSyncCell`s that are all updated by a single call to `tick`.
+/// This is useful in game loops where many held cells must be updated once per frame.
+///
+/// Unlike `UpdateGroup`, cells are updated one after another in the order they were registered, so
+/// other threads may observe some cells updated before others.
+/// Cells are held weakly and are removed from the scheduler once they are dropped.
+///
+/// # Usage
+/// ```
+/// use std::sync::Arc;
+/// use sync_cell::{HeldSyncCell, UpdateScheduler};
+///
+/// let scene = Arc::new(HeldSyncCell::new("menu"));
+/// let volume = Arc::new(HeldSyncCell::new(10));
+///
+/// let scheduler = UpdateScheduler::new();
+/// scheduler.register(&scene);
+/// scheduler.register(&volume);
+///
+/// scene.set("level");
+///
+/// // At the end of every frame.
+/// assert_eq!(1, scheduler.tick());
+/// assert_eq!("level", scene.get());
+/// ```
+#[derive(Default)]
+pub struct UpdateScheduler {
+    /// The registered cells in the order they were registered.
+    cells: SyncCell<Vec<Weak<dyn Scheduled>>>,
+}
+
+impl UpdateScheduler {
+    /// Creates a new empty `UpdateScheduler`.
+    pub const fn new() -> Self {
+        Self {
+            cells: SyncCell::new(Vec::new()),
+        }
+    }
+
+    /// Adds a cell to this scheduler.
+    /// The cell is updated after every cell registered before it.
+    ///
+    /// - `cell` - The cell to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn register<T: Send + Sync + 'static>(&self, cell: &Arc<HeldSyncCell<T>>) {
+        let cell: Arc<dyn Scheduled> = cell.clone();
+
+        self.cells.borrow_mut().push(Arc::downgrade(&cell));
+    }
+
+    /// Gets the number of cells registered with this scheduler that have not been dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        let mut cells = self.cells.borrow_mut();
+        cells.retain(|cell| cell.strong_count() > 0);
+        cells.len()
+    }
+
+    /// Checks if every cell registered with this scheduler has been dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Updates every registered cell in the order they were registered.
+    /// Returns the number of cells that had an update applied.
+    ///
+    /// The list of cells is not locked while the cells are updated, so listeners of the cells may
+    /// register new cells. Cells registered during a tick are first updated by the next tick.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn tick(&self) -> usize {
+        let cells: Vec<_> = {
+            let mut cells = self.cells.borrow_mut();
+            cells.retain(|cell| cell.strong_count() > 0);
+            cells.iter().filter_map(Weak::upgrade).collect()
+        };
+
+        cells.iter()
+            .map(|cell| cell.update())
+            .filter(|updated| *updated)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{HeldSyncCell, UpdateScheduler};
+
+    #[test]
+    pub fn test_update_scheduler_tick_in_order() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let first = Arc::new(HeldSyncCell::new(0));
+        let second = Arc::new(HeldSyncCell::new(0));
+        let scheduler = UpdateScheduler::new();
+
+        for (name, cell) in [("first", &first), ("second", &second)] {
+            let order = order.clone();
+            cell.on_update(move |_, _| order.lock().unwrap().push(name));
+        }
+
+        scheduler.register(&second);
+        scheduler.register(&first);
+
+        first.set(1);
+        second.set(2);
+
+        assert_eq!(2, scheduler.tick());
+        assert_eq!(vec!["second", "first"], *order.lock().unwrap());
+        assert_eq!(0, scheduler.tick());
+    }
+
+    #[test]
+    pub fn test_update_scheduler_dropped_cells() {
+        let cell = Arc::new(HeldSyncCell::new(1));
+        let scheduler = UpdateScheduler::new();
+
+        scheduler.register(&cell);
+        assert_eq!(1, scheduler.len());
+
+        drop(cell);
+
+        assert!(scheduler.is_empty());
+        assert_eq!(0, scheduler.tick());
+    }
+}
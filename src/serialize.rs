@@ -0,0 +1,115 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{HeldSyncCell, RawSyncLock, SyncCell};
+
+/// Serializes the value of the cell.
+///
+/// # Panicking
+/// Serializing the cell will panic if the lock becomes poisoned.
+impl <T: Serialize + ?Sized, L: RawSyncLock> Serialize for SyncCell<T, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+/// Deserializes a cell holding the deserialized value.
+impl <'de, T: Deserialize<'de>, L: RawSyncLock> Deserialize<'de> for SyncCell<T, L> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(SyncCell::with_lock(T::deserialize(deserializer)?, L::INIT))
+    }
+}
+
+/// Serializes the cell as a tuple of its current value and its incoming value, which is `None` if
+/// no new value has been set since the last call to `update`.
+/// Both values are locked together, so a value set by another thread is either fully included or
+/// not at all. The incoming value is not applied even if the update policy of the cell requires
+/// it. Deferred functions, listeners, the merge function and the update policy are not serialized.
+///
+/// # Usage
+/// ```
+/// use sync_cell::HeldSyncCell;
+///
+/// let cell = HeldSyncCell::new(1);
+/// cell.set(2);
+///
+/// let json = serde_json::to_string(&cell).unwrap();
+/// assert_eq!("[1,2]", json);
+///
+/// let cell: HeldSyncCell<i32> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(1, cell.get());
+///
+/// cell.update();
+/// assert_eq!(2, cell.get());
+/// ```
+///
+/// # Panicking
+/// Serializing the cell will panic if any of the locks become poisoned.
+impl <T: Serialize> Serialize for HeldSyncCell<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let staged = self.staged.borrow();
+        let current = self.current_value.borrow();
+
+        (&*current, &staged.value).serialize(serializer)
+    }
+}
+
+/// Deserializes a cell from a tuple of its current value and its incoming value, which is
+/// restored so that it is applied by the next call to `update`.
+impl <'de, T: Deserialize<'de>> Deserialize<'de> for HeldSyncCell<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (current, pending) = <(T, Option<T>)>::deserialize(deserializer)?;
+
+        Ok(HeldSyncCell::from_parts(current, pending))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{HeldSyncCell, SyncCell};
+
+    #[derive(Serialize, Deserialize)]
+    struct SaveState {
+        score: SyncCell<u32>,
+        level: HeldSyncCell<String>,
+    }
+
+    #[test]
+    pub fn test_sync_cell_serde() {
+        let cell = SyncCell::new(vec![1, 2, 3]);
+        let json = serde_json::to_string(&cell).unwrap();
+
+        assert_eq!("[1,2,3]", json);
+
+        let cell: SyncCell<Vec<i32>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(vec![1, 2, 3], cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_serde_round_trip() {
+        let state = SaveState {
+            score: SyncCell::new(10),
+            level: HeldSyncCell::new(String::from("menu")),
+        };
+
+        state.level.set(String::from("level 1"));
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(r#"{"score":10,"level":["menu","level 1"]}"#, json);
+
+        let state: SaveState = serde_json::from_str(&json).unwrap();
+        assert_eq!(10, state.score.get());
+        assert_eq!("menu", state.level.get());
+        assert_eq!(Some(String::from("level 1")), state.level.get_next());
+
+        state.level.update();
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(r#"{"score":10,"level":["level 1",null]}"#, json);
+
+        let state: SaveState = serde_json::from_str(&json).unwrap();
+        assert_eq!("level 1", state.level.get());
+        assert!(!state.level.has_update());
+    }
+}
@@ -0,0 +1,214 @@
+use std::{cell::Cell, iter::Sum, sync::atomic::{AtomicUsize, Ordering}, thread};
+
+use crate::SyncCell;
+
+/// The next shard index to hand out to a thread.
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// The shard index assigned to the current thread.
+    static THREAD_SHARD: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Gets the shard index assigned to the current thread.
+/// Threads are assigned indices in a round robin fashion the first time this is called.
+pub(crate) fn thread_shard() -> usize {
+    THREAD_SHARD.with(|shard| match shard.get() {
+        Some(index) => index,
+        None => {
+            let index = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+            shard.set(Some(index));
+            index
+        },
+    })
+}
+
+/// A shard of a `ShardedCell` aligned to a cache line to avoid false sharing.
+#[repr(align(128))]
+struct Shard<T> {
+    /// The value of this shard.
+    value: SyncCell<T>,
+}
+
+/// A cell that splits its value into a number of shards to reduce contention.
+/// Each thread updates the shard it is assigned to so that threads rarely compete for the same
+/// lock. Reading the overall value combines every shard.
+///
+/// This is useful for values that are updated far more often than they are read such as request
+/// counters.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::ShardedCell;
+///
+/// let counter = Arc::new(ShardedCell::new(|| 0u64));
+///
+/// let handles: Vec<_> = (0..4).map(|_| {
+///     let counter = counter.clone();
+///     thread::spawn(move || {
+///         for _ in 0..100 {
+///             counter.update(|count| *count += 1);
+///         }
+///     })
+/// }).collect();
+/// # for handle in handles { handle.join().unwrap(); }
+///
+/// assert_eq!(400, counter.sum());
+/// ```
+pub struct ShardedCell<T> {
+    /// The shards of this cell.
+    shards: Box<[Shard<T>]>,
+}
+
+impl <T> ShardedCell<T> {
+    /// Creates a new `ShardedCell` with one shard per available CPU.
+    ///
+    /// - `init` - A function creating the initial value of each shard.
+    pub fn new(init: impl FnMut() -> T) -> Self {
+        let shards = thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(4);
+
+        Self::with_shards(shards, init)
+    }
+
+    /// Creates a new `ShardedCell` with a fixed number of shards.
+    ///
+    /// - `shards` - The number of shards. At least one shard is always created.
+    /// - `init` - A function creating the initial value of each shard.
+    pub fn with_shards(shards: usize, mut init: impl FnMut() -> T) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Shard {
+                    value: SyncCell::new(init()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Gets the number of shards in this cell.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Calls a function with a mutable reference to the shard assigned to the current thread.
+    ///
+    /// - `f` - The function used to update the shard.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let shard = &self.shards[thread_shard() % self.shards.len()];
+
+        f(&mut shard.value.borrow_mut())
+    }
+
+    /// Combines the values of every shard.
+    /// Shards are locked one at a time so the result may include some concurrent updates but not
+    /// others.
+    ///
+    /// - `init` - The initial value of the result.
+    /// - `f` - The function combining the result with the value of a shard.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn fold<A>(&self, init: A, mut f: impl FnMut(A, &T) -> A) -> A {
+        self.shards.iter()
+            .fold(init, |acc, shard| f(acc, &shard.value.borrow()))
+    }
+
+    /// Replaces the value of every shard.
+    ///
+    /// - `init` - A function creating the new value of each shard.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn reset(&self, mut init: impl FnMut() -> T) {
+        for shard in self.shards.iter() {
+            shard.value.set(init());
+        }
+    }
+
+    /// Retrieves the values of every shard stored in this `ShardedCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> Vec<T> {
+        self.shards.into_vec().into_iter()
+            .map(|shard| shard.value.into_inner())
+            .collect()
+    }
+}
+
+impl <T: Clone + Sum> ShardedCell<T> {
+    /// Gets the sum of the values of every shard.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn sum(&self) -> T {
+        self.shards.iter()
+            .map(|shard| shard.value.get())
+            .sum()
+    }
+}
+
+impl <T: Default> Default for ShardedCell<T> {
+    fn default() -> Self {
+        Self::new(T::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::ShardedCell;
+
+    #[test]
+    pub fn test_sharded_cell_update() {
+        let cell = ShardedCell::with_shards(4, || 0);
+
+        cell.update(|value| *value += 2);
+        cell.update(|value| *value += 3);
+
+        assert_eq!(4, cell.shard_count());
+        assert_eq!(5, cell.sum());
+    }
+
+    #[test]
+    pub fn test_sharded_cell_fold() {
+        let cell = ShardedCell::with_shards(3, || 2);
+
+        assert_eq!(8, cell.fold(1, |acc, value| acc * value));
+    }
+
+    #[test]
+    pub fn test_sharded_cell_reset() {
+        let cell = ShardedCell::with_shards(0, || 1);
+
+        cell.reset(|| 7);
+
+        assert_eq!(vec![7], cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_sharded_cell_threads() {
+        let cell = Arc::new(ShardedCell::with_shards(2, || 0u64));
+
+        let handles: Vec<_> = (0..8).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    cell.update(|value| *value += 1);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(800, cell.sum());
+    }
+}
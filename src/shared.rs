@@ -0,0 +1,157 @@
+use std::{mem::replace, sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard}};
+
+/// A variant of `SyncCell` that wraps a shared `Arc<RwLock<T>>`.
+/// Other code that is not written with sync-cell can keep its own handle to the same lock, which
+/// allows a large codebase to move to sync-cell gradually.
+///
+/// A `SyncCell` owns its lock directly so it can be created in a `const` context, so a shared lock
+/// is given its own type rather than being stored in a `SyncCell`.
+///
+/// # Usage
+/// ```
+/// use std::sync::{Arc, RwLock};
+/// use sync_cell::SharedSyncCell;
+///
+/// let lock = Arc::new(RwLock::new(0));
+/// let cell = SharedSyncCell::from_shared(lock.clone());
+///
+/// cell.set(1);
+/// assert_eq!(1, *lock.read().unwrap());
+///
+/// *lock.write().unwrap() = 2;
+/// assert_eq!(2, cell.get());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this cell will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct SharedSyncCell<T: ?Sized> {
+    /// The shared lock holding the data of this cell.
+    data: Arc<RwLock<T>>,
+}
+
+impl <T> SharedSyncCell<T> {
+    /// Creates a new `SharedSyncCell` with a lock that is not yet shared.
+    ///
+    /// - `data` - The initial value of the `SharedSyncCell`.
+    pub fn new(data: T) -> Self {
+        Self::from_shared(Arc::new(RwLock::new(data)))
+    }
+
+    /// Sets the value contained in this cell.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the internal value contained in this cell.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut(), value)
+    }
+}
+
+impl <T: ?Sized> SharedSyncCell<T> {
+    /// Creates a new `SharedSyncCell` that wraps an existing shared lock.
+    ///
+    /// - `shared` - The lock to wrap.
+    pub fn from_shared(shared: Arc<RwLock<T>>) -> Self {
+        Self {
+            data: shared,
+        }
+    }
+
+    /// Gets a new handle to the lock wrapped by this cell.
+    /// This can be given to code that uses the lock directly.
+    pub fn as_shared(&self) -> Arc<RwLock<T>> {
+        self.data.clone()
+    }
+
+    /// Retrieves the lock wrapped by this cell.
+    pub fn into_shared(self) -> Arc<RwLock<T>> {
+        self.data
+    }
+
+    /// Borrows a reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        match self.data.read() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> RwLockWriteGuard<'_, T> {
+        match self.data.write() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+impl <T: Clone> SharedSyncCell<T> {
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl <T: Default> Default for SharedSyncCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T: ?Sized> From<Arc<RwLock<T>>> for SharedSyncCell<T> {
+    fn from(shared: Arc<RwLock<T>>) -> Self {
+        Self::from_shared(shared)
+    }
+}
+
+impl <T: ?Sized> From<SharedSyncCell<T>> for Arc<RwLock<T>> {
+    fn from(cell: SharedSyncCell<T>) -> Self {
+        cell.into_shared()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, RwLock}, thread};
+
+    use crate::SharedSyncCell;
+
+    #[test]
+    pub fn test_shared_sync_cell() {
+        let lock = Arc::new(RwLock::new(0));
+        let cell = SharedSyncCell::from(lock.clone());
+
+        let handle = {
+            let lock = cell.as_shared();
+            thread::spawn(move || *lock.write().unwrap() += 1)
+        };
+        handle.join().unwrap();
+
+        assert_eq!(1, cell.replace(2));
+        assert!(Arc::ptr_eq(&lock, &cell.into_shared()));
+        assert_eq!(2, *lock.read().unwrap());
+    }
+}
@@ -0,0 +1,347 @@
+use std::{fs::{File, OpenOptions}, io::{self, Read, Seek, SeekFrom, Write}, mem::{size_of, MaybeUninit}, ops::{Deref, DerefMut}, path::{Path, PathBuf}, slice, sync::{Mutex, MutexGuard}};
+
+/// A type that can be stored in a `ShmCell`.
+///
+/// # Safety
+/// Values are copied to and from shared memory as raw bytes. Implementors must guarantee that the
+/// type contains no padding bytes, no pointers or references and that every possible bit pattern
+/// is a valid value of the type. Types should be `#[repr(C)]` so that their layout is the same in
+/// every process using the cell.
+pub unsafe trait ShmValue: Copy + Send + 'static {}
+
+/// Implements `ShmValue` for types where every bit pattern is valid.
+macro_rules! impl_shm_value {
+    ($($t:ty),*) => {
+        $(
+            // SAFETY: Primitive integers and floats have no padding and no invalid bit patterns.
+            unsafe impl ShmValue for $t {}
+        )*
+    };
+}
+
+impl_shm_value!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// SAFETY: Arrays of valid values have no padding between elements.
+unsafe impl <T: ShmValue, const N: usize> ShmValue for [T; N] {}
+
+/// A cell stored in a named shared memory segment that can be accessed from multiple processes.
+/// Access is protected by a lock on the backing file across processes and by a mutex within the
+/// current process.
+///
+/// On Linux the segment is stored in `/dev/shm` which is backed by memory. On other platforms it is
+/// stored in the temporary directory.
+///
+/// This type is only available with the `shm` feature.
+///
+/// # Usage
+/// ```
+/// use sync_cell::ShmCell;
+///
+/// let cell = ShmCell::open("sync_cell_shm_doc", 0u32).unwrap();
+/// cell.set(5);
+///
+/// // Another process opening the same name sees the same value.
+/// let other = ShmCell::<u32>::open("sync_cell_shm_doc", 0).unwrap();
+/// assert_eq!(5, other.get());
+/// # std::fs::remove_file(cell.path()).unwrap();
+/// ```
+///
+/// # Panicking
+/// The same as other cells in this crate, access methods panic if the in-process lock becomes
+/// poisoned. They will also panic if the shared memory cannot be read or written.
+pub struct ShmCell<T: ShmValue> {
+    /// The path of the backing file.
+    path: PathBuf,
+    /// The backing file of the segment.
+    file: Mutex<File>,
+    /// The type of value stored in the segment.
+    _value: std::marker::PhantomData<T>,
+}
+
+impl <T: ShmValue> ShmCell<T> {
+    /// Opens or creates the named shared memory segment.
+    /// If the segment does not exist, it is created and set to `initial`.
+    ///
+    /// - `name` - The name of the segment shared between processes.
+    /// - `initial` - The value of the cell if the segment does not yet exist.
+    ///
+    /// # Errors
+    /// An error is returned if the segment cannot be opened or created.
+    pub fn open(name: &str, initial: T) -> io::Result<Self> {
+        let dir = Path::new("/dev/shm");
+
+        if cfg!(target_os = "linux") && dir.is_dir() {
+            Self::open_path(dir.join(name), initial)
+        } else {
+            Self::open_path(std::env::temp_dir().join(name), initial)
+        }
+    }
+
+    /// Opens or creates a shared memory segment backed by a specific file.
+    /// If the file is empty or does not exist, it is set to `initial`.
+    ///
+    /// - `path` - The backing file of the segment.
+    /// - `initial` - The value of the cell if the segment does not yet exist.
+    ///
+    /// # Errors
+    /// An error is returned if the file cannot be opened or created.
+    pub fn open_path(path: impl Into<PathBuf>, initial: T) -> io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        file.lock()?;
+        let result = if file.metadata()?.len() < size_of::<T>() as u64 {
+            write_value(&file, &initial)
+        } else {
+            Ok(())
+        };
+        file.unlock()?;
+        result?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+            _value: std::marker::PhantomData,
+        })
+    }
+
+    /// Gets the path of the backing file of this cell.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or the segment cannot be read.
+    pub fn get(&self) -> T {
+        *self.borrow()
+    }
+
+    /// Sets the value contained in this cell.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or the segment cannot be written.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the value contained in this cell.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or the segment cannot be written.
+    pub fn replace(&self, value: T) -> T {
+        let mut borrow = self.borrow_mut();
+        let old = *borrow;
+        *borrow = value;
+        old
+    }
+
+    /// Borrows a copy of the value stored in this cell while holding a shared lock.
+    /// Other processes cannot change the value until the borrow is dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or the segment cannot be read.
+    pub fn borrow(&self) -> ShmReadGuard<'_, T> {
+        let file = self.lock_file();
+
+        if let Err(err) = file.lock_shared() {
+            panic!("Failed to lock shared memory: {}", err);
+        }
+
+        match read_value(&file) {
+            Ok(value) => ShmReadGuard {
+                file,
+                value,
+            },
+            Err(err) => {
+                let _ = file.unlock();
+                panic!("Failed to read shared memory: {}", err)
+            },
+        }
+    }
+
+    /// Borrows a mutable copy of the value stored in this cell while holding an exclusive lock.
+    /// Changes are written back to the segment when the borrow is dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or the segment cannot be read or
+    /// written.
+    pub fn borrow_mut(&self) -> ShmWriteGuard<'_, T> {
+        let file = self.lock_file();
+
+        if let Err(err) = file.lock() {
+            panic!("Failed to lock shared memory: {}", err);
+        }
+
+        match read_value(&file) {
+            Ok(value) => ShmWriteGuard {
+                file,
+                value,
+            },
+            Err(err) => {
+                let _ = file.unlock();
+                panic!("Failed to read shared memory: {}", err)
+            },
+        }
+    }
+
+    /// Acquires the in-process lock over the backing file.
+    fn lock_file(&self) -> MutexGuard<'_, File> {
+        match self.file.lock() {
+            Ok(file) => file,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+/// Reads a value from the start of a file.
+fn read_value<T: ShmValue>(mut file: &File) -> io::Result<T> {
+    let mut value = MaybeUninit::<T>::uninit();
+
+    // SAFETY: The buffer covers exactly the bytes of `value`.
+    let bytes = unsafe { slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, size_of::<T>()) };
+
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(bytes)?;
+
+    // SAFETY: `ShmValue` guarantees every bit pattern is a valid value.
+    Ok(unsafe { value.assume_init() })
+}
+
+/// Writes a value to the start of a file.
+fn write_value<T: ShmValue>(mut file: &File, value: &T) -> io::Result<()> {
+    // SAFETY: `ShmValue` guarantees the value has no padding so every byte is initialized.
+    let bytes = unsafe { slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) };
+
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(bytes)
+}
+
+/// A shared borrow of the value in a `ShmCell`.
+pub struct ShmReadGuard<'a, T: ShmValue> {
+    /// The locked backing file.
+    file: MutexGuard<'a, File>,
+    /// The value read from the segment.
+    value: T,
+}
+
+impl <T: ShmValue> Deref for ShmReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl <T: ShmValue> Drop for ShmReadGuard<'_, T> {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// An exclusive borrow of the value in a `ShmCell`.
+/// Changes made to the value are written back to the segment when this guard is dropped.
+pub struct ShmWriteGuard<'a, T: ShmValue> {
+    /// The locked backing file.
+    file: MutexGuard<'a, File>,
+    /// The value read from the segment.
+    value: T,
+}
+
+impl <T: ShmValue> Deref for ShmWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl <T: ShmValue> DerefMut for ShmWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl <T: ShmValue> Drop for ShmWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        let result = write_value(&self.file, &self.value);
+        let _ = self.file.unlock();
+
+        if let Err(err) = result {
+            if !std::thread::panicking() {
+                panic!("Failed to write shared memory: {}", err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc, thread};
+
+    use crate::ShmCell;
+
+    /// Gets a unique segment name for a test.
+    fn name(test: &str) -> String {
+        format!("sync_cell_{}_{}", test, std::process::id())
+    }
+
+    #[test]
+    pub fn test_shm_cell_initial() {
+        let cell = ShmCell::open(&name("shm_initial"), 7u64).unwrap();
+
+        assert_eq!(7, cell.get());
+
+        fs::remove_file(cell.path()).unwrap();
+    }
+
+    #[test]
+    pub fn test_shm_cell_shared() {
+        let name = name("shm_shared");
+        let a = ShmCell::open(&name, [0u8; 4]).unwrap();
+        let b = ShmCell::open(&name, [9u8; 4]).unwrap();
+
+        assert_eq!([0; 4], b.get());
+
+        a.set([1, 2, 3, 4]);
+        assert_eq!([1, 2, 3, 4], b.get());
+        assert_eq!([1, 2, 3, 4], b.replace([5; 4]));
+        assert_eq!([5; 4], a.get());
+
+        fs::remove_file(a.path()).unwrap();
+    }
+
+    #[test]
+    pub fn test_shm_cell_threads() {
+        let cell = Arc::new(ShmCell::open(&name("shm_threads"), 0u32).unwrap());
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    *cell.borrow_mut() += 1;
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(200, cell.get());
+
+        fs::remove_file(cell.path()).unwrap();
+    }
+}
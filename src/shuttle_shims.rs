@@ -0,0 +1,133 @@
+//! The replacements used when the crate is built with `--cfg shuttle`, so that `shuttle` can
+//! schedule the threads using a cell in random orders.
+//! Every lock taken by a cell is then a `shuttle` lock, and the thread locals used to detect
+//! reentrant borrows are kept for each `shuttle` thread, since `shuttle` runs all of them on one
+//! thread of the test.
+
+use std::sync::OnceLock;
+
+use shuttle::sync::{Condvar, Mutex, MutexGuard};
+
+use crate::RawSyncLock;
+
+#[cfg(debug_assertions)]
+pub(crate) use shuttle::thread_local;
+
+/// The borrows held of a `ShuttleRawLock`.
+#[derive(Debug)]
+struct ShuttleLockState {
+    /// The number of shared locks held.
+    readers: usize,
+    /// Whether an exclusive lock is held.
+    writer: bool,
+}
+
+/// The `shuttle` primitives of a `ShuttleRawLock`.
+#[derive(Debug)]
+struct ShuttleLock {
+    /// The borrows currently held.
+    state: Mutex<ShuttleLockState>,
+    /// The condition variable used to wake threads waiting for the lock.
+    released: Condvar,
+}
+
+/// The backend of `SyncCell` and `RawSyncCell` when the crate is built with `--cfg shuttle`.
+/// This is the `DefaultRawLock` in that case, so `HeldSyncCell` and the other cells are scheduled
+/// by `shuttle` as well.
+///
+/// The primitives of `shuttle` are boxed and created when the lock is first used, so that the lock
+/// stays as small as the other backends.
+#[derive(Debug)]
+pub struct ShuttleRawLock {
+    /// The primitives of the lock, created when it is first used.
+    lock: OnceLock<Box<ShuttleLock>>,
+}
+
+impl ShuttleRawLock {
+    /// Locks the borrow counts of this lock.
+    fn state(&self) -> (MutexGuard<'_, ShuttleLockState>, &Condvar) {
+        let lock = self.lock.get_or_init(|| Box::new(ShuttleLock {
+            state: Mutex::new(ShuttleLockState {
+                readers: 0,
+                writer: false,
+            }),
+            released: Condvar::new(),
+        }));
+
+        // A panic never leaves the counts inconsistent, so a poisoned lock can be used.
+        let state = match lock.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        };
+
+        (state, &lock.released)
+    }
+
+    /// Blocks while `condition` holds for the locked borrow counts and then changes them.
+    fn wait(&self, condition: impl Fn(&ShuttleLockState) -> bool, change: impl FnOnce(&mut ShuttleLockState)) {
+        let (mut state, released) = self.state();
+
+        while condition(&state) {
+            state = match released.wait(state) {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+        }
+
+        change(&mut state);
+    }
+}
+
+unsafe impl RawSyncLock for ShuttleRawLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        lock: OnceLock::new(),
+    };
+
+    fn lock_shared(&self) {
+        self.wait(|state| state.writer, |state| state.readers += 1);
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        let (mut state, _) = self.state();
+
+        if state.writer {
+            return false;
+        }
+
+        state.readers += 1;
+        true
+    }
+
+    unsafe fn unlock_shared(&self) {
+        let (mut state, released) = self.state();
+
+        state.readers -= 1;
+
+        if state.readers == 0 {
+            released.notify_all();
+        }
+    }
+
+    fn lock_exclusive(&self) {
+        self.wait(|state| state.writer || state.readers > 0, |state| state.writer = true);
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        let (mut state, _) = self.state();
+
+        if state.writer || state.readers > 0 {
+            return false;
+        }
+
+        state.writer = true;
+        true
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        let (mut state, released) = self.state();
+
+        state.writer = false;
+        released.notify_all();
+    }
+}
@@ -0,0 +1,153 @@
+use std::{convert::Infallible, fmt::{self, Debug, Formatter}, pin::Pin, task::{Context, Poll}};
+
+use futures_sink::Sink;
+
+use crate::{DefaultRawLock, HeldSyncCell, RawSyncLock, SyncCell};
+
+impl <T, L: RawSyncLock> SyncCell<T, L> {
+    /// Creates a `futures::Sink` that sets the value of this cell to every item sent into it.
+    /// This allows a stream of values to be driven into the cell with `stream.forward(cell.sink())`.
+    /// This method is only available with the `futures` feature.
+    ///
+    /// # Usage
+    /// ```
+    /// use futures_util::{StreamExt, stream};
+    /// use sync_cell::SyncCell;
+    ///
+    /// # async fn example() {
+    /// let cell = SyncCell::new(0);
+    ///
+    /// stream::iter([Ok(1), Ok(2), Ok(3)]).forward(cell.sink()).await.unwrap();
+    /// assert_eq!(3, cell.get());
+    /// # }
+    /// ```
+    pub fn sink(&self) -> SyncCellSink<'_, T, L> {
+        SyncCellSink {
+            cell: self,
+        }
+    }
+}
+
+impl <T> HeldSyncCell<T> {
+    /// Creates a `futures::Sink` that sets the incoming value of this cell to every item sent into
+    /// it.
+    /// Items are applied by `update` or the update policy of the cell like any other set value.
+    /// This method is only available with the `futures` feature.
+    pub fn sink(&self) -> HeldSyncCellSink<'_, T> {
+        HeldSyncCellSink {
+            cell: self,
+        }
+    }
+}
+
+/// A `futures::Sink` that sets the value of a `SyncCell`, returned by `SyncCell::sink`.
+/// The sink is always ready. Sending an item takes the lock of the cell in the same way as
+/// `SyncCell::set`, so it waits for other borrows of the cell to end.
+///
+/// # Panicking
+/// Sending an item will panic if the lock of the cell becomes poisoned.
+pub struct SyncCellSink<'a, T, L = DefaultRawLock> {
+    /// The cell the items are sent to.
+    cell: &'a SyncCell<T, L>,
+}
+
+impl <T, L> Debug for SyncCellSink<'_, T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncCellSink").finish_non_exhaustive()
+    }
+}
+
+impl <T, L: RawSyncLock> Sink<T> for SyncCellSink<'_, T, L> {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        self.cell.set(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A `futures::Sink` that sets the incoming value of a `HeldSyncCell`, returned by
+/// `HeldSyncCell::sink`.
+/// The sink is always ready. Sending an item takes the lock of the incoming value in the same way
+/// as `HeldSyncCell::set`.
+///
+/// # Panicking
+/// Sending an item will panic if any of the locks of the cell become poisoned.
+pub struct HeldSyncCellSink<'a, T> {
+    /// The cell the items are sent to.
+    cell: &'a HeldSyncCell<T>,
+}
+
+impl <T> Debug for HeldSyncCellSink<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeldSyncCellSink").finish_non_exhaustive()
+    }
+}
+
+impl <T> Sink<T> for HeldSyncCellSink<'_, T> {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        self.cell.set(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+
+    use futures_sink::Sink;
+    use futures_util::{StreamExt, stream};
+
+    use crate::{HeldSyncCell, SyncCell};
+
+    #[test]
+    pub fn test_sync_cell_sink() {
+        let cell = SyncCell::new(0);
+        let mut sink = pin!(cell.sink());
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(Poll::Ready(Ok(())), sink.as_mut().poll_ready(&mut cx));
+        assert_eq!(Ok(()), sink.as_mut().start_send(1));
+        assert_eq!(Poll::Ready(Ok(())), sink.as_mut().poll_close(&mut cx));
+
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_held_sync_cell_sink_forward() {
+        let cell = HeldSyncCell::new(0);
+        let mut forward = pin!(stream::iter([Ok(1), Ok(2)]).forward(cell.sink()));
+
+        assert_eq!(Poll::Ready(Ok(())), forward.as_mut().poll(&mut Context::from_waker(Waker::noop())));
+        assert_eq!(0, cell.get());
+
+        cell.update();
+        assert_eq!(2, cell.get());
+    }
+}
@@ -0,0 +1,441 @@
+use std::{cell::UnsafeCell, fmt::{self, Debug, Formatter}, marker::PhantomData, ops::{Deref, DerefMut}, sync::Arc};
+
+use crate::{RawSyncLock, StdRawLock, SyncCell};
+
+/// A key identifying a value in a `SyncSlab`.
+/// A key stays valid until its value is removed. Keys of removed values are never mistaken for
+/// the key of a value inserted later in the same slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SlabKey {
+    /// The index of the slot holding the value.
+    index: usize,
+    /// The generation of the slot when the value was inserted.
+    generation: u64,
+}
+
+/// A value of a `SyncSlab` with its own lock.
+/// Entries are shared with their borrows so that a borrow does not keep the slots of the slab
+/// locked.
+struct Entry<T> {
+    /// The lock of the value.
+    lock: StdRawLock,
+    /// The value, which is taken out while locked exclusively when it is removed from the slab.
+    value: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: The value of an entry is only accessed through its lock, in the same way as an
+// `RwLock`.
+unsafe impl <T: Send + Sync> Sync for Entry<T> {}
+
+/// A slot of a `SyncSlab`.
+struct Slot<T> {
+    /// The number of values that have been removed from this slot.
+    generation: u64,
+    /// The value in this slot if it is occupied.
+    entry: Option<Arc<Entry<T>>>,
+}
+
+/// The slots of a `SyncSlab`.
+struct Slots<T> {
+    /// Every slot that has been allocated.
+    slots: Vec<Slot<T>>,
+    /// The indices of the empty slots.
+    free: Vec<usize>,
+}
+
+impl <T> Slots<T> {
+    /// Gets the entry of the occupied slot identified by a key.
+    fn entry(&self, key: SlabKey) -> Option<&Arc<Entry<T>>> {
+        let slot = self.slots.get(key.index)?;
+
+        if slot.generation == key.generation {
+            slot.entry.as_ref()
+        } else {
+            None
+        }
+    }
+}
+
+/// A collection that stores values at stable keys, such as a registry of entities or handles
+/// shared between threads.
+/// Each value has its own lock, so threads borrowing different values do not wait for each
+/// other. Borrows do not keep the slab locked, so values can be inserted, removed and borrowed
+/// while other values are borrowed. Removing a value waits until it is no longer borrowed.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncSlab;
+///
+/// let slab = SyncSlab::new();
+///
+/// let a = slab.insert(String::from("a"));
+/// let b = slab.insert(String::from("b"));
+///
+/// slab.borrow_mut(a).unwrap().push('!');
+///
+/// assert_eq!("a!", *slab.borrow(a).unwrap());
+/// assert_eq!(Some(String::from("b")), slab.remove(b));
+/// assert!(slab.borrow(b).is_none());
+/// ```
+///
+/// # Poisoning
+/// Unlike `SyncCell`, values are never poisoned. A panic while a value is borrowed releases its
+/// lock and leaves the value as it was when the panic occurred.
+pub struct SyncSlab<T> {
+    /// The slots of this slab.
+    data: SyncCell<Slots<T>>,
+}
+
+impl <T> SyncSlab<T> {
+    /// Creates a new empty `SyncSlab`.
+    pub const fn new() -> Self {
+        Self {
+            data: SyncCell::new(Slots {
+                slots: Vec::new(),
+                free: Vec::new(),
+            }),
+        }
+    }
+
+    /// Gets the number of values in this slab.
+    pub fn len(&self) -> usize {
+        let data = self.data.borrow();
+        data.slots.len() - data.free.len()
+    }
+
+    /// Checks if this slab has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if a key identifies a value in this slab.
+    ///
+    /// - `key` - The key to look for.
+    pub fn contains(&self, key: SlabKey) -> bool {
+        self.data.borrow().entry(key).is_some()
+    }
+
+    /// Inserts a value into this slab.
+    /// The key of the value is returned.
+    ///
+    /// - `value` - The value to insert.
+    pub fn insert(&self, value: T) -> SlabKey {
+        let entry = Arc::new(Entry {
+            lock: StdRawLock::INIT,
+            value: UnsafeCell::new(Some(value)),
+        });
+        let mut data = self.data.borrow_mut();
+
+        match data.free.pop() {
+            Some(index) => {
+                let slot = &mut data.slots[index];
+                slot.entry = Some(entry);
+
+                SlabKey {
+                    index,
+                    generation: slot.generation,
+                }
+            },
+            None => {
+                data.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+
+                SlabKey {
+                    index: data.slots.len() - 1,
+                    generation: 0,
+                }
+            },
+        }
+    }
+
+    /// Removes a value from this slab.
+    /// The removed value is returned or `None` if the key does not identify a value. If the value
+    /// is borrowed, this waits until the borrows have ended.
+    ///
+    /// - `key` - The key of the value.
+    pub fn remove(&self, key: SlabKey) -> Option<T> {
+        let entry = {
+            let mut data = self.data.borrow_mut();
+            data.entry(key)?;
+
+            let slot = &mut data.slots[key.index];
+            slot.generation += 1;
+            let entry = slot.entry.take();
+
+            data.free.push(key.index);
+            entry
+        }?;
+
+        // The slots are no longer locked, so the borrows of this value can end while waiting.
+        entry.lock.lock_exclusive();
+
+        // SAFETY: The entry is locked exclusively so no other reference to the value exists.
+        let value = unsafe { (*entry.value.get()).take() };
+
+        // SAFETY: The exclusive lock was acquired above.
+        unsafe { entry.lock.unlock_exclusive() }
+
+        value
+    }
+
+    /// Borrows a reference to a value in this slab.
+    /// Returns `None` if the key does not identify a value.
+    pub fn borrow(&self, key: SlabKey) -> Option<SlabRef<'_, T>> {
+        let entry = self.entry(key)?;
+
+        entry.lock.lock_shared();
+
+        let borrowed = SlabRef {
+            entry,
+            _slab: PhantomData,
+        };
+
+        // The value may have been removed while waiting for the lock.
+        // SAFETY: The entry is locked for sharing.
+        unsafe { (*borrowed.entry.value.get()).is_some() }.then_some(borrowed)
+    }
+
+    /// Borrows a mutable reference to a value in this slab.
+    /// Returns `None` if the key does not identify a value.
+    pub fn borrow_mut(&self, key: SlabKey) -> Option<SlabGuard<'_, T>> {
+        let entry = self.entry(key)?;
+
+        entry.lock.lock_exclusive();
+
+        let borrowed = SlabGuard {
+            entry,
+            _slab: PhantomData,
+        };
+
+        // The value may have been removed while waiting for the lock.
+        // SAFETY: The entry is locked exclusively.
+        unsafe { (*borrowed.entry.value.get()).is_some() }.then_some(borrowed)
+    }
+
+    /// Retrieves the values stored in this `SyncSlab` with their keys.
+    pub fn into_inner(self) -> Vec<(SlabKey, T)> {
+        self.data.into_inner().slots.into_iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let key = SlabKey {
+                    index,
+                    generation: slot.generation,
+                };
+
+                // Borrows cannot outlive the slab, so no other reference to the entry exists.
+                match Arc::into_inner(slot.entry?) {
+                    Some(entry) => entry.value.into_inner().map(|value| (key, value)),
+                    None => unreachable!("Slab entry is still borrowed"),
+                }
+            })
+            .collect()
+    }
+
+    /// Gets a shared reference to the entry identified by a key.
+    /// The slots are only locked while the entry is looked up.
+    fn entry(&self, key: SlabKey) -> Option<Arc<Entry<T>>> {
+        self.data.borrow().entry(key).cloned()
+    }
+}
+
+impl <T: Clone> SyncSlab<T> {
+    /// Gets a copy of a value in this slab.
+    /// Returns `None` if the key does not identify a value.
+    ///
+    /// - `key` - The key of the value.
+    pub fn get(&self, key: SlabKey) -> Option<T> {
+        self.borrow(key).map(|value| value.clone())
+    }
+}
+
+impl <T> Debug for SyncSlab<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncSlab")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl <T> Default for SyncSlab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An immutable borrow of a value in a `SyncSlab`.
+pub struct SlabRef<'a, T> {
+    /// The borrowed entry, which is occupied and locked for sharing.
+    entry: Arc<Entry<T>>,
+    /// The slab the value is borrowed from.
+    _slab: PhantomData<&'a SyncSlab<T>>,
+}
+
+impl <T> Deref for SlabRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: The entry is locked for sharing and cannot be emptied while it is borrowed.
+        match unsafe { &*self.entry.value.get() } {
+            Some(value) => value,
+            None => unreachable!("Borrowed slab entry is empty"),
+        }
+    }
+}
+
+impl <T> Drop for SlabRef<'_, T> {
+    fn drop(&mut self) {
+        // SAFETY: The shared lock was acquired when this borrow was created.
+        unsafe { self.entry.lock.unlock_shared() }
+    }
+}
+
+/// A mutable borrow of a value in a `SyncSlab`.
+pub struct SlabGuard<'a, T> {
+    /// The borrowed entry, which is occupied and locked exclusively.
+    entry: Arc<Entry<T>>,
+    /// The slab the value is borrowed from.
+    _slab: PhantomData<&'a SyncSlab<T>>,
+}
+
+impl <T> Deref for SlabGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: The entry is locked exclusively and cannot be emptied while it is borrowed.
+        match unsafe { &*self.entry.value.get() } {
+            Some(value) => value,
+            None => unreachable!("Borrowed slab entry is empty"),
+        }
+    }
+}
+
+impl <T> DerefMut for SlabGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The entry is locked exclusively so no other reference to the value exists.
+        match unsafe { &mut *self.entry.value.get() } {
+            Some(value) => value,
+            None => unreachable!("Borrowed slab entry is empty"),
+        }
+    }
+}
+
+impl <T> Drop for SlabGuard<'_, T> {
+    fn drop(&mut self) {
+        // SAFETY: The exclusive lock was acquired when this borrow was created.
+        unsafe { self.entry.lock.unlock_exclusive() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, Barrier}, thread, time::Duration};
+
+    use crate::SyncSlab;
+
+    #[test]
+    pub fn test_sync_slab() {
+        let slab = SyncSlab::new();
+
+        let a = slab.insert(1);
+        let b = slab.insert(2);
+
+        assert_eq!(2, slab.len());
+        assert_eq!(Some(1), slab.remove(a));
+        assert_eq!(None, slab.remove(a));
+
+        // The slot of the removed value is reused with a new key.
+        let c = slab.insert(3);
+        assert!(!slab.contains(a));
+        assert_ne!(a, c);
+        assert_eq!(None, slab.get(a));
+        assert_eq!(Some(3), slab.get(c));
+
+        assert_eq!(vec![(c, 3), (b, 2)], slab.into_inner());
+    }
+
+    #[test]
+    pub fn test_sync_slab_entry_locks() {
+        let slab = Arc::new(SyncSlab::new());
+        let keys = [slab.insert(0), slab.insert(0)];
+        let barrier = Arc::new(Barrier::new(2));
+
+        let handles: Vec<_> = keys.into_iter().map(|key| {
+            let slab = slab.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut value = slab.borrow_mut(key).unwrap();
+                barrier.wait();
+                *value += 1;
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(keys.iter().all(|key| slab.get(*key) == Some(1)));
+    }
+
+    #[test]
+    pub fn test_sync_slab_nested_borrow_while_inserting() {
+        let slab = Arc::new(SyncSlab::new());
+        let a = slab.insert(1);
+        let b = slab.insert(2);
+        let borrowed = slab.borrow(a).unwrap();
+
+        let inserter = {
+            let slab = slab.clone();
+            thread::spawn(move || slab.insert(3))
+        };
+
+        // Holding a borrow does not keep the slab locked, so the insert completes and later
+        // borrows do not queue behind it.
+        let c = inserter.join().unwrap();
+
+        assert_eq!(3, *borrowed + *slab.borrow(b).unwrap());
+        assert_eq!(Some(3), slab.get(c));
+    }
+
+    #[test]
+    pub fn test_sync_slab_remove_while_borrowed() {
+        let slab = Arc::new(SyncSlab::new());
+        let key = slab.insert(1);
+        let mut borrowed = slab.borrow_mut(key).unwrap();
+
+        let remover = {
+            let slab = slab.clone();
+            thread::spawn(move || slab.remove(key))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+
+        // The value is no longer found but stays borrowed until the guard is dropped.
+        assert!(!slab.contains(key));
+        *borrowed += 1;
+        drop(borrowed);
+
+        assert_eq!(Some(2), remover.join().unwrap());
+        assert!(slab.borrow(key).is_none());
+    }
+
+    #[test]
+    pub fn test_sync_slab_panic_while_borrowed() {
+        let slab = Arc::new(SyncSlab::new());
+        let key = slab.insert(1);
+
+        let panicked = {
+            let slab = slab.clone();
+            thread::spawn(move || {
+                *slab.borrow_mut(key).unwrap() = 2;
+                let _guard = slab.borrow_mut(key).unwrap();
+                panic!("Test panic");
+            }).join()
+        };
+
+        // The value is not poisoned and keeps the last value written.
+        assert!(panicked.is_err());
+        assert_eq!(Some(2), slab.get(key));
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use crate::CondCell;
+
+/// A cell holding at most one value that is used to hand values from one thread to another.
+/// Putting a value into the slot blocks while the slot is full and taking a value out of the slot
+/// blocks while the slot is empty.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::SlotCell;
+///
+/// let slot = Arc::new(SlotCell::new());
+///
+/// let producer = {
+///     let slot = slot.clone();
+///     thread::spawn(move || {
+///         slot.put(1);
+///         slot.put(2);
+///     })
+/// };
+///
+/// assert_eq!(1, slot.take());
+/// assert_eq!(2, slot.take());
+/// # producer.join().unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct SlotCell<T> {
+    /// The value in the slot if it is full.
+    slot: CondCell<Option<T>>,
+}
+
+impl <T> SlotCell<T> {
+    /// Creates a new empty `SlotCell`.
+    pub const fn new() -> Self {
+        Self {
+            slot: CondCell::new(None),
+        }
+    }
+
+    /// Creates a new `SlotCell` that is already full.
+    ///
+    /// - `data` - The initial value in the slot.
+    pub const fn full(data: T) -> Self {
+        Self {
+            slot: CondCell::new(Some(data)),
+        }
+    }
+
+    /// Puts a value into the slot blocking while the slot is full.
+    ///
+    /// - `value` - The value to put into the slot.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn put(&self, value: T) {
+        *self.slot.wait_until(Option::is_none) = Some(value);
+    }
+
+    /// Puts a value into the slot blocking while the slot is full for at most `timeout`.
+    /// If the slot is still full once the timeout has elapsed, the value is returned.
+    ///
+    /// - `value` - The value to put into the slot.
+    /// - `timeout` - The maximum amount of time to wait.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn put_timeout(&self, value: T, timeout: Duration) -> Result<(), T> {
+        match self.slot.wait_until_timeout(timeout, Option::is_none) {
+            Some(mut slot) => {
+                *slot = Some(value);
+                Ok(())
+            },
+            None => Err(value),
+        }
+    }
+
+    /// Puts a value into the slot if it is empty.
+    /// If the slot is full the value is returned.
+    ///
+    /// - `value` - The value to put into the slot.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn try_put(&self, value: T) -> Result<(), T> {
+        let mut slot = self.slot.borrow_mut();
+
+        if slot.is_some() {
+            return Err(value);
+        }
+
+        *slot = Some(value);
+        Ok(())
+    }
+
+    /// Takes the value out of the slot blocking while the slot is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take(&self) -> T {
+        match self.slot.wait_until(Option::is_some).take() {
+            Some(value) => value,
+            None => unreachable!("Slot was empty after waiting for a value."),
+        }
+    }
+
+    /// Takes the value out of the slot blocking while the slot is empty for at most `timeout`.
+    /// Returns `None` if the slot is still empty once the timeout has elapsed.
+    ///
+    /// - `timeout` - The maximum amount of time to wait.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take_timeout(&self, timeout: Duration) -> Option<T> {
+        self.slot.wait_until_timeout(timeout, Option::is_some)
+            .and_then(|mut slot| slot.take())
+    }
+
+    /// Takes the value out of the slot if it is full.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn try_take(&self) -> Option<T> {
+        let mut slot = self.slot.borrow_mut();
+
+        if slot.is_none() {
+            return None;
+        }
+
+        slot.take()
+    }
+
+    /// Checks if the slot currently holds a value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_full(&self) -> bool {
+        self.slot.borrow().is_some()
+    }
+
+    /// Retrieves the value in the slot if it is full.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> Option<T> {
+        self.slot.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::SlotCell;
+
+    #[test]
+    pub fn test_slot_cell_try() {
+        let slot = SlotCell::new();
+
+        assert_eq!(None, slot.try_take());
+        assert_eq!(Ok(()), slot.try_put(1));
+        assert_eq!(Err(2), slot.try_put(2));
+        assert!(slot.is_full());
+        assert_eq!(Some(1), slot.try_take());
+        assert!(!slot.is_full());
+    }
+
+    #[test]
+    pub fn test_slot_cell_timeout() {
+        let slot = SlotCell::full(1);
+
+        assert_eq!(Err(2), slot.put_timeout(2, Duration::from_millis(10)));
+        assert_eq!(Some(1), slot.take_timeout(Duration::from_millis(10)));
+        assert_eq!(None, slot.take_timeout(Duration::from_millis(10)));
+    }
+
+    #[test]
+    pub fn test_slot_cell_hand_off() {
+        let slot = Arc::new(SlotCell::new());
+        let slot2 = slot.clone();
+
+        let handle = thread::spawn(move || {
+            for i in 0..100 {
+                slot2.put(i);
+            }
+        });
+
+        for i in 0..100 {
+            assert_eq!(i, slot.take());
+        }
+
+        handle.join().unwrap();
+        assert_eq!(None, Arc::try_unwrap(slot).unwrap().into_inner());
+    }
+}
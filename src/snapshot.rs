@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use crate::SyncCell;
+
+/// A cell that hands out cheap immutable snapshots of its value.
+/// The value is stored behind an `Arc` so that taking a snapshot only requires cloning a pointer.
+/// Writers replace the stored `Arc` rather than mutating the value in place so existing snapshots
+/// never change.
+///
+/// Taking a snapshot is not lock-free. A read lock is held for as long as it takes to clone the
+/// `Arc`, so a snapshot can wait behind a writer, including one running the function passed to
+/// `update`. Once taken, a snapshot holds no lock and can be kept for as long as needed without
+/// blocking writers.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SnapshotCell;
+///
+/// let cell = SnapshotCell::new(vec![1, 2]);
+///
+/// let snapshot = cell.snapshot();
+///
+/// cell.update(|value| {
+///     let mut value = value.clone();
+///     value.push(3);
+///     value
+/// });
+///
+/// // The snapshot is unaffected by the update.
+/// assert_eq!(vec![1, 2], *snapshot);
+/// assert_eq!(vec![1, 2, 3], *cell.snapshot());
+/// ```
+#[derive(Debug)]
+pub struct SnapshotCell<T: ?Sized> {
+    /// The current snapshot of this cell.
+    data: SyncCell<Arc<T>>,
+}
+
+impl <T> SnapshotCell<T> {
+    /// Creates a new `SnapshotCell`.
+    ///
+    /// - `data` - The initial value of the `SnapshotCell`.
+    pub fn new(data: T) -> Self {
+        Self::from_arc(Arc::new(data))
+    }
+
+    /// Sets the value contained in this cell.
+    /// Existing snapshots continue to refer to the previous value.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        self.data.set(Arc::new(value))
+    }
+
+    /// Replaces the value of this cell with the result of a function applied to the current
+    /// value. The write lock is held while the function runs so concurrent updates are applied
+    /// one after another.
+    ///
+    /// - `f` - The function used to compute the new value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn update(&self, f: impl FnOnce(&T) -> T) {
+        let mut data = self.data.borrow_mut();
+        let value = f(&data);
+
+        *data = Arc::new(value);
+    }
+}
+
+impl <T: ?Sized> SnapshotCell<T> {
+    /// Creates a new `SnapshotCell` from an existing `Arc`.
+    ///
+    /// - `data` - The initial value of the `SnapshotCell`.
+    pub const fn from_arc(data: Arc<T>) -> Self {
+        Self {
+            data: SyncCell::new(data),
+        }
+    }
+
+    /// Takes a snapshot of the current value of this cell.
+    /// The snapshot will not change even if the cell is updated. This briefly takes a read lock
+    /// and will block while a write is in progress.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn snapshot(&self) -> Arc<T> {
+        self.data.get()
+    }
+
+    /// Sets the value contained in this cell to an existing `Arc`.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set_arc(&self, value: Arc<T>) {
+        self.data.set(value)
+    }
+
+    /// Replaces the value contained in this cell.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace_arc(&self, value: Arc<T>) -> Arc<T> {
+        self.data.replace(value)
+    }
+
+    /// Retrieves the current value stored in this `SnapshotCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> Arc<T> {
+        self.data.into_inner()
+    }
+}
+
+impl <T: Clone> SnapshotCell<T> {
+    /// Gets a copy of the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        T::clone(&self.snapshot())
+    }
+}
+
+impl <T: ?Sized> Clone for SnapshotCell<T> {
+    fn clone(&self) -> Self {
+        Self::from_arc(self.snapshot())
+    }
+}
+
+impl <T: Default> Default for SnapshotCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for SnapshotCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::SnapshotCell;
+
+    #[test]
+    pub fn test_snapshot_cell_snapshot() {
+        let cell = SnapshotCell::new(1);
+
+        assert_eq!(1, *cell.snapshot());
+    }
+
+    #[test]
+    pub fn test_snapshot_cell_set_keeps_snapshot() {
+        let cell = SnapshotCell::new(1);
+        let snapshot = cell.snapshot();
+
+        cell.set(2);
+
+        assert_eq!(1, *snapshot);
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_snapshot_cell_update() {
+        let cell = SnapshotCell::new(1);
+
+        cell.update(|value| value + 1);
+
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_snapshot_cell_unsized() {
+        let cell: SnapshotCell<str> = SnapshotCell::from_arc(Arc::from("a"));
+
+        let old = cell.replace_arc(Arc::from("b"));
+
+        assert_eq!("a", &*old);
+        assert_eq!("b", &*cell.snapshot());
+    }
+}
@@ -0,0 +1,193 @@
+use std::mem::take;
+
+use crate::SyncCell;
+
+/// A last in first out stack that can be shared between threads.
+/// Every operation locks the stack once, so a push or pop is never interleaved with another.
+/// This is useful as a shared free list or undo stack.
+///
+/// # Usage
+/// ```
+/// use sync_cell::SyncStack;
+///
+/// let stack = SyncStack::new();
+///
+/// stack.push(1);
+/// stack.push(2);
+///
+/// assert_eq!(Some(2), stack.peek_cloned());
+/// assert_eq!(Some(2), stack.pop());
+/// assert_eq!(vec![1], stack.drain());
+/// assert!(stack.is_empty());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this stack will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct SyncStack<T> {
+    /// The values of the stack with the top at the end.
+    data: SyncCell<Vec<T>>,
+}
+
+impl <T> SyncStack<T> {
+    /// Creates a new empty `SyncStack`.
+    pub const fn new() -> Self {
+        Self {
+            data: SyncCell::new(Vec::new()),
+        }
+    }
+
+    /// Gets the number of values in this stack.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.data.borrow().len()
+    }
+
+    /// Checks if this stack has no values.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.data.borrow().is_empty()
+    }
+
+    /// Adds a value to the top of this stack.
+    ///
+    /// - `value` - The value to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push(&self, value: T) {
+        self.data.borrow_mut().push(value);
+    }
+
+    /// Removes the value at the top of this stack.
+    /// Returns `None` if the stack is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn pop(&self) -> Option<T> {
+        self.data.borrow_mut().pop()
+    }
+
+    /// Removes every value from this stack.
+    /// The values are returned from the bottom of the stack to the top, which is the order they
+    /// were pushed in.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn drain(&self) -> Vec<T> {
+        take(&mut *self.data.borrow_mut())
+    }
+
+    /// Retrieves the values stored in this `SyncStack` from the bottom to the top.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> Vec<T> {
+        self.data.into_inner()
+    }
+}
+
+impl <T: Clone> SyncStack<T> {
+    /// Gets a copy of the value at the top of this stack without removing it.
+    /// Returns `None` if the stack is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn peek_cloned(&self) -> Option<T> {
+        self.data.borrow().last().cloned()
+    }
+}
+
+impl <T: Clone> Clone for SyncStack<T> {
+    fn clone(&self) -> Self {
+        Self::from(self.data.get())
+    }
+}
+
+impl <T> Default for SyncStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> From<Vec<T>> for SyncStack<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self {
+            data: SyncCell::new(values),
+        }
+    }
+}
+
+impl <T> FromIterator<T> for SyncStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+/// Pushes the values onto the stack in order taking the lock once per call.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <T> Extend<T> for &SyncStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.data.borrow_mut().extend(iter);
+    }
+}
+
+/// Pushes the values onto the stack in order.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <T> Extend<T> for SyncStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        (&*self).extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::SyncStack;
+
+    #[test]
+    pub fn test_sync_stack() {
+        let stack = SyncStack::from(vec![1, 2]);
+
+        stack.push(3);
+
+        assert_eq!(3, stack.len());
+        assert_eq!(Some(3), stack.pop());
+        assert_eq!(Some(2), stack.peek_cloned());
+        assert_eq!(vec![1, 2], stack.into_inner());
+    }
+
+    #[test]
+    pub fn test_sync_stack_threads() {
+        let stack = Arc::new(SyncStack::new());
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let stack = stack.clone();
+            thread::spawn(move || {
+                for i in 0..25 {
+                    stack.push(i);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let values = stack.drain();
+
+        assert_eq!(100, values.len());
+        assert_eq!(4 * 300, values.iter().sum::<i32>());
+        assert_eq!(None, stack.pop());
+    }
+}
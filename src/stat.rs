@@ -0,0 +1,239 @@
+use std::{mem::replace, sync::{LockResult, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError, TryLockResult, atomic::{AtomicU64, Ordering}}, time::{Duration, Instant}};
+
+/// A snapshot of the counters recorded by a `StatCell`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CellStats {
+    /// The number of immutable borrows of the cell.
+    pub reads: u64,
+    /// The number of mutable borrows of the cell.
+    pub writes: u64,
+    /// The number of borrows that had to wait for another borrow to be released.
+    pub contended: u64,
+    /// The longest time a borrow spent waiting for the lock.
+    pub max_wait: Duration,
+}
+
+/// A cell that counts how often it is read and written and how often it is contended.
+/// This can be used to find the cells in an application that are hot enough to warrant a
+/// specialised cell type.
+///
+/// # Usage
+/// ```
+/// use sync_cell::StatCell;
+///
+/// let cell = StatCell::new(0);
+///
+/// cell.set(1);
+/// cell.get();
+///
+/// let stats = cell.stats();
+///
+/// assert_eq!(1, stats.reads);
+/// assert_eq!(1, stats.writes);
+/// ```
+///
+/// # Panicking
+/// Unlike `std::sync::RwLock`, `StatCell` will panic rather than return an error when the lock
+/// becomes poisoned.
+#[derive(Debug)]
+pub struct StatCell<T> {
+    /// The internal lock holding the data of this cell.
+    data: RwLock<T>,
+    /// The number of immutable borrows.
+    reads: AtomicU64,
+    /// The number of mutable borrows.
+    writes: AtomicU64,
+    /// The number of borrows that had to wait.
+    contended: AtomicU64,
+    /// The longest wait in nanoseconds.
+    max_wait: AtomicU64,
+}
+
+impl <T> StatCell<T> {
+    /// Creates a new `StatCell` with all counters set to zero.
+    ///
+    /// - `data` - The initial value of the `StatCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            data: RwLock::new(data),
+            reads: AtomicU64::new(0),
+            writes: AtomicU64::new(0),
+            contended: AtomicU64::new(0),
+            max_wait: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// This is counted as a write.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the value contained in this cell.
+    /// This is counted as a write. The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// This is counted as a read.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+
+        match self.acquire(|| self.data.try_read(), || self.data.read()) {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// This is counted as a write.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> RwLockWriteGuard<'_, T> {
+        self.writes.fetch_add(1, Ordering::Relaxed);
+
+        match self.acquire(|| self.data.try_write(), || self.data.write()) {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Gets a snapshot of the counters recorded by this cell.
+    pub fn stats(&self) -> CellStats {
+        CellStats {
+            reads: self.reads.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+            contended: self.contended.load(Ordering::Relaxed),
+            max_wait: Duration::from_nanos(self.max_wait.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Resets all counters recorded by this cell to zero.
+    /// The previous counters are returned.
+    pub fn reset_stats(&self) -> CellStats {
+        CellStats {
+            reads: self.reads.swap(0, Ordering::Relaxed),
+            writes: self.writes.swap(0, Ordering::Relaxed),
+            contended: self.contended.swap(0, Ordering::Relaxed),
+            max_wait: Duration::from_nanos(self.max_wait.swap(0, Ordering::Relaxed)),
+        }
+    }
+
+    /// Retrieves the inner value stored in this `StatCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        match self.data.into_inner() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Acquires a lock without blocking if possible and otherwise records the time spent
+    /// blocking.
+    fn acquire<G>(&self, try_lock: impl FnOnce() -> TryLockResult<G>, lock: impl FnOnce() -> LockResult<G>) -> LockResult<G> {
+        match try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::Poisoned(err)) => Err(err),
+            Err(TryLockError::WouldBlock) => {
+                let start = Instant::now();
+                let guard = lock();
+                let waited = start.elapsed().as_nanos().min(u64::MAX as u128) as u64;
+
+                self.contended.fetch_add(1, Ordering::Relaxed);
+                self.max_wait.fetch_max(waited, Ordering::Relaxed);
+
+                guard
+            },
+        }
+    }
+}
+
+impl <T: Clone> StatCell<T> {
+    /// Gets the value contained in this cell.
+    /// This is counted as a read.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl <T: Default> Default for StatCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for StatCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use crate::{CellStats, StatCell};
+
+    #[test]
+    pub fn test_stat_cell_counts() {
+        let cell = StatCell::new(1);
+
+        cell.set(2);
+        cell.replace(3);
+        cell.get();
+
+        let stats = cell.stats();
+        assert_eq!(1, stats.reads);
+        assert_eq!(2, stats.writes);
+        assert_eq!(0, stats.contended);
+    }
+
+    #[test]
+    pub fn test_stat_cell_contended() {
+        let cell = Arc::new(StatCell::new(1));
+        let guard = cell.borrow_mut();
+
+        let reader = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.get())
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        drop(guard);
+
+        assert_eq!(1, reader.join().unwrap());
+
+        let stats = cell.stats();
+        assert_eq!(1, stats.contended);
+        assert!(stats.max_wait >= Duration::from_millis(10));
+    }
+
+    #[test]
+    pub fn test_stat_cell_reset_stats() {
+        let cell = StatCell::new(1);
+        cell.get();
+
+        assert_eq!(1, cell.reset_stats().reads);
+        assert_eq!(CellStats::default(), cell.stats());
+    }
+}
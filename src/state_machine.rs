@@ -0,0 +1,167 @@
+use std::mem::replace;
+
+use crate::{SyncCell, SyncCellRef};
+
+/// A function checking whether a transition between two states is allowed.
+type TransitionFn<S> = Box<dyn Fn(&S, &S) -> bool + Send + Sync>;
+
+/// A function called after a transition between two states.
+type ObserverFn<S> = Box<dyn Fn(&S, &S) + Send + Sync>;
+
+/// A cell holding a state that can only change through allowed transitions.
+/// Every transition is checked against a predicate given when the cell is created. Observers can
+/// be registered to be notified of each successful transition.
+///
+/// # Usage
+/// ```
+/// use sync_cell::StateMachineCell;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Connection {
+///     Disconnected,
+///     Connecting,
+///     Connected,
+/// }
+///
+/// let cell = StateMachineCell::new(Connection::Disconnected, |from, to| matches!(
+///     (from, to),
+///     (Connection::Disconnected, Connection::Connecting)
+///         | (Connection::Connecting, Connection::Connected)
+///         | (_, Connection::Disconnected)
+/// ));
+///
+/// assert!(cell.transition_to(Connection::Connected).is_err());
+/// assert!(cell.transition_to(Connection::Connecting).is_ok());
+/// assert!(cell.transition_to(Connection::Connected).is_ok());
+/// assert_eq!(Connection::Connected, cell.get());
+/// ```
+pub struct StateMachineCell<S> {
+    /// The current state.
+    state: SyncCell<S>,
+    /// The predicate checking whether a transition is allowed.
+    allowed: TransitionFn<S>,
+    /// The observers notified of each transition.
+    observers: SyncCell<Vec<ObserverFn<S>>>,
+}
+
+impl <S> StateMachineCell<S> {
+    /// Creates a new `StateMachineCell`.
+    ///
+    /// - `initial` - The initial state.
+    /// - `allowed` - A function returning `true` if a transition from the first state to the
+    ///   second is allowed.
+    pub fn new(initial: S, allowed: impl Fn(&S, &S) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            state: SyncCell::new(initial),
+            allowed: Box::new(allowed),
+            observers: SyncCell::new(Vec::new()),
+        }
+    }
+
+    /// Transitions to a new state if the transition is allowed.
+    /// On success, all observers are notified and the previous state is returned. Otherwise the
+    /// requested state is returned as an error.
+    ///
+    /// Observers are called while the state is locked so they must not access this cell.
+    ///
+    /// - `next` - The state to transition to.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn transition_to(&self, next: S) -> Result<S, S> {
+        let mut state = self.state.borrow_mut();
+
+        if !(self.allowed)(&state, &next) {
+            return Err(next);
+        }
+
+        let previous = replace(&mut *state, next);
+
+        for observer in self.observers.borrow().iter() {
+            observer(&previous, &state);
+        }
+
+        Ok(previous)
+    }
+
+    /// Checks if a transition from the current state to another state is allowed.
+    ///
+    /// - `next` - The state to check.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn can_transition_to(&self, next: &S) -> bool {
+        (self.allowed)(&self.state.borrow(), next)
+    }
+
+    /// Registers an observer that is called after each successful transition with the previous
+    /// and the new state.
+    ///
+    /// - `observer` - The function to call.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn on_transition(&self, observer: impl Fn(&S, &S) + Send + Sync + 'static) {
+        self.observers.borrow_mut().push(Box::new(observer));
+    }
+
+    /// Borrows a immutable reference to the current state.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, S> {
+        self.state.borrow()
+    }
+
+    /// Retrieves the current state stored in this `StateMachineCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> S {
+        self.state.into_inner()
+    }
+}
+
+impl <S: Clone> StateMachineCell<S> {
+    /// Gets the current state.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> S {
+        self.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::StateMachineCell;
+
+    #[test]
+    pub fn test_state_machine_cell_transition() {
+        let cell = StateMachineCell::new(0, |from: &i32, to: &i32| *to == from + 1);
+
+        assert!(cell.can_transition_to(&1));
+        assert_eq!(Ok(0), cell.transition_to(1));
+        assert_eq!(Err(3), cell.transition_to(3));
+        assert_eq!(1, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_state_machine_cell_observers() {
+        let cell = StateMachineCell::new(0, |from: &i32, to: &i32| to > from);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        {
+            let seen = seen.clone();
+            cell.on_transition(move |from, to| seen.lock().unwrap().push((*from, *to)));
+        }
+
+        cell.transition_to(2).unwrap();
+        cell.transition_to(1).unwrap_err();
+        cell.transition_to(5).unwrap();
+
+        assert_eq!(vec![(0, 2), (2, 5)], *seen.lock().unwrap());
+    }
+}
@@ -0,0 +1,277 @@
+use std::{fmt::{self, Display, Formatter, Write}, mem::{replace, take}, sync::{Mutex, MutexGuard}};
+
+/// A string that many threads can append to at the same time, such as shared textual output.
+/// Every append locks the string once, so text appended by one call is never interleaved with text
+/// from another thread.
+///
+/// This uses a `Mutex` rather than an `RwLock` because appending is far more common than reading.
+///
+/// # Usage
+/// ```
+/// use std::{fmt::Write, sync::Arc, thread};
+/// use sync_cell::SyncString;
+///
+/// let output = Arc::new(SyncString::new());
+///
+/// let handles: Vec<_> = (0..4).map(|id| {
+///     let output = output.clone();
+///     thread::spawn(move || {
+///         writeln!(&*output, "Worker {} done", id).unwrap();
+///     })
+/// }).collect();
+/// # for handle in handles { handle.join().unwrap(); }
+///
+/// assert_eq!(4, output.take().lines().count());
+/// assert!(output.is_empty());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this string will panic rather than return an error when the lock becomes
+/// poisoned.
+#[derive(Debug, Default)]
+pub struct SyncString {
+    /// The contents of the string.
+    data: Mutex<String>,
+}
+
+impl SyncString {
+    /// Creates a new empty `SyncString`.
+    pub const fn new() -> Self {
+        Self {
+            data: Mutex::new(String::new()),
+        }
+    }
+
+    /// Creates a new empty `SyncString` with space for a number of bytes.
+    ///
+    /// - `capacity` - The number of bytes to allocate space for.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from(String::with_capacity(capacity))
+    }
+
+    /// Gets the length of this string in bytes.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Checks if this string is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Appends a string slice to the end of this string.
+    ///
+    /// - `value` - The text to append.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push_str(&self, value: &str) {
+        self.lock().push_str(value);
+    }
+
+    /// Appends a character to the end of this string.
+    ///
+    /// - `value` - The character to append.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push(&self, value: char) {
+        self.lock().push(value);
+    }
+
+    /// Appends a line to the end of this string followed by a newline.
+    /// The line and its newline are appended together.
+    ///
+    /// - `line` - The text of the line.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push_line(&self, line: &str) {
+        let mut data = self.lock();
+        data.push_str(line);
+        data.push('\n');
+    }
+
+    /// Takes the contents of this string leaving it empty.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take(&self) -> String {
+        take(&mut *self.lock())
+    }
+
+    /// Takes every complete line of this string, leaving any text after the last newline.
+    /// This lets a consumer flush whole lines while producers are still writing a partial line.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncString;
+    ///
+    /// let output = SyncString::new();
+    ///
+    /// output.push_str("first\nsec");
+    ///
+    /// assert_eq!(vec!["first"], output.take_lines());
+    /// assert_eq!("sec", output.snapshot());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn take_lines(&self) -> Vec<String> {
+        let complete = {
+            let mut data = self.lock();
+
+            match data.rfind('\n') {
+                Some(end) => {
+                    let rest = data.split_off(end + 1);
+                    replace(&mut *data, rest)
+                },
+                None => return Vec::new(),
+            }
+        };
+
+        complete.lines().map(String::from).collect()
+    }
+
+    /// Gets a copy of the contents of this string.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn snapshot(&self) -> String {
+        self.lock().clone()
+    }
+
+    /// Retrieves the contents of this `SyncString`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> String {
+        match self.data.into_inner() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+
+    /// Locks the contents of this string.
+    fn lock(&self) -> MutexGuard<'_, String> {
+        match self.data.lock() {
+            Ok(data) => data,
+            Err(err) => panic!("Failed to get cell value. Lock was poisoned: {}", err),
+        }
+    }
+}
+
+/// Appends formatted text to the string taking the lock once per call.
+///
+/// # Panicking
+/// Each method will panic if the lock becomes poisoned.
+impl Write for &SyncString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        // Formatting under one lock keeps the pieces of the text from being interleaved.
+        self.lock().write_fmt(args)
+    }
+}
+
+/// Appends formatted text to the string.
+///
+/// # Panicking
+/// Each method will panic if the lock becomes poisoned.
+impl Write for SyncString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        (&*self).write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        (&*self).write_char(c)
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        (&*self).write_fmt(args)
+    }
+}
+
+impl Display for SyncString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.lock())
+    }
+}
+
+impl Clone for SyncString {
+    fn clone(&self) -> Self {
+        Self::from(self.snapshot())
+    }
+}
+
+impl From<String> for SyncString {
+    fn from(value: String) -> Self {
+        Self {
+            data: Mutex::new(value),
+        }
+    }
+}
+
+impl From<&str> for SyncString {
+    fn from(value: &str) -> Self {
+        Self::from(String::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Write, sync::Arc, thread};
+
+    use crate::SyncString;
+
+    #[test]
+    pub fn test_sync_string() {
+        let mut string = SyncString::from("a");
+
+        string.push('b');
+        string.push_line("c");
+        write!(string, "{}", 1).unwrap();
+
+        assert_eq!("abc\n1", string.to_string());
+        assert_eq!(vec!["abc"], string.take_lines());
+        assert!(string.take_lines().is_empty());
+        assert_eq!("1", string.into_inner());
+    }
+
+    #[test]
+    pub fn test_sync_string_threads() {
+        let string = Arc::new(SyncString::new());
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let string = string.clone();
+            thread::spawn(move || {
+                for _ in 0..25 {
+                    string.push_line("line");
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let lines = string.take_lines();
+
+        assert_eq!(100, lines.len());
+        assert!(lines.iter().all(|line| line == "line"));
+    }
+}
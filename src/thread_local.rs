@@ -0,0 +1,234 @@
+use std::{any::Any, cell::RefCell, collections::HashMap, mem::replace, sync::{Arc, Weak, atomic::{AtomicUsize, Ordering}}};
+
+use crate::{SyncCell, SyncCellRef};
+
+/// The next identifier to give to a `ThreadLocalCell`.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// The staging values of the current thread keyed by the identifier of their cell.
+    static STAGES: RefCell<HashMap<usize, Weak<dyn Any + Send + Sync>>> = RefCell::new(HashMap::new());
+}
+
+/// A function creating an empty staging value.
+type InitFn<S> = Box<dyn Fn() -> S + Send + Sync>;
+/// A function merging a staging value into the shared value.
+type CombineFn<T, S> = Box<dyn Fn(&mut T, S) + Send + Sync>;
+
+/// A cell where each thread writes to its own staging value which is later merged into a shared
+/// value.
+/// Updates only lock the staging value of the current thread which is never contended except
+/// while it is being flushed. Calling `flush_all` merges the staging value of every thread into
+/// the shared value using a combine function and resets the staging values.
+///
+/// This is useful for statistics that are updated very frequently from many threads but only
+/// read occasionally.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::ThreadLocalCell;
+///
+/// let cell = Arc::new(ThreadLocalCell::new(0u64, || 0u64, |total, local| *total += local));
+///
+/// let handles: Vec<_> = (0..4).map(|_| {
+///     let cell = cell.clone();
+///     thread::spawn(move || {
+///         for _ in 0..100 {
+///             cell.update(|local| *local += 1);
+///         }
+///     })
+/// }).collect();
+/// # for handle in handles { handle.join().unwrap(); }
+///
+/// assert_eq!(400, cell.collect());
+/// ```
+pub struct ThreadLocalCell<T, S = T> {
+    /// The identifier of this cell used to find the staging value of a thread.
+    id: usize,
+    /// The shared value that staging values are merged into.
+    shared: SyncCell<T>,
+    /// The staging value of every thread that has updated this cell.
+    stages: SyncCell<Vec<Arc<SyncCell<S>>>>,
+    /// The function creating an empty staging value.
+    init: InitFn<S>,
+    /// The function merging a staging value into the shared value.
+    combine: CombineFn<T, S>,
+}
+
+impl <T, S: Send + Sync + 'static> ThreadLocalCell<T, S> {
+    /// Creates a new `ThreadLocalCell`.
+    ///
+    /// - `data` - The initial shared value.
+    /// - `init` - A function creating an empty staging value.
+    /// - `combine` - A function merging a staging value into the shared value.
+    pub fn new(data: T, init: impl Fn() -> S + Send + Sync + 'static,
+            combine: impl Fn(&mut T, S) + Send + Sync + 'static) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            shared: SyncCell::new(data),
+            stages: SyncCell::new(Vec::new()),
+            init: Box::new(init),
+            combine: Box::new(combine),
+        }
+    }
+
+    /// Calls a function with a mutable reference to the staging value of the current thread.
+    ///
+    /// - `f` - The function used to update the staging value.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update<R>(&self, f: impl FnOnce(&mut S) -> R) -> R {
+        f(&mut self.local_stage().borrow_mut())
+    }
+
+    /// Merges the staging value of the current thread into the shared value.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn flush_local(&self) {
+        let stage = self.local_stage();
+        let mut shared = self.shared.borrow_mut();
+
+        (self.combine)(&mut shared, stage.replace((self.init)()));
+    }
+
+    /// Merges the staging value of every thread into the shared value.
+    /// Updates made while flushing may be merged now or by the next flush.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn flush_all(&self) {
+        let stages = self.stages.borrow();
+        let mut shared = self.shared.borrow_mut();
+
+        for stage in stages.iter() {
+            let value = replace(&mut *stage.borrow_mut(), (self.init)());
+            (self.combine)(&mut shared, value);
+        }
+    }
+
+    /// Borrows a immutable reference to the shared value without flushing.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.shared.borrow()
+    }
+
+    /// Merges the staging value of every thread and retrieves the shared value stored in this
+    /// `ThreadLocalCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> T {
+        self.flush_all();
+        self.shared.into_inner()
+    }
+
+    /// Gets the staging value of the current thread creating it if needed.
+    fn local_stage(&self) -> Arc<SyncCell<S>> {
+        STAGES.with(|stages| {
+            let mut stages = stages.borrow_mut();
+
+            if let Some(stage) = stages.get(&self.id).and_then(Weak::upgrade) {
+                if let Ok(stage) = stage.downcast::<SyncCell<S>>() {
+                    return stage;
+                }
+            }
+
+            let stage = Arc::new(SyncCell::new((self.init)()));
+            self.stages.borrow_mut().push(stage.clone());
+
+            // Remove the stages of cells that no longer exist.
+            stages.retain(|_, stage| stage.strong_count() > 0);
+
+            let weak: Weak<dyn Any + Send + Sync> = Arc::downgrade(&stage) as Weak<SyncCell<S>>;
+            stages.insert(self.id, weak);
+
+            stage
+        })
+    }
+}
+
+impl <T: Clone, S: Send + Sync + 'static> ThreadLocalCell<T, S> {
+    /// Gets the shared value without flushing.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get(&self) -> T {
+        self.shared.get()
+    }
+
+    /// Merges the staging value of every thread and gets the shared value.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn collect(&self) -> T {
+        self.flush_all();
+        self.shared.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::ThreadLocalCell;
+
+    #[test]
+    pub fn test_thread_local_cell_update_not_visible() {
+        let cell = ThreadLocalCell::new(0, || 0, |total, local| *total += local);
+
+        cell.update(|local| *local += 5);
+
+        assert_eq!(0, cell.get());
+        assert_eq!(5, cell.collect());
+        assert_eq!(5, cell.collect());
+    }
+
+    #[test]
+    pub fn test_thread_local_cell_flush_local() {
+        let cell = ThreadLocalCell::new(Vec::new(), Vec::new, |all: &mut Vec<i32>, local| all.extend(local));
+
+        cell.update(|local| local.push(1));
+        cell.flush_local();
+        cell.update(|local| local.push(2));
+
+        assert_eq!(vec![1], cell.get());
+        assert_eq!(vec![1, 2], cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_thread_local_cell_separate_cells() {
+        let a = ThreadLocalCell::new(0, || 0, |total, local| *total += local);
+        let b = ThreadLocalCell::new(0, || 0, |total, local| *total += local);
+
+        a.update(|local| *local += 1);
+        b.update(|local| *local += 2);
+
+        assert_eq!(1, a.collect());
+        assert_eq!(2, b.collect());
+    }
+
+    #[test]
+    pub fn test_thread_local_cell_threads() {
+        let cell = Arc::new(ThreadLocalCell::new(0u64, || 0u64, |total, local| *total += local));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    cell.update(|local| *local += 1);
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(400, cell.collect());
+    }
+}
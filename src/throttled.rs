@@ -0,0 +1,235 @@
+use std::time::{Duration, Instant};
+
+use crate::{SyncCell, SyncCellError, SyncCellGuard, SyncCellRef};
+
+/// The publishing state of a `ThrottledCell`.
+struct Throttle<T> {
+    /// The most recent value that has not yet been published.
+    pending: Option<T>,
+    /// The instant at which a value was last published.
+    last_publish: Option<Instant>,
+}
+
+/// A cell that accepts new values at any rate but publishes them at most once per interval.
+/// If a value is set before the interval since the last publish has elapsed, it is held as a
+/// pending value and replaced by any later value. The pending value is published the next time the
+/// cell is polled after the interval has elapsed. Polling happens automatically when setting,
+/// getting or borrowing the value and can be triggered explicitly by calling `poll`. Polling never
+/// blocks on the published value, so a value that is borrowed at the time of a poll stays pending
+/// until the next poll.
+///
+/// # Usage
+/// ```
+/// use std::time::Duration;
+/// use sync_cell::ThrottledCell;
+///
+/// let cell = ThrottledCell::new(0, Duration::from_secs(60));
+///
+/// // The first value is published immediately.
+/// cell.set(1);
+/// assert_eq!(1, cell.get());
+///
+/// // Later values are held until the interval has elapsed.
+/// cell.set(2);
+/// cell.set(3);
+/// assert_eq!(1, cell.get());
+/// ```
+pub struct ThrottledCell<T> {
+    /// The minimum amount of time between published values.
+    interval: Duration,
+    /// The published value of this cell.
+    current_value: SyncCell<T>,
+    /// The pending value and publish time of this cell.
+    /// This lock is always acquired before `current_value` when both are needed.
+    throttle: SyncCell<Throttle<T>>,
+}
+
+impl <T> ThrottledCell<T> {
+    /// Creates a new `ThrottledCell`.
+    /// The first value set on the cell is published immediately.
+    ///
+    /// - `data` - The initial value of the `ThrottledCell`.
+    /// - `interval` - The minimum amount of time between published values.
+    pub const fn new(data: T, interval: Duration) -> Self {
+        Self {
+            interval,
+            current_value: SyncCell::new(data),
+            throttle: SyncCell::new(Throttle {
+                pending: None,
+                last_publish: None,
+            }),
+        }
+    }
+
+    /// Gets the minimum amount of time between published values.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Sets the value of this cell.
+    /// The value is published immediately if the interval since the last publish has elapsed.
+    /// Otherwise it replaces the pending value.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, value: T) {
+        let mut throttle = self.throttle.borrow_mut();
+
+        throttle.pending = Some(value);
+        self.publish_if_ready(&mut throttle);
+    }
+
+    /// Publishes the pending value if the interval since the last publish has elapsed.
+    /// If the published value is currently borrowed the pending value is kept for a later poll.
+    ///
+    /// Returns `true` if a new value was published.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn poll(&self) -> bool {
+        self.publish_if_ready(&mut self.throttle.borrow_mut())
+    }
+
+    /// Checks if a pending value is waiting to be published.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn has_pending(&self) -> bool {
+        self.throttle.borrow().pending.is_some()
+    }
+
+    /// Retrieves the inner value stored in this `ThrottledCell`.
+    /// This will return the pending value if there is one even if the interval has not elapsed.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> T {
+        match self.throttle.into_inner().pending {
+            Some(value) => value,
+            None => self.current_value.into_inner(),
+        }
+    }
+
+    /// Borrows a immutable reference to the published value of this cell.
+    /// The cell is polled before the value is borrowed.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.poll();
+        self.current_value.borrow()
+    }
+
+    /// Borrows a mutable reference to the published value of this cell.
+    /// The cell is polled before the value is borrowed. Changes made through this reference do
+    /// not count as a publish.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        self.poll();
+        self.current_value.borrow_mut()
+    }
+
+    /// Publishes the pending value if there is one and the interval has elapsed.
+    /// This does not block on the published value since the caller may be holding a borrow of it.
+    fn publish_if_ready(&self, throttle: &mut Throttle<T>) -> bool {
+        let ready = match throttle.last_publish {
+            Some(last) => last.elapsed() >= self.interval,
+            None => true,
+        };
+
+        match throttle.pending.take() {
+            Some(value) if ready => match self.current_value.try_borrow_mut() {
+                Ok(mut current) => {
+                    *current = value;
+                    throttle.last_publish = Some(Instant::now());
+                    true
+                },
+                Err(SyncCellError::WouldBlock(_) | SyncCellError::Timeout(_)) => {
+                    throttle.pending = Some(value);
+                    false
+                },
+                Err(SyncCellError::Poisoned) => panic!("Failed to get cell value. Lock was poisoned"),
+                Err(err @ SyncCellError::Invalid) => panic!("Failed to publish the pending value: {}", err),
+            },
+            other => {
+                throttle.pending = other;
+                false
+            },
+        }
+    }
+}
+
+impl <T: Clone> ThrottledCell<T> {
+    /// Gets the published value of this cell.
+    /// The cell is polled before the value is retrieved.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get(&self) -> T {
+        self.poll();
+        self.current_value.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crate::ThrottledCell;
+
+    #[test]
+    pub fn test_throttled_cell_first_set() {
+        let cell = ThrottledCell::new(1, Duration::from_secs(60));
+
+        cell.set(2);
+
+        assert!(!cell.has_pending());
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_throttled_cell_set_while_borrowed() {
+        let cell = ThrottledCell::new(1, Duration::from_secs(60));
+        let guard = cell.borrow();
+
+        cell.set(2);
+
+        assert_eq!(1, *guard);
+        assert!(cell.has_pending());
+        assert_eq!(1, cell.get());
+        drop(guard);
+
+        assert_eq!(2, cell.get());
+        assert!(!cell.has_pending());
+    }
+
+    #[test]
+    pub fn test_throttled_cell_held() {
+        let cell = ThrottledCell::new(1, Duration::from_secs(60));
+
+        cell.set(2);
+        cell.set(3);
+        cell.set(4);
+
+        assert!(cell.has_pending());
+        assert!(!cell.poll());
+        assert_eq!(2, cell.get());
+        assert_eq!(4, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_throttled_cell_published_after_interval() {
+        let cell = ThrottledCell::new(1, Duration::from_millis(10));
+
+        cell.set(2);
+        cell.set(3);
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(3, cell.get());
+        assert!(!cell.has_pending());
+    }
+}
@@ -0,0 +1,246 @@
+use std::{mem::replace, sync::atomic::{AtomicU64, Ordering}, time::{Duration, Instant}};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// The stored offset used to indicate that a cell has never been read.
+const NEVER: u64 = u64::MAX;
+
+/// A cell that records when its value was last written and optionally when it was last read.
+/// Instants are stored as offsets from the creation time of the cell so that they can be updated
+/// without taking a lock.
+///
+/// This is useful for cache invalidation where the age of a value decides whether it should be
+/// recomputed.
+///
+/// # Usage
+/// ```
+/// use std::time::Duration;
+/// use sync_cell::TimedCell;
+///
+/// let cell = TimedCell::new(0);
+///
+/// cell.set(1);
+///
+/// if cell.elapsed_since_write() > Duration::from_secs(60) {
+///     // Recompute the value.
+/// }
+/// # assert_eq!(1, cell.get());
+/// ```
+#[derive(Debug)]
+pub struct TimedCell<T> {
+    /// The instant this cell was created which all stored offsets are relative to.
+    created: Instant,
+    /// The offset in nanoseconds of the last write.
+    modified: AtomicU64,
+    /// The offset in nanoseconds of the last read or `NEVER`.
+    read: AtomicU64,
+    /// Whether reads are recorded.
+    track_reads: bool,
+    /// The value of this cell.
+    data: SyncCell<T>,
+}
+
+impl <T> TimedCell<T> {
+    /// Creates a new `TimedCell` that records the time of writes.
+    /// The value is considered written at the time of creation.
+    ///
+    /// - `data` - The initial value of the `TimedCell`.
+    pub fn new(data: T) -> Self {
+        Self::create(data, false)
+    }
+
+    /// Creates a new `TimedCell` that records the time of both writes and reads.
+    ///
+    /// - `data` - The initial value of the `TimedCell`.
+    pub fn with_read_tracking(data: T) -> Self {
+        Self::create(data, true)
+    }
+
+    /// Creates a new `TimedCell`.
+    fn create(data: T, track_reads: bool) -> Self {
+        Self {
+            created: Instant::now(),
+            modified: AtomicU64::new(0),
+            read: AtomicU64::new(NEVER),
+            track_reads,
+            data: SyncCell::new(data),
+        }
+    }
+
+    /// Sets the value contained in this cell and records the time of the write.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Replaces the value contained in this cell and records the time of the write.
+    /// The previous value is returned.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn replace(&self, value: T) -> T {
+        replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Sets the value contained in this cell and returns how long the previous value had been
+    /// stored.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set_returning_age(&self, value: T) -> Duration {
+        let mut data = self.data.borrow_mut();
+        let age = self.elapsed_since_write();
+
+        self.record(&self.modified);
+        *data = value;
+
+        age
+    }
+
+    /// Gets the instant the value of this cell was last written.
+    pub fn modified_at(&self) -> Instant {
+        self.instant(self.modified.load(Ordering::Acquire))
+    }
+
+    /// Gets the amount of time since the value of this cell was last written.
+    pub fn elapsed_since_write(&self) -> Duration {
+        self.modified_at().elapsed()
+    }
+
+    /// Gets the instant the value of this cell was last read.
+    /// Returns `None` if the value has never been read or reads are not being recorded.
+    pub fn read_at(&self) -> Option<Instant> {
+        match self.read.load(Ordering::Acquire) {
+            NEVER => None,
+            offset => Some(self.instant(offset)),
+        }
+    }
+
+    /// Gets the amount of time since the value of this cell was last read.
+    /// Returns `None` if the value has never been read or reads are not being recorded.
+    pub fn elapsed_since_read(&self) -> Option<Duration> {
+        self.read_at().map(|instant| instant.elapsed())
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    /// This is recorded as a read if read tracking is enabled.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        let data = self.data.borrow();
+
+        if self.track_reads {
+            self.record(&self.read);
+        }
+
+        data
+    }
+
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// This is recorded as a write at the time the borrow is acquired.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        let data = self.data.borrow_mut();
+
+        self.record(&self.modified);
+
+        data
+    }
+
+    /// Retrieves the inner value stored in this `TimedCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Stores the current time as an offset from the creation of this cell.
+    fn record(&self, offset: &AtomicU64) {
+        let nanos = self.created.elapsed().as_nanos().min((NEVER - 1) as u128) as u64;
+
+        offset.store(nanos, Ordering::Release);
+    }
+
+    /// Converts an offset from the creation of this cell into an instant.
+    fn instant(&self, offset: u64) -> Instant {
+        self.created + Duration::from_nanos(offset)
+    }
+}
+
+impl <T: Clone> TimedCell<T> {
+    /// Gets the value contained in this cell.
+    /// This is recorded as a read if read tracking is enabled.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl <T: Default> Default for TimedCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for TimedCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::{Duration, Instant}};
+
+    use crate::TimedCell;
+
+    #[test]
+    pub fn test_timed_cell_modified_at() {
+        let before = Instant::now();
+        let cell = TimedCell::new(1);
+
+        thread::sleep(Duration::from_millis(10));
+        cell.set(2);
+
+        assert!(cell.modified_at() >= before + Duration::from_millis(10));
+        assert!(cell.elapsed_since_write() < Duration::from_secs(10));
+    }
+
+    #[test]
+    pub fn test_timed_cell_set_returning_age() {
+        let cell = TimedCell::new(1);
+
+        thread::sleep(Duration::from_millis(10));
+
+        assert!(cell.set_returning_age(2) >= Duration::from_millis(10));
+        assert!(cell.set_returning_age(3) < Duration::from_millis(10));
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_timed_cell_read_tracking() {
+        let cell = TimedCell::new(1);
+        cell.get();
+        assert_eq!(None, cell.read_at());
+
+        let cell = TimedCell::with_read_tracking(1);
+        assert_eq!(None, cell.read_at());
+
+        cell.get();
+        assert!(cell.elapsed_since_read().is_some());
+    }
+}
@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, sync::{Condvar, Mutex, MutexGuard, Once}, task::Waker, thread, time::Instant};
+
+/// The tasks waiting to be woken by the timer thread.
+struct Timers {
+    /// The wakers to wake, ordered by deadline and then by the order they were registered in.
+    queue: BTreeMap<(Instant, u64), Waker>,
+    /// The identifier given to the next registered waker.
+    next_id: u64,
+}
+
+/// The timers shared by every `Sleep`.
+static TIMERS: Mutex<Timers> = Mutex::new(Timers {
+    queue: BTreeMap::new(),
+    next_id: 0,
+});
+
+/// Notified whenever a timer is registered that is due before every other timer.
+static CHANGED: Condvar = Condvar::new();
+
+/// Starts the timer thread the first time a timer is registered.
+static START: Once = Once::new();
+
+/// Locks the shared timers.
+fn lock_timers() -> MutexGuard<'static, Timers> {
+    // The timers are never left inconsistent while locked, so a poisoned lock can be used.
+    match TIMERS.lock() {
+        Ok(timers) => timers,
+        Err(err) => err.into_inner(),
+    }
+}
+
+/// Wakes every task whose deadline has passed and then waits for the next deadline.
+/// This runs on a single thread shared by every timer.
+fn run() {
+    let mut timers = lock_timers();
+
+    loop {
+        let now = Instant::now();
+        let mut due = Vec::new();
+
+        while let Some(entry) = timers.queue.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+
+            due.push(entry.remove());
+        }
+
+        if !due.is_empty() {
+            // Tasks are woken without the lock held so that they can register new timers.
+            drop(timers);
+
+            for waker in due {
+                waker.wake();
+            }
+
+            timers = lock_timers();
+            continue;
+        }
+
+        timers = match timers.queue.first_key_value() {
+            Some((&(deadline, _), _)) => match CHANGED.wait_timeout(timers, deadline.saturating_duration_since(now)) {
+                Ok((timers, _)) => timers,
+                Err(err) => err.into_inner().0,
+            },
+            None => match CHANGED.wait(timers) {
+                Ok(timers) => timers,
+                Err(err) => err.into_inner(),
+            },
+        };
+    }
+}
+
+/// A registration to wake a task once a deadline has passed.
+/// Every `Sleep` is served by one shared timer thread that is started the first time it is
+/// needed. Dropping the `Sleep` cancels the wake up.
+pub(crate) struct Sleep {
+    /// The key of the waker in the shared timers.
+    key: (Instant, u64),
+}
+
+impl Sleep {
+    /// Registers a task to be woken once a deadline has passed.
+    ///
+    /// - `deadline` - The instant after which the task is woken.
+    /// - `waker` - The waker of the task.
+    ///
+    /// # Panicking
+    /// This method will panic if the timer thread cannot be started.
+    pub(crate) fn new(deadline: Instant, waker: &Waker) -> Self {
+        START.call_once(|| {
+            if let Err(err) = thread::Builder::new().name(String::from("sync-cell-timer")).spawn(run) {
+                panic!("Failed to start the timer thread: {}", err);
+            }
+        });
+
+        let mut timers = lock_timers();
+        let key = (deadline, timers.next_id);
+
+        timers.next_id += 1;
+        timers.queue.insert(key, waker.clone());
+
+        if timers.queue.first_key_value().is_some_and(|(first, _)| *first == key) {
+            CHANGED.notify_one();
+        }
+
+        Self {
+            key,
+        }
+    }
+
+    /// Replaces the waker that is woken once the deadline has passed.
+    /// Does nothing if the task has already been woken.
+    ///
+    /// - `waker` - The waker of the task.
+    pub(crate) fn set_waker(&self, waker: &Waker) {
+        if let Some(current) = lock_timers().queue.get_mut(&self.key) {
+            current.clone_from(waker);
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        lock_timers().queue.remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, atomic::{AtomicBool, Ordering}}, task::{Wake, Waker}, thread, time::{Duration, Instant}};
+
+    use super::Sleep;
+
+    /// A waker that records whether it was woken.
+    struct FlagWaker(AtomicBool);
+
+    impl Wake for FlagWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    pub fn test_sleep_wake_and_cancel() {
+        let woken = Arc::new(FlagWaker(AtomicBool::new(false)));
+        let cancelled = Arc::new(FlagWaker(AtomicBool::new(false)));
+
+        let sleep = Sleep::new(Instant::now() + Duration::from_millis(10), &Waker::from(woken.clone()));
+        drop(Sleep::new(Instant::now() + Duration::from_millis(10), &Waker::from(cancelled.clone())));
+
+        thread::sleep(Duration::from_millis(100));
+
+        assert!(woken.0.load(Ordering::SeqCst));
+        assert!(!cancelled.0.load(Ordering::SeqCst));
+        drop(sleep);
+    }
+}
@@ -0,0 +1,165 @@
+use tokio::sync::watch::{self, Receiver, Sender};
+
+use crate::{AsyncSyncCell, RawSyncLock, SyncCell};
+
+impl <T, L: RawSyncLock> SyncCell<T, L> {
+    /// Converts this cell into a `tokio::sync::watch` channel whose initial value is the value of
+    /// this cell.
+    /// This method is only available with the `tokio` feature.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(1);
+    /// let (sender, receiver) = cell.into_tokio_watch();
+    ///
+    /// sender.send_replace(2);
+    /// assert_eq!(2, *receiver.borrow());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_tokio_watch(self) -> (Sender<T>, Receiver<T>) {
+        watch::channel(self.into_inner())
+    }
+}
+
+impl <T: Clone> SyncCell<T> {
+    /// Creates a new `SyncCell` holding the value currently seen by a `tokio::sync::watch`
+    /// receiver.
+    /// The receiver is not marked as having seen the value. This method is only available with
+    /// the `tokio` feature.
+    ///
+    /// - `receiver` - The receiver to copy the value from.
+    pub fn from_tokio_watch(receiver: &Receiver<T>) -> Self {
+        Self::new(receiver.borrow().clone())
+    }
+}
+
+impl <T: Clone, L: RawSyncLock> SyncCell<T, L> {
+    /// Sets this cell to the current value of a `tokio::sync::watch` channel and then to every
+    /// value sent on it, until all of its senders have been dropped.
+    /// Awaiting this in a task replaces a hand written forwarding loop. This method is only
+    /// available with the `tokio` feature.
+    ///
+    /// - `receiver` - The receiver of the channel to follow.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    /// use tokio::sync::watch::Receiver;
+    ///
+    /// # async fn example(cell: &SyncCell<i32>, receiver: Receiver<i32>) {
+    /// cell.follow_tokio_watch(receiver).await;
+    /// println!("The last value sent was {}", cell.get());
+    /// # }
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub async fn follow_tokio_watch(&self, mut receiver: Receiver<T>) {
+        loop {
+            // The value is copied out first so that the channel is not locked while the cell is.
+            let value = receiver.borrow_and_update().clone();
+            self.set(value);
+
+            if receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl <T> AsyncSyncCell<T> {
+    /// Converts this cell into a `tokio::sync::watch` channel whose initial value is the value of
+    /// this cell.
+    /// This method is only available with the `tokio` feature.
+    pub fn into_tokio_watch(self) -> (Sender<T>, Receiver<T>) {
+        watch::channel(self.into_inner())
+    }
+}
+
+impl <T: Clone> AsyncSyncCell<T> {
+    /// Creates a new `AsyncSyncCell` holding the value currently seen by a `tokio::sync::watch`
+    /// receiver.
+    /// The receiver is not marked as having seen the value. This method is only available with
+    /// the `tokio` feature.
+    ///
+    /// - `receiver` - The receiver to copy the value from.
+    pub fn from_tokio_watch(receiver: &Receiver<T>) -> Self {
+        Self::new(receiver.borrow().clone())
+    }
+
+    /// Sets this cell to the current value of a `tokio::sync::watch` channel and then to every
+    /// value sent on it, until all of its senders have been dropped.
+    /// Tasks waiting in `changed` are woken by each value. This method is only available with the
+    /// `tokio` feature.
+    ///
+    /// - `receiver` - The receiver of the channel to follow.
+    pub async fn follow_tokio_watch(&self, mut receiver: Receiver<T>) {
+        loop {
+            // The value is copied out first so that the channel is not locked while the cell is.
+            let value = receiver.borrow_and_update().clone();
+            self.set(value).await;
+
+            if receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use tokio::sync::watch;
+
+    use crate::{AsyncSyncCell, SyncCell, block_on::block_on};
+
+    #[test]
+    pub fn test_sync_cell_tokio_watch_conversions() {
+        let (sender, receiver) = SyncCell::new(1).into_tokio_watch();
+        assert_eq!(1, *receiver.borrow());
+
+        sender.send_replace(2);
+
+        let cell = SyncCell::from_tokio_watch(&receiver);
+        assert_eq!(2, cell.get());
+
+        let (_sender, receiver) = AsyncSyncCell::new(3).into_tokio_watch();
+        assert_eq!(3, AsyncSyncCell::from_tokio_watch(&receiver).blocking_get());
+    }
+
+    #[test]
+    pub fn test_sync_cell_follow_tokio_watch() {
+        let (sender, receiver) = watch::channel(0);
+        let cell = SyncCell::new(-1);
+
+        thread::scope(|scope| {
+            let follower = scope.spawn(|| block_on(cell.follow_tokio_watch(receiver)));
+
+            for value in 1..=3 {
+                sender.send_replace(value);
+            }
+
+            drop(sender);
+            follower.join().unwrap();
+        });
+
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_async_sync_cell_follow_tokio_watch() {
+        let (sender, receiver) = watch::channel(0);
+        let cell = AsyncSyncCell::new(-1);
+
+        sender.send_replace(4);
+        drop(sender);
+
+        block_on(cell.follow_tokio_watch(receiver));
+        assert_eq!(4, cell.blocking_get());
+    }
+}
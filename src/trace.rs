@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use tracing::{Span, trace, trace_span};
+
+/// The span of a borrow of a `SyncCell`, or `None` if no subscriber records it.
+/// The span is boxed so that borrows stay small.
+pub(crate) type BorrowSpan = Option<Box<Span>>;
+
+/// Creates the span of a borrow of a `SyncCell`.
+/// The span is closed when the borrow is released, so its duration is how long the borrow was
+/// held.
+///
+/// - `label` - The label of the borrowed cell, recorded as the `cell` field.
+/// - `mutable` - Whether the borrow is mutable.
+pub(crate) fn borrow_span(label: Option<&'static str>, mutable: bool) -> BorrowSpan {
+    let span = trace_span!("sync_cell_borrow", cell = label, mutable);
+
+    match span.is_disabled() {
+        true => None,
+        false => Some(Box::new(span)),
+    }
+}
+
+/// Records that a borrow acquired the lock of its cell as an event in the span of the borrow.
+///
+/// - `span` - The span of the borrow.
+/// - `wait` - How long the borrow waited for the lock.
+pub(crate) fn acquired(span: &BorrowSpan, wait: Duration) {
+    if let Some(span) = span {
+        trace!(parent: &**span, wait_nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX), "acquired");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex, atomic::{AtomicU64, Ordering}};
+
+    use tracing::{Event, Metadata, Subscriber, field::{Field, Visit}, span::{Attributes, Id, Record}, subscriber};
+
+    use crate::SyncCell;
+
+    /// A subscriber that records the spans and events it receives.
+    #[derive(Default)]
+    struct Recorder {
+        /// The id given to the next span.
+        next_id: AtomicU64,
+        /// A description of each span, event and closed span, in the order they were received.
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    /// Collects the fields of a span or event into a description.
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Fields(format!("open {}", span.metadata().name()));
+            span.record(&mut fields);
+            self.log.lock().unwrap().push(fields.0);
+
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(String::from("event"));
+            event.record(&mut fields);
+            self.log.lock().unwrap().push(fields.0);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+
+        fn try_close(&self, _: Id) -> bool {
+            self.log.lock().unwrap().push(String::from("close"));
+            true
+        }
+    }
+
+    #[test]
+    pub fn test_sync_cell_tracing_borrow() {
+        let recorder = Recorder::default();
+        let log = recorder.log.clone();
+
+        subscriber::with_default(recorder, || {
+            let cell = SyncCell::with_label(0, "counter");
+
+            *cell.borrow_mut() += 1;
+            assert_eq!(1, *cell.borrow());
+        });
+
+        let log = log.lock().unwrap();
+
+        assert_eq!(6, log.len());
+        assert_eq!("open sync_cell_borrow cell=\"counter\" mutable=true", log[0]);
+        assert!(log[1].starts_with("event message=acquired wait_nanos="));
+        assert_eq!("close", log[2]);
+        assert_eq!("open sync_cell_borrow cell=\"counter\" mutable=false", log[3]);
+        assert!(log[4].starts_with("event message=acquired wait_nanos="));
+        assert_eq!("close", log[5]);
+    }
+}
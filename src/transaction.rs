@@ -0,0 +1,139 @@
+use std::{mem::ManuallyDrop, ops::{Deref, DerefMut}, thread};
+
+use crate::{DefaultRawLock, RawSyncLock, SyncCell, SyncCellGuard};
+
+impl <T: Clone, L: RawSyncLock> SyncCell<T, L> {
+    /// Borrows a mutable reference to the data stored in this cell that is only kept if it is
+    /// committed.
+    /// The value is copied when the borrow is taken. If the returned guard is dropped without
+    /// calling `commit`, such as after an early return or a panic, the copy is restored so the
+    /// change is all or nothing. A panic that restores the value does not poison the lock.
+    ///
+    /// To change several cells all or nothing use `lock_all`, or `transaction` for
+    /// `TransactionalCell`s.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(vec![1]);
+    ///
+    /// let mut values = cell.borrow_mut_transactional();
+    /// values.push(2);
+    /// drop(values);
+    ///
+    /// assert_eq!(vec![1], cell.get());
+    ///
+    /// let mut values = cell.borrow_mut_transactional();
+    /// values.push(2);
+    /// values.commit();
+    ///
+    /// assert_eq!(vec![1, 2], cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow_mut_transactional(&self) -> TransactionGuard<'_, T, L> {
+        let data = self.borrow_mut();
+
+        TransactionGuard {
+            cell: self,
+            backup: Some(data.clone()),
+            data: ManuallyDrop::new(data),
+        }
+    }
+}
+
+/// A mutable borrow of the value in a `SyncCell` that is restored unless it is committed.
+pub struct TransactionGuard<'a, T, L: RawSyncLock = DefaultRawLock> {
+    /// The borrowed cell.
+    cell: &'a SyncCell<T, L>,
+    /// The value from before the borrow or `None` if the change was committed.
+    pub(crate) backup: Option<T>,
+    /// The lock guard over the value, which is always dropped by the `Drop` implementation.
+    data: ManuallyDrop<SyncCellGuard<'a, T, L>>,
+}
+
+impl <T, L: RawSyncLock> TransactionGuard<'_, T, L> {
+    /// Keeps the changes made through this guard and releases the lock.
+    pub fn commit(mut self) {
+        self.backup = None;
+    }
+
+    /// Discards the changes made through this guard and releases the lock.
+    pub fn abort(self) {}
+}
+
+impl <T, L: RawSyncLock> Deref for TransactionGuard<'_, T, L> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl <T, L: RawSyncLock> DerefMut for TransactionGuard<'_, T, L> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl <T, L: RawSyncLock> Drop for TransactionGuard<'_, T, L> {
+    fn drop(&mut self) {
+        let restored = match self.backup.take() {
+            Some(backup) => {
+                **self.data = backup;
+                true
+            },
+            None => false,
+        };
+
+        // SAFETY: The guard is not used again after it is dropped here.
+        unsafe { ManuallyDrop::drop(&mut self.data) }
+
+        // Dropping the guard during a panic poisons the lock, but the value has been restored so
+        // it is still consistent.
+        if restored && thread::panicking() {
+            self.cell.clear_poison();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_transaction() {
+        let cell = SyncCell::new(1);
+
+        let mut value = cell.borrow_mut_transactional();
+        *value = 2;
+        value.abort();
+        assert_eq!(1, cell.get());
+
+        let mut value = cell.borrow_mut_transactional();
+        *value = 3;
+        value.commit();
+        assert_eq!(3, cell.get());
+    }
+
+    #[test]
+    pub fn test_sync_cell_transaction_panic() {
+        let cell = Arc::new(SyncCell::new(1));
+
+        let result = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let mut value = cell.borrow_mut_transactional();
+                *value = 2;
+                panic!("Failed before committing");
+            }).join()
+        };
+
+        assert!(result.is_err());
+        assert_eq!(1, cell.get());
+    }
+}
@@ -0,0 +1,374 @@
+use std::any::Any;
+
+use crate::{SyncCell, SyncCellGuard};
+
+/// A value stored in a `TransactionalCell` along with the number of times it has been changed.
+struct Versioned<T> {
+    /// The number of times this value has been changed.
+    version: u64,
+    /// The stored value.
+    value: T,
+}
+
+/// A cell whose value can be read and written as part of a transaction spanning many cells.
+/// Reads and writes performed through a `Transaction` are buffered and only applied when the
+/// transaction commits. A transaction only commits if none of the cells it read have been changed
+/// since they were read. Otherwise the transaction is run again.
+///
+/// Transactions are run using the `transaction` function. For plain `SyncCell`s see `lock_all`.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{transaction, TransactionalCell};
+///
+/// let from = TransactionalCell::new(10);
+/// let to = TransactionalCell::new(0);
+///
+/// transaction(|tx| {
+///     let amount = tx.read(&from);
+///     tx.write(&from, 0);
+///     tx.modify(&to, |value| value + amount);
+/// });
+///
+/// assert_eq!(0, from.get());
+/// assert_eq!(10, to.get());
+/// ```
+pub struct TransactionalCell<T> {
+    /// The current value and version of this cell.
+    data: SyncCell<Versioned<T>>,
+}
+
+impl <T> TransactionalCell<T> {
+    /// Creates a new `TransactionalCell`.
+    ///
+    /// - `data` - The initial value of the `TransactionalCell`.
+    pub const fn new(data: T) -> Self {
+        Self {
+            data: SyncCell::new(Versioned {
+                version: 0,
+                value: data,
+            }),
+        }
+    }
+
+    /// Sets the value contained in this cell outside of a transaction.
+    /// Any running transaction that has read this cell will be retried.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn set(&self, value: T) {
+        let mut data = self.data.borrow_mut();
+
+        data.version += 1;
+        data.value = value;
+    }
+
+    /// Retrieves the inner value stored in this `TransactionalCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner().value
+    }
+}
+
+impl <T: Clone> TransactionalCell<T> {
+    /// Gets the value contained in this cell outside of a transaction.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.data.borrow().value.clone()
+    }
+}
+
+impl <T: Default> Default for TransactionalCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for TransactionalCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A type erased `TransactionalCell` that can be locked during a commit.
+trait TxCell: Sync {
+    /// Acquires exclusive access to this cell.
+    fn lock(&self) -> Box<dyn TxLocked + '_>;
+}
+
+/// A type erased exclusive lock over a `TransactionalCell`.
+trait TxLocked {
+    /// Gets the current version of the locked cell.
+    fn version(&self) -> u64;
+
+    /// Replaces the value of the locked cell and increments its version.
+    fn commit(&mut self, value: Box<dyn Any>);
+}
+
+impl <T: Any + Send + Sync> TxCell for TransactionalCell<T> {
+    fn lock(&self) -> Box<dyn TxLocked + '_> {
+        Box::new(self.data.borrow_mut())
+    }
+}
+
+impl <T: Any> TxLocked for SyncCellGuard<'_, Versioned<T>> {
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn commit(&mut self, value: Box<dyn Any>) {
+        match value.downcast::<T>() {
+            Ok(value) => {
+                self.version += 1;
+                self.value = *value;
+            },
+            Err(_) => unreachable!("Transaction value does not match the type of its cell."),
+        }
+    }
+}
+
+/// A cell that has been accessed during a transaction.
+struct TxEntry<'a> {
+    /// The cell that was accessed.
+    cell: &'a dyn TxCell,
+    /// The address of the cell used to identify it and to order locking.
+    address: usize,
+    /// The version of the cell when it was first read if it was read.
+    read_version: Option<u64>,
+    /// The value of the cell as seen by the transaction.
+    value: Box<dyn Any>,
+    /// Whether the value has been written by the transaction.
+    written: bool,
+}
+
+/// A set of buffered reads and writes across any number of `TransactionalCell`s.
+/// A `Transaction` is created by the `transaction` function.
+pub struct Transaction<'a> {
+    /// The cells accessed during this transaction.
+    entries: Vec<TxEntry<'a>>,
+}
+
+impl <'a> Transaction<'a> {
+    /// Finds the entry for a cell in this transaction.
+    fn entry<T: Any>(&mut self, cell: &'a TransactionalCell<T>) -> Option<&mut TxEntry<'a>> {
+        let address = cell as *const TransactionalCell<T> as usize;
+
+        self.entries.iter_mut()
+            .find(|entry| entry.address == address)
+    }
+
+    /// Reads the value of a cell as seen by this transaction.
+    /// Reading the same cell multiple times returns the same value unless it was written by this
+    /// transaction.
+    ///
+    /// - `cell` - The cell to read.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock of the cell becomes poisoned.
+    pub fn read<T: Any + Clone + Send + Sync>(&mut self, cell: &'a TransactionalCell<T>) -> T {
+        if let Some(entry) = self.entry(cell) {
+            return Self::downcast::<T>(entry).clone();
+        }
+
+        let data = cell.data.borrow();
+        let value = data.value.clone();
+
+        self.entries.push(TxEntry {
+            cell,
+            address: cell as *const TransactionalCell<T> as usize,
+            read_version: Some(data.version),
+            value: Box::new(value.clone()),
+            written: false,
+        });
+
+        value
+    }
+
+    /// Writes a value to a cell.
+    /// The value is only applied to the cell if the transaction commits.
+    ///
+    /// - `cell` - The cell to write.
+    /// - `value` - The new value of the cell.
+    pub fn write<T: Any + Send + Sync>(&mut self, cell: &'a TransactionalCell<T>, value: T) {
+        match self.entry(cell) {
+            Some(entry) => {
+                entry.value = Box::new(value);
+                entry.written = true;
+            },
+            None => self.entries.push(TxEntry {
+                cell,
+                address: cell as *const TransactionalCell<T> as usize,
+                read_version: None,
+                value: Box::new(value),
+                written: true,
+            }),
+        }
+    }
+
+    /// Reads the value of a cell and writes back the result of a function applied to it.
+    ///
+    /// - `cell` - The cell to modify.
+    /// - `f` - The function used to compute the new value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock of the cell becomes poisoned.
+    pub fn modify<T: Any + Clone + Send + Sync>(&mut self, cell: &'a TransactionalCell<T>,
+            f: impl FnOnce(T) -> T) {
+        let value = self.read(cell);
+        self.write(cell, f(value));
+    }
+
+    /// Attempts to apply all writes made during this transaction.
+    /// Returns `false` if any cell read by this transaction has been changed.
+    fn commit(mut self) -> bool {
+        self.entries.sort_by_key(|entry| entry.address);
+
+        let mut locks: Vec<_> = self.entries.iter()
+            .map(|entry| entry.cell.lock())
+            .collect();
+
+        let valid = self.entries.iter()
+            .zip(&locks)
+            .all(|(entry, lock)| match entry.read_version {
+                Some(version) => version == lock.version(),
+                None => true,
+            });
+
+        if !valid {
+            return false;
+        }
+
+        for (entry, lock) in self.entries.into_iter().zip(&mut locks) {
+            if entry.written {
+                lock.commit(entry.value);
+            }
+        }
+
+        true
+    }
+
+    /// Gets the value of an entry as a concrete type.
+    fn downcast<'b, T: Any>(entry: &'b TxEntry<'_>) -> &'b T {
+        match entry.value.downcast_ref() {
+            Some(value) => value,
+            None => unreachable!("Transaction value does not match the type of its cell."),
+        }
+    }
+}
+
+/// Runs a transaction over any number of `TransactionalCell`s.
+/// The function is run with a `Transaction` that buffers all reads and writes. Once the function
+/// returns, all writes are applied atomically as long as no cell read by the transaction has been
+/// changed in the meantime. If a conflict is detected the function is run again with a new
+/// transaction.
+///
+/// Because the function may be run multiple times it should not have side effects other than
+/// those made through the transaction.
+///
+/// To change plain `SyncCell`s together use `lock_all` instead, which locks the cells rather than
+/// retrying. A single `SyncCell` can be changed all or nothing with
+/// `SyncCell::borrow_mut_transactional`.
+///
+/// - `f` - The function to run as a transaction.
+///
+/// # Panicking
+/// This function will panic if the lock of any accessed cell becomes poisoned.
+pub fn transaction<'a, R>(mut f: impl FnMut(&mut Transaction<'a>) -> R) -> R {
+    loop {
+        let mut tx = Transaction {
+            entries: Vec::new(),
+        };
+
+        let result = f(&mut tx);
+
+        if tx.commit() {
+            return result;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::{transaction, TransactionalCell};
+
+    #[test]
+    pub fn test_transactional_cell_commit() {
+        let a = TransactionalCell::new(1);
+        let b = TransactionalCell::new(2);
+
+        transaction(|tx| {
+            let value = tx.read(&a);
+            tx.write(&b, value + 10);
+        });
+
+        assert_eq!(1, a.get());
+        assert_eq!(11, b.get());
+    }
+
+    #[test]
+    pub fn test_transactional_cell_read_own_write() {
+        let a = TransactionalCell::new(1);
+
+        let value = transaction(|tx| {
+            tx.write(&a, 5);
+            tx.read(&a)
+        });
+
+        assert_eq!(5, value);
+    }
+
+    #[test]
+    pub fn test_transactional_cell_retry_on_conflict() {
+        let a = TransactionalCell::new(1);
+        let mut attempts = 0;
+
+        transaction(|tx| {
+            attempts += 1;
+            let value = tx.read(&a);
+
+            if attempts == 1 {
+                a.set(100);
+            }
+
+            tx.write(&a, value + 1);
+        });
+
+        assert_eq!(2, attempts);
+        assert_eq!(101, a.get());
+    }
+
+    #[test]
+    pub fn test_transactional_cell_threads() {
+        let a = Arc::new(TransactionalCell::new(50));
+        let b = Arc::new(TransactionalCell::new(50));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let a = a.clone();
+            let b = b.clone();
+
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    transaction(|tx| {
+                        tx.modify(&*a, |value| value - 1);
+                        tx.modify(&*b, |value| value + 1);
+                    });
+                }
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(-350, a.get());
+        assert_eq!(450, b.get());
+    }
+}
@@ -0,0 +1,220 @@
+use std::{cell::UnsafeCell, sync::{Arc, atomic::{AtomicU8, Ordering}}};
+
+/// The bits of the shared state holding the index of the shared buffer.
+const INDEX_MASK: u8 = 0b011;
+/// The bit of the shared state set when the shared buffer contains a value not yet read.
+const DIRTY_BIT: u8 = 0b100;
+
+/// The storage shared between a `TripleBufferWriter` and a `TripleBufferReader`.
+///
+/// A triple buffer allows a single producer and a single consumer to exchange values without
+/// either side ever blocking. The producer always has a free buffer to write into while the
+/// consumer always reads the most recently published buffer. Exchanging buffers is done with a
+/// single atomic operation.
+///
+/// This is a common pattern in audio and game engines where a producer generates frames at a
+/// different rate than they are consumed.
+///
+/// # Usage
+/// ```
+/// use sync_cell::TripleBufferCell;
+///
+/// let (mut writer, mut reader) = TripleBufferCell::channel(0);
+///
+/// writer.write(1);
+/// assert_eq!(1, *reader.read());
+///
+/// // Values that are not published are not visible to the reader.
+/// *writer.input_mut() = 2;
+/// assert_eq!(1, *reader.read());
+///
+/// writer.publish();
+/// assert_eq!(2, *reader.read());
+/// ```
+pub struct TripleBufferCell<T> {
+    /// The three buffers of this cell.
+    buffers: [UnsafeCell<T>; 3],
+    /// The index of the buffer owned by neither side along with the dirty bit.
+    shared: AtomicU8,
+}
+
+// SAFETY: Each buffer is only ever accessed by the side that currently owns its index. Ownership
+// of indices is only transferred through the atomic shared state.
+unsafe impl <T: Send + Sync> Sync for TripleBufferCell<T> {}
+
+impl <T: Clone> TripleBufferCell<T> {
+    /// Creates a new triple buffer returning the writing and reading halves.
+    ///
+    /// - `data` - The initial value of every buffer.
+    pub fn channel(data: T) -> (TripleBufferWriter<T>, TripleBufferReader<T>) {
+        let cell = Arc::new(Self {
+            buffers: [
+                UnsafeCell::new(data.clone()),
+                UnsafeCell::new(data.clone()),
+                UnsafeCell::new(data),
+            ],
+            shared: AtomicU8::new(1),
+        });
+
+        (
+            TripleBufferWriter {
+                cell: cell.clone(),
+                index: 0,
+            },
+            TripleBufferReader {
+                cell,
+                index: 2,
+            },
+        )
+    }
+}
+
+/// The producing half of a `TripleBufferCell`.
+pub struct TripleBufferWriter<T> {
+    /// The shared buffers.
+    cell: Arc<TripleBufferCell<T>>,
+    /// The index of the buffer owned by this writer.
+    index: u8,
+}
+
+impl <T> TripleBufferWriter<T> {
+    /// Borrows a mutable reference to the input buffer.
+    /// Changes made to the input buffer are made visible to the reader by calling `publish`.
+    ///
+    /// The input buffer contains whichever value was exchanged during the last publish which may
+    /// not be the most recently written value.
+    pub fn input_mut(&mut self) -> &mut T {
+        // SAFETY: The buffer at `index` is owned exclusively by this writer.
+        unsafe { &mut *self.cell.buffers[self.index as usize].get() }
+    }
+
+    /// Makes the input buffer available to the reader and takes ownership of a free buffer.
+    /// This method never blocks.
+    pub fn publish(&mut self) {
+        let previous = self.cell.shared.swap(self.index | DIRTY_BIT, Ordering::AcqRel);
+
+        self.index = previous & INDEX_MASK;
+    }
+
+    /// Writes a value to the input buffer and publishes it.
+    ///
+    /// - `value` - The new value to make available to the reader.
+    pub fn write(&mut self, value: T) {
+        *self.input_mut() = value;
+        self.publish();
+    }
+
+    /// Checks if the last published value has been read by the reader.
+    pub fn is_consumed(&self) -> bool {
+        self.cell.shared.load(Ordering::Acquire) & DIRTY_BIT == 0
+    }
+}
+
+/// The consuming half of a `TripleBufferCell`.
+pub struct TripleBufferReader<T> {
+    /// The shared buffers.
+    cell: Arc<TripleBufferCell<T>>,
+    /// The index of the buffer owned by this reader.
+    index: u8,
+}
+
+impl <T> TripleBufferReader<T> {
+    /// Checks if a value has been published that has not yet been read.
+    pub fn has_update(&self) -> bool {
+        self.cell.shared.load(Ordering::Acquire) & DIRTY_BIT != 0
+    }
+
+    /// Takes ownership of the most recently published buffer if there is one.
+    /// This method never blocks.
+    ///
+    /// Returns `true` if a new value was received.
+    pub fn update(&mut self) -> bool {
+        if !self.has_update() {
+            return false;
+        }
+
+        let previous = self.cell.shared.swap(self.index, Ordering::AcqRel);
+        self.index = previous & INDEX_MASK;
+
+        true
+    }
+
+    /// Borrows a immutable reference to the output buffer without checking for new values.
+    pub fn output(&self) -> &T {
+        // SAFETY: The buffer at `index` is owned exclusively by this reader.
+        unsafe { &*self.cell.buffers[self.index as usize].get() }
+    }
+
+    /// Receives the most recently published value and returns a reference to it.
+    pub fn read(&mut self) -> &T {
+        self.update();
+        self.output()
+    }
+}
+
+impl <T: Clone> TripleBufferReader<T> {
+    /// Receives the most recently published value and returns a copy of it.
+    pub fn get(&mut self) -> T {
+        self.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::TripleBufferCell;
+
+    #[test]
+    pub fn test_triple_buffer_cell_initial() {
+        let (_writer, mut reader) = TripleBufferCell::channel(1);
+
+        assert!(!reader.has_update());
+        assert_eq!(1, reader.get());
+    }
+
+    #[test]
+    pub fn test_triple_buffer_cell_write_read() {
+        let (mut writer, mut reader) = TripleBufferCell::channel(1);
+
+        writer.write(2);
+
+        assert!(!writer.is_consumed());
+        assert!(reader.has_update());
+        assert_eq!(2, reader.get());
+        assert!(writer.is_consumed());
+        assert!(!reader.update());
+    }
+
+    #[test]
+    pub fn test_triple_buffer_cell_latest_wins() {
+        let (mut writer, mut reader) = TripleBufferCell::channel(1);
+
+        writer.write(2);
+        writer.write(3);
+        writer.write(4);
+
+        assert_eq!(4, reader.get());
+        assert_eq!(4, *reader.output());
+    }
+
+    #[test]
+    pub fn test_triple_buffer_cell_threads() {
+        let (mut writer, mut reader) = TripleBufferCell::channel(0);
+
+        let producer = thread::spawn(move || {
+            for i in 1..=1000 {
+                writer.write(i);
+            }
+        });
+
+        let mut last = 0;
+        while last < 1000 {
+            let value = reader.get();
+            assert!(value >= last);
+            last = value;
+        }
+
+        producer.join().unwrap();
+    }
+}
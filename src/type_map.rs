@@ -0,0 +1,198 @@
+use std::{any::{Any, TypeId}, collections::HashMap};
+
+use crate::SyncCell;
+
+/// A cell holding at most one value of each type.
+/// Values are inserted and retrieved by their type, which makes this suitable as a registry of
+/// extensions shared between threads.
+///
+/// # Usage
+/// ```
+/// use sync_cell::TypeMapCell;
+///
+/// struct Config {
+///     verbose: bool,
+/// }
+///
+/// let extensions = TypeMapCell::new();
+///
+/// extensions.insert(Config { verbose: true });
+/// extensions.insert(42u32);
+///
+/// assert_eq!(Some(true), extensions.with(|config: &Config| config.verbose));
+/// assert_eq!(Some(42), extensions.get::<u32>());
+/// ```
+#[derive(Debug, Default)]
+pub struct TypeMapCell {
+    /// The values keyed by their type.
+    map: SyncCell<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl TypeMapCell {
+    /// Creates a new empty `TypeMapCell`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value replacing any value of the same type.
+    /// The previous value of the type is returned.
+    ///
+    /// - `value` - The value to insert.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn insert<T: Any + Send + Sync>(&self, value: T) -> Option<T> {
+        self.map.borrow_mut()
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .map(Self::downcast)
+    }
+
+    /// Removes the value of a type.
+    /// The removed value is returned.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn remove<T: Any + Send + Sync>(&self) -> Option<T> {
+        self.map.borrow_mut()
+            .remove(&TypeId::of::<T>())
+            .map(Self::downcast)
+    }
+
+    /// Checks if a value of a type is contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn contains<T: Any>(&self) -> bool {
+        self.map.borrow().contains_key(&TypeId::of::<T>())
+    }
+
+    /// Gets the number of values contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.map.borrow().len()
+    }
+
+    /// Checks if this cell contains no values.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.map.borrow().is_empty()
+    }
+
+    /// Calls a function with a reference to the value of a type.
+    /// Returns `None` if no value of the type is contained in this cell.
+    ///
+    /// - `f` - The function to call.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with<T: Any, R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.map.borrow()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+            .map(f)
+    }
+
+    /// Calls a function with a mutable reference to the value of a type.
+    /// Returns `None` if no value of the type is contained in this cell.
+    ///
+    /// - `f` - The function to call.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with_mut<T: Any, R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.map.borrow_mut()
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut())
+            .map(f)
+    }
+
+    /// Calls a function with a mutable reference to the value of a type, inserting a value
+    /// created by `init` first if no value of the type is contained in this cell.
+    ///
+    /// - `init` - The function creating the value if it is missing.
+    /// - `f` - The function to call.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with_or_insert<T: Any + Send + Sync, R>(&self, init: impl FnOnce() -> T,
+            f: impl FnOnce(&mut T) -> R) -> R {
+        let mut map = self.map.borrow_mut();
+        let value = map.entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(init()));
+
+        match value.downcast_mut() {
+            Some(value) => f(value),
+            None => unreachable!("Value was stored under the wrong type."),
+        }
+    }
+
+    /// Gets the value of a type.
+    /// Returns `None` if no value of the type is contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get<T: Any + Clone>(&self) -> Option<T> {
+        self.with(T::clone)
+    }
+
+    /// Removes all values from this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn clear(&self) {
+        self.map.borrow_mut().clear()
+    }
+
+    /// Converts a value stored under the type `T` back into a `T`.
+    fn downcast<T: Any>(value: Box<dyn Any + Send + Sync>) -> T {
+        match value.downcast() {
+            Ok(value) => *value,
+            Err(_) => unreachable!("Value was stored under the wrong type."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TypeMapCell;
+
+    #[test]
+    pub fn test_type_map_cell_insert() {
+        let cell = TypeMapCell::new();
+
+        assert_eq!(None, cell.insert(1u8));
+        assert_eq!(Some(1u8), cell.insert(2u8));
+        assert_eq!(None, cell.insert(3u16));
+
+        assert_eq!(2, cell.len());
+        assert_eq!(Some(2u8), cell.get());
+        assert_eq!(Some(3u16), cell.get());
+        assert_eq!(None, cell.get::<u32>());
+    }
+
+    #[test]
+    pub fn test_type_map_cell_with() {
+        let cell = TypeMapCell::new();
+
+        assert_eq!(None, cell.with_mut(|value: &mut Vec<i32>| value.push(1)));
+        assert_eq!(1, cell.with_or_insert(Vec::new, |value: &mut Vec<i32>| {
+            value.push(1);
+            value.len()
+        }));
+        assert_eq!(Some(vec![1]), cell.get::<Vec<i32>>());
+    }
+
+    #[test]
+    pub fn test_type_map_cell_remove() {
+        let cell = TypeMapCell::new();
+        cell.insert(String::from("value"));
+
+        assert!(cell.contains::<String>());
+        assert_eq!(Some(String::from("value")), cell.remove::<String>());
+        assert!(cell.is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::VecDeque;
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// The undo and redo history of an `UndoRedoCell`.
+struct History<T> {
+    /// Previous values of the cell with the most recent value at the back.
+    undo: VecDeque<T>,
+    /// Values that have been undone with the most recently undone value at the back.
+    redo: Vec<T>,
+    /// The maximum number of values kept in the undo stack.
+    depth: usize,
+}
+
+/// A cell that records previous values so that changes can be undone and redone.
+/// Every call to `set` pushes the value being replaced onto an undo stack and clears the redo
+/// stack. Calling `undo` restores the most recent previous value while `redo` reapplies the most
+/// recently undone value.
+///
+/// Changes made through `borrow_mut` are not recorded automatically. A call to `checkpoint` can be
+/// used to record the current value before mutating it in place.
+///
+/// # Usage
+/// ```
+/// use sync_cell::UndoRedoCell;
+///
+/// let cell = UndoRedoCell::new(0);
+///
+/// cell.set(1);
+/// cell.set(2);
+///
+/// assert!(cell.undo());
+/// assert_eq!(1, cell.get());
+///
+/// assert!(cell.redo());
+/// assert_eq!(2, cell.get());
+/// ```
+pub struct UndoRedoCell<T> {
+    /// The undo and redo history of this cell.
+    /// This lock is always acquired before `current_value` when both are needed.
+    history: SyncCell<History<T>>,
+    /// The current value of this cell.
+    current_value: SyncCell<T>,
+}
+
+impl <T> UndoRedoCell<T> {
+    /// Creates a new `UndoRedoCell` with an unlimited history depth.
+    ///
+    /// - `data` - The initial value of the `UndoRedoCell`.
+    pub const fn new(data: T) -> Self {
+        Self::with_depth(data, usize::MAX)
+    }
+
+    /// Creates a new `UndoRedoCell` that keeps at most `depth` previous values.
+    /// Once the limit is reached the oldest value is discarded.
+    ///
+    /// - `data` - The initial value of the `UndoRedoCell`.
+    /// - `depth` - The maximum number of values that can be undone.
+    pub const fn with_depth(data: T, depth: usize) -> Self {
+        Self {
+            history: SyncCell::new(History {
+                undo: VecDeque::new(),
+                redo: Vec::new(),
+                depth,
+            }),
+            current_value: SyncCell::new(data),
+        }
+    }
+
+    /// Sets the value contained in this cell.
+    /// The previous value is recorded so that it can be restored by `undo`. Any values that could
+    /// have been restored by `redo` are discarded.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, value: T) {
+        let mut history = self.history.borrow_mut();
+        let old = self.current_value.replace(value);
+
+        history.push_undo(old);
+        history.redo.clear();
+    }
+
+    /// Restores the most recent previous value of this cell.
+    /// The replaced value can be restored by calling `redo`.
+    ///
+    /// Returns `true` if a value was restored.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn undo(&self) -> bool {
+        let mut history = self.history.borrow_mut();
+
+        match history.undo.pop_back() {
+            Some(previous) => {
+                let old = self.current_value.replace(previous);
+                history.redo.push(old);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Reapplies the most recently undone value of this cell.
+    ///
+    /// Returns `true` if a value was reapplied.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn redo(&self) -> bool {
+        let mut history = self.history.borrow_mut();
+
+        match history.redo.pop() {
+            Some(next) => {
+                let old = self.current_value.replace(next);
+                history.push_undo(old);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Checks if there is a previous value that can be restored by calling `undo`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn can_undo(&self) -> bool {
+        !self.history.borrow().undo.is_empty()
+    }
+
+    /// Checks if there is an undone value that can be reapplied by calling `redo`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn can_redo(&self) -> bool {
+        !self.history.borrow().redo.is_empty()
+    }
+
+    /// Discards all recorded undo and redo values.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn clear_history(&self) {
+        let mut history = self.history.borrow_mut();
+
+        history.undo.clear();
+        history.redo.clear();
+    }
+
+    /// Retrieves the current value stored in this `UndoRedoCell` discarding any history.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_inner(self) -> T {
+        self.current_value.into_inner()
+    }
+
+    /// Borrows a immutable reference to the current value stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.current_value.borrow()
+    }
+
+    /// Borrows a mutable reference to the current value stored in this cell.
+    /// Changes made through this reference are not recorded. Call `checkpoint` beforehand to make
+    /// them undoable.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn borrow_mut(&self) -> SyncCellGuard<'_, T> {
+        self.current_value.borrow_mut()
+    }
+}
+
+impl <T: Clone> UndoRedoCell<T> {
+    /// Gets the current value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get(&self) -> T {
+        self.current_value.get()
+    }
+
+    /// Records a copy of the current value so that it can be restored by `undo`.
+    /// This is useful before making changes to the value through `borrow_mut`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn checkpoint(&self) {
+        let mut history = self.history.borrow_mut();
+
+        history.push_undo(self.current_value.get());
+        history.redo.clear();
+    }
+}
+
+impl <T: Default> Default for UndoRedoCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl <T> From<T> for UndoRedoCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl <T> History<T> {
+    /// Pushes a value onto the undo stack discarding the oldest value if the depth is exceeded.
+    fn push_undo(&mut self, value: T) {
+        if self.depth == 0 {
+            return;
+        }
+
+        if self.undo.len() >= self.depth {
+            self.undo.pop_front();
+        }
+
+        self.undo.push_back(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UndoRedoCell;
+
+    #[test]
+    pub fn test_undo_redo_cell_undo() {
+        let cell = UndoRedoCell::new(1);
+
+        cell.set(2);
+        cell.set(3);
+
+        assert!(cell.undo());
+        assert_eq!(2, cell.get());
+        assert!(cell.undo());
+        assert_eq!(1, cell.get());
+        assert!(!cell.undo());
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_undo_redo_cell_redo() {
+        let cell = UndoRedoCell::new(1);
+
+        cell.set(2);
+        cell.undo();
+
+        assert!(cell.redo());
+        assert_eq!(2, cell.get());
+        assert!(!cell.redo());
+    }
+
+    #[test]
+    pub fn test_undo_redo_cell_set_clears_redo() {
+        let cell = UndoRedoCell::new(1);
+
+        cell.set(2);
+        cell.undo();
+        cell.set(3);
+
+        assert!(!cell.can_redo());
+        assert!(cell.undo());
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_undo_redo_cell_depth() {
+        let cell = UndoRedoCell::with_depth(1, 2);
+
+        cell.set(2);
+        cell.set(3);
+        cell.set(4);
+
+        assert!(cell.undo());
+        assert!(cell.undo());
+        assert!(!cell.undo());
+        assert_eq!(2, cell.get());
+    }
+
+    #[test]
+    pub fn test_undo_redo_cell_checkpoint() {
+        let cell = UndoRedoCell::new(1);
+
+        cell.checkpoint();
+        *cell.borrow_mut() = 5;
+
+        assert!(cell.undo());
+        assert_eq!(1, cell.get());
+    }
+}
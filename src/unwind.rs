@@ -0,0 +1,144 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{RawSyncLock, SyncCell};
+
+impl <T: ?Sized, L: RawSyncLock> SyncCell<T, L> {
+    /// Calls a function with an immutable reference to the value of this cell.
+    /// This does not poison the lock if the function panics.
+    ///
+    /// - `f` - The function to call with the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.borrow())
+    }
+
+    /// Calls a function with a mutable reference to the value of this cell.
+    /// If the function panics the lock is poisoned in the same way as a panic while holding
+    /// `borrow_mut`.
+    ///
+    /// - `f` - The function to call with the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.borrow_mut())
+    }
+
+    /// Calls a function with a mutable reference to the value of this cell without poisoning the
+    /// lock if the function panics.
+    /// The panic is caught, the lock is released and the panic is then resumed, so other threads
+    /// can keep using the cell. The value is kept as the function left it, so this should only be
+    /// used when a partial change still leaves the value consistent.
+    ///
+    /// - `f` - The function to call with the value.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or resume the panic of the function.
+    pub fn with_mut_unpoisoned<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let result = {
+            let mut data = self.borrow_mut();
+
+            panic::catch_unwind(AssertUnwindSafe(|| f(&mut data)))
+        };
+
+        match result {
+            Ok(result) => result,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+impl <T: Clone, L: RawSyncLock> SyncCell<T, L> {
+    /// Calls a function with a mutable reference to the value of this cell, restoring the
+    /// previous value and leaving the lock unpoisoned if the function panics.
+    /// The panic is resumed once the value has been restored and the lock released, so a
+    /// panicking mutation leaves the cell exactly as it was for every other thread.
+    ///
+    /// - `f` - The function to call with the value.
+    ///
+    /// # Usage
+    /// ```
+    /// use std::panic;
+    /// use sync_cell::SyncCell;
+    ///
+    /// let cell = SyncCell::new(vec![1, 2]);
+    ///
+    /// let result = panic::catch_unwind(|| cell.with_mut_rollback(|values| {
+    ///     values.push(3);
+    ///     panic!("Failed halfway through");
+    /// }));
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(vec![1, 2], cell.get());
+    /// ```
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or resume the panic of the function.
+    pub fn with_mut_rollback<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let result = {
+            let mut data = self.borrow_mut();
+            let backup = data.clone();
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(&mut data)));
+
+            if result.is_err() {
+                *data = backup;
+            }
+
+            result
+        };
+
+        match result {
+            Ok(result) => result,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{panic, sync::Arc, thread};
+
+    use crate::SyncCell;
+
+    #[test]
+    pub fn test_sync_cell_with() {
+        let cell = SyncCell::new(1);
+
+        cell.with_mut(|value| *value += 1);
+
+        assert_eq!(4, cell.with(|value| value * 2));
+    }
+
+    #[test]
+    pub fn test_sync_cell_with_mut_unpoisoned() {
+        let cell = Arc::new(SyncCell::new(0));
+
+        let result = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.with_mut_unpoisoned(|value| {
+                *value = 1;
+                panic!("Failed after a change");
+            })).join()
+        };
+
+        assert!(result.is_err());
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_sync_cell_with_mut_rollback() {
+        let cell = SyncCell::new(String::from("a"));
+
+        let result = panic::catch_unwind(|| cell.with_mut_rollback(|value| {
+            value.push('b');
+            panic!("Failed after a change");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!("a", cell.get());
+        assert_eq!(2, cell.with_mut_rollback(|value| { value.push('c'); value.len() }));
+    }
+}
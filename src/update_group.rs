@@ -0,0 +1,194 @@
+use std::sync::{Arc, Weak};
+
+use crate::{HeldSyncCell, Staged, SyncCell, SyncCellGuard};
+
+/// A type-erased `HeldSyncCell` that has been registered in an `UpdateGroup`.
+trait GroupMember: Send + Sync {
+    /// Locks the incoming and the current value of this cell.
+    fn lock(&self) -> Box<dyn LockedMember + '_>;
+}
+
+/// A `HeldSyncCell` whose incoming and current values are locked.
+trait LockedMember {
+    /// Applies the incoming value if one is available.
+    /// Returns `true` if a value was applied.
+    fn apply(&mut self) -> bool;
+}
+
+/// The locks held on a `HeldSyncCell` while a group update is applied.
+struct LockedCell<'a, T> {
+    /// The cell being updated.
+    cell: &'a HeldSyncCell<T>,
+    /// The lock guard over the incoming state.
+    staged: SyncCellGuard<'a, Staged<T>>,
+    /// The lock guard over the current value.
+    current: SyncCellGuard<'a, T>,
+}
+
+impl <T: Send + Sync> GroupMember for HeldSyncCell<T> {
+    fn lock(&self) -> Box<dyn LockedMember + '_> {
+        let (staged, current) = self.lock_update();
+
+        Box::new(LockedCell {
+            cell: self,
+            staged,
+            current,
+        })
+    }
+}
+
+impl <T> LockedMember for LockedCell<'_, T> {
+    fn apply(&mut self) -> bool {
+        let applied = self.staged.has_update();
+
+        self.cell.apply_staged(&mut self.staged, &mut self.current);
+
+        applied
+    }
+}
+
+/// A group of `HeldSyncCell`s that are updated together.
+/// Calling `update_all` locks every cell in the group before applying any of the incoming values,
+/// so no thread can observe some cells of the group updated and others not.
+///
+/// Cells are held weakly and are removed from the group once they are dropped.
+///
+/// # Usage
+/// ```
+/// use std::sync::Arc;
+/// use sync_cell::{HeldSyncCell, UpdateGroup};
+///
+/// let position = Arc::new(HeldSyncCell::new((0, 0)));
+/// let velocity = Arc::new(HeldSyncCell::new((0, 0)));
+///
+/// let group = UpdateGroup::new();
+/// group.register(&position);
+/// group.register(&velocity);
+///
+/// position.set((1, 1));
+/// velocity.set((2, 2));
+///
+/// assert_eq!(2, group.update_all());
+/// assert_eq!((1, 1), position.get());
+/// assert_eq!((2, 2), velocity.get());
+/// ```
+#[derive(Default)]
+pub struct UpdateGroup {
+    /// The cells in this group.
+    members: SyncCell<Vec<Weak<dyn GroupMember>>>,
+}
+
+impl UpdateGroup {
+    /// Creates a new empty `UpdateGroup`.
+    pub const fn new() -> Self {
+        Self {
+            members: SyncCell::new(Vec::new()),
+        }
+    }
+
+    /// Adds a cell to this group.
+    ///
+    /// - `cell` - The cell to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn register<T: Send + Sync + 'static>(&self, cell: &Arc<HeldSyncCell<T>>) {
+        let cell: Arc<dyn GroupMember> = cell.clone();
+
+        self.members.borrow_mut().push(Arc::downgrade(&cell));
+    }
+
+    /// Gets the number of cells in this group that have not been dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        let mut members = self.members.borrow_mut();
+        members.retain(|member| member.strong_count() > 0);
+        members.len()
+    }
+
+    /// Checks if every cell in this group has been dropped.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Updates every cell in this group at once.
+    /// All cells are locked in a consistent order before any incoming value is applied. Returns
+    /// the number of cells that had an incoming value applied.
+    ///
+    /// Listeners of the cells are called while every cell in the group is locked, so they must
+    /// not access any cell in the group.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn update_all(&self) -> usize {
+        let mut members: Vec<_> = {
+            let mut members = self.members.borrow_mut();
+            members.retain(|member| member.strong_count() > 0);
+            members.iter().filter_map(Weak::upgrade).collect()
+        };
+
+        // Locking in address order prevents deadlocks between groups sharing cells.
+        members.sort_by_key(|member| Arc::as_ptr(member) as *const () as usize);
+        members.dedup_by_key(|member| Arc::as_ptr(member) as *const () as usize);
+
+        let mut locked: Vec<_> = members.iter()
+            .map(|member| member.lock())
+            .collect();
+
+        locked.iter_mut()
+            .map(|member| member.apply())
+            .filter(|applied| *applied)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{HeldSyncCell, UpdateGroup};
+
+    #[test]
+    pub fn test_update_group_update_all() {
+        let first = Arc::new(HeldSyncCell::new(1));
+        let second = Arc::new(HeldSyncCell::new(String::from("a")));
+        let group = UpdateGroup::new();
+
+        group.register(&first);
+        group.register(&second);
+
+        first.set(2);
+
+        assert_eq!(1, group.update_all());
+        assert_eq!(2, first.get());
+
+        second.set(String::from("b"));
+        first.set(3);
+
+        assert_eq!(2, group.update_all());
+        assert_eq!("b", second.get());
+        assert_eq!(0, group.update_all());
+    }
+
+    #[test]
+    pub fn test_update_group_dropped_cells() {
+        let cell = Arc::new(HeldSyncCell::new(1));
+        let group = UpdateGroup::new();
+
+        group.register(&cell);
+        group.register(&cell);
+
+        cell.set(2);
+
+        assert_eq!(1, group.update_all());
+
+        drop(cell);
+
+        assert!(group.is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use std::{mem::replace, ops::{Deref, DerefMut}, thread};
+
+use crate::{SyncCell, SyncCellGuard, SyncCellRef};
+
+/// A function checking whether a value upholds an invariant.
+type ValidatorFn<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// What a `ValidatedCell` does when a write would violate its invariant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ValidationPolicy {
+    /// The write is rejected and the cell keeps its previous value.
+    #[default]
+    Reject,
+    /// The writing thread panics.
+    Panic,
+}
+
+/// A cell that checks an invariant every time its value is written.
+/// Invalid writes are either rejected or cause a panic depending on the `ValidationPolicy` of the
+/// cell. This catches corruption of shared state at the point where it happens rather than where
+/// it is later observed.
+///
+/// # Usage
+/// ```
+/// use sync_cell::ValidatedCell;
+///
+/// let cell = ValidatedCell::new(1, |value: &i32| *value > 0);
+///
+/// assert_eq!(Ok(()), cell.set(2));
+/// assert_eq!(Err(-1), cell.set(-1));
+/// assert_eq!(2, cell.get());
+/// ```
+pub struct ValidatedCell<T> {
+    /// The value of this cell.
+    data: SyncCell<T>,
+    /// The function checking the invariant.
+    validator: ValidatorFn<T>,
+    /// What happens when a write violates the invariant.
+    policy: ValidationPolicy,
+}
+
+impl <T> ValidatedCell<T> {
+    /// Creates a new `ValidatedCell` that rejects invalid writes.
+    /// Use `try_new` to handle an invalid initial value without panicking.
+    ///
+    /// - `data` - The initial value of the `ValidatedCell`.
+    /// - `validator` - A function returning `true` if a value upholds the invariant.
+    ///
+    /// # Panicking
+    /// This method will panic if the initial value is invalid.
+    pub fn new(data: T, validator: impl Fn(&T) -> bool + Send + Sync + 'static) -> Self {
+        Self::with_policy(data, validator, ValidationPolicy::Reject)
+    }
+
+    /// Creates a new `ValidatedCell` with a given policy for invalid writes.
+    /// Use `try_with_policy` to handle an invalid initial value without panicking.
+    ///
+    /// - `data` - The initial value of the `ValidatedCell`.
+    /// - `validator` - A function returning `true` if a value upholds the invariant.
+    /// - `policy` - What happens when a write violates the invariant.
+    ///
+    /// # Panicking
+    /// This method will panic if the initial value is invalid.
+    pub fn with_policy(data: T, validator: impl Fn(&T) -> bool + Send + Sync + 'static,
+            policy: ValidationPolicy) -> Self {
+        match Self::try_with_policy(data, validator, policy) {
+            Ok(cell) => cell,
+            Err(_) => panic!("Initial cell value is invalid."),
+        }
+    }
+
+    /// Creates a new `ValidatedCell` that rejects invalid writes if the initial value is valid.
+    /// An invalid initial value is returned as an error.
+    ///
+    /// - `data` - The initial value of the `ValidatedCell`.
+    /// - `validator` - A function returning `true` if a value upholds the invariant.
+    ///
+    /// # Usage
+    /// ```
+    /// use sync_cell::ValidatedCell;
+    ///
+    /// let cell = ValidatedCell::try_new(-1, |value: &i32| *value > 0);
+    ///
+    /// assert_eq!(Some(-1), cell.err());
+    /// ```
+    pub fn try_new(data: T, validator: impl Fn(&T) -> bool + Send + Sync + 'static) -> Result<Self, T> {
+        Self::try_with_policy(data, validator, ValidationPolicy::Reject)
+    }
+
+    /// Creates a new `ValidatedCell` with a given policy for invalid writes if the initial value
+    /// is valid.
+    /// The initial value is checked according to the policy, so an invalid initial value is
+    /// returned as an error under `ValidationPolicy::Reject`.
+    ///
+    /// - `data` - The initial value of the `ValidatedCell`.
+    /// - `validator` - A function returning `true` if a value upholds the invariant.
+    /// - `policy` - What happens when a write violates the invariant.
+    ///
+    /// # Panicking
+    /// This method will panic if the initial value is invalid and the policy is
+    /// `ValidationPolicy::Panic`.
+    pub fn try_with_policy(data: T, validator: impl Fn(&T) -> bool + Send + Sync + 'static,
+            policy: ValidationPolicy) -> Result<Self, T> {
+        if !validator(&data) {
+            return match policy {
+                ValidationPolicy::Reject => Err(data),
+                ValidationPolicy::Panic => panic!("Initial cell value is invalid."),
+            };
+        }
+
+        Ok(Self {
+            data: SyncCell::new(data),
+            validator: Box::new(validator),
+            policy,
+        })
+    }
+
+    /// Gets the policy used by this cell for invalid writes.
+    pub fn policy(&self) -> ValidationPolicy {
+        self.policy
+    }
+
+    /// Checks if a value would be accepted by this cell.
+    ///
+    /// - `value` - The value to check.
+    pub fn is_valid(&self, value: &T) -> bool {
+        (self.validator)(value)
+    }
+
+    /// Sets the value contained in this cell.
+    /// If the value is invalid it is returned as an error.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or if the value is invalid and the
+    /// policy is `ValidationPolicy::Panic`.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.replace(value).map(drop)
+    }
+
+    /// Replaces the value contained in this cell.
+    /// The previous value is returned. If the value is invalid it is returned as an error.
+    ///
+    /// - `value` - The new value of the cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned or if the value is invalid and the
+    /// policy is `ValidationPolicy::Panic`.
+    pub fn replace(&self, value: T) -> Result<T, T> {
+        self.check(value)
+            .map(|value| replace(&mut *self.data.borrow_mut(), value))
+    }
+
+    /// Borrows a immutable reference to the data stored in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn borrow(&self) -> SyncCellRef<'_, T> {
+        self.data.borrow()
+    }
+
+    /// Retrieves the inner value stored in this `ValidatedCell`.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Validates a value and applies the policy of this cell if it is invalid.
+    fn check(&self, value: T) -> Result<T, T> {
+        if self.is_valid(&value) {
+            Ok(value)
+        } else {
+            match self.policy {
+                ValidationPolicy::Reject => Err(value),
+                ValidationPolicy::Panic => panic!("Cell value is invalid."),
+            }
+        }
+    }
+}
+
+impl <T: Clone> ValidatedCell<T> {
+    /// Borrows a mutable reference to the data stored in this cell.
+    /// The value is validated when the guard is dropped. If it is invalid it is either rolled back
+    /// to the value it had when it was borrowed or the thread panics depending on the policy.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned. The guard will panic when dropped if
+    /// the value is invalid and the policy is `ValidationPolicy::Panic`.
+    pub fn borrow_mut(&self) -> ValidatedCellGuard<'_, T> {
+        let data = self.data.borrow_mut();
+
+        ValidatedCellGuard {
+            backup: Some(data.clone()),
+            data,
+            cell: self,
+        }
+    }
+
+    /// Gets the value contained in this cell.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn get(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+/// A mutable borrow of the value in a `ValidatedCell`.
+/// The value is validated when the guard is dropped.
+pub struct ValidatedCellGuard<'a, T> {
+    /// The value before it was borrowed.
+    backup: Option<T>,
+    /// The lock guard over the value.
+    data: SyncCellGuard<'a, T>,
+    /// The cell being borrowed.
+    cell: &'a ValidatedCell<T>,
+}
+
+impl <T> Deref for ValidatedCellGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl <T> DerefMut for ValidatedCellGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl <T> Drop for ValidatedCellGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.cell.is_valid(&self.data) {
+            return;
+        }
+
+        match self.cell.policy {
+            ValidationPolicy::Panic if !thread::panicking() => panic!("Cell value is invalid."),
+            _ => if let Some(backup) = self.backup.take() {
+                *self.data = backup;
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use crate::{ValidatedCell, ValidationPolicy};
+
+    #[test]
+    pub fn test_validated_cell_reject() {
+        let cell = ValidatedCell::new(1, |value: &i32| *value % 2 == 1);
+
+        assert_eq!(Ok(()), cell.set(3));
+        assert_eq!(Err(4), cell.set(4));
+        assert_eq!(Ok(3), cell.replace(5));
+        assert_eq!(5, cell.into_inner());
+    }
+
+    #[test]
+    pub fn test_validated_cell_guard_rollback() {
+        let cell = ValidatedCell::new(vec![1], |value: &Vec<i32>| value.len() < 3);
+
+        cell.borrow_mut().push(2);
+        assert_eq!(vec![1, 2], cell.get());
+
+        cell.borrow_mut().push(3);
+        assert_eq!(vec![1, 2], cell.get());
+    }
+
+    #[test]
+    pub fn test_validated_cell_panic() {
+        let cell = ValidatedCell::with_policy(1, |value: &i32| *value > 0, ValidationPolicy::Panic);
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| cell.set(0)));
+
+        assert!(result.is_err());
+        assert_eq!(1, cell.get());
+    }
+
+    #[test]
+    pub fn test_validated_cell_try_new() {
+        let valid = |value: &i32| *value > 0;
+
+        assert_eq!(Some(0), ValidatedCell::try_new(0, valid).err());
+        assert_eq!(Some(0), ValidatedCell::try_with_policy(0, valid, ValidationPolicy::Reject).err());
+        assert_eq!(1, ValidatedCell::try_new(1, valid).unwrap().get());
+
+        let result = panic::catch_unwind(|| ValidatedCell::try_with_policy(0, valid, ValidationPolicy::Panic).is_ok());
+        assert!(result.is_err());
+    }
+}
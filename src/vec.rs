@@ -0,0 +1,289 @@
+use std::mem::replace;
+
+use crate::SyncCell;
+
+/// A vector whose elements are each stored in their own `SyncCell`.
+/// Pushing and popping lock the whole vector, but accessing an element only locks that element,
+/// so threads working on different elements do not wait for each other. A `SyncCell<Vec<T>>` on
+/// the other hand serializes every access to every element.
+///
+/// Element accesses share the lock of the vector, so a push or pop waits until no element is
+/// borrowed.
+///
+/// # Usage
+/// ```
+/// use std::{sync::Arc, thread};
+/// use sync_cell::SyncVec;
+///
+/// let vec = Arc::new(SyncVec::from(vec![0, 0]));
+///
+/// let handles: Vec<_> = (0..2).map(|index| {
+///     let vec = vec.clone();
+///     thread::spawn(move || {
+///         for _ in 0..100 {
+///             vec.with_mut(index, |value| *value += 1);
+///         }
+///     })
+/// }).collect();
+/// # for handle in handles { handle.join().unwrap(); }
+///
+/// vec.push(5);
+///
+/// assert_eq!(vec![100, 100, 5], vec.to_vec());
+/// ```
+///
+/// # Panicking
+/// Like `SyncCell`, this vector will panic rather than return an error when a lock becomes
+/// poisoned.
+#[derive(Debug)]
+pub struct SyncVec<T> {
+    /// The cells of the elements of this vector.
+    slots: SyncCell<Vec<SyncCell<T>>>,
+}
+
+impl <T> SyncVec<T> {
+    /// Creates a new empty `SyncVec`.
+    pub const fn new() -> Self {
+        Self {
+            slots: SyncCell::new(Vec::new()),
+        }
+    }
+
+    /// Creates a new empty `SyncVec` with space for a number of elements.
+    ///
+    /// - `capacity` - The number of elements to allocate space for.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: SyncCell::new(Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Gets the number of elements in this vector.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn len(&self) -> usize {
+        self.slots.borrow().len()
+    }
+
+    /// Checks if this vector has no elements.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn is_empty(&self) -> bool {
+        self.slots.borrow().is_empty()
+    }
+
+    /// Adds an element to the end of this vector.
+    ///
+    /// - `value` - The element to add.
+    ///
+    /// # Panicking
+    /// This method will panic if the lock becomes poisoned.
+    pub fn push(&self, value: T) {
+        self.slots.borrow_mut().push(SyncCell::new(value));
+    }
+
+    /// Removes the last element of this vector.
+    /// Returns `None` if the vector is empty.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn pop(&self) -> Option<T> {
+        self.slots.borrow_mut().pop().map(SyncCell::into_inner)
+    }
+
+    /// Calls a function with a reference to an element of this vector.
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// - `index` - The index of the element.
+    /// - `f` - The function to call with the element.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn with<R>(&self, index: usize, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.slots.borrow()
+            .get(index)
+            .map(|slot| f(&slot.borrow()))
+    }
+
+    /// Calls a function with a mutable reference to an element of this vector.
+    /// Only the element is locked mutably, so other elements can be accessed at the same time.
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// - `index` - The index of the element.
+    /// - `f` - The function to call with the element.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn with_mut<R>(&self, index: usize, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.slots.borrow()
+            .get(index)
+            .map(|slot| f(&mut slot.borrow_mut()))
+    }
+
+    /// Sets an element of this vector.
+    /// If the index is out of bounds the value is returned as an error.
+    ///
+    /// - `index` - The index of the element.
+    /// - `value` - The new value of the element.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn set(&self, index: usize, value: T) -> Result<(), T> {
+        match self.slots.borrow().get(index) {
+            Some(slot) => {
+                slot.set(value);
+                Ok(())
+            },
+            None => Err(value),
+        }
+    }
+
+    /// Replaces an element of this vector.
+    /// The previous value is returned. If the index is out of bounds the value is returned as an
+    /// error.
+    ///
+    /// - `index` - The index of the element.
+    /// - `value` - The new value of the element.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn replace(&self, index: usize, value: T) -> Result<T, T> {
+        match self.slots.borrow().get(index) {
+            Some(slot) => Ok(replace(&mut *slot.borrow_mut(), value)),
+            None => Err(value),
+        }
+    }
+
+    /// Retrieves the elements stored in this `SyncVec`.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn into_vec(self) -> Vec<T> {
+        self.slots.into_inner()
+            .into_iter()
+            .map(SyncCell::into_inner)
+            .collect()
+    }
+}
+
+impl <T: Clone> SyncVec<T> {
+    /// Gets a copy of an element of this vector.
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// - `index` - The index of the element.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.with(index, T::clone)
+    }
+
+    /// Gets a copy of every element of this vector.
+    /// Elements are locked one at a time while the vector is locked, so no element can be pushed
+    /// or popped during the copy.
+    ///
+    /// # Panicking
+    /// This method will panic if any of the locks become poisoned.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.slots.borrow()
+            .iter()
+            .map(SyncCell::get)
+            .collect()
+    }
+}
+
+impl <T: Clone> Clone for SyncVec<T> {
+    fn clone(&self) -> Self {
+        Self::from(self.to_vec())
+    }
+}
+
+impl <T> Default for SyncVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <T> From<Vec<T>> for SyncVec<T> {
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl <T> FromIterator<T> for SyncVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            slots: SyncCell::new(iter.into_iter().map(SyncCell::new).collect()),
+        }
+    }
+}
+
+/// Adds the values to the end of the vector taking the lock once per call.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <T> Extend<T> for &SyncVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.slots.borrow_mut().extend(iter.into_iter().map(SyncCell::new));
+    }
+}
+
+/// Adds the values to the end of the vector.
+///
+/// # Panicking
+/// This method will panic if the lock becomes poisoned.
+impl <T> Extend<T> for SyncVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        (&*self).extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, Barrier}, thread};
+
+    use crate::SyncVec;
+
+    #[test]
+    pub fn test_sync_vec() {
+        let vec = SyncVec::new();
+
+        vec.push(1);
+        vec.push(2);
+
+        assert_eq!(2, vec.len());
+        assert_eq!(Ok(()), vec.set(0, 3));
+        assert_eq!(Err(4), vec.set(2, 4));
+        assert_eq!(Ok(2), vec.replace(1, 5));
+        assert_eq!(Some(5), vec.pop());
+        assert_eq!(None, vec.get(1));
+        assert_eq!(vec![3], vec.into_vec());
+    }
+
+    #[test]
+    pub fn test_sync_vec_element_locks() {
+        let vec = Arc::new(SyncVec::from(vec![0, 0]));
+        let barrier = Arc::new(Barrier::new(2));
+
+        // Both threads hold a mutable borrow of their own element while waiting for each other,
+        // which would deadlock if element access locked the whole vector.
+        let handles: Vec<_> = (0..2).map(|index| {
+            let vec = vec.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                vec.with_mut(index, |value| {
+                    barrier.wait();
+                    *value += 1;
+                })
+            })
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(vec![1, 1], vec.to_vec());
+    }
+}
@@ -0,0 +1,50 @@
+use core::sync::atomic::Ordering;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+#[cfg(not(target_has_atomic = "64"))]
+use core::sync::atomic::AtomicUsize;
+
+/// A counter of the changes made to a cell.
+/// This is 64 bits wide on targets with 64-bit atomics and the width of a pointer otherwise, in
+/// which case versions wrap around sooner.
+#[derive(Debug)]
+pub(crate) struct VersionCounter {
+    /// The number of changes made to the cell.
+    #[cfg(target_has_atomic = "64")]
+    count: AtomicU64,
+    /// The number of changes made to the cell.
+    #[cfg(not(target_has_atomic = "64"))]
+    count: AtomicUsize,
+}
+
+impl VersionCounter {
+    /// Creates a new `VersionCounter` with no changes made.
+    pub(crate) const fn new() -> Self {
+        Self {
+            #[cfg(target_has_atomic = "64")]
+            count: AtomicU64::new(0),
+            #[cfg(not(target_has_atomic = "64"))]
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Gets the current version.
+    ///
+    /// - `order` - The memory ordering of the load.
+    pub(crate) fn load(&self, order: Ordering) -> u64 {
+        #[cfg(target_has_atomic = "64")]
+        let version = self.count.load(order);
+
+        #[cfg(not(target_has_atomic = "64"))]
+        let version = self.count.load(order) as u64;
+
+        version
+    }
+
+    /// Records a change, moving to the next version.
+    ///
+    /// - `order` - The memory ordering of the increment.
+    pub(crate) fn increment(&self, order: Ordering) {
+        self.count.fetch_add(1, order);
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(all(debug_assertions, feature = "std"))]
+use std::{backtrace::Backtrace, sync::{Mutex, atomic::{AtomicBool, Ordering}}, thread, time::Instant};
+use core::time::Duration;
+
+/// What the guard watchdog does when a borrow is held for longer than its threshold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WatchdogAction {
+    /// A report is printed to standard error.
+    #[default]
+    Log,
+    /// The thread releasing the borrow panics with the report.
+    Panic,
+}
+
+/// The threshold and action of the guard watchdog.
+#[cfg(all(debug_assertions, feature = "std"))]
+#[derive(Debug, Clone, Copy)]
+struct Watchdog {
+    /// The longest a borrow can be held without being reported.
+    threshold: Duration,
+    /// What to do with a report.
+    action: WatchdogAction,
+}
+
+/// Whether the guard watchdog is enabled, which lets borrows skip reading its settings.
+#[cfg(all(debug_assertions, feature = "std"))]
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The settings of the guard watchdog.
+#[cfg(all(debug_assertions, feature = "std"))]
+static WATCHDOG: Mutex<Option<Watchdog>> = Mutex::new(None);
+
+/// Enables or disables the guard watchdog for borrows of `SyncCell`s, `HeldSyncCell`s and
+/// `RawSyncCell`s.
+/// While the watchdog is enabled, every borrow records when and where it was taken. When a borrow
+/// that was held for longer than the threshold is released, a report including the backtrace of
+/// where it was taken is printed or raised as a panic. This finds borrows that are accidentally
+/// held across a blocking call.
+///
+/// The watchdog only exists in debug builds with the `std` feature. Otherwise this function does
+/// nothing.
+///
+/// - `threshold` - The longest a borrow can be held without being reported, or `None` to disable
+///   the watchdog.
+/// - `action` - What to do with a report.
+///
+/// # Usage
+/// ```
+/// use std::time::Duration;
+/// use sync_cell::{RawSyncCell, WatchdogAction, set_guard_watchdog};
+///
+/// set_guard_watchdog(Some(Duration::from_secs(1)), WatchdogAction::Log);
+///
+/// let cell: RawSyncCell<i32> = RawSyncCell::new(0);
+/// *cell.borrow_mut() += 1;
+///
+/// set_guard_watchdog(None, WatchdogAction::Log);
+/// ```
+#[cfg_attr(not(all(debug_assertions, feature = "std")), allow(unused_variables))]
+pub fn set_guard_watchdog(threshold: Option<Duration>, action: WatchdogAction) {
+    #[cfg(all(debug_assertions, feature = "std"))]
+    {
+        let watchdog = threshold.map(|threshold| Watchdog {
+            threshold,
+            action,
+        });
+
+        // The settings are always left valid, so a poisoned lock can be used.
+        match WATCHDOG.lock() {
+            Ok(mut settings) => *settings = watchdog,
+            Err(err) => *err.into_inner() = watchdog,
+        }
+
+        ENABLED.store(watchdog.is_some(), Ordering::Release);
+    }
+}
+
+/// When and where a borrow was taken, recorded while the guard watchdog is enabled.
+/// This is empty in release builds and without the `std` feature.
+#[derive(Debug)]
+pub(crate) struct Watch {
+    /// The watchdog settings when the borrow was taken, the instant it was taken and the
+    /// backtrace of where it was taken.
+    #[cfg(all(debug_assertions, feature = "std"))]
+    start: Option<(Watchdog, Instant, Backtrace)>,
+}
+
+impl Watch {
+    /// Starts watching a borrow that has just been taken.
+    pub(crate) fn start() -> Self {
+        #[cfg(all(debug_assertions, feature = "std"))]
+        let watchdog = match ENABLED.load(Ordering::Acquire) {
+            // The settings are always left valid, so a poisoned lock can be used.
+            true => match WATCHDOG.lock() {
+                Ok(settings) => *settings,
+                Err(err) => *err.into_inner(),
+            },
+            false => None,
+        };
+
+        Self {
+            #[cfg(all(debug_assertions, feature = "std"))]
+            start: watchdog.map(|watchdog| (watchdog, Instant::now(), Backtrace::force_capture())),
+        }
+    }
+
+    /// Reports the watched borrow if it was held for too long.
+    ///
+    /// - `exclusive` - Whether the borrow was mutable.
+    #[cfg_attr(not(all(debug_assertions, feature = "std")), allow(unused_variables))]
+    pub(crate) fn finish(&mut self, exclusive: bool) {
+        #[cfg(all(debug_assertions, feature = "std"))]
+        if let Some((watchdog, start, backtrace)) = self.start.take() {
+            let held = start.elapsed();
+
+            if held <= watchdog.threshold {
+                return;
+            }
+
+            let kind = if exclusive { "mutable borrow" } else { "borrow" };
+            let report = format!("A {} was held for {:?}, longer than the watchdog threshold of {:?}. It was taken at:\n{}", kind, held, watchdog.threshold, backtrace);
+
+            match watchdog.action {
+                WatchdogAction::Log => eprintln!("{}", report),
+                // Panicking while already panicking would abort, so the report is printed instead.
+                WatchdogAction::Panic if thread::panicking() => eprintln!("{}", report),
+                WatchdogAction::Panic => panic!("{}", report),
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+
+use crate::{SyncCell, SyncCellRef};
+
+/// Creates a read-only view of two cells that reads both of their values together.
+/// Both cells are borrowed at the same time, so the values read are always a consistent
+/// snapshot. The cells are borrowed in the same order as `lock_all`, by their address, so views
+/// and transactions using the same cells cannot deadlock.
+///
+/// The two cells must be different cells.
+///
+/// - `a` - The first cell.
+/// - `b` - The second cell.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{SyncCell, zip};
+///
+/// let balance = SyncCell::new(100);
+/// let pending = SyncCell::new(vec![20, 30]);
+///
+/// let view = zip(&balance, &pending);
+///
+/// let available = view.with(|balance, pending| balance - pending.iter().sum::<i32>());
+///
+/// assert_eq!(50, available);
+/// assert_eq!((100, vec![20, 30]), view.get());
+/// ```
+pub fn zip<'a, A: ?Sized, B: ?Sized>(a: &'a SyncCell<A>, b: &'a SyncCell<B>) -> Zip<'a, A, B> {
+    Zip {
+        a,
+        b,
+    }
+}
+
+/// Creates a read-only view of two cells whose value is computed from both of their values.
+/// Both cells are borrowed together in the same way as `zip`.
+///
+/// - `a` - The first cell.
+/// - `b` - The second cell.
+/// - `f` - The function combining the values of the cells.
+///
+/// # Usage
+/// ```
+/// use sync_cell::{SyncCell, merge};
+///
+/// let width = SyncCell::new(3);
+/// let height = SyncCell::new(4);
+///
+/// let area = merge(&width, &height, |width, height| width * height);
+///
+/// assert_eq!(12, area.get());
+///
+/// width.set(5);
+/// assert_eq!(20, area.get());
+/// ```
+pub fn merge<'a, A: ?Sized, B: ?Sized, R, F: Fn(&A, &B) -> R>(a: &'a SyncCell<A>, b: &'a SyncCell<B>, f: F) -> Merged<'a, A, B, F> {
+    Merged {
+        cells: zip(a, b),
+        f,
+    }
+}
+
+/// A read-only view of two cells, returned by `zip`.
+pub struct Zip<'a, A: ?Sized, B: ?Sized> {
+    /// The first cell.
+    a: &'a SyncCell<A>,
+    /// The second cell.
+    b: &'a SyncCell<B>,
+}
+
+impl <'a, A: ?Sized, B: ?Sized> Zip<'a, A, B> {
+    /// Calls a function with references to the values of both cells.
+    ///
+    /// - `f` - The function to call with the values.
+    ///
+    /// # Panicking
+    /// This method will panic if either lock becomes poisoned.
+    pub fn with<R>(&self, f: impl FnOnce(&A, &B) -> R) -> R {
+        let (a, b) = self.borrow();
+        f(&a, &b)
+    }
+
+    /// Borrows immutable references to the values of both cells.
+    ///
+    /// # Panicking
+    /// This method will panic if either lock becomes poisoned.
+    pub fn borrow(&self) -> (SyncCellRef<'a, A>, SyncCellRef<'a, B>) {
+        let a_key = (self.a as *const SyncCell<A>).cast::<()>() as usize;
+        let b_key = (self.b as *const SyncCell<B>).cast::<()>() as usize;
+
+        if a_key <= b_key {
+            let a = self.a.borrow();
+            (a, self.b.borrow())
+        } else {
+            let b = self.b.borrow();
+            (self.a.borrow(), b)
+        }
+    }
+}
+
+impl <A: Clone, B: Clone> Zip<'_, A, B> {
+    /// Gets copies of the values of both cells.
+    ///
+    /// # Panicking
+    /// This method will panic if either lock becomes poisoned.
+    pub fn get(&self) -> (A, B) {
+        self.with(|a, b| (a.clone(), b.clone()))
+    }
+}
+
+impl <A: ?Sized, B: ?Sized> Clone for Zip<'_, A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <A: ?Sized, B: ?Sized> Copy for Zip<'_, A, B> {
+}
+
+/// A read-only view of a value computed from two cells, returned by `merge`.
+pub struct Merged<'a, A: ?Sized, B: ?Sized, F> {
+    /// The cells being combined.
+    cells: Zip<'a, A, B>,
+    /// The function combining the values of the cells.
+    f: F,
+}
+
+impl <A: ?Sized, B: ?Sized, R, F: Fn(&A, &B) -> R> Merged<'_, A, B, F> {
+    /// Computes the value of this view from the current values of both cells.
+    ///
+    /// # Panicking
+    /// This method will panic if either lock becomes poisoned.
+    pub fn get(&self) -> R {
+        self.cells.with(&self.f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use crate::{SyncCell, lock_all, zip};
+
+    #[test]
+    pub fn test_zip_consistent() {
+        let a = Arc::new(SyncCell::new(0));
+        let b = Arc::new(SyncCell::new(0));
+
+        let writer = {
+            let (a, b) = (a.clone(), b.clone());
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    lock_all(&[&*b, &*a], |locked| {
+                        *locked.get_mut(&*a) += 1;
+                        *locked.get_mut(&*b) -= 1;
+                        Ok::<_, ()>(())
+                    }).unwrap();
+                }
+            })
+        };
+
+        for _ in 0..1000 {
+            assert_eq!(0, zip(&*b, &*a).with(|b, a| a + b));
+        }
+
+        writer.join().unwrap();
+        assert_eq!((1000, -1000), zip(&*a, &*b).get());
+    }
+}
@@ -0,0 +1,229 @@
+//! Derive macros for the `sync-cell` crate.
+//! These are re-exported by `sync-cell` with the `derive` feature and should be used from there.
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// A field of the struct a macro is derived for.
+struct Field {
+    /// The visibility of the field, which may be empty.
+    vis: String,
+    /// The name of the field.
+    name: String,
+    /// The type of the field.
+    ty: String,
+}
+
+/// The struct a macro is derived for.
+struct Input {
+    /// The visibility of the struct, which may be empty.
+    vis: String,
+    /// The name of the struct.
+    name: String,
+    /// The named fields of the struct.
+    fields: Vec<Field>,
+}
+
+/// Generates a companion struct that stores each field of a struct in its own `SyncCell`.
+/// For a struct `Name` this generates `NameCells` with a method for each field returning the
+/// cell of that field, so fields can be locked independently instead of the whole struct being
+/// locked at once.
+///
+/// Only structs with named fields and no generic parameters are supported.
+#[proc_macro_derive(SyncFields)]
+pub fn derive_sync_fields(input: TokenStream) -> TokenStream {
+    let input = match parse(input) {
+        Ok(input) => input,
+        Err(message) => return format!("compile_error!({:?});", message).parse().unwrap(),
+    };
+
+    let name = &input.name;
+    let cells = format!("{}Cells", name);
+
+    let mut fields = String::new();
+    let mut new = String::new();
+    let mut into_inner = String::new();
+    let mut accessors = String::new();
+
+    for field in &input.fields {
+        let Field { vis, name: field, ty } = field;
+
+        fields += &format!("{field}: ::sync_cell::SyncCell<{ty}>,");
+        new += &format!("{field}: ::sync_cell::SyncCell::new(value.{field}),");
+        into_inner += &format!("{field}: self.{field}.into_inner(),");
+        accessors += &format!(
+            "#[doc = \"Gets the cell storing the `{field}` field.\"]
+            {vis} fn {field}(&self) -> &::sync_cell::SyncCell<{ty}> {{
+                &self.{field}
+            }}"
+        );
+    }
+
+    let vis = &input.vis;
+
+    format!(
+        "#[doc = \"The fields of `{name}` each stored in a `SyncCell`.\"]
+        {vis} struct {cells} {{
+            {fields}
+        }}
+
+        impl {cells} {{
+            #[doc = \"Stores each field of a value in its own cell.\"]
+            pub fn new(value: {name}) -> Self {{
+                Self {{
+                    {new}
+                }}
+            }}
+
+            #[doc = \"Retrieves the value stored in the cells.\"]
+            pub fn into_inner(self) -> {name} {{
+                {name} {{
+                    {into_inner}
+                }}
+            }}
+
+            {accessors}
+        }}
+
+        impl ::core::convert::From<{name}> for {cells} {{
+            fn from(value: {name}) -> Self {{
+                Self::new(value)
+            }}
+        }}
+
+        impl ::core::convert::From<{cells}> for {name} {{
+            fn from(cells: {cells}) -> Self {{
+                cells.into_inner()
+            }}
+        }}"
+    ).parse().unwrap()
+}
+
+/// Parses the struct a macro is derived for.
+///
+/// - `input` - The tokens of the struct.
+fn parse(input: TokenStream) -> Result<Input, String> {
+    let mut tokens = input.into_iter().peekable();
+
+    skip_attributes(&mut tokens);
+    let vis = parse_visibility(&mut tokens);
+
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => {},
+        _ => return Err(String::from("SyncFields can only be derived for structs")),
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err(String::from("Expected the name of the struct")),
+    };
+
+    let body = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group.stream(),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            return Err(String::from("SyncFields cannot be derived for generic structs"));
+        },
+        _ => return Err(String::from("SyncFields can only be derived for structs with named fields")),
+    };
+
+    let fields = split_fields(body)
+        .into_iter()
+        .map(parse_field)
+        .collect::<Result<_, _>>()?;
+
+    Ok(Input {
+        vis,
+        name,
+        fields,
+    })
+}
+
+/// Splits the tokens of the body of a struct into the tokens of each field.
+/// Commas inside the generic arguments of a type do not separate fields.
+///
+/// - `body` - The tokens between the braces of the struct.
+fn split_fields(body: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut fields = vec![Vec::new()];
+    let mut depth = 0usize;
+    let mut arrow = false;
+
+    for token in body {
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                ',' if depth == 0 => {
+                    fields.push(Vec::new());
+                    continue;
+                },
+                '<' => depth += 1,
+                // The `>` of `->` in a function type does not close a generic argument.
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                _ => {},
+            }
+
+            arrow = punct.as_char() == '-' && punct.spacing() == Spacing::Joint;
+        } else {
+            arrow = false;
+        }
+
+        fields.last_mut().unwrap().push(token);
+    }
+
+    fields.retain(|field| !field.is_empty());
+    fields
+}
+
+/// Parses the tokens of a single named field.
+///
+/// - `tokens` - The tokens of the field without the trailing comma.
+fn parse_field(tokens: Vec<TokenTree>) -> Result<Field, String> {
+    let mut tokens = tokens.into_iter().peekable();
+
+    skip_attributes(&mut tokens);
+    let vis = parse_visibility(&mut tokens);
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err(String::from("Expected the name of a field")),
+    };
+
+    match tokens.next() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {},
+        _ => return Err(format!("Expected the type of the field `{}`", name)),
+    }
+
+    Ok(Field {
+        vis,
+        name,
+        ty: tokens.collect::<TokenStream>().to_string(),
+    })
+}
+
+/// Skips any attributes at the start of the tokens.
+///
+/// - `tokens` - The tokens to skip attributes of.
+fn skip_attributes(tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>) {
+    while matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '#') {
+        tokens.next();
+        tokens.next();
+    }
+}
+
+/// Parses a visibility such as `pub` or `pub(crate)` at the start of the tokens.
+/// Returns an empty string if there is no visibility.
+///
+/// - `tokens` - The tokens to parse the visibility of.
+fn parse_visibility(tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>) -> String {
+    match tokens.peek() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {},
+        _ => return String::new(),
+    }
+
+    let mut vis = tokens.next().unwrap().to_string();
+
+    if let Some(TokenTree::Group(group)) = tokens.peek() {
+        if group.delimiter() == Delimiter::Parenthesis {
+            vis += &tokens.next().unwrap().to_string();
+        }
+    }
+
+    vis
+}
@@ -0,0 +1,104 @@
+//! Tests that explore every interleaving of threads using a cell with `loom`.
+//! These only run when the crate is built with `--cfg loom`:
+//! `RUSTFLAGS="--cfg loom" cargo test --test loom --release`
+#![cfg(loom)]
+
+use loom::{sync::Arc, thread};
+
+use sync_cell::{HeldSyncCell, SyncCell, UpdatePolicy};
+
+#[test]
+pub fn test_loom_sync_cell_set_get() {
+    loom::model(|| {
+        let cell = Arc::new(SyncCell::new(0));
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(1))
+        };
+
+        let seen = cell.get();
+        assert!(seen == 0 || seen == 1);
+
+        writer.join().unwrap();
+        assert_eq!(1, cell.get());
+    });
+}
+
+#[test]
+pub fn test_loom_sync_cell_borrow_mut() {
+    loom::model(|| {
+        let cell = Arc::new(SyncCell::new(0));
+
+        let handles: Vec<_> = (0..2).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || *cell.borrow_mut() += 1)
+        }).collect();
+
+        let seen = *cell.borrow();
+        assert!((0..=2).contains(&seen));
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(2, cell.get());
+    });
+}
+
+#[test]
+pub fn test_loom_held_sync_cell_update() {
+    loom::model(|| {
+        let cell = Arc::new(HeldSyncCell::new(0));
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(1))
+        };
+
+        let updated = cell.update();
+        let seen = cell.get();
+
+        writer.join().unwrap();
+
+        // The value can only have been applied if it was set before the update.
+        assert_eq!(updated, seen == 1);
+
+        cell.update();
+        assert_eq!(1, cell.get());
+    });
+}
+
+/// `HeldSyncCell` locks the incoming value before the current value whenever it takes both, so
+/// applying values from the update policy while other threads set and update the cell must not
+/// deadlock.
+/// The three threads make every interleaving too many to explore, so preemptions are bounded.
+#[test]
+pub fn test_loom_held_sync_cell_lock_order() {
+    let mut builder = loom::model::Builder::new();
+    builder.preemption_bound = Some(3);
+
+    builder.check(|| {
+        let cell = Arc::new(HeldSyncCell::with_update_policy(0, UpdatePolicy::AfterSets(1)));
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(1))
+        };
+
+        let updater = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                cell.update();
+                *cell.borrow()
+            })
+        };
+
+        let seen = cell.get();
+        assert!(seen == 0 || seen == 1);
+
+        writer.join().unwrap();
+        assert!(updater.join().unwrap() <= 1);
+        assert_eq!(1, cell.get());
+    });
+}
@@ -0,0 +1,287 @@
+//! Tests that run threads using cells in many random orders with `shuttle`.
+//! These only run when the crate is built with `--cfg shuttle`:
+//! `RUSTFLAGS="--cfg shuttle" cargo test --test shuttle --release`
+#![cfg(shuttle)]
+
+use shuttle::{sync::Arc, thread};
+
+use sync_cell::{DirtyCell, DoubleBufferedCell, GenerationalCell, HeldSyncCell, QueuedSyncCell, RawSyncCell, SyncCell, SyncStack, SyncVec, UndoRedoCell, UpdatePolicy};
+
+/// The number of random schedules tried by each test.
+const ITERATIONS: usize = 1000;
+
+#[test]
+pub fn test_shuttle_sync_cell_set_get() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(SyncCell::new(0));
+
+        let handles: Vec<_> = (1..=2).map(|value| {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(value))
+        }).collect();
+
+        let seen = cell.get();
+        assert!((0..=2).contains(&seen));
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(cell.get() == 1 || cell.get() == 2);
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_sync_cell_borrow() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(SyncCell::new((0, 0)));
+
+        let handles: Vec<_> = (0..2).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let mut value = cell.borrow_mut();
+                value.0 += 1;
+                thread::yield_now();
+                value.1 += 1;
+            })
+        }).collect();
+
+        // Both halves are changed under one borrow, so a reader never sees them differ.
+        let (first, second) = *cell.borrow();
+        assert_eq!(first, second);
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!((2, 2), cell.get());
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_raw_sync_cell_borrow() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(RawSyncCell::<_>::new(0));
+
+        let handles: Vec<_> = (0..2).map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || *cell.borrow_mut() += 1)
+        }).collect();
+
+        let seen = *cell.borrow();
+        assert!((0..=2).contains(&seen));
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(2, *cell.borrow());
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_held_sync_cell_update() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(HeldSyncCell::new(0));
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(1))
+        };
+
+        let updater = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                cell.update();
+                cell.get()
+            })
+        };
+
+        assert!(cell.get() <= 1);
+
+        writer.join().unwrap();
+        assert!(updater.join().unwrap() <= 1);
+
+        cell.update();
+        assert_eq!(1, cell.get());
+        assert!(!cell.has_update());
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_held_sync_cell_update_policy() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(HeldSyncCell::with_update_policy(0, UpdatePolicy::AfterSets(1)));
+
+        let handles: Vec<_> = (1..=2).map(|value| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                cell.set(value);
+                *cell.borrow()
+            })
+        }).collect();
+
+        for handle in handles {
+            assert!(handle.join().unwrap() <= 2);
+        }
+
+        cell.update();
+        assert!(cell.get() == 1 || cell.get() == 2);
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_undo_redo_cell() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(UndoRedoCell::new(0));
+
+        let handles: Vec<_> = (1..=2).map(|value| {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(value))
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // Whichever set came last, undoing both returns to the first value.
+        assert!(cell.undo());
+        assert!(cell.undo());
+        assert_eq!(0, cell.get());
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_double_buffered_cell() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(DoubleBufferedCell::new(0, 0));
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                cell.write_back(|value| *value = 1);
+                cell.swap();
+            })
+        };
+
+        let seen = cell.get();
+        assert!(seen == 0 || seen == 1);
+
+        writer.join().unwrap();
+        assert_eq!(1, cell.get());
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_queued_sync_cell() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(QueuedSyncCell::new(0));
+
+        let handles: Vec<_> = (1..=2).map(|value| {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(value))
+        }).collect();
+
+        let updater = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.update_all())
+        };
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let applied = updater.join().unwrap() + cell.update_all();
+        assert_eq!(2, applied);
+        assert_eq!(0, cell.pending());
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_dirty_cell() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(DirtyCell::new(0));
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(1))
+        };
+
+        let cleaned = cell.mark_clean();
+
+        writer.join().unwrap();
+
+        // The set is only lost if it happened before the cell was marked clean.
+        assert!(cleaned || cell.is_dirty());
+        assert_eq!(1, cell.get());
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_generational_cell() {
+    shuttle::check_random(|| {
+        let cell = Arc::new(GenerationalCell::new(0));
+        let token = cell.generation();
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.set(1))
+        };
+
+        let (value, generation) = cell.get();
+        assert_eq!(value == 0, generation == token);
+
+        let new_token = writer.join().unwrap();
+        assert!(!cell.validate(token));
+        assert_eq!(Some(1), cell.get_if_current(new_token));
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_sync_stack() {
+    shuttle::check_random(|| {
+        let stack = Arc::new(SyncStack::new());
+
+        let handles: Vec<_> = (0..2).map(|value| {
+            let stack = stack.clone();
+            thread::spawn(move || stack.push(value))
+        }).collect();
+
+        let popped = stack.pop();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut values = stack.drain();
+        values.extend(popped);
+        values.sort();
+        assert_eq!(vec![0, 1], values);
+    }, ITERATIONS);
+}
+
+#[test]
+pub fn test_shuttle_sync_vec() {
+    shuttle::check_random(|| {
+        let vec = Arc::new(SyncVec::new());
+        vec.push(0);
+
+        let handles: Vec<_> = (0..2).map(|_| {
+            let vec = vec.clone();
+            thread::spawn(move || vec.with_mut(0, |value| *value += 1))
+        }).collect();
+
+        let pusher = {
+            let vec = vec.clone();
+            thread::spawn(move || vec.push(10))
+        };
+
+        for handle in handles {
+            assert_eq!(Some(()), handle.join().unwrap());
+        }
+
+        pusher.join().unwrap();
+        assert_eq!(Some(2), vec.get(0));
+        assert_eq!(Some(10), vec.get(1));
+    }, ITERATIONS);
+}
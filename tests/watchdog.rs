@@ -0,0 +1,25 @@
+//! The guard watchdog settings are global, so this test runs in its own binary where no other
+//! test can take borrows while the watchdog is enabled.
+#![cfg(debug_assertions)]
+
+use std::{thread, time::Duration};
+
+use sync_cell::{RawSyncCell, WatchdogAction, set_guard_watchdog};
+
+#[test]
+#[should_panic(expected = "A mutable borrow was held for")]
+pub fn test_guard_watchdog() {
+    let cell: RawSyncCell<i32> = RawSyncCell::new(0);
+    let guard = cell.borrow_mut();
+
+    // Only borrows taken while the watchdog is enabled are watched, using the settings from
+    // when they were taken.
+    set_guard_watchdog(Some(Duration::from_millis(20)), WatchdogAction::Panic);
+    thread::sleep(Duration::from_millis(40));
+    drop(guard);
+
+    let guard = cell.borrow_mut();
+    set_guard_watchdog(None, WatchdogAction::Panic);
+    thread::sleep(Duration::from_millis(40));
+    drop(guard);
+}
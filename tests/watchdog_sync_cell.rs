@@ -0,0 +1,19 @@
+//! The guard watchdog settings are global, so this test runs in its own binary where no other
+//! test can take borrows while the watchdog is enabled.
+#![cfg(debug_assertions)]
+
+use std::{thread, time::Duration};
+
+use sync_cell::{HeldSyncCell, WatchdogAction, set_guard_watchdog};
+
+#[test]
+#[should_panic(expected = "A mutable borrow was held for")]
+pub fn test_guard_watchdog_held_sync_cell() {
+    let cell = HeldSyncCell::new(0);
+
+    set_guard_watchdog(Some(Duration::from_millis(20)), WatchdogAction::Panic);
+
+    let guard = cell.borrow_mut();
+    thread::sleep(Duration::from_millis(40));
+    drop(guard);
+}